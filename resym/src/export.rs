@@ -0,0 +1,79 @@
+use resym_core::pdb_types::OutputFormat;
+
+use std::{fs, path::Path};
+
+/// Format the current tab's type can be exported in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Reconstruction(OutputFormat),
+    /// C++ reconstruction, highlighted as HTML
+    Html,
+}
+
+/// Formats offered when exporting, with their name and file extension
+pub const EXPORT_FORMATS: &[(&str, &str, ExportFormat)] = &[
+    ("C++", "h", ExportFormat::Reconstruction(OutputFormat::Cpp)),
+    ("C", "h", ExportFormat::Reconstruction(OutputFormat::C)),
+    ("HTML", "html", ExportFormat::Html),
+    (
+        "JSON",
+        "json",
+        ExportFormat::Reconstruction(OutputFormat::Json),
+    ),
+    (
+        "C#",
+        "cs",
+        ExportFormat::Reconstruction(OutputFormat::CSharp),
+    ),
+    (
+        "Rust",
+        "rs",
+        ExportFormat::Reconstruction(OutputFormat::Rust),
+    ),
+    ("Go", "go", ExportFormat::Reconstruction(OutputFormat::Go)),
+    (
+        "Python ctypes",
+        "py",
+        ExportFormat::Reconstruction(OutputFormat::Ctypes),
+    ),
+    (
+        "WinDbg",
+        "txt",
+        ExportFormat::Reconstruction(OutputFormat::WinDbg),
+    ),
+    (
+        "ImHex",
+        "hexpat",
+        ExportFormat::Reconstruction(OutputFormat::ImHex),
+    ),
+    (
+        "010 Editor",
+        "bt",
+        ExportFormat::Reconstruction(OutputFormat::BinaryTemplate),
+    ),
+    (
+        "Kaitai Struct",
+        "ksy",
+        ExportFormat::Reconstruction(OutputFormat::Kaitai),
+    ),
+];
+
+/// Return the name of the file a type is exported to by default, type names
+/// may contain characters file names can't.
+pub fn default_file_name(type_name: &str, extension: &str) -> String {
+    let file_stem = type_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+
+    format!("{}.{}", file_stem, extension)
+}
+
+/// Write an exported type to the given file, and report the result in the
+/// console.
+pub fn write_export(file_path: &Path, content: &str) {
+    match fs::write(file_path, content) {
+        Ok(()) => log::info!("Exported the type to '{}'", file_path.display()),
+        Err(err) => log::error!("Failed to export the type: {}", err),
+    }
+}
@@ -0,0 +1,93 @@
+use regex::RegexBuilder;
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+/// Byte ranges of the matches of a search, or the reason the query is invalid
+type SearchResult = Result<Vec<Range<usize>>, String>;
+
+/// State of the bar used to search the text displayed in the code view.
+#[derive(Default)]
+pub struct FindBar {
+    pub open: bool,
+    pub query: String,
+    pub case_sensitive: bool,
+    pub use_regex: bool,
+    /// Index of the match the code view is scrolled to
+    pub current_match: usize,
+    /// Set when the code view should scroll to the current match
+    pub scroll_to_current_match: bool,
+    /// Set when the query's text edit should get the focus
+    pub focus_query: bool,
+    /// Hash of the last search's parameters, and its matches (searching
+    /// isn't free on large reconstructions and happens each frame)
+    last_search: Option<(u64, SearchResult)>,
+}
+
+impl FindBar {
+    /// Search the given text, unless neither it nor the search's parameters
+    /// changed since the last search.
+    pub fn update_matches(&mut self, text: &str) {
+        let mut hasher = DefaultHasher::new();
+        (&self.query, self.case_sensitive, self.use_regex, text).hash(&mut hasher);
+        let search_hash = hasher.finish();
+
+        let up_to_date = self
+            .last_search
+            .as_ref()
+            .is_some_and(|(last_search_hash, _)| *last_search_hash == search_hash);
+        if !up_to_date {
+            self.last_search = Some((search_hash, self.search(text)));
+        }
+    }
+
+    /// Return the byte ranges of the last search's matches, or an error if
+    /// the query isn't a valid regular expression.
+    pub fn matches(&self) -> Result<&[Range<usize>], &str> {
+        match &self.last_search {
+            Some((_, Ok(matches))) => Ok(matches),
+            Some((_, Err(err))) => Err(err),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Select the next match (or the previous one if `backwards` is set),
+    /// wrapping around the ends.
+    pub fn cycle(&mut self, match_count: usize, backwards: bool) {
+        if match_count == 0 {
+            return;
+        }
+        self.current_match = if backwards {
+            (self.current_match + match_count - 1) % match_count
+        } else {
+            (self.current_match + 1) % match_count
+        };
+        self.scroll_to_current_match = true;
+    }
+
+    fn search(&self, text: &str) -> SearchResult {
+        if self.query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let pattern = if self.use_regex {
+            self.query.clone()
+        } else {
+            regex::escape(&self.query)
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .map_err(|err| err.to_string())?;
+
+        // Empty matches couldn't be highlighted
+        Ok(regex
+            .find_iter(text)
+            .map(|m| m.range())
+            .filter(|range| !range.is_empty())
+            .collect())
+    }
+}
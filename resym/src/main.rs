@@ -1,27 +1,41 @@
 #![windows_subsystem = "windows"]
 
+mod export;
+mod find_bar;
 mod frontend;
 mod settings;
 mod syntax_highlighting;
+mod type_diff;
+mod type_tabs;
 
 use anyhow::Result;
 use eframe::egui::{self, ScrollArea, TextStyle};
 use memory_logger::blocking::MemoryLogger;
 use resym_core::{
-    backend::{Backend, BackendCommand, PDBSlot},
-    diffing::DiffChange,
-    frontend::{FrontendCommand, TypeList},
-    pdb_types::PrimitiveReconstructionFlavor,
+    backend::{Backend, BackendCommand, FilterMatchMode, PDBSlot},
+    frontend::{FrontendCommand, ProgressStage, TypeList},
+    pdb_types::{OutputFormat, PrimitiveReconstructionFlavor},
     syntax_highlighting::CodeTheme,
 };
-use tinyfiledialogs::open_file_dialog;
+use tinyfiledialogs::{open_file_dialog, save_file_dialog_with_filter};
 
-use std::fmt::Write;
-use std::{sync::Arc, vec};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    vec,
+};
 
 use crate::{
-    frontend::EguiFrontendController, settings::ResymAppSettings,
-    syntax_highlighting::highlight_code,
+    export::{default_file_name, write_export, ExportFormat, EXPORT_FORMATS},
+    find_bar::FindBar,
+    frontend::EguiFrontendController,
+    settings::{ResymAppSettings, CODE_FONT_SIZE_RANGE, DEFAULT_CODE_FONT_SIZE},
+    syntax_highlighting::{
+        apply_text_highlights, highlight_code, highlight_code_html, set_font_size,
+        LineDescriptions, TextHighlights,
+    },
+    type_diff::TypeDiffView,
+    type_tabs::TypeTabs,
 };
 
 const PKG_NAME: &str = env!("CARGO_PKG_NAME");
@@ -31,6 +45,18 @@ const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 const PDB_MAIN_SLOT: PDBSlot = 0;
 /// Slot used for the PDB we're diffing to
 const PDB_DIFF_SLOT: PDBSlot = 1;
+/// Key the open tabs are persisted with
+const TYPE_TABS_KEY: &str = "type_tabs";
+/// Key the recently opened PDB files are persisted with
+const RECENT_PDB_FILES_KEY: &str = "recent_pdb_files";
+/// Maximum number of recently opened PDB files to remember
+const MAX_RECENT_PDB_FILES: usize = 10;
+/// Duration toasts are displayed for, in seconds
+const TOAST_DURATION: f64 = 3.0;
+/// Background of the search results in the code view
+const FIND_MATCH_COLOR: egui::Color32 = egui::Color32::from_rgb(0xe0, 0xc0, 0x40);
+/// Background of the search result the code view is scrolled to
+const FIND_CURRENT_MATCH_COLOR: egui::Color32 = egui::Color32::from_rgb(0xf0, 0x80, 0x20);
 
 fn main() -> Result<()> {
     let logger = MemoryLogger::setup(log::Level::Info)?;
@@ -53,6 +79,20 @@ struct ResymApp {
     console_content: Vec<String>,
     settings_wnd_open: bool,
     settings: ResymAppSettings,
+    /// Tabs displaying the types reconstructed from the main PDB
+    type_tabs: TypeTabs,
+    find_bar: FindBar,
+    /// Path of the PDB being loaded into the main slot, if any
+    loading_pdb_path: Option<PathBuf>,
+    /// PDB files recently opened into the main slot, the most recent first
+    recent_pdb_files: Vec<PathBuf>,
+    /// Progress of the long-running operation in progress, if any
+    progress: Option<(usize, usize, ProgressStage)>,
+    /// Whether the system prefers dark mode, `None` if unknown
+    system_prefers_dark_mode: Option<bool>,
+    /// Message displayed briefly at the bottom of the window, and the time
+    /// it expires at
+    toast: Option<(String, f64)>,
     frontend_controller: Arc<EguiFrontendController>,
     backend: Backend,
 }
@@ -61,10 +101,11 @@ struct ResymApp {
 enum ResymAppMode {
     /// Mode in which the application starts
     Idle,
-    /// This mode means we're browsing a single PDB file
-    Browsing(String, usize, String),
+    /// This mode means we're browsing a single PDB file, reconstructed types
+    /// are displayed in tabs
+    Browsing,
     /// This mode means we're comparing two PDB files for differences
-    Comparing(String, String, usize, Vec<DiffChange>, String),
+    Comparing(Box<TypeDiffView>),
 }
 
 // GUI-related trait
@@ -72,22 +113,94 @@ impl eframe::App for ResymApp {
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         // Save settings on shutdown
         eframe::set_value(storage, eframe::APP_KEY, &self.settings);
+        eframe::set_value(storage, TYPE_TABS_KEY, &self.type_tabs);
+        eframe::set_value(storage, RECENT_PDB_FILES_KEY, &self.recent_pdb_files);
     }
 
     /// Called each time the UI needs repainting, which may be many times per second.
     /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // Process incoming commands, if any
-        self.process_ui_commands();
+        self.process_ui_commands(ctx);
 
         // Update theme
-        let theme = if self.settings.use_light_theme {
+        self.system_prefers_dark_mode = frame.info().prefer_dark_mode;
+        let theme = if self
+            .settings
+            .light_theme_enabled(self.system_prefers_dark_mode)
+        {
             egui::Visuals::light()
         } else {
             egui::Visuals::dark()
         };
         ctx.set_visuals(theme);
 
+        // Cycle through tabs with Ctrl-Tab (and Ctrl-Shift-Tab)
+        if ctx.input_mut().consume_key(
+            egui::Modifiers {
+                ctrl: true,
+                shift: true,
+                ..Default::default()
+            },
+            egui::Key::Tab,
+        ) {
+            self.type_tabs.cycle(true);
+        } else if ctx
+            .input_mut()
+            .consume_key(egui::Modifiers::CTRL, egui::Key::Tab)
+        {
+            self.type_tabs.cycle(false);
+        }
+        // Scale the code view's font with Ctrl-+ and Ctrl--, and reset it
+        // with Ctrl-0
+        let font_size_delta = ctx
+            .input()
+            .events
+            .iter()
+            .map(|event| match event {
+                egui::Event::Text(text) if ctx.input().modifiers.command => match text.as_str() {
+                    "+" | "=" => 1.0,
+                    "-" => -1.0,
+                    _ => 0.0,
+                },
+                _ => 0.0,
+            })
+            .sum::<f32>();
+        if font_size_delta != 0.0 {
+            self.settings.code_font_size = (self.settings.code_font_size + font_size_delta)
+                .clamp(*CODE_FONT_SIZE_RANGE.start(), *CODE_FONT_SIZE_RANGE.end());
+        }
+        if ctx
+            .input_mut()
+            .consume_key(egui::Modifiers::COMMAND, egui::Key::Num0)
+        {
+            self.settings.code_font_size = DEFAULT_CODE_FONT_SIZE;
+        }
+        // Search the code view with Ctrl-F
+        if ctx
+            .input_mut()
+            .consume_key(egui::Modifiers::COMMAND, egui::Key::F)
+        {
+            self.find_bar.open = true;
+            self.find_bar.focus_query = true;
+        }
+        // Navigate through the followed type references with Alt-Left and
+        // Alt-Right
+        if ctx
+            .input_mut()
+            .consume_key(egui::Modifiers::ALT, egui::Key::ArrowLeft)
+        {
+            self.type_tabs.navigate_history(true);
+        } else if ctx
+            .input_mut()
+            .consume_key(egui::Modifiers::ALT, egui::Key::ArrowRight)
+        {
+            self.type_tabs.navigate_history(false);
+        }
+
+        // Open dropped PDB files
+        self.handle_dropped_files(ctx);
+
         // Draw "Settings" window if open
         self.update_settings_window(ctx);
 
@@ -111,14 +224,14 @@ impl eframe::App for ResymApp {
                                 vec![PDB_MAIN_SLOT, PDB_DIFF_SLOT],
                                 self.search_filter.clone(),
                                 self.settings.search_case_insensitive,
-                                self.settings.search_use_regex,
+                                self.settings.search_match_mode(),
                             ))
                     } else {
                         self.backend.send_command(BackendCommand::UpdateTypeFilter(
                             PDB_MAIN_SLOT,
                             self.search_filter.clone(),
                             self.settings.search_case_insensitive,
-                            self.settings.search_use_regex,
+                            self.settings.search_match_mode(),
                         ))
                     };
                     if let Err(err) = result {
@@ -146,20 +259,32 @@ impl eframe::App for ResymApp {
 
         egui::CentralPanel::default().show(ctx, |ui| {
             // The central panel the region left after adding TopPanel's and SidePanel's
-            ui.label(if let ResymAppMode::Comparing(..) = self.current_mode {
-                "Differences between reconstructed type(s) - C++"
-            } else {
-                "Reconstructed type(s) - C++"
+            ui.horizontal(|ui| {
+                ui.label(if let ResymAppMode::Comparing(..) = self.current_mode {
+                    "Differences between reconstructed type(s) - C++"
+                } else {
+                    "Reconstructed type(s) - C++"
+                });
+                self.update_copy_buttons(ui);
             });
             ui.add_space(4.0);
 
+            if let ResymAppMode::Browsing = self.current_mode {
+                self.update_tab_bar(ui);
+            }
+            if self.find_bar.open {
+                self.update_find_bar(ui);
+            }
             self.update_code_view(ui);
         });
+
+        self.update_drag_and_drop_overlay(ctx);
+        self.update_toast(ctx);
     }
 }
 
 // Utility associated functions and methods
-impl<'p> ResymApp {
+impl ResymApp {
     fn new(cc: &eframe::CreationContext<'_>, logger: &'static MemoryLogger) -> Result<Self> {
         let (tx_ui, rx_ui) = crossbeam_channel::unbounded::<FrontendCommand>();
         let frontend_controller = Arc::new(EguiFrontendController::new(
@@ -171,12 +296,18 @@ impl<'p> ResymApp {
 
         // Load settings on launch
         let mut settings = ResymAppSettings::default();
+        let mut type_tabs = TypeTabs::default();
+        let mut recent_pdb_files: Vec<PathBuf> = vec![];
         if let Some(storage) = cc.storage {
-            settings = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default()
+            settings = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+            type_tabs = eframe::get_value(storage, TYPE_TABS_KEY).unwrap_or_default();
+            recent_pdb_files = eframe::get_value(storage, RECENT_PDB_FILES_KEY).unwrap_or_default();
         }
+        // Forget the files that don't exist anymore
+        recent_pdb_files.retain(|pdb_path| pdb_path.is_file());
 
         log::info!("{} {}", PKG_NAME, PKG_VERSION);
-        Ok(Self {
+        let mut app = Self {
             logger,
             current_mode: ResymAppMode::Idle,
             filtered_type_list: vec![],
@@ -185,19 +316,50 @@ impl<'p> ResymApp {
             console_content: vec![],
             settings_wnd_open: false,
             settings,
+            type_tabs,
+            find_bar: FindBar::default(),
+            loading_pdb_path: None,
+            recent_pdb_files,
+            progress: None,
+            system_prefers_dark_mode: cc.integration_info.prefer_dark_mode,
+            toast: None,
             frontend_controller,
             backend,
-        })
+        };
+        if app.settings.open_last_pdb_on_startup {
+            if let Some(pdb_path) = app.recent_pdb_files.first().cloned() {
+                app.load_pdb_file(pdb_path);
+            }
+        }
+
+        Ok(app)
+    }
+
+    /// Load the given PDB file into the main slot.
+    fn load_pdb_file(&mut self, file_path: PathBuf) {
+        self.loading_pdb_path = Some(file_path.clone());
+        // Note: PDB files are read rather than mapped, as they may be rebuilt
+        // while being browsed
+        if let Err(err) = self
+            .backend
+            .send_command(BackendCommand::LoadPDBBuffered(PDB_MAIN_SLOT, file_path))
+        {
+            log::error!("Failed to load the PDB file: {}", err);
+        }
     }
 
-    fn process_ui_commands(&mut self) {
+    fn process_ui_commands(&mut self, ctx: &egui::Context) {
         while let Ok(cmd) = self.frontend_controller.rx_ui.try_recv() {
             match cmd {
-                FrontendCommand::LoadPDBResult(result) => match result {
+                FrontendCommand::LoadPDBResult(pdb_slot, result) => match result {
                     Err(err) => {
+                        self.progress = None;
+                        if pdb_slot == PDB_MAIN_SLOT {
+                            self.loading_pdb_path = None;
+                        }
                         log::error!("Failed to load PDB file: {}", err);
                     }
-                    Ok(pdb_slot) => {
+                    Ok(()) => {
                         if pdb_slot == PDB_MAIN_SLOT {
                             // Unload the PDB used for diffing if one is loaded
                             if let ResymAppMode::Comparing(..) = self.current_mode {
@@ -212,27 +374,32 @@ impl<'p> ResymApp {
                                 }
                             }
 
-                            self.current_mode =
-                                ResymAppMode::Browsing(String::default(), 0, String::default());
+                            self.current_mode = ResymAppMode::Browsing;
+                            let loaded_pdb_path = self.loading_pdb_path.take();
+                            if let Some(pdb_path) = &loaded_pdb_path {
+                                self.recent_pdb_files.retain(|path| path != pdb_path);
+                                self.recent_pdb_files.insert(0, pdb_path.clone());
+                                self.recent_pdb_files.truncate(MAX_RECENT_PDB_FILES);
+                            }
+                            // Reconstruct the types of the tabs that are kept
+                            for command in self.type_tabs.on_pdb_loaded(loaded_pdb_path) {
+                                if let Err(err) = self.backend.send_command(command) {
+                                    log::error!("Failed to reconstruct type: {}", err);
+                                }
+                            }
                             // Request a type list update
                             if let Err(err) =
                                 self.backend.send_command(BackendCommand::UpdateTypeFilter(
                                     PDB_MAIN_SLOT,
                                     String::default(),
                                     false,
-                                    false,
+                                    FilterMatchMode::Substring,
                                 ))
                             {
                                 log::error!("Failed to update type filter value: {}", err);
                             }
                         } else if pdb_slot == PDB_DIFF_SLOT {
-                            self.current_mode = ResymAppMode::Comparing(
-                                String::default(),
-                                String::default(),
-                                0,
-                                vec![],
-                                String::default(),
-                            );
+                            self.current_mode = ResymAppMode::Comparing(Box::default());
                             // Request a type list update
                             if let Err(err) =
                                 self.backend
@@ -240,7 +407,7 @@ impl<'p> ResymApp {
                                         vec![PDB_MAIN_SLOT, PDB_DIFF_SLOT],
                                         String::default(),
                                         false,
-                                        false,
+                                        FilterMatchMode::Substring,
                                     ))
                             {
                                 log::error!("Failed to update type filter value: {}", err);
@@ -249,23 +416,19 @@ impl<'p> ResymApp {
                     }
                 },
 
-                FrontendCommand::ReconstructTypeResult(type_reconstruction_result) => {
+                FrontendCommand::ReconstructTypeResult(_, type_reconstruction_result) => {
                     match type_reconstruction_result {
                         Err(err) => {
                             log::error!("Failed to reconstruct type: {}", err);
+                            self.type_tabs.on_type_reconstructed(None);
                         }
                         Ok(reconstructed_type) => {
-                            let last_line_number = 1 + reconstructed_type.lines().count();
-                            let line_numbers =
-                                (1..last_line_number).fold(String::default(), |mut acc, e| {
-                                    let _r = writeln!(&mut acc, "{}", e);
-                                    acc
-                                });
-                            self.current_mode = ResymAppMode::Browsing(
-                                line_numbers,
-                                last_line_number,
-                                reconstructed_type,
-                            );
+                            if let Some((file_path, exported_type)) = self
+                                .type_tabs
+                                .on_type_reconstructed(Some(reconstructed_type))
+                            {
+                                write_export(&file_path, &exported_type);
+                            }
                         }
                     }
                 }
@@ -275,49 +438,80 @@ impl<'p> ResymApp {
                         log::error!("Failed to diff type: {}", err);
                     }
                     Ok(type_diff) => {
-                        let mut last_line_number = 1;
-                        let (line_numbers_old, line_numbers_new, line_changes) =
-                            type_diff.metadata.iter().fold(
-                                (String::default(), String::default(), vec![]),
-                                |(mut acc_old, mut acc_new, mut acc_changes), metadata| {
-                                    let indices = metadata.0;
-
-                                    if let Some(indice) = indices.0 {
-                                        last_line_number =
-                                            std::cmp::max(last_line_number, 1 + indice);
-                                        let _r = writeln!(&mut acc_old, "{}", 1 + indice);
-                                    } else {
-                                        let _r = writeln!(&mut acc_old);
-                                    }
+                        self.current_mode =
+                            ResymAppMode::Comparing(Box::new(TypeDiffView::new(type_diff)));
+                    }
+                },
 
-                                    if let Some(indice) = indices.1 {
-                                        last_line_number =
-                                            std::cmp::max(last_line_number, 1 + indice);
-                                        let _r = writeln!(&mut acc_new, "{}", 1 + indice);
-                                    } else {
-                                        let _r = writeln!(&mut acc_new);
-                                    }
+                FrontendCommand::UpdateFilteredTypes(_, filtered_types_result) => {
+                    match filtered_types_result {
+                        Err(err) => {
+                            // The filter may be incomplete while being typed
+                            log::debug!("Failed to filter types: {}", err);
+                            self.filtered_type_list.clear();
+                        }
+                        Ok(filtered_types) => {
+                            self.filtered_type_list = filtered_types;
+                        }
+                    }
+                    self.selected_row = usize::MAX;
+                }
 
-                                    acc_changes.push(metadata.1);
+                FrontendCommand::Cancelled => {
+                    self.progress = None;
+                    log::info!("Operation cancelled");
+                }
 
-                                    (acc_old, acc_new, acc_changes)
-                                },
-                            );
+                FrontendCommand::UpdateProgress {
+                    current,
+                    total,
+                    stage,
+                } => {
+                    self.progress = (current < total).then_some((current, total, stage));
+                }
 
-                        self.current_mode = ResymAppMode::Comparing(
-                            line_numbers_old,
-                            line_numbers_new,
-                            last_line_number,
-                            line_changes,
-                            type_diff.data,
-                        );
+                FrontendCommand::GetTypeModelResult(_, type_model_result) => {
+                    match type_model_result
+                        .map_err(anyhow::Error::from)
+                        .and_then(|type_model| Ok(serde_json::to_string_pretty(&type_model)?))
+                    {
+                        Err(err) => {
+                            log::error!("Failed to retrieve type model: {}", err);
+                        }
+                        Ok(type_model_json) => {
+                            ctx.output().copied_text = type_model_json;
+                            log::info!("Copied the type model to the clipboard");
+                        }
                     }
-                },
-
-                FrontendCommand::UpdateFilteredTypes(filtered_types) => {
-                    self.filtered_type_list = filtered_types;
-                    self.selected_row = usize::MAX;
                 }
+
+                // Reconstructing all types, filtering types by size,
+                // streaming type lists, browsing
+                // symbols, globals and modules, computing statistics,
+                // checking PDBs, verifying PEs, reconstructing COM
+                // definitions, retrieving source information, searching
+                // fields, finding referencing types, building type graphs,
+                // semantic diffs and type list diffs aren't supported by the
+                // GUI
+                FrontendCommand::ReconstructAllTypesChunk(_)
+                | FrontendCommand::FilterTypesResult(..)
+                | FrontendCommand::ListTypesChunk(..)
+                | FrontendCommand::ListTypesResult(..)
+                | FrontendCommand::ReconstructAllTypesResult(_)
+                | FrontendCommand::ListSymbolsResult(..)
+                | FrontendCommand::ListGlobalsResult(..)
+                | FrontendCommand::ResolveAddressResult(..)
+                | FrontendCommand::ListModulesResult(..)
+                | FrontendCommand::ComputeStatisticsResult(..)
+                | FrontendCommand::CheckPDBResult(..)
+                | FrontendCommand::VerifyPEResult(..)
+                | FrontendCommand::ReconstructComDefinitionsResult(..)
+                | FrontendCommand::GetSourceInfoResult(..)
+                | FrontendCommand::FindTypesWithFieldResult(..)
+                | FrontendCommand::FindReferencingTypesResult(..)
+                | FrontendCommand::BuildTypeGraphResult(..)
+                | FrontendCommand::SemanticDiffTypeResult(_)
+                | FrontendCommand::DiffTypeListsResult(_) => {}
             }
         }
     }
@@ -328,31 +522,79 @@ impl<'p> ResymApp {
                 if ui.button("Open PDB file").clicked() {
                     ui.close_menu();
                     if let Some(file_path) = Self::select_pdb_file() {
-                        if let Err(err) = self
-                            .backend
-                            .send_command(BackendCommand::LoadPDB(PDB_MAIN_SLOT, file_path.into()))
-                        {
-                            log::error!("Failed to load the PDB file: {}", err);
-                        }
+                        self.load_pdb_file(file_path.into());
                     }
                 }
+                ui.add_enabled_ui(!self.recent_pdb_files.is_empty(), |ui| {
+                    ui.menu_button("Open recent", |ui| {
+                        let mut selected_pdb_path = None;
+                        for pdb_path in &self.recent_pdb_files {
+                            if ui.button(pdb_path.display().to_string()).clicked() {
+                                selected_pdb_path = Some(pdb_path.clone());
+                            }
+                        }
+                        if let Some(pdb_path) = selected_pdb_path {
+                            ui.close_menu();
+                            if pdb_path.is_file() {
+                                self.load_pdb_file(pdb_path);
+                            } else {
+                                log::error!("'{}' does not exist", pdb_path.display());
+                                self.recent_pdb_files.retain(|path| *path != pdb_path);
+                            }
+                        }
+                    });
+                });
                 if ui
                     .add_enabled(
-                        matches!(self.current_mode, ResymAppMode::Browsing(..)),
+                        matches!(self.current_mode, ResymAppMode::Browsing),
                         egui::Button::new("Compare with..."),
                     )
                     .clicked()
                 {
                     ui.close_menu();
                     if let Some(file_path) = Self::select_pdb_file() {
-                        if let Err(err) = self
-                            .backend
-                            .send_command(BackendCommand::LoadPDB(PDB_DIFF_SLOT, file_path.into()))
-                        {
+                        if let Err(err) = self.backend.send_command(
+                            BackendCommand::LoadPDBBuffered(PDB_DIFF_SLOT, file_path.into()),
+                        ) {
                             log::error!("Failed to load the PDB file: {}", err);
                         }
                     }
                 }
+                if ui
+                    .add_enabled(
+                        matches!(self.current_mode, ResymAppMode::Browsing),
+                        egui::Button::new("Reload PDB file"),
+                    )
+                    .clicked()
+                {
+                    ui.close_menu();
+                    if let Err(err) = self
+                        .backend
+                        .send_command(BackendCommand::ReloadPDB(PDB_MAIN_SLOT))
+                    {
+                        log::error!("Failed to reload the PDB file: {}", err);
+                    }
+                }
+                ui.add_enabled_ui(
+                    matches!(self.current_mode, ResymAppMode::Browsing)
+                        && self.type_tabs.current().is_some(),
+                    |ui| {
+                        ui.menu_button("Export…", |ui| {
+                            for (format_name, extension, export_format) in EXPORT_FORMATS {
+                                if ui.button(*format_name).clicked() {
+                                    ui.close_menu();
+                                    self.export_current_tab(format_name, extension, *export_format);
+                                }
+                            }
+                        });
+                    },
+                );
+                if ui.button("Cancel loading").clicked() {
+                    ui.close_menu();
+                    if let Err(err) = self.backend.send_command(BackendCommand::Cancel) {
+                        log::error!("Failed to cancel the current operation: {}", err);
+                    }
+                }
                 if ui.button("Settings").clicked() {
                     ui.close_menu();
                     self.settings_wnd_open = true;
@@ -362,13 +604,30 @@ impl<'p> ResymApp {
                     frame.quit();
                 }
             });
+            ui.menu_button("View", |ui| {
+                update_theme_selector(ui, &mut self.settings);
+                ui.separator();
+                ui.checkbox(
+                    &mut self.settings.side_by_side_diff,
+                    "Side-by-side differences",
+                );
+            });
+
+            if let Some((current, total, stage)) = self.progress {
+                ui.separator();
+                ui.add(egui::Spinner::new());
+                ui.label(format!("{} ({}%)", stage, current * 100 / total));
+            }
         });
     }
 
     fn update_type_list(&mut self, ui: &mut egui::Ui) {
         let num_rows = self.filtered_type_list.len();
-        const TEXT_STYLE: TextStyle = TextStyle::Body;
-        let row_height = ui.text_style_height(&TEXT_STYLE);
+        // Only the visible rows are laid out, which requires knowing the
+        // exact height of the rows (i.e., of selectable labels)
+        const TEXT_STYLE: TextStyle = TextStyle::Button;
+        let row_height = (ui.text_style_height(&TEXT_STYLE) + 2.0 * ui.spacing().button_padding.y)
+            .max(ui.spacing().interact_size.y);
         ui.with_layout(
             egui::Layout::top_down(egui::Align::Min).with_cross_justify(true),
             |ui| {
@@ -384,18 +643,15 @@ impl<'p> ResymApp {
                             {
                                 self.selected_row = row_index;
                                 match self.current_mode {
-                                    ResymAppMode::Browsing(..) => {
-                                        if let Err(err) = self.backend.send_command(
-                                            BackendCommand::ReconstructTypeByIndex(
-                                                PDB_MAIN_SLOT,
-                                                *type_index,
-                                                self.settings.primitive_types_flavor,
-                                                self.settings.print_header,
-                                                self.settings.reconstruct_dependencies,
-                                                self.settings.print_access_specifiers,
-                                            ),
+                                    ResymAppMode::Browsing => {
+                                        if let Some(command) = self.type_tabs.open(
+                                            type_name,
+                                            *type_index,
+                                            self.settings.dump_flags(),
                                         ) {
-                                            log::error!("Failed to reconstruct type: {}", err);
+                                            if let Err(err) = self.backend.send_command(command) {
+                                                log::error!("Failed to reconstruct type: {}", err);
+                                            }
                                         }
                                     }
                                     ResymAppMode::Comparing(..) => {
@@ -407,7 +663,9 @@ impl<'p> ResymApp {
                                                 self.settings.primitive_types_flavor,
                                                 self.settings.print_header,
                                                 self.settings.reconstruct_dependencies,
-                                                self.settings.print_access_specifiers,
+                                                self.settings
+                                                    .dump_flags()
+                                                    .data_format_configuration(),
                                             ),
                                         ) {
                                             log::error!("Failed to reconstruct type diff: {}", err);
@@ -445,24 +703,348 @@ impl<'p> ResymApp {
             });
     }
 
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped_file_paths = ctx
+            .input()
+            .raw
+            .dropped_files
+            .iter()
+            .filter_map(|dropped_file| dropped_file.path.clone())
+            .collect::<Vec<_>>();
+        // Only a single PDB can be browsed, open the first one
+        let dropped_pdb_path = dropped_file_paths
+            .iter()
+            .find(|file_path| is_pdb_file(file_path));
+        match dropped_pdb_path {
+            Some(pdb_path) => self.load_pdb_file(pdb_path.clone()),
+            None => {
+                if let Some(file_path) = dropped_file_paths.first() {
+                    log::error!("'{}' isn't a PDB file", file_path.display());
+                    self.toast = Some((
+                        "Only PDB files can be opened".to_string(),
+                        ctx.input().time + TOAST_DURATION,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn update_drag_and_drop_overlay(&self, ctx: &egui::Context) {
+        let hovered_file_count = ctx.input().raw.hovered_files.len();
+        if hovered_file_count == 0 {
+            return;
+        }
+
+        // Dim the window while files are dragged onto it
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("drag_and_drop_overlay"),
+        ));
+        let screen_rect = ctx.input().screen_rect();
+        painter.rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(192));
+        painter.text(
+            screen_rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "Drop a PDB file to open it",
+            egui::FontId::proportional(20.0),
+            egui::Color32::WHITE,
+        );
+    }
+
+    fn update_toast(&mut self, ctx: &egui::Context) {
+        let message = match &self.toast {
+            Some((message, expiration_time)) if *expiration_time > ctx.input().time => message,
+            _ => {
+                self.toast = None;
+                return;
+            }
+        };
+
+        egui::Area::new("toast")
+            .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -40.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(message.as_str());
+                });
+            });
+        // Keep repainting until the toast expires
+        ctx.request_repaint();
+    }
+
+    /// Return the text displayed in the code view, if any.
+    fn displayed_code<'a>(
+        current_mode: &'a ResymAppMode,
+        type_tabs: &'a TypeTabs,
+    ) -> Option<&'a str> {
+        match current_mode {
+            ResymAppMode::Browsing => type_tabs
+                .current()
+                .map(|tab| tab.reconstructed_type.as_str()),
+            ResymAppMode::Comparing(type_diff) => Some(type_diff.code.as_str()),
+            ResymAppMode::Idle => None,
+        }
+    }
+
+    fn update_find_bar(&mut self, ui: &mut egui::Ui) {
+        let find_bar = &mut self.find_bar;
+        let mut query_changed = false;
+        let mut cycle_backwards = None;
+        ui.horizontal(|ui| {
+            ui.label("Find");
+            let response = ui.text_edit_singleline(&mut find_bar.query);
+            if find_bar.focus_query {
+                response.request_focus();
+                find_bar.focus_query = false;
+            }
+            query_changed |= response.changed();
+            // Enter jumps to the next match (and Shift-Enter to the previous
+            // one), the text edit loses the focus in the process
+            if response.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
+                cycle_backwards = Some(ui.input().modifiers.shift);
+                response.request_focus();
+            }
+            if (response.has_focus() || response.lost_focus())
+                && ui.input().key_pressed(egui::Key::Escape)
+            {
+                find_bar.open = false;
+            }
+            query_changed |= ui
+                .checkbox(&mut find_bar.case_sensitive, "Match case")
+                .changed();
+            query_changed |= ui.checkbox(&mut find_bar.use_regex, "Regex").changed();
+            if ui.button("⏶").on_hover_text("Previous match").clicked() {
+                cycle_backwards = Some(true);
+            }
+            if ui.button("⏷").on_hover_text("Next match").clicked() {
+                cycle_backwards = Some(false);
+            }
+        });
+
+        if query_changed {
+            find_bar.current_match = 0;
+            find_bar.scroll_to_current_match = true;
+        }
+        let code = Self::displayed_code(&self.current_mode, &self.type_tabs).unwrap_or_default();
+        find_bar.update_matches(code);
+        let match_count = find_bar.matches().map_or(0, |matches| matches.len());
+        if let Some(backwards) = cycle_backwards {
+            find_bar.cycle(match_count, backwards);
+        }
+
+        ui.horizontal(|ui| {
+            match find_bar.matches() {
+                Err(err) => ui.label(format!("Invalid regular expression: {}", err)),
+                Ok([]) => ui.label("No matches"),
+                Ok(matches) => ui.label(format!(
+                    "Match {} of {}",
+                    1 + find_bar.current_match.min(matches.len() - 1),
+                    matches.len()
+                )),
+            };
+            if ui.small_button("Close").clicked() {
+                find_bar.open = false;
+            }
+        });
+        ui.add_space(4.0);
+    }
+
+    fn update_copy_buttons(&mut self, ui: &mut egui::Ui) {
+        // Note: Ctrl-C also copies the whole code view's content when
+        // nothing is selected
+        let content = Self::displayed_code(&self.current_mode, &self.type_tabs);
+        if ui
+            .add_enabled(
+                content.is_some_and(|content| !content.is_empty()),
+                egui::Button::new("Copy"),
+            )
+            .clicked()
+        {
+            ui.output().copied_text = content.unwrap_or_default().to_string();
+        }
+
+        let tab = match self.current_mode {
+            ResymAppMode::Browsing => self.type_tabs.current(),
+            _ => None,
+        };
+        if ui
+            .add_enabled(tab.is_some(), egui::Button::new("Copy as JSON"))
+            .clicked()
+        {
+            if let Some(tab) = tab {
+                if let Err(err) = self.backend.send_command(BackendCommand::GetTypeModel(
+                    PDB_MAIN_SLOT,
+                    tab.type_name.clone(),
+                    tab.dump_flags.primitive_types_flavor,
+                    tab.dump_flags.reconstruct_dependencies,
+                )) {
+                    log::error!("Failed to retrieve type model: {}", err);
+                }
+            }
+        }
+    }
+
+    fn update_tab_bar(&mut self, ui: &mut egui::Ui) {
+        let mut selected_tab = None;
+        let mut closed_tab = None;
+        ui.horizontal(|ui| {
+            // Navigate through the type references followed with Ctrl-click
+            if ui
+                .add_enabled(self.type_tabs.can_navigate_back(), egui::Button::new("⏴"))
+                .on_hover_text("Back (Alt-Left)")
+                .clicked()
+            {
+                self.type_tabs.navigate_history(true);
+            }
+            if ui
+                .add_enabled(
+                    self.type_tabs.can_navigate_forward(),
+                    egui::Button::new("⏵"),
+                )
+                .on_hover_text("Forward (Alt-Right)")
+                .clicked()
+            {
+                self.type_tabs.navigate_history(false);
+            }
+            ui.separator();
+
+            ScrollArea::horizontal()
+                .id_source("tab_bar")
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        let current_tab_index = self.type_tabs.current_tab_index();
+                        for (tab_index, tab) in self.type_tabs.tabs().iter().enumerate() {
+                            let response =
+                                ui.selectable_label(tab_index == current_tab_index, &tab.type_name);
+                            if response.clicked() {
+                                selected_tab = Some(tab_index);
+                            }
+                            // Tabs can be closed with a middle-click, like in editors
+                            if ui.small_button("🗙").clicked() || response.middle_clicked() {
+                                closed_tab = Some(tab_index);
+                            }
+                            ui.separator();
+                        }
+                    });
+                });
+        });
+        if let Some(tab_index) = selected_tab {
+            self.type_tabs.select(tab_index);
+        }
+        if let Some(tab_index) = closed_tab {
+            self.type_tabs.close(tab_index);
+        }
+
+        // Flags the current tab's type is reconstructed with
+        let tab = match self.type_tabs.current_mut() {
+            Some(tab) => tab,
+            None => return,
+        };
+        let mut dump_flags = tab.dump_flags.clone();
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("tab_primitive_types_flavor")
+                .selected_text(format!("{:?}", dump_flags.primitive_types_flavor))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(
+                        &mut dump_flags.primitive_types_flavor,
+                        PrimitiveReconstructionFlavor::Portable,
+                        "Portable",
+                    );
+                    ui.selectable_value(
+                        &mut dump_flags.primitive_types_flavor,
+                        PrimitiveReconstructionFlavor::Microsoft,
+                        "Microsoft",
+                    );
+                    ui.selectable_value(
+                        &mut dump_flags.primitive_types_flavor,
+                        PrimitiveReconstructionFlavor::Raw,
+                        "Raw",
+                    );
+                });
+            ui.checkbox(&mut dump_flags.print_header, "Print header");
+            ui.checkbox(
+                &mut dump_flags.reconstruct_dependencies,
+                "Print definitions of referenced types",
+            );
+            ui.checkbox(
+                &mut dump_flags.print_access_specifiers,
+                "Print access specifiers",
+            );
+        });
+        if dump_flags != tab.dump_flags {
+            tab.dump_flags = dump_flags;
+            let command = self
+                .type_tabs
+                .reconstruction_command(self.type_tabs.current_tab_index());
+            if let Err(err) = self.backend.send_command(command) {
+                log::error!("Failed to reconstruct type: {}", err);
+            }
+        }
+        ui.add_space(4.0);
+    }
+
     fn update_code_view(&mut self, ui: &mut egui::Ui) {
         const LANGUAGE_SYNTAX: &str = "cpp";
-        let theme = if self.settings.use_light_theme {
-            CodeTheme::light()
-        } else {
-            CodeTheme::dark()
+        let theme = self.code_theme();
+
+        let (line_desc, side_by_side_diff) = match &self.current_mode {
+            ResymAppMode::Comparing(type_diff) => (
+                Some(&type_diff.line_changes),
+                self.settings.side_by_side_diff.then_some(type_diff),
+            ),
+            _ => (None, None),
         };
 
-        let line_desc =
-            if let ResymAppMode::Comparing(_, _, _, line_changes, _) = &self.current_mode {
-                Some(line_changes)
-            } else {
-                None
-            };
+        let code_font_id = egui::FontId::monospace(self.settings.code_font_size);
 
-        // Layouter that'll disable wrapping and apply syntax highlighting if needed
-        let mut layouter = |ui: &egui::Ui, string: &str, _wrap_width: f32| {
-            let layout_job = highlight_code(
+        // Highlight the search results, if searching
+        let code = Self::displayed_code(&self.current_mode, &self.type_tabs);
+        let mut text_highlights = vec![];
+        let mut scroll_target = None;
+        if let (true, Some(code)) = (self.find_bar.open, code) {
+            self.find_bar.update_matches(code);
+            if let Ok(matches) = self.find_bar.matches() {
+                let current_match = self.find_bar.current_match;
+                text_highlights = matches
+                    .iter()
+                    .enumerate()
+                    .map(|(match_index, range)| {
+                        let color = if match_index == current_match {
+                            FIND_CURRENT_MATCH_COLOR
+                        } else {
+                            FIND_MATCH_COLOR
+                        };
+                        (range.clone(), color)
+                    })
+                    .collect::<Vec<_>>();
+                if self.find_bar.scroll_to_current_match {
+                    scroll_target = matches.get(current_match).cloned();
+                }
+            }
+            self.find_bar.scroll_to_current_match = false;
+        }
+
+        // Search results are mapped to the sides of side-by-side diffs
+        let side_highlights = side_by_side_diff.map(|type_diff| {
+            [&type_diff.old, &type_diff.new].map(|side| {
+                text_highlights
+                    .iter()
+                    .filter_map(|(range, color)| {
+                        type_diff
+                            .map_range_to_side(side, range)
+                            .map(|side_range| (side_range, *color))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        // Disable wrapping and apply syntax highlighting if needed
+        let layout = |ui: &egui::Ui,
+                      string: &str,
+                      line_desc: Option<&LineDescriptions>,
+                      text_highlights: &TextHighlights| {
+            let mut layout_job = highlight_code(
                 ui.ctx(),
                 &theme,
                 string,
@@ -470,98 +1052,217 @@ impl<'p> ResymApp {
                 self.settings.enable_syntax_hightlighting,
                 line_desc,
             );
+            apply_text_highlights(&mut layout_job, text_highlights);
+            set_font_size(&mut layout_job, self.settings.code_font_size);
             ui.fonts().layout_job(layout_job)
         };
+        let mut layouter = |ui: &egui::Ui, string: &str, _wrap_width: f32| {
+            layout(ui, string, line_desc, &text_highlights)
+        };
 
-        // Type dump area
-        egui::ScrollArea::both()
-            .auto_shrink([false, false])
-            .show(ui, |ui| {
-                const LINE_NUMBER_DIGIT_WIDTH: usize = 10;
-                let (num_colums, min_column_width) = if self.settings.print_line_numbers {
-                    match self.current_mode {
-                        ResymAppMode::Comparing(_, _, last_line_number, ..) => {
-                            // Compute the columns' sizes from the number of digits
-                            let char_count = int_log10(last_line_number);
-                            let line_number_width = (char_count * LINE_NUMBER_DIGIT_WIDTH) as f32;
-
-                            // Old index + new index + code editor
-                            (3, line_number_width)
-                        }
-                        ResymAppMode::Browsing(_, last_line_number, _) => {
-                            // Compute the columns' sizes from the number of digits
-                            let char_count = int_log10(last_line_number);
-                            let line_number_width = (char_count * LINE_NUMBER_DIGIT_WIDTH) as f32;
+        // Type dump area, tabs have their own scroll position
+        let mut scroll_area = egui::ScrollArea::both().auto_shrink([false, false]);
+        if let (ResymAppMode::Browsing, Some(tab)) = (&self.current_mode, self.type_tabs.current())
+        {
+            scroll_area = scroll_area
+                .id_source(("type_tab", tab.id))
+                .scroll_offset(tab.scroll_offset);
+        }
+        let mut referenced_type_name = None;
+        let scroll_area_output = scroll_area.show(ui, |ui| {
+            // Width of digits at the default font size
+            const LINE_NUMBER_DIGIT_WIDTH: usize = 10;
+            let font_scale = self.settings.code_font_size / DEFAULT_CODE_FONT_SIZE;
+            let (num_colums, min_column_width) = if self.settings.print_line_numbers {
+                match &self.current_mode {
+                    ResymAppMode::Comparing(type_diff) => {
+                        // Compute the columns' sizes from the number of digits
+                        let char_count = int_log10(type_diff.last_line_number);
+                        let line_number_width =
+                            (char_count * LINE_NUMBER_DIGIT_WIDTH) as f32 * font_scale;
 
-                            // Line numbers + code editor
-                            (2, line_number_width)
-                        }
-                        _ => {
-                            // Code editor only
-                            (1, 0.0)
-                        }
+                        // Old index + new index + code editor, or old index +
+                        // old code editor + new index + new code editor
+                        (
+                            if side_by_side_diff.is_some() { 4 } else { 3 },
+                            line_number_width,
+                        )
                     }
-                } else {
-                    // Code editor only
-                    (1, 0.0)
-                };
-
-                egui::Grid::new("code_editor_grid")
-                    .num_columns(num_colums)
-                    .min_col_width(min_column_width)
-                    .show(ui, |ui| {
-                        match &self.current_mode {
-                            ResymAppMode::Comparing(
-                                line_numbers_old,
-                                line_numbers_new,
-                                _,
-                                _,
-                                reconstructed_type_diff,
-                            ) => {
+                    ResymAppMode::Browsing => {
+                        let last_line_number = self
+                            .type_tabs
+                            .current()
+                            .map(|tab| tab.last_line_number)
+                            .unwrap_or_default();
+                        // Compute the columns' sizes from the number of digits
+                        let char_count = int_log10(last_line_number);
+                        let line_number_width =
+                            (char_count * LINE_NUMBER_DIGIT_WIDTH) as f32 * font_scale;
+
+                        // Line numbers + code editor
+                        (2, line_number_width)
+                    }
+                    _ => {
+                        // Code editor only
+                        (1, 0.0)
+                    }
+                }
+            } else if side_by_side_diff.is_some() {
+                // Old code editor + new code editor
+                (2, 0.0)
+            } else {
+                // Code editor only
+                (1, 0.0)
+            };
+            // Sides of side-by-side diffs share the available width
+            let side_width = (ui.available_width()
+                - (num_colums - 2) as f32 * min_column_width
+                - (num_colums - 1) as f32 * ui.spacing().item_spacing.x)
+                / 2.0;
+
+            egui::Grid::new("code_editor_grid")
+                .num_columns(num_colums)
+                .min_col_width(min_column_width)
+                .show(ui, |ui| {
+                    match &self.current_mode {
+                        ResymAppMode::Comparing(type_diff) if side_by_side_diff.is_some() => {
+                            for (side, side_highlights) in [&type_diff.old, &type_diff.new]
+                                .into_iter()
+                                .zip(side_highlights.iter().flatten())
+                            {
                                 // Line numbers
                                 if self.settings.print_line_numbers {
                                     ui.add(
-                                        egui::TextEdit::multiline(&mut line_numbers_old.as_str())
-                                            .interactive(false)
-                                            .desired_width(min_column_width),
-                                    );
-                                    ui.add(
-                                        egui::TextEdit::multiline(&mut line_numbers_new.as_str())
+                                        egui::TextEdit::multiline(&mut side.line_numbers.as_str())
                                             .interactive(false)
+                                            .font(code_font_id.clone())
                                             .desired_width(min_column_width),
                                     );
                                 }
                                 // Text content
+                                let output = egui::TextEdit::multiline(&mut side.code.as_str())
+                                    .code_editor()
+                                    .desired_width(side_width)
+                                    .layouter(&mut |ui: &egui::Ui, string: &str, _wrap_width| {
+                                        layout(
+                                            ui,
+                                            string,
+                                            Some(&side.line_changes),
+                                            side_highlights,
+                                        )
+                                    })
+                                    .show(ui);
+                                if let Some(range) = &scroll_target {
+                                    if let Some(side_range) =
+                                        type_diff.map_range_to_side(side, range)
+                                    {
+                                        scroll_to_byte_index(
+                                            ui,
+                                            &output,
+                                            &side.code,
+                                            side_range.start,
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                        ResymAppMode::Comparing(type_diff) => {
+                            // Line numbers
+                            if self.settings.print_line_numbers {
                                 ui.add(
                                     egui::TextEdit::multiline(
-                                        &mut reconstructed_type_diff.as_str(),
+                                        &mut type_diff.line_numbers_old.as_str(),
                                     )
-                                    .code_editor()
-                                    .layouter(&mut layouter),
+                                    .interactive(false)
+                                    .font(code_font_id.clone())
+                                    .desired_width(min_column_width),
+                                );
+                                ui.add(
+                                    egui::TextEdit::multiline(
+                                        &mut type_diff.line_numbers_new.as_str(),
+                                    )
+                                    .interactive(false)
+                                    .font(code_font_id.clone())
+                                    .desired_width(min_column_width),
                                 );
                             }
-                            ResymAppMode::Browsing(line_numbers, _, reconstructed_type_content) => {
+                            // Text content
+                            let output = egui::TextEdit::multiline(&mut type_diff.code.as_str())
+                                .code_editor()
+                                .layouter(&mut layouter)
+                                .show(ui);
+                            if let Some(range) = &scroll_target {
+                                scroll_to_byte_index(ui, &output, &type_diff.code, range.start);
+                            }
+                        }
+                        ResymAppMode::Browsing => {
+                            if let Some(tab) = self.type_tabs.current() {
                                 // Line numbers
                                 if self.settings.print_line_numbers {
                                     ui.add(
-                                        egui::TextEdit::multiline(&mut line_numbers.as_str())
+                                        egui::TextEdit::multiline(&mut tab.line_numbers.as_str())
                                             .interactive(false)
+                                            .font(code_font_id.clone())
                                             .desired_width(min_column_width),
                                     );
                                 }
                                 // Text content
-                                ui.add(
-                                    egui::TextEdit::multiline(
-                                        &mut reconstructed_type_content.as_str(),
-                                    )
-                                    .code_editor()
-                                    .layouter(&mut layouter),
-                                );
+                                let output =
+                                    egui::TextEdit::multiline(&mut tab.reconstructed_type.as_str())
+                                        .code_editor()
+                                        .layouter(&mut layouter)
+                                        .show(ui);
+                                if let Some(range) = &scroll_target {
+                                    scroll_to_byte_index(
+                                        ui,
+                                        &output,
+                                        &tab.reconstructed_type,
+                                        range.start,
+                                    );
+                                }
+                                // Ctrl-click on a type name opens its
+                                // reconstruction
+                                if output.response.clicked() && ui.input().modifiers.command {
+                                    if let Some(pointer_pos) =
+                                        output.response.interact_pointer_pos()
+                                    {
+                                        let cursor = output
+                                            .galley
+                                            .cursor_from_pos(pointer_pos - output.text_draw_pos);
+                                        referenced_type_name = identifier_at(
+                                            &tab.reconstructed_type,
+                                            cursor.ccursor.index,
+                                        )
+                                        .map(str::to_string);
+                                    }
+                                }
                             }
-                            ResymAppMode::Idle => {}
                         }
-                    });
-            });
+                        ResymAppMode::Idle => {}
+                    }
+                });
+        });
+
+        if let (ResymAppMode::Browsing, Some(tab)) =
+            (&self.current_mode, self.type_tabs.current_mut())
+        {
+            // Keep the position while the type is being reconstructed (e.g.,
+            // for restored tabs), as it's clamped to the empty content
+            if !tab.reconstructed_type.is_empty() {
+                tab.scroll_offset = scroll_area_output.state.offset;
+            }
+        }
+
+        if let Some(type_name) = referenced_type_name {
+            if let Some(command) = self
+                .type_tabs
+                .navigate_to(&type_name, self.settings.dump_flags())
+            {
+                if let Err(err) = self.backend.send_command(command) {
+                    log::error!("Failed to reconstruct type: {}", err);
+                }
+            }
+        }
     }
 
     fn update_settings_window(&mut self, ctx: &egui::Context) {
@@ -572,11 +1273,14 @@ impl<'p> ResymApp {
             .collapsible(false)
             .show(ctx, |ui| {
                 ui.label("Theme");
-                // Show radio-buttons to switch between light and dark mode.
-                ui.horizontal(|ui| {
-                    ui.selectable_value(&mut self.settings.use_light_theme, true, "☀ Light");
-                    ui.selectable_value(&mut self.settings.use_light_theme, false, "🌙 Dark");
-                });
+                update_theme_selector(ui, &mut self.settings);
+                ui.add_space(5.0);
+
+                ui.label("Startup");
+                ui.checkbox(
+                    &mut self.settings.open_last_pdb_on_startup,
+                    "Open the last PDB file",
+                );
                 ui.add_space(5.0);
 
                 ui.label("Search");
@@ -588,6 +1292,13 @@ impl<'p> ResymApp {
                     &mut self.settings.search_use_regex,
                     "Enable regular expressions",
                 );
+                ui.add_enabled(
+                    !self.settings.search_use_regex,
+                    egui::Checkbox::new(
+                        &mut self.settings.search_use_fuzzy_matching,
+                        "Enable fuzzy matching",
+                    ),
+                );
                 ui.add_space(5.0);
 
                 ui.label("Type reconstruction");
@@ -630,9 +1341,79 @@ impl<'p> ResymApp {
                     "Print access specifiers",
                 );
                 ui.checkbox(&mut self.settings.print_line_numbers, "Print line numbers");
+                ui.add(
+                    egui::Slider::new(&mut self.settings.code_font_size, CODE_FONT_SIZE_RANGE)
+                        .step_by(1.0)
+                        .text("Font size"),
+                );
             });
     }
 
+    /// Return the theme to highlight code with, matching the UI's theme.
+    fn code_theme(&self) -> CodeTheme {
+        if self
+            .settings
+            .light_theme_enabled(self.system_prefers_dark_mode)
+        {
+            CodeTheme::light()
+        } else {
+            CodeTheme::dark()
+        }
+    }
+
+    /// Export the type of the current tab to a file selected by the user.
+    fn export_current_tab(
+        &mut self,
+        format_name: &str,
+        extension: &str,
+        export_format: ExportFormat,
+    ) {
+        let tab = match self.type_tabs.current() {
+            Some(tab) => tab,
+            None => return,
+        };
+        let file_pattern = format!("*.{}", extension);
+        let file_path = match save_file_dialog_with_filter(
+            "Export the reconstructed type",
+            &default_file_name(&tab.type_name, extension),
+            &[&file_pattern],
+            &format!("{} files ({})", format_name, file_pattern),
+        ) {
+            Some(file_path) => PathBuf::from(file_path),
+            None => return,
+        };
+
+        match export_format {
+            // The displayed reconstruction is written as is
+            ExportFormat::Reconstruction(OutputFormat::Cpp) => {
+                write_export(&file_path, &tab.reconstructed_type);
+            }
+            ExportFormat::Html => {
+                const LANGUAGE_SYNTAX: &str = "cpp";
+                match highlight_code_html(
+                    &self.code_theme(),
+                    &tab.reconstructed_type,
+                    LANGUAGE_SYNTAX,
+                ) {
+                    Some(html_reconstructed_type) => {
+                        write_export(&file_path, &html_reconstructed_type);
+                    }
+                    None => log::error!("Failed to convert the type to HTML"),
+                }
+            }
+            ExportFormat::Reconstruction(output_format) => {
+                let command = self.type_tabs.export_command(
+                    self.type_tabs.current_tab_index(),
+                    output_format,
+                    file_path,
+                );
+                if let Err(err) = self.backend.send_command(command) {
+                    log::error!("Failed to reconstruct type: {}", err);
+                }
+            }
+        }
+    }
+
     fn select_pdb_file() -> Option<String> {
         open_file_dialog(
             "Select a PDB file",
@@ -642,6 +1423,66 @@ impl<'p> ResymApp {
     }
 }
 
+/// Show radio-buttons to switch between light and dark mode, or to follow the
+/// system's theme.
+fn update_theme_selector(ui: &mut egui::Ui, settings: &mut ResymAppSettings) {
+    ui.checkbox(&mut settings.follow_system_theme, "Follow the system theme");
+    ui.add_enabled_ui(!settings.follow_system_theme, |ui| {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut settings.use_light_theme, true, "☀ Light");
+            ui.selectable_value(&mut settings.use_light_theme, false, "🌙 Dark");
+        });
+    });
+}
+
+/// Scroll the code view so that the character at the given byte index is
+/// visible.
+fn scroll_to_byte_index(
+    ui: &egui::Ui,
+    output: &egui::text_edit::TextEditOutput,
+    text: &str,
+    byte_index: usize,
+) {
+    let char_index = text[..byte_index].chars().count();
+    let cursor = output
+        .galley
+        .from_ccursor(egui::text::CCursor::new(char_index));
+    let rect = output.galley.pos_from_cursor(&cursor);
+    ui.scroll_to_rect(
+        rect.translate(output.text_draw_pos.to_vec2()),
+        Some(egui::Align::Center),
+    );
+}
+
+/// Return the (possibly qualified) identifier containing the character at the
+/// given index, if any.
+fn identifier_at(text: &str, char_index: usize) -> Option<&str> {
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_' || c == ':';
+    let (byte_index, c) = text.char_indices().nth(char_index)?;
+    if !is_identifier_char(c) {
+        return None;
+    }
+
+    let start = text[..byte_index]
+        .rfind(|c: char| !is_identifier_char(c))
+        .map_or(0, |index| index + 1);
+    let end = text[byte_index..]
+        .find(|c: char| !is_identifier_char(c))
+        .map_or(text.len(), |index| byte_index + index);
+    let identifier = text[start..end].trim_matches(':');
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(identifier)
+    }
+}
+
+fn is_pdb_file(file_path: &Path) -> bool {
+    file_path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pdb"))
+}
+
 // FIXME: Replace with `checked_log10` once it's stabilized.
 fn int_log10<T>(mut i: T) -> usize
 where
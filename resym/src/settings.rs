@@ -1,12 +1,28 @@
-use resym_core::pdb_types::PrimitiveReconstructionFlavor;
+use resym_core::{backend::FilterMatchMode, pdb_types::PrimitiveReconstructionFlavor};
 use serde::{Deserialize, Serialize};
 
+use std::ops::RangeInclusive;
+
+use crate::type_tabs::DumpFlags;
+
+/// Default size of the code view's font
+pub const DEFAULT_CODE_FONT_SIZE: f32 = 14.0;
+/// Sizes the code view's font can be scaled to
+pub const CODE_FONT_SIZE_RANGE: RangeInclusive<f32> = 8.0..=32.0;
+
 /// This struct represents the persistent settings of the application.
 #[derive(Serialize, Deserialize)]
 pub struct ResymAppSettings {
     pub use_light_theme: bool,
+    /// The system's theme is used instead of `use_light_theme`, when known
+    #[serde(default)]
+    pub follow_system_theme: bool,
+    #[serde(default)]
+    pub open_last_pdb_on_startup: bool,
     pub search_case_insensitive: bool,
     pub search_use_regex: bool,
+    #[serde(default)]
+    pub search_use_fuzzy_matching: bool,
     pub enable_syntax_hightlighting: bool,
     #[serde(with = "PrimitiveReconstructionFlavorDef")]
     pub primitive_types_flavor: PrimitiveReconstructionFlavor,
@@ -14,20 +30,70 @@ pub struct ResymAppSettings {
     pub reconstruct_dependencies: bool,
     pub print_access_specifiers: bool,
     pub print_line_numbers: bool,
+    #[serde(default = "default_code_font_size")]
+    pub code_font_size: f32,
+    /// Type differences are displayed side by side instead of unified
+    #[serde(default = "default_side_by_side_diff")]
+    pub side_by_side_diff: bool,
 }
 
 impl Default for ResymAppSettings {
     fn default() -> Self {
         Self {
             use_light_theme: false,
+            follow_system_theme: true,
+            open_last_pdb_on_startup: false,
             search_case_insensitive: true,
             search_use_regex: false,
+            search_use_fuzzy_matching: false,
             enable_syntax_hightlighting: true,
             primitive_types_flavor: PrimitiveReconstructionFlavor::Portable,
             print_header: true,
             reconstruct_dependencies: true,
             print_access_specifiers: true,
             print_line_numbers: false,
+            code_font_size: DEFAULT_CODE_FONT_SIZE,
+            side_by_side_diff: default_side_by_side_diff(),
+        }
+    }
+}
+
+fn default_code_font_size() -> f32 {
+    DEFAULT_CODE_FONT_SIZE
+}
+
+fn default_side_by_side_diff() -> bool {
+    true
+}
+
+impl ResymAppSettings {
+    /// Return whether the light theme should be used, given whether the
+    /// system prefers dark mode (`None` if unknown).
+    pub fn light_theme_enabled(&self, prefer_dark_mode: Option<bool>) -> bool {
+        match prefer_dark_mode {
+            Some(prefer_dark_mode) if self.follow_system_theme => !prefer_dark_mode,
+            _ => self.use_light_theme,
+        }
+    }
+
+    /// Return the match mode to use when filtering types.
+    pub fn search_match_mode(&self) -> FilterMatchMode {
+        if self.search_use_regex {
+            FilterMatchMode::Regex
+        } else if self.search_use_fuzzy_matching {
+            FilterMatchMode::Fuzzy
+        } else {
+            FilterMatchMode::Substring
+        }
+    }
+
+    /// Return the flags to reconstruct types with (e.g., in new tabs).
+    pub fn dump_flags(&self) -> DumpFlags {
+        DumpFlags {
+            primitive_types_flavor: self.primitive_types_flavor,
+            print_header: self.print_header,
+            reconstruct_dependencies: self.reconstruct_dependencies,
+            print_access_specifiers: self.print_access_specifiers,
         }
     }
 }
@@ -35,7 +101,7 @@ impl Default for ResymAppSettings {
 // Definition of the remote enum so that serde can its traits
 #[derive(Serialize, Deserialize)]
 #[serde(remote = "PrimitiveReconstructionFlavor")]
-enum PrimitiveReconstructionFlavorDef {
+pub(crate) enum PrimitiveReconstructionFlavorDef {
     Portable,
     Microsoft,
     Raw,
@@ -4,9 +4,14 @@ use eframe::{
 };
 use syntect::{easy::HighlightLines, highlighting::FontStyle, util::LinesWithEndings};
 
+use std::ops::Range;
+
 use resym_core::{diffing::DiffChange, syntax_highlighting::CodeTheme};
 
 pub type LineDescriptions = Vec<DiffChange>;
+/// Byte ranges of the code to highlight (e.g., search results), ordered and
+/// non-overlapping, with their background color
+pub type TextHighlights = [(Range<usize>, egui::Color32)];
 
 /// Memoized code highlighting
 pub fn highlight_code(
@@ -17,26 +22,6 @@ pub fn highlight_code(
     enabled: bool,
     line_descriptions: Option<&LineDescriptions>,
 ) -> LayoutJob {
-    impl
-        egui::util::cache::ComputerMut<
-            (&CodeTheme, &str, &str, bool, Option<&LineDescriptions>),
-            LayoutJob,
-        > for CodeHighlighter
-    {
-        fn compute(
-            &mut self,
-            (theme, code, lang, enabled, line_descriptions): (
-                &CodeTheme,
-                &str,
-                &str,
-                bool,
-                Option<&LineDescriptions>,
-            ),
-        ) -> LayoutJob {
-            self.highlight(theme, code, lang, enabled, line_descriptions)
-        }
-    }
-
     type HighlightCache<'a> = egui::util::cache::FrameCache<LayoutJob, CodeHighlighter>;
 
     let mut memory = ctx.memory();
@@ -44,6 +29,97 @@ pub fn highlight_code(
     highlight_cache.get((theme, code, language, enabled, line_descriptions))
 }
 
+/// Highlight the given code as HTML. In case of success, the result is a
+/// self-contained `<pre>` block, styled inline.
+pub fn highlight_code_html(theme: &CodeTheme, code: &str, language: &str) -> Option<String> {
+    let highlighter = CodeHighlighter::default();
+    let syntax = highlighter
+        .ps
+        .find_syntax_by_name(language)
+        .or_else(|| highlighter.ps.find_syntax_by_extension(language))?;
+
+    syntect::html::highlighted_html_for_string(
+        code,
+        &highlighter.ps,
+        syntax,
+        &highlighter.ts.themes[theme.syntect_theme.syntect_key_name()],
+    )
+    .ok()
+}
+
+/// Change the size of the font used by the given job.
+pub fn set_font_size(job: &mut LayoutJob, font_size: f32) {
+    for section in &mut job.sections {
+        section.format.font_id.size = font_size;
+    }
+}
+
+/// Split the sections of the given job so that highlighted ranges get their
+/// own background color.
+pub fn apply_text_highlights(job: &mut LayoutJob, text_highlights: &TextHighlights) {
+    if text_highlights.is_empty() {
+        return;
+    }
+
+    let mut sections = Vec::with_capacity(job.sections.len() + 2 * text_highlights.len());
+    let mut text_highlights = text_highlights.iter().peekable();
+    for section in job.sections.drain(..) {
+        let mut start = section.byte_range.start;
+        while start < section.byte_range.end {
+            while text_highlights
+                .peek()
+                .is_some_and(|(range, _)| range.end <= start)
+            {
+                text_highlights.next();
+            }
+            let (end, background) = match text_highlights.peek() {
+                Some((range, background)) if range.start <= start => {
+                    (range.end.min(section.byte_range.end), Some(*background))
+                }
+                Some((range, _)) => (range.start.min(section.byte_range.end), None),
+                None => (section.byte_range.end, None),
+            };
+
+            let mut format = section.format.clone();
+            if let Some(background) = background {
+                format.background = background;
+                format.color = egui::Color32::BLACK;
+            }
+            sections.push(egui::text::LayoutSection {
+                leading_space: if start == section.byte_range.start {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: start..end,
+                format,
+            });
+            start = end;
+        }
+    }
+    job.sections = sections;
+}
+
+impl
+    egui::util::cache::ComputerMut<
+        (&CodeTheme, &str, &str, bool, Option<&LineDescriptions>),
+        LayoutJob,
+    > for CodeHighlighter
+{
+    fn compute(
+        &mut self,
+        (theme, code, lang, enabled, line_descriptions): (
+            &CodeTheme,
+            &str,
+            &str,
+            bool,
+            Option<&LineDescriptions>,
+        ),
+    ) -> LayoutJob {
+        self.highlight(theme, code, lang, enabled, line_descriptions)
+    }
+}
+
 struct CodeHighlighter {
     ps: syntect::parsing::SyntaxSet,
     ts: syntect::highlighting::ThemeSet,
@@ -0,0 +1,203 @@
+use resym_core::diffing::{DiffChange, DiffedType};
+
+use std::{fmt::Write, ops::Range};
+
+use crate::syntax_highlighting::LineDescriptions;
+
+/// One side of a side-by-side diff, i.e. the reconstruction from one of the
+/// PDBs, padded with empty lines so that it stays aligned with the other side.
+#[derive(Default, PartialEq)]
+pub struct DiffSide {
+    pub code: String,
+    pub line_numbers: String,
+    pub line_changes: LineDescriptions,
+    /// Byte offset in `code` of each line of the unified diff, `None` for the
+    /// lines that only belong to the other side
+    line_offsets: Vec<Option<usize>>,
+}
+
+impl DiffSide {
+    fn push_line(&mut self, unified_line_index: usize, line: &DiffLine) {
+        self.line_offsets[unified_line_index] = Some(self.code.len());
+        self.code.push_str(line.content);
+        if !line.content.ends_with('\n') {
+            self.code.push('\n');
+        }
+        let _r = writeln!(&mut self.line_numbers, "{}", 1 + line.index);
+        self.line_changes.push(line.change);
+    }
+
+    fn push_padding(&mut self) {
+        self.code.push('\n');
+        let _r = writeln!(&mut self.line_numbers);
+        self.line_changes.push(DiffChange::Equal);
+    }
+}
+
+/// Line of the unified diff, without its prefix.
+struct DiffLine<'a> {
+    index: usize,
+    change: DiffChange,
+    content: &'a str,
+}
+
+/// Type diff displayed in the code view, either unified or side by side.
+#[derive(Default, PartialEq)]
+pub struct TypeDiffView {
+    /// Unified diff, whose lines are prefixed with '+', '-' or ' '
+    pub code: String,
+    pub line_numbers_old: String,
+    pub line_numbers_new: String,
+    pub line_changes: LineDescriptions,
+    pub last_line_number: usize,
+    pub old: DiffSide,
+    pub new: DiffSide,
+    /// Byte offset in `code` of each line of the unified diff
+    line_offsets: Vec<usize>,
+}
+
+impl TypeDiffView {
+    pub fn new(type_diff: DiffedType) -> Self {
+        let mut view = Self {
+            last_line_number: 1,
+            ..Default::default()
+        };
+        let line_count = type_diff.metadata.len();
+        view.old.line_offsets = vec![None; line_count];
+        view.new.line_offsets = vec![None; line_count];
+
+        let code = type_diff.data;
+        let mut line_start = 0;
+        // Deleted and inserted lines are buffered until the next equal line,
+        // so that they can be displayed next to each other
+        let mut deleted_lines = vec![];
+        let mut inserted_lines = vec![];
+        for (unified_line_index, ((old_index, new_index), change)) in
+            type_diff.metadata.into_iter().enumerate()
+        {
+            let line = code[line_start..]
+                .split_inclusive('\n')
+                .next()
+                .unwrap_or_default();
+            view.line_offsets.push(line_start);
+            line_start += line.len();
+            // Skip the line's prefix
+            let content = line.get(1..).unwrap_or_default();
+
+            match old_index {
+                Some(index) => {
+                    view.last_line_number = view.last_line_number.max(1 + index);
+                    let _r = writeln!(&mut view.line_numbers_old, "{}", 1 + index);
+                }
+                None => {
+                    let _r = writeln!(&mut view.line_numbers_old);
+                }
+            }
+            match new_index {
+                Some(index) => {
+                    view.last_line_number = view.last_line_number.max(1 + index);
+                    let _r = writeln!(&mut view.line_numbers_new, "{}", 1 + index);
+                }
+                None => {
+                    let _r = writeln!(&mut view.line_numbers_new);
+                }
+            }
+            view.line_changes.push(change);
+
+            match (change, old_index, new_index) {
+                (DiffChange::Delete, Some(index), _) => deleted_lines.push((
+                    unified_line_index,
+                    DiffLine {
+                        index,
+                        change,
+                        content,
+                    },
+                )),
+                (DiffChange::Insert, _, Some(index)) => inserted_lines.push((
+                    unified_line_index,
+                    DiffLine {
+                        index,
+                        change,
+                        content,
+                    },
+                )),
+                (_, Some(old_index), Some(new_index)) => {
+                    view.flush_changed_lines(&mut deleted_lines, &mut inserted_lines);
+                    view.old.push_line(
+                        unified_line_index,
+                        &DiffLine {
+                            index: old_index,
+                            change,
+                            content,
+                        },
+                    );
+                    view.new.push_line(
+                        unified_line_index,
+                        &DiffLine {
+                            index: new_index,
+                            change,
+                            content,
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+        view.flush_changed_lines(&mut deleted_lines, &mut inserted_lines);
+        drop((deleted_lines, inserted_lines));
+        view.code = code;
+
+        view
+    }
+
+    fn flush_changed_lines(
+        &mut self,
+        deleted_lines: &mut Vec<(usize, DiffLine)>,
+        inserted_lines: &mut Vec<(usize, DiffLine)>,
+    ) {
+        let line_count = deleted_lines.len().max(inserted_lines.len());
+        for line_index in 0..line_count {
+            match deleted_lines.get(line_index) {
+                Some((unified_line_index, line)) => self.old.push_line(*unified_line_index, line),
+                None => self.old.push_padding(),
+            }
+            match inserted_lines.get(line_index) {
+                Some((unified_line_index, line)) => self.new.push_line(*unified_line_index, line),
+                None => self.new.push_padding(),
+            }
+        }
+        deleted_lines.clear();
+        inserted_lines.clear();
+    }
+
+    /// Map a byte range of the unified diff (e.g., a search result) to the
+    /// given side's code. Returns `None` if the range's line isn't displayed
+    /// on that side. Ranges spanning several lines are truncated to their
+    /// first line.
+    pub fn map_range_to_side(&self, side: &DiffSide, range: &Range<usize>) -> Option<Range<usize>> {
+        let unified_line_index = self
+            .line_offsets
+            .partition_point(|line_offset| *line_offset <= range.start)
+            .checked_sub(1)?;
+        let side_line_offset = side.line_offsets[unified_line_index]?;
+        // Content starts after the line's prefix, and ends before its newline
+        let content_start = self.line_offsets[unified_line_index] + 1;
+        let line_end = self
+            .line_offsets
+            .get(unified_line_index + 1)
+            .copied()
+            .unwrap_or(self.code.len());
+        let content_end = self.code[..line_end]
+            .strip_suffix('\n')
+            .map_or(line_end, str::len)
+            .max(content_start);
+
+        let start = range.start.clamp(content_start, content_end) - content_start;
+        let end = range.end.clamp(content_start, content_end) - content_start;
+        if start == end {
+            return None;
+        }
+
+        Some(side_line_offset + start..side_line_offset + end)
+    }
+}
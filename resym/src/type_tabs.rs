@@ -0,0 +1,367 @@
+use eframe::egui;
+use resym_core::{
+    backend::BackendCommand,
+    pdb_types::{DataFormatConfiguration, OutputFormat, PrimitiveReconstructionFlavor},
+};
+use serde::{Deserialize, Serialize};
+
+use std::{collections::VecDeque, fmt::Write, path::PathBuf};
+
+use crate::{settings::PrimitiveReconstructionFlavorDef, PDB_MAIN_SLOT};
+
+/// Flags used to reconstruct the type displayed in a tab.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpFlags {
+    #[serde(with = "PrimitiveReconstructionFlavorDef")]
+    pub primitive_types_flavor: PrimitiveReconstructionFlavor,
+    pub print_header: bool,
+    pub reconstruct_dependencies: bool,
+    pub print_access_specifiers: bool,
+}
+
+impl DumpFlags {
+    /// Return the configuration to use when formatting reconstructed types.
+    pub fn data_format_configuration(&self) -> DataFormatConfiguration {
+        DataFormatConfiguration {
+            print_access_specifiers: self.print_access_specifiers,
+            ..Default::default()
+        }
+    }
+}
+
+/// Tab displaying a reconstructed type.
+#[derive(Serialize, Deserialize)]
+pub struct TypeTab {
+    pub type_name: String,
+    pub dump_flags: DumpFlags,
+    pub scroll_offset: egui::Vec2,
+    /// Identifier used to match the backend's responses with their tab
+    #[serde(skip)]
+    pub id: u64,
+    /// Index of the type, if known. Persisted tabs are reconstructed by name
+    /// as indices aren't stable across PDB builds.
+    #[serde(skip)]
+    pub type_index: Option<pdb::TypeIndex>,
+    #[serde(skip)]
+    pub line_numbers: String,
+    #[serde(skip)]
+    pub last_line_number: usize,
+    #[serde(skip)]
+    pub reconstructed_type: String,
+}
+
+impl TypeTab {
+    fn set_reconstructed_type(&mut self, reconstructed_type: String) {
+        self.last_line_number = 1 + reconstructed_type.lines().count();
+        self.line_numbers = (1..self.last_line_number).fold(String::default(), |mut acc, e| {
+            let _r = writeln!(&mut acc, "{}", e);
+            acc
+        });
+        self.reconstructed_type = reconstructed_type;
+    }
+
+    fn reconstruction_command(&self, output_format: OutputFormat) -> BackendCommand {
+        let flags = &self.dump_flags;
+        match self.type_index {
+            Some(type_index) => BackendCommand::ReconstructTypeByIndex(
+                PDB_MAIN_SLOT,
+                type_index,
+                flags.primitive_types_flavor,
+                output_format,
+                flags.print_header,
+                flags.reconstruct_dependencies,
+                flags.data_format_configuration(),
+            ),
+            None => reconstruct_type_by_name_command(&self.type_name, flags, output_format),
+        }
+    }
+}
+
+fn reconstruct_type_by_name_command(
+    type_name: &str,
+    flags: &DumpFlags,
+    output_format: OutputFormat,
+) -> BackendCommand {
+    BackendCommand::ReconstructTypeByName(
+        PDB_MAIN_SLOT,
+        type_name.to_string(),
+        flags.primitive_types_flavor,
+        output_format,
+        flags.print_header,
+        flags.reconstruct_dependencies,
+        flags.data_format_configuration(),
+    )
+}
+
+/// Reconstruction waiting for the backend's response.
+enum PendingReconstruction {
+    /// The type of the tab with the given identifier is reconstructed
+    Tab(u64),
+    /// A type reference is followed, its tab is opened once the type has
+    /// been reconstructed
+    Navigation(String, DumpFlags),
+    /// The type of a tab is exported to the given file
+    Export(PathBuf),
+}
+
+/// Tabs opened while browsing the main PDB. They're persisted along with the
+/// path of their PDB, and restored when that PDB gets loaded again.
+#[derive(Default, Serialize, Deserialize)]
+pub struct TypeTabs {
+    tabs: Vec<TypeTab>,
+    current_tab: usize,
+    pdb_file_path: Option<PathBuf>,
+    #[serde(skip)]
+    next_tab_id: u64,
+    /// Reconstructions, in the order the commands have been sent to the
+    /// backend
+    #[serde(skip)]
+    pending_reconstructions: VecDeque<PendingReconstruction>,
+    /// Names of the types navigated away from (the most recent last)
+    #[serde(skip)]
+    back_history: Vec<String>,
+    /// Names of the types navigated back from (the most recent last)
+    #[serde(skip)]
+    forward_history: Vec<String>,
+}
+
+impl TypeTabs {
+    /// Called once a PDB has been loaded into the main slot. Tabs are kept
+    /// (e.g., when reloading) if the PDB is the one they were opened from and
+    /// closed otherwise. Returns the commands that reconstruct the types of
+    /// the kept tabs.
+    pub fn on_pdb_loaded(&mut self, pdb_file_path: Option<PathBuf>) -> Vec<BackendCommand> {
+        if pdb_file_path.is_some() && pdb_file_path != self.pdb_file_path {
+            self.tabs.clear();
+            self.current_tab = 0;
+            self.pdb_file_path = pdb_file_path;
+        }
+
+        // Tabs get new identifiers, responses to the commands sent before
+        // the PDB was loaded are dropped
+        self.back_history.clear();
+        self.forward_history.clear();
+        let mut commands = Vec::with_capacity(self.tabs.len());
+        for tab_index in 0..self.tabs.len() {
+            let tab = &mut self.tabs[tab_index];
+            tab.id = self.next_tab_id;
+            self.next_tab_id += 1;
+            // The PDB may have been rebuilt since the tab's been opened
+            tab.type_index = None;
+            tab.set_reconstructed_type(String::default());
+            commands.push(self.reconstruction_command(tab_index));
+        }
+
+        commands
+    }
+
+    /// Select the tab displaying the given type, or open a new one. Returns
+    /// the command that reconstructs the type if a tab has been opened.
+    pub fn open(
+        &mut self,
+        type_name: &str,
+        type_index: pdb::TypeIndex,
+        dump_flags: DumpFlags,
+    ) -> Option<BackendCommand> {
+        if self.select_by_name(type_name) {
+            return None;
+        }
+
+        self.push_tab(type_name, Some(type_index), dump_flags);
+
+        Some(self.reconstruction_command(self.current_tab))
+    }
+
+    /// Follow a reference to the given type from the current tab, like
+    /// "go to definition" in editors. As type references may not name actual
+    /// types, the type's tab is only opened (and the navigation recorded, so
+    /// that it can be reverted) once the type has been reconstructed.
+    /// Returns the command that reconstructs the type, if needed.
+    pub fn navigate_to(
+        &mut self,
+        type_name: &str,
+        dump_flags: DumpFlags,
+    ) -> Option<BackendCommand> {
+        if self.tabs.iter().any(|tab| tab.type_name == type_name) {
+            self.record_navigation();
+            self.select_by_name(type_name);
+            return None;
+        }
+
+        let command = reconstruct_type_by_name_command(type_name, &dump_flags, OutputFormat::Cpp);
+        self.pending_reconstructions
+            .push_back(PendingReconstruction::Navigation(
+                type_name.to_string(),
+                dump_flags,
+            ));
+
+        Some(command)
+    }
+
+    /// Go back to the previous type navigated away from (or forward to the
+    /// next one navigated back from if `backwards` isn't set). Types whose
+    /// tab has been closed are skipped.
+    pub fn navigate_history(&mut self, backwards: bool) {
+        let (history, other_history) = if backwards {
+            (&mut self.back_history, &mut self.forward_history)
+        } else {
+            (&mut self.forward_history, &mut self.back_history)
+        };
+        while let Some(type_name) = history.pop() {
+            if let Some(tab_index) = self.tabs.iter().position(|tab| tab.type_name == type_name) {
+                if let Some(tab) = self.tabs.get(self.current_tab) {
+                    other_history.push(tab.type_name.clone());
+                }
+                self.current_tab = tab_index;
+                return;
+            }
+        }
+    }
+
+    pub fn can_navigate_back(&self) -> bool {
+        !self.back_history.is_empty()
+    }
+
+    pub fn can_navigate_forward(&self) -> bool {
+        !self.forward_history.is_empty()
+    }
+
+    fn record_navigation(&mut self) {
+        if let Some(tab) = self.tabs.get(self.current_tab) {
+            self.back_history.push(tab.type_name.clone());
+        }
+        self.forward_history.clear();
+    }
+
+    fn select_by_name(&mut self, type_name: &str) -> bool {
+        match self.tabs.iter().position(|tab| tab.type_name == type_name) {
+            Some(tab_index) => {
+                self.current_tab = tab_index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn push_tab(
+        &mut self,
+        type_name: &str,
+        type_index: Option<pdb::TypeIndex>,
+        dump_flags: DumpFlags,
+    ) -> &mut TypeTab {
+        self.tabs.push(TypeTab {
+            type_name: type_name.to_string(),
+            dump_flags,
+            scroll_offset: egui::Vec2::ZERO,
+            id: self.next_tab_id,
+            type_index,
+            line_numbers: String::default(),
+            last_line_number: 0,
+            reconstructed_type: String::default(),
+        });
+        self.next_tab_id += 1;
+        self.current_tab = self.tabs.len() - 1;
+
+        &mut self.tabs[self.current_tab]
+    }
+
+    pub fn close(&mut self, tab_index: usize) {
+        if tab_index >= self.tabs.len() {
+            return;
+        }
+        self.tabs.remove(tab_index);
+        if self.current_tab > tab_index || self.current_tab >= self.tabs.len() {
+            self.current_tab = self.current_tab.saturating_sub(1);
+        }
+    }
+
+    pub fn select(&mut self, tab_index: usize) {
+        if tab_index < self.tabs.len() {
+            self.current_tab = tab_index;
+        }
+    }
+
+    /// Select the next tab (or the previous one if `backwards` is set),
+    /// wrapping around the ends.
+    pub fn cycle(&mut self, backwards: bool) {
+        if self.tabs.is_empty() {
+            return;
+        }
+        self.current_tab = if backwards {
+            (self.current_tab + self.tabs.len() - 1) % self.tabs.len()
+        } else {
+            (self.current_tab + 1) % self.tabs.len()
+        };
+    }
+
+    pub fn tabs(&self) -> &[TypeTab] {
+        &self.tabs
+    }
+
+    pub fn current_tab_index(&self) -> usize {
+        self.current_tab
+    }
+
+    pub fn current(&self) -> Option<&TypeTab> {
+        self.tabs.get(self.current_tab)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut TypeTab> {
+        self.tabs.get_mut(self.current_tab)
+    }
+
+    /// Return the command that reconstructs the type of the given tab, the
+    /// tab then waits for the backend's response.
+    pub fn reconstruction_command(&mut self, tab_index: usize) -> BackendCommand {
+        let tab = &self.tabs[tab_index];
+        self.pending_reconstructions
+            .push_back(PendingReconstruction::Tab(tab.id));
+
+        tab.reconstruction_command(OutputFormat::Cpp)
+    }
+
+    /// Return the command that reconstructs the type of the given tab in
+    /// the given format, to export it to the given file.
+    pub fn export_command(
+        &mut self,
+        tab_index: usize,
+        output_format: OutputFormat,
+        file_path: PathBuf,
+    ) -> BackendCommand {
+        self.pending_reconstructions
+            .push_back(PendingReconstruction::Export(file_path));
+
+        self.tabs[tab_index].reconstruction_command(output_format)
+    }
+
+    /// Called when the backend answers a reconstruction command. The result
+    /// is dropped if its tab has been closed in the meantime. Exported types
+    /// are returned along with the path of the file to write them to.
+    pub fn on_type_reconstructed(
+        &mut self,
+        reconstructed_type: Option<String>,
+    ) -> Option<(PathBuf, String)> {
+        let pending_reconstruction = self.pending_reconstructions.pop_front();
+        let reconstructed_type = reconstructed_type?;
+        match pending_reconstruction {
+            Some(PendingReconstruction::Tab(tab_id)) => {
+                if let Some(tab) = self.tabs.iter_mut().find(|tab| tab.id == tab_id) {
+                    tab.set_reconstructed_type(reconstructed_type);
+                }
+            }
+            Some(PendingReconstruction::Navigation(type_name, dump_flags)) => {
+                self.record_navigation();
+                if !self.select_by_name(&type_name) {
+                    self.push_tab(&type_name, None, dump_flags)
+                        .set_reconstructed_type(reconstructed_type);
+                }
+            }
+            Some(PendingReconstruction::Export(file_path)) => {
+                return Some((file_path, reconstructed_type));
+            }
+            None => {}
+        }
+
+        None
+    }
+}
@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use resym_core::backend::{filter_named_list, FilterMatchMode};
+
+/// Build a list of `count` type names, shaped like the ones found in PDBs.
+fn type_list(count: usize) -> Vec<(String, usize)> {
+    (0..count)
+        .map(|i| {
+            (
+                format!(
+                    "namespace_{}::detail::Type_{}<int,std::allocator<int> >",
+                    i % 97,
+                    i
+                ),
+                i,
+            )
+        })
+        .collect()
+}
+
+fn bench_type_filtering(c: &mut Criterion) {
+    let mut group = c.benchmark_group("type_filtering");
+    for count in [1_000, 100_000, 500_000] {
+        let type_list = type_list(count);
+        for (match_mode, search_filter) in [
+            (FilterMatchMode::Substring, "type_42"),
+            (FilterMatchMode::Regex, r"Type_\d*42<"),
+            (FilterMatchMode::Glob, "*::Type_*42<*"),
+        ] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("{:?}", match_mode), count),
+                &type_list,
+                |b, type_list| {
+                    b.iter(|| filter_named_list(type_list, search_filter, true, match_mode))
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_type_filtering);
+criterion_main!(benches);
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use crate::{
+    backend::{worker_thread_routine, BackendCommand, DEFAULT_RECONSTRUCTION_CACHE_SIZE},
+    error::ResymError,
+    frontend::{FrontendCommand, FrontendController},
+};
+
+/// Number of commands that can be queued before `send_command` waits for
+/// the backend to process them.
+const COMMAND_CHANNEL_CAPACITY: usize = 64;
+
+/// Variant of `Backend` for `tokio` applications. Commands are processed by
+/// the same routine, on a blocking thread (`spawn_blocking`) so that PDB
+/// processing doesn't stall the runtime.
+pub struct AsyncBackend {
+    tx_worker: mpsc::Sender<BackendCommand>,
+    /// Set when a `Cancel` command is sent, and read by the worker while
+    /// processing long-running commands
+    cancellation_flag: Arc<AtomicBool>,
+}
+
+impl AsyncBackend {
+    /// Start the backend. This must be called from within a `tokio` runtime.
+    pub fn new(
+        frontend_controller: Arc<impl FrontendController + Send + Sync + 'static>,
+    ) -> Result<Self, ResymError> {
+        Self::with_cache_size(frontend_controller, DEFAULT_RECONSTRUCTION_CACHE_SIZE)
+    }
+
+    /// Start a backend that keeps up to `reconstruction_cache_size`
+    /// reconstructed types in cache, see `Backend::with_cache_size`.
+    pub fn with_cache_size(
+        frontend_controller: Arc<impl FrontendController + Send + Sync + 'static>,
+        reconstruction_cache_size: usize,
+    ) -> Result<Self, ResymError> {
+        let (tx_worker, mut rx_worker) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+        let cancellation_flag = Arc::new(AtomicBool::new(false));
+
+        let worker_cancellation_flag = cancellation_flag.clone();
+        tokio::task::spawn_blocking(move || {
+            let exit_result = worker_thread_routine(
+                || rx_worker.blocking_recv(),
+                frontend_controller.as_ref(),
+                &worker_cancellation_flag,
+                reconstruction_cache_size,
+            );
+            if let Err(err) = exit_result {
+                log::error!("Background task aborted: {}", err);
+            }
+        });
+        log::debug!("Background task started");
+
+        Ok(Self {
+            tx_worker,
+            cancellation_flag,
+        })
+    }
+
+    pub async fn send_command(&self, command: BackendCommand) -> Result<(), ResymError> {
+        // Commands are processed sequentially, notify the command being
+        // processed without waiting for the cancellation to be dequeued
+        if let BackendCommand::Cancel = command {
+            self.cancellation_flag.store(true, Ordering::Relaxed);
+        }
+        self.tx_worker
+            .send(command)
+            .await
+            .map_err(|_| ResymError::BackendStopped)
+    }
+}
+
+/// Frontend controller that forwards the backend's responses to a `tokio`
+/// channel, so that they can be awaited.
+pub struct AsyncFrontendController {
+    tx_ui: mpsc::UnboundedSender<FrontendCommand>,
+}
+
+impl AsyncFrontendController {
+    /// Create a controller along with the receiver its responses are sent to.
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<FrontendCommand>) {
+        let (tx_ui, rx_ui) = mpsc::unbounded_channel();
+
+        (Self { tx_ui }, rx_ui)
+    }
+}
+
+impl FrontendController for AsyncFrontendController {
+    fn send_command(&self, command: FrontendCommand) -> Result<()> {
+        Ok(self.tx_ui.send(command)?)
+    }
+}
@@ -1,5 +1,7 @@
-use anyhow::Result;
-use crossbeam_channel::{Receiver, Sender};
+use anyhow::{anyhow, Result};
+use crossbeam_channel::Sender;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use lru::LruCache;
 use rayon::{
     iter::{IntoParallelRefIterator, ParallelIterator},
     slice::ParallelSliceMut,
@@ -7,25 +9,108 @@ use rayon::{
 };
 
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
+    num::NonZeroUsize,
     path::PathBuf,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 use crate::{
-    diffing::diff_type_by_name,
+    diffing::{diff_type_by_name, diff_type_lists, semantic_diff_type_by_name},
+    error::ResymError,
     frontend::FrontendCommand,
-    frontend::FrontendController,
-    pdb_file::PdbFile,
-    pdb_types::{include_headers_for_flavor, PrimitiveReconstructionFlavor},
+    frontend::{
+        FieldList, FrontendController, GlobalList, ProgressStage, SizedTypeList, SymbolList,
+        TypeList,
+    },
+    pdb_file::{OperationCancelled, PdbFile, TypeKind, TypeReferenceKind},
+    pdb_types::{
+        c_include_headers_for_flavor, include_headers_for_flavor, DataFormatConfiguration,
+        OutputFormat, PrimitiveReconstructionFlavor,
+    },
     PKG_VERSION,
 };
 
+/// Identifies a PDB loaded by the backend. Slots are chosen by the frontend
+/// and several PDBs can be loaded at the same time, responses to commands
+/// targeting a single PDB carry its slot.
 pub type PDBSlot = usize;
 
+/// Number of reconstructed types kept in cache by default.
+pub const DEFAULT_RECONSTRUCTION_CACHE_SIZE: usize = 64;
+
+/// Number of types sent at once in response to `ListTypes`.
+const TYPE_LIST_CHUNK_SIZE: usize = 0x1000;
+
+/// Number of items above which lists are filtered in parallel. Smaller lists
+/// are filtered faster on the calling thread.
+const PARALLEL_FILTER_THRESHOLD: usize = 0x4000;
+
+/// Describes how type names are matched against a search filter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FilterMatchMode {
+    /// The filter is a plain (sub-)string
+    Substring,
+    /// The filter is a regular expression
+    Regex,
+    /// The filter is a shell-style glob pattern (`*`, `?`, `[...]`), matched
+    /// against the full type name
+    Glob,
+    /// The filter's characters must appear in the type name in the same
+    /// order, but not necessarily consecutively (e.g., `sockctx` matches
+    /// `SocketContext`). Matches are ranked from the best to the worst one.
+    Fuzzy,
+}
+
+/// Criteria types must meet, besides having a name matching the search
+/// filter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeFilter {
+    /// Minimum size of the types, in bytes
+    pub min_size: Option<u64>,
+    /// Maximum size of the types, in bytes
+    pub max_size: Option<u64>,
+    /// Kinds of the types, complete types of any kind match if empty
+    /// (typedefs only match if requested)
+    pub kinds: Vec<TypeKind>,
+    /// Filters matched like the search filter, types whose name matches any
+    /// of them are left out
+    pub exclusion_filters: Vec<String>,
+}
+
+impl TypeFilter {
+    /// Indicate whether a type of the given kind and size meets the
+    /// criteria.
+    pub fn matches(&self, type_kind: TypeKind, type_size: u64) -> bool {
+        self.min_size.is_none_or(|min_size| type_size >= min_size)
+            && self.max_size.is_none_or(|max_size| type_size <= max_size)
+            && if self.kinds.is_empty() {
+                type_kind != TypeKind::Typedef
+            } else {
+                self.kinds.contains(&type_kind)
+            }
+    }
+}
+
 pub enum BackendCommand {
-    /// Load a PDB file given its path as a `PathBuf`.
+    /// Load a PDB file given its path as a `PathBuf`. The file is mapped into
+    /// memory and must not be modified while it's loaded, see
+    /// `LoadPDBBuffered`.
     LoadPDB(PDBSlot, PathBuf),
+    /// Load a PDB file given its path, reading it entirely into memory. This
+    /// is meant for files that may be rewritten while they're loaded (e.g.,
+    /// by a linker), and is answered with a `LoadPDBResult`.
+    LoadPDBBuffered(PDBSlot, PathBuf),
+    /// Load a PDB file from memory, given its name and content. This is
+    /// answered with a `LoadPDBResult`.
+    LoadPDBBytes(PDBSlot, String, Arc<[u8]>),
+    /// Load the PDB file loaded into a slot again, from the same path. The
+    /// file is read entirely into memory, as it's likely to be rewritten
+    /// again. This is answered with a `LoadPDBResult`.
+    ReloadPDB(PDBSlot),
     /// Unload a PDB file given its slot.
     UnloadPDB(PDBSlot),
     /// Reconstruct a type given its type index for a given PDB.
@@ -33,24 +118,104 @@ pub enum BackendCommand {
         PDBSlot,
         pdb::TypeIndex,
         PrimitiveReconstructionFlavor,
+        OutputFormat,
         bool,
         bool,
-        bool,
+        DataFormatConfiguration,
     ),
     /// Reconstruct a type given its name for a given PDB.
     ReconstructTypeByName(
         PDBSlot,
         String,
         PrimitiveReconstructionFlavor,
+        OutputFormat,
         bool,
         bool,
+        DataFormatConfiguration,
+    ),
+    /// Retrieve a serializable description of a type (and of its
+    /// dependencies, if requested) given its name for a given PDB.
+    GetTypeModel(PDBSlot, String, PrimitiveReconstructionFlavor, bool),
+    /// Reconstruct a C++ forward declaration of a type given its name for a
+    /// given PDB.
+    ReconstructForwardDeclarationByName(PDBSlot, String, PrimitiveReconstructionFlavor, bool),
+    /// Reconstruct C++ forward declarations of types given their type indices
+    /// for a given PDB.
+    ReconstructForwardDeclarations(
+        PDBSlot,
+        Vec<pdb::TypeIndex>,
+        PrimitiveReconstructionFlavor,
         bool,
     ),
+    /// Reconstruct all the types of a given PDB. The result is sent back in
+    /// chunks (`ReconstructAllTypesChunk`), followed by a
+    /// `ReconstructAllTypesResult`.
+    ReconstructAllTypes(
+        PDBSlot,
+        PrimitiveReconstructionFlavor,
+        bool,
+        bool,
+        DataFormatConfiguration,
+    ),
+    /// Retrieve the list of all the types of a given PDB, ordered by type
+    /// index. The list is sent back in chunks (`ListTypesChunk`), followed by
+    /// a `ListTypesResult`.
+    ListTypes(PDBSlot),
     /// Retrieve a list of types that match the given filter for a given PDB.
-    UpdateTypeFilter(PDBSlot, String, bool, bool),
+    UpdateTypeFilter(PDBSlot, String, bool, FilterMatchMode),
+    /// Retrieve a list of types (and typedefs, if requested) that match the
+    /// given filter and criteria for a given PDB, along with their sizes.
+    /// Typedefs come after complete types, and refer to the type they alias.
+    FilterTypes(PDBSlot, String, bool, FilterMatchMode, TypeFilter),
     /// Retrieve a list of types that match the given filter for multiple PDBs
     /// and merge the result.
-    UpdateTypeFilterMerged(Vec<PDBSlot>, String, bool, bool),
+    UpdateTypeFilterMerged(Vec<PDBSlot>, String, bool, FilterMatchMode),
+    /// Retrieve a list of public symbols whose demangled name matches the
+    /// given filter for a given PDB.
+    ListSymbols(PDBSlot, String, bool, FilterMatchMode),
+    /// Retrieve a list of global and static variables whose demangled name
+    /// matches the given filter (along with their types) for a given PDB.
+    ListGlobals(
+        PDBSlot,
+        String,
+        bool,
+        FilterMatchMode,
+        PrimitiveReconstructionFlavor,
+    ),
+    /// Find the public symbol a given RVA belongs to for a given PDB.
+    ResolveAddress(PDBSlot, u32),
+    /// Retrieve the list of modules of a given PDB, optionally along with
+    /// their symbol counts.
+    ListModules(PDBSlot, bool),
+    /// Count the types, symbols and modules of a given PDB.
+    ComputeStatistics(PDBSlot),
+    /// Check that the type and debug information of a given PDB can be
+    /// entirely parsed.
+    CheckPDB(PDBSlot),
+    /// Compare a given PDB with the PDB referenced by the PE file at the
+    /// given path.
+    VerifyPE(PDBSlot, PathBuf),
+    /// Reconstruct the GUID constants and COM interfaces of a given PDB,
+    /// reading the values of GUIDs from the given PE file.
+    ReconstructComDefinitions(PDBSlot, Option<PathBuf>, PrimitiveReconstructionFlavor),
+    /// Retrieve the source locations of the definitions of the types or
+    /// functions of the given name for a given PDB.
+    GetSourceInfo(PDBSlot, String),
+    /// Retrieve a list of members whose name matches the given filter (along
+    /// with the types declaring them) for a given PDB.
+    FindTypesWithField(
+        PDBSlot,
+        String,
+        bool,
+        FilterMatchMode,
+        PrimitiveReconstructionFlavor,
+    ),
+    /// Retrieve the list of types that refer to the type of the given name
+    /// for a given PDB, optionally considering a single kind of references.
+    FindReferencingTypes(PDBSlot, String, Option<TypeReferenceKind>),
+    /// Retrieve the graph of the types a given type refers to, following
+    /// references up to a given depth.
+    BuildTypeGraph(PDBSlot, String, usize),
     /// Reconstruct a diff of a type given its name.
     DiffTypeByName(
         PDBSlot,
@@ -59,22 +224,53 @@ pub enum BackendCommand {
         PrimitiveReconstructionFlavor,
         bool,
         bool,
-        bool,
+        DataFormatConfiguration,
     ),
+    /// Compare the layouts of a type given its name, between two PDBs.
+    SemanticDiffTypeByName(PDBSlot, PDBSlot, String, PrimitiveReconstructionFlavor),
+    /// Compare the type lists of two PDBs.
+    DiffTypeLists(PDBSlot, PDBSlot),
+    /// Drop all the reconstructed types kept in cache.
+    ClearCache,
+    /// Cancel the long-running commands (i.e., `LoadPDB` and
+    /// `ReconstructAllTypes`) sent before this one. Cancelled commands don't
+    /// send their usual result, a `Cancelled` acknowledgment is sent once
+    /// they've been aborted.
+    Cancel,
 }
 
 /// Struct that represents the backend. The backend is responsible
 /// for the actual PDB processing (e.g., type listing and reconstruction).
+///
+/// Commands can be sent from any thread. They're processed sequentially by a
+/// single worker thread which owns the loaded PDBs, so that responses (which
+/// only carry the slot of the PDB they refer to) are sent in the order of the
+/// commands. To reconstruct and list types from several threads at once, use
+/// a `Session` instead.
 pub struct Backend {
     tx_worker: Sender<BackendCommand>,
+    /// Set when a `Cancel` command is sent, and read by the worker while
+    /// processing long-running commands
+    cancellation_flag: Arc<AtomicBool>,
     _thread_pool: ThreadPool,
 }
 
 impl Backend {
     pub fn new(
         frontend_controller: Arc<impl FrontendController + Send + Sync + 'static>,
-    ) -> Result<Self> {
+    ) -> Result<Self, ResymError> {
+        Self::with_cache_size(frontend_controller, DEFAULT_RECONSTRUCTION_CACHE_SIZE)
+    }
+
+    /// Create a backend that keeps up to `reconstruction_cache_size` types
+    /// reconstructed with `ReconstructTypeByIndex` or `ReconstructTypeByName`
+    /// in cache (0 disables the cache).
+    pub fn with_cache_size(
+        frontend_controller: Arc<impl FrontendController + Send + Sync + 'static>,
+        reconstruction_cache_size: usize,
+    ) -> Result<Self, ResymError> {
         let (tx_worker, rx_worker) = crossbeam_channel::unbounded::<BackendCommand>();
+        let cancellation_flag = Arc::new(AtomicBool::new(false));
 
         // Start a thread pool with as many threads as there are CPUs on the machine,
         // minus one (because we account for the GUI thread).
@@ -82,9 +278,16 @@ impl Backend {
         let cpu_count = num_cpus::get();
         let thread_pool = rayon::ThreadPoolBuilder::new()
             .num_threads(cpu_count - 1)
-            .build()?;
+            .build()
+            .map_err(|err| ResymError::Other(err.into()))?;
+        let worker_cancellation_flag = cancellation_flag.clone();
         thread_pool.spawn(move || {
-            let exit_result = worker_thread_routine(rx_worker, frontend_controller.as_ref());
+            let exit_result = worker_thread_routine(
+                || rx_worker.recv().ok(),
+                frontend_controller.as_ref(),
+                &worker_cancellation_flag,
+                reconstruction_cache_size,
+            );
             if let Err(err) = exit_result {
                 log::error!("Background thread aborted: {}", err);
             }
@@ -93,37 +296,112 @@ impl Backend {
 
         Ok(Self {
             tx_worker,
+            cancellation_flag,
             _thread_pool: thread_pool,
         })
     }
 
-    pub fn send_command(&self, command: BackendCommand) -> Result<()> {
+    pub fn send_command(&self, command: BackendCommand) -> Result<(), ResymError> {
+        // Commands are processed sequentially, notify the command being
+        // processed without waiting for the cancellation to be dequeued
+        if let BackendCommand::Cancel = command {
+            self.cancellation_flag.store(true, Ordering::Relaxed);
+        }
         Ok(self.tx_worker.send(command)?)
     }
 }
 
 /// Main backend routine. This processes commands sent by the frontend and sends
-/// the result back.
-fn worker_thread_routine(
-    rx_worker: Receiver<BackendCommand>,
+/// the result back, until `recv_command` returns `None` (i.e., the backend is
+/// dropped).
+pub(crate) fn worker_thread_routine(
+    mut recv_command: impl FnMut() -> Option<BackendCommand>,
     frontend_controller: &impl FrontendController,
+    cancellation_flag: &AtomicBool,
+    reconstruction_cache_size: usize,
 ) -> Result<()> {
     let mut pdb_files: BTreeMap<PDBSlot, PdbFile> = BTreeMap::new();
-    while let Ok(command) = rx_worker.recv() {
+    let mut reconstruction_cache = ReconstructionCache::new(reconstruction_cache_size);
+    while let Some(command) = recv_command() {
         match command {
             BackendCommand::LoadPDB(pdb_slot, pdb_file_path) => {
                 log::info!("Loading a new PDB file ...");
-                match PdbFile::load_from_file(&pdb_file_path) {
-                    Err(err) => frontend_controller
-                        .send_command(FrontendCommand::LoadPDBResult(Err(err)))?,
-                    Ok(loaded_pdb_file) => {
-                        frontend_controller
-                            .send_command(FrontendCommand::LoadPDBResult(Ok(pdb_slot)))?;
-                        pdb_files.insert(pdb_slot, loaded_pdb_file);
-                        log::info!(
-                            "'{}' has been loaded successfully!",
-                            pdb_file_path.display()
-                        );
+                load_pdb_command(
+                    frontend_controller,
+                    cancellation_flag,
+                    &mut pdb_files,
+                    &mut reconstruction_cache,
+                    pdb_slot,
+                    |report_progress| {
+                        PdbFile::load_from_file_with_progress(&pdb_file_path, report_progress)
+                    },
+                )?;
+            }
+
+            BackendCommand::LoadPDBBuffered(pdb_slot, pdb_file_path) => {
+                log::info!("Loading a new PDB file ...");
+                load_pdb_command(
+                    frontend_controller,
+                    cancellation_flag,
+                    &mut pdb_files,
+                    &mut reconstruction_cache,
+                    pdb_slot,
+                    |report_progress| {
+                        PdbFile::load_from_file_buffered_with_progress(
+                            &pdb_file_path,
+                            report_progress,
+                        )
+                    },
+                )?;
+            }
+
+            BackendCommand::LoadPDBBytes(pdb_slot, pdb_name, pdb_data) => {
+                log::info!("Loading a new PDB file from memory ...");
+                load_pdb_command(
+                    frontend_controller,
+                    cancellation_flag,
+                    &mut pdb_files,
+                    &mut reconstruction_cache,
+                    pdb_slot,
+                    |report_progress| {
+                        PdbFile::load_from_bytes_with_progress(&pdb_name, pdb_data, report_progress)
+                    },
+                )?;
+            }
+
+            BackendCommand::ReloadPDB(pdb_slot) => {
+                match pdb_files
+                    .get(&pdb_slot)
+                    .map(|pdb_file| pdb_file.file_path.clone())
+                {
+                    None => frontend_controller.send_command(FrontendCommand::LoadPDBResult(
+                        pdb_slot,
+                        Err(ResymError::PdbNotLoaded),
+                    ))?,
+                    // PDBs loaded from memory cannot be read again
+                    Some(None) => {
+                        frontend_controller.send_command(FrontendCommand::LoadPDBResult(
+                            pdb_slot,
+                            Err(ResymError::Other(anyhow!(
+                                "PDB files loaded from memory cannot be reloaded"
+                            ))),
+                        ))?
+                    }
+                    Some(Some(pdb_file_path)) => {
+                        log::info!("Reloading '{}' ...", pdb_file_path.display());
+                        load_pdb_command(
+                            frontend_controller,
+                            cancellation_flag,
+                            &mut pdb_files,
+                            &mut reconstruction_cache,
+                            pdb_slot,
+                            |report_progress| {
+                                PdbFile::load_from_file_buffered_with_progress(
+                                    &pdb_file_path,
+                                    report_progress,
+                                )
+                            },
+                        )?;
                     }
                 }
             }
@@ -133,7 +411,8 @@ fn worker_thread_routine(
                     log::error!("Trying to unload an inexistent PDB");
                 }
                 Some(pdb_file) => {
-                    log::info!("'{}' has been unloaded.", pdb_file.file_path.display());
+                    reconstruction_cache.invalidate(pdb_slot);
+                    log::info!("'{}' has been unloaded.", pdb_file.name);
                 }
             },
 
@@ -141,21 +420,28 @@ fn worker_thread_routine(
                 pdb_slot,
                 type_index,
                 primitives_flavor,
+                output_format,
                 print_header,
                 reconstruct_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
-                    let reconstructed_type_result = reconstruct_type_by_index_command(
+                    let reconstructed_type_result = reconstruct_type_cached(
                         pdb_file,
-                        type_index,
-                        primitives_flavor,
-                        print_header,
-                        reconstruct_dependencies,
-                        print_access_specifiers,
+                        &mut reconstruction_cache,
+                        ReconstructionCacheKey {
+                            pdb_slot,
+                            type_index,
+                            primitives_flavor,
+                            output_format,
+                            print_header,
+                            reconstruct_dependencies,
+                            fmt_configuration,
+                        },
                     );
                     frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
-                        reconstructed_type_result,
+                        pdb_slot,
+                        reconstructed_type_result.map_err(ResymError::from),
                     ))?;
                 }
             }
@@ -164,21 +450,114 @@ fn worker_thread_routine(
                 pdb_slot,
                 type_name,
                 primitives_flavor,
+                output_format,
                 print_header,
                 reconstruct_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
-                    let reconstructed_type_result = reconstruct_type_by_name_command(
+                    // Note: Types are looked up by name first, so that they
+                    // share cache entries with `ReconstructTypeByIndex`
+                    let reconstructed_type_result = pdb_file
+                        .find_type_index_by_name(&type_name)
+                        .and_then(|type_index| {
+                            reconstruct_type_cached(
+                                pdb_file,
+                                &mut reconstruction_cache,
+                                ReconstructionCacheKey {
+                                    pdb_slot,
+                                    type_index,
+                                    primitives_flavor,
+                                    output_format,
+                                    print_header,
+                                    reconstruct_dependencies,
+                                    fmt_configuration,
+                                },
+                            )
+                        });
+                    frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
+                        pdb_slot,
+                        reconstructed_type_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ReconstructForwardDeclarationByName(
+                pdb_slot,
+                type_name,
+                primitives_flavor,
+                print_header,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let reconstructed_type_result = reconstruct_forward_declaration_by_name_command(
                         pdb_file,
                         &type_name,
                         primitives_flavor,
                         print_header,
-                        reconstruct_dependencies,
-                        print_access_specifiers,
                     );
                     frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
-                        reconstructed_type_result,
+                        pdb_slot,
+                        reconstructed_type_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ReconstructForwardDeclarations(
+                pdb_slot,
+                type_indices,
+                primitives_flavor,
+                print_header,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let reconstructed_type_result = reconstruct_forward_declarations_command(
+                        pdb_file,
+                        &type_indices,
+                        primitives_flavor,
+                        print_header,
+                    );
+                    frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
+                        pdb_slot,
+                        reconstructed_type_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ReconstructAllTypes(
+                pdb_slot,
+                primitives_flavor,
+                print_header,
+                reconstruct_dependencies,
+                fmt_configuration,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let reconstruction_result = reconstruct_all_types_command(
+                        frontend_controller,
+                        cancellation_flag,
+                        pdb_file,
+                        primitives_flavor,
+                        print_header,
+                        reconstruct_dependencies,
+                        &fmt_configuration,
+                    );
+                    match reconstruction_result {
+                        Err(err) if err.is::<OperationCancelled>() => {
+                            log::info!("Reconstruction of all types cancelled");
+                        }
+                        _ => frontend_controller.send_command(
+                            FrontendCommand::ReconstructAllTypesResult(
+                                reconstruction_result.map_err(ResymError::from),
+                            ),
+                        )?,
+                    }
+                }
+            }
+
+            BackendCommand::ListTypes(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let list_result = list_types_command(frontend_controller, pdb_slot, pdb_file);
+                    frontend_controller.send_command(FrontendCommand::ListTypesResult(
+                        pdb_slot,
+                        list_result.map_err(ResymError::from),
                     ))?;
                 }
             }
@@ -187,18 +566,42 @@ fn worker_thread_routine(
                 pdb_slot,
                 search_filter,
                 case_insensitive_search,
-                use_regex,
+                match_mode,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
                     let filtered_type_list = update_type_filter_command(
                         pdb_file,
                         &search_filter,
                         case_insensitive_search,
-                        use_regex,
+                        match_mode,
                         true,
                     );
-                    frontend_controller
-                        .send_command(FrontendCommand::UpdateFilteredTypes(filtered_type_list))?;
+                    frontend_controller.send_command(FrontendCommand::UpdateFilteredTypes(
+                        pdb_slot,
+                        filtered_type_list.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::FilterTypes(
+                pdb_slot,
+                search_filter,
+                case_insensitive_search,
+                match_mode,
+                type_filter,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let filtered_type_list = filter_types_command(
+                        pdb_file,
+                        &search_filter,
+                        case_insensitive_search,
+                        match_mode,
+                        &type_filter,
+                    );
+                    frontend_controller.send_command(FrontendCommand::FilterTypesResult(
+                        pdb_slot,
+                        filtered_type_list.map_err(ResymError::from),
+                    ))?;
                 }
             }
 
@@ -206,31 +609,241 @@ fn worker_thread_routine(
                 pdb_slots,
                 search_filter,
                 case_insensitive_search,
-                use_regex,
+                match_mode,
             ) => {
+                // The merged result is attributed to the first slot
+                let result_slot = pdb_slots.first().copied().unwrap_or_default();
                 let mut filtered_type_set = BTreeSet::default();
+                let mut filter_result = Ok(());
                 for pdb_slot in pdb_slots {
                     if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
-                        let filtered_type_list = update_type_filter_command(
+                        match update_type_filter_command(
                             pdb_file,
                             &search_filter,
                             case_insensitive_search,
-                            use_regex,
+                            match_mode,
                             false,
-                        );
-                        filtered_type_set.extend(filtered_type_list.into_iter().map(|(s, _)| {
-                            // Collapse all type indices to `default`. When merging
-                            // type lists, we can only count on type names to
-                            // represent the types.
-                            (s, pdb::TypeIndex::default())
-                        }));
+                        ) {
+                            Err(err) => {
+                                filter_result = Err(err);
+                                break;
+                            }
+                            Ok(filtered_type_list) => {
+                                filtered_type_set.extend(filtered_type_list.into_iter().map(
+                                    |(s, _)| {
+                                        // Collapse all type indices to `default`. When merging
+                                        // type lists, we can only count on type names to
+                                        // represent the types.
+                                        (s, pdb::TypeIndex::default())
+                                    },
+                                ));
+                            }
+                        }
                     }
                 }
+                let merged_type_list = filter_result.and_then(|_| {
+                    let merged_type_list = filtered_type_set.into_iter().collect::<Vec<_>>();
+                    if match_mode == FilterMatchMode::Fuzzy {
+                        // Rank the merged matches
+                        filter_named_list(
+                            &merged_type_list,
+                            &search_filter,
+                            case_insensitive_search,
+                            match_mode,
+                        )
+                    } else {
+                        Ok(merged_type_list)
+                    }
+                });
                 frontend_controller.send_command(FrontendCommand::UpdateFilteredTypes(
-                    filtered_type_set.into_iter().collect(),
+                    result_slot,
+                    merged_type_list.map_err(ResymError::from),
                 ))?;
             }
 
+            BackendCommand::ListSymbols(
+                pdb_slot,
+                search_filter,
+                case_insensitive_search,
+                match_mode,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let symbol_list_result = list_symbols_command(
+                        pdb_file,
+                        &search_filter,
+                        case_insensitive_search,
+                        match_mode,
+                    );
+                    frontend_controller.send_command(FrontendCommand::ListSymbolsResult(
+                        pdb_slot,
+                        symbol_list_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ListGlobals(
+                pdb_slot,
+                search_filter,
+                case_insensitive_search,
+                match_mode,
+                primitives_flavor,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let global_list_result = list_globals_command(
+                        pdb_file,
+                        &search_filter,
+                        case_insensitive_search,
+                        match_mode,
+                        primitives_flavor,
+                    );
+                    frontend_controller.send_command(FrontendCommand::ListGlobalsResult(
+                        pdb_slot,
+                        global_list_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ResolveAddress(pdb_slot, rva) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let resolved_address_result = pdb_file.resolve_address(rva);
+                    frontend_controller.send_command(FrontendCommand::ResolveAddressResult(
+                        pdb_slot,
+                        resolved_address_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ListModules(pdb_slot, count_symbols) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let module_list_result = pdb_file.modules(count_symbols);
+                    frontend_controller.send_command(FrontendCommand::ListModulesResult(
+                        pdb_slot,
+                        module_list_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ComputeStatistics(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let statistics_result = pdb_file.compute_statistics();
+                    frontend_controller.send_command(FrontendCommand::ComputeStatisticsResult(
+                        pdb_slot,
+                        statistics_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::CheckPDB(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let check_result = pdb_file.check_integrity();
+                    frontend_controller.send_command(FrontendCommand::CheckPDBResult(
+                        pdb_slot,
+                        check_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::VerifyPE(pdb_slot, pe_file_path) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let verification_result = pdb_file.verify_pe(&pe_file_path);
+                    frontend_controller.send_command(FrontendCommand::VerifyPEResult(
+                        pdb_slot,
+                        verification_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::ReconstructComDefinitions(
+                pdb_slot,
+                pe_file_path,
+                primitives_flavor,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let reconstructed_com_result = pdb_file
+                        .reconstruct_com_definitions(pe_file_path.as_deref(), &primitives_flavor);
+                    frontend_controller.send_command(
+                        FrontendCommand::ReconstructComDefinitionsResult(
+                            pdb_slot,
+                            reconstructed_com_result.map_err(ResymError::from),
+                        ),
+                    )?;
+                }
+            }
+
+            BackendCommand::GetSourceInfo(pdb_slot, symbol_name) => {
+                if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
+                    let source_info_result = pdb_file.source_info(&symbol_name);
+                    frontend_controller.send_command(FrontendCommand::GetSourceInfoResult(
+                        pdb_slot,
+                        source_info_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::FindTypesWithField(
+                pdb_slot,
+                search_filter,
+                case_insensitive_search,
+                match_mode,
+                primitives_flavor,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let field_list_result = find_types_with_field_command(
+                        pdb_file,
+                        &search_filter,
+                        case_insensitive_search,
+                        match_mode,
+                        primitives_flavor,
+                    );
+                    frontend_controller.send_command(FrontendCommand::FindTypesWithFieldResult(
+                        pdb_slot,
+                        field_list_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::GetTypeModel(
+                pdb_slot,
+                type_name,
+                primitives_flavor,
+                reconstruct_dependencies,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let type_model_result = pdb_file.reconstruct_type_model_by_name(
+                        &type_name,
+                        primitives_flavor,
+                        reconstruct_dependencies,
+                    );
+                    frontend_controller.send_command(FrontendCommand::GetTypeModelResult(
+                        pdb_slot,
+                        type_model_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
+            BackendCommand::FindReferencingTypes(pdb_slot, type_name, reference_kind) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let referencing_types_result =
+                        pdb_file.find_referencing_types(&type_name, reference_kind);
+                    frontend_controller.send_command(
+                        FrontendCommand::FindReferencingTypesResult(
+                            pdb_slot,
+                            referencing_types_result.map_err(ResymError::from),
+                        ),
+                    )?;
+                }
+            }
+
+            BackendCommand::BuildTypeGraph(pdb_slot, type_name, max_depth) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let type_graph_result = pdb_file.type_graph(&type_name, max_depth);
+                    frontend_controller.send_command(FrontendCommand::BuildTypeGraphResult(
+                        pdb_slot,
+                        type_graph_result.map_err(ResymError::from),
+                    ))?;
+                }
+            }
+
             BackendCommand::DiffTypeByName(
                 pdb_from_slot,
                 pdb_to_slot,
@@ -238,7 +851,7 @@ fn worker_thread_routine(
                 primitives_flavor,
                 print_header,
                 reconstruct_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             ) => {
                 if let Some(pdb_file_from) = pdb_files.get(&pdb_from_slot) {
                     if let Some(pdb_file_to) = pdb_files.get(&pdb_to_slot) {
@@ -249,68 +862,336 @@ fn worker_thread_routine(
                             primitives_flavor,
                             print_header,
                             reconstruct_dependencies,
-                            print_access_specifiers,
+                            &fmt_configuration,
+                        );
+                        frontend_controller.send_command(FrontendCommand::DiffTypeResult(
+                            type_diff_result.map_err(ResymError::from),
+                        ))?;
+                    }
+                }
+            }
+
+            BackendCommand::SemanticDiffTypeByName(
+                pdb_from_slot,
+                pdb_to_slot,
+                type_name,
+                primitives_flavor,
+            ) => {
+                if let Some(pdb_file_from) = pdb_files.get(&pdb_from_slot) {
+                    if let Some(pdb_file_to) = pdb_files.get(&pdb_to_slot) {
+                        let type_changes_result = semantic_diff_type_by_name(
+                            pdb_file_from,
+                            pdb_file_to,
+                            &type_name,
+                            primitives_flavor,
                         );
-                        frontend_controller
-                            .send_command(FrontendCommand::DiffTypeResult(type_diff_result))?;
+                        frontend_controller.send_command(
+                            FrontendCommand::SemanticDiffTypeResult(
+                                type_changes_result.map_err(ResymError::from),
+                            ),
+                        )?;
+                    }
+                }
+            }
+
+            BackendCommand::DiffTypeLists(pdb_from_slot, pdb_to_slot) => {
+                if let Some(pdb_file_from) = pdb_files.get(&pdb_from_slot) {
+                    if let Some(pdb_file_to) = pdb_files.get(&pdb_to_slot) {
+                        let type_list_changes_result = diff_type_lists(pdb_file_from, pdb_file_to);
+                        frontend_controller.send_command(FrontendCommand::DiffTypeListsResult(
+                            type_list_changes_result.map_err(ResymError::from),
+                        ))?;
                     }
                 }
             }
+
+            BackendCommand::ClearCache => reconstruction_cache.clear(),
+
+            BackendCommand::Cancel => {
+                // Commands sent before this one have been aborted (or have
+                // completed) by now
+                cancellation_flag.store(false, Ordering::Relaxed);
+                frontend_controller.send_command(FrontendCommand::Cancelled)?;
+            }
         }
     }
 
     Ok(())
 }
 
-fn reconstruct_type_by_index_command(
+/// Parameters of a `ReconstructTypeByIndex` or `ReconstructTypeByName`
+/// command (once the type's name has been resolved), which identify its
+/// output.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ReconstructionCacheKey {
+    pdb_slot: PDBSlot,
+    type_index: pdb::TypeIndex,
+    primitives_flavor: PrimitiveReconstructionFlavor,
+    output_format: OutputFormat,
+    print_header: bool,
+    reconstruct_dependencies: bool,
+    fmt_configuration: DataFormatConfiguration,
+}
+
+/// Least recently used reconstructed types. Entries of a slot are dropped
+/// when another PDB is loaded into it.
+struct ReconstructionCache {
+    /// `None` if caching is disabled
+    cache: Option<LruCache<ReconstructionCacheKey, String>>,
+}
+
+impl ReconstructionCache {
+    fn new(cache_size: usize) -> Self {
+        Self {
+            cache: NonZeroUsize::new(cache_size).map(LruCache::new),
+        }
+    }
+
+    fn get(&mut self, key: &ReconstructionCacheKey) -> Option<String> {
+        self.cache.as_mut()?.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: ReconstructionCacheKey, reconstructed_type: String) {
+        if let Some(cache) = self.cache.as_mut() {
+            cache.put(key, reconstructed_type);
+        }
+    }
+
+    fn invalidate(&mut self, pdb_slot: PDBSlot) {
+        if let Some(cache) = self.cache.as_mut() {
+            let stale_keys = cache
+                .iter()
+                .filter(|(key, _)| key.pdb_slot == pdb_slot)
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+            for key in stale_keys {
+                cache.pop(&key);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        if let Some(cache) = self.cache.as_mut() {
+            cache.clear();
+        }
+    }
+}
+
+/// Load a PDB with `load_pdb` (which is given a progress reporter) into
+/// `pdb_slot`, and send the result to the frontend. The PDB previously loaded
+/// into the slot (if any) is kept if loading fails.
+fn load_pdb_command<'p>(
+    frontend_controller: &impl FrontendController,
+    cancellation_flag: &AtomicBool,
+    pdb_files: &mut BTreeMap<PDBSlot, PdbFile<'p>>,
+    reconstruction_cache: &mut ReconstructionCache,
+    pdb_slot: PDBSlot,
+    load_pdb: impl FnOnce(&mut dyn FnMut(usize, usize) -> Result<()>) -> Result<PdbFile<'p>>,
+) -> Result<()> {
+    let mut report_progress = progress_reporter(
+        frontend_controller,
+        cancellation_flag,
+        ProgressStage::LoadingPdb,
+    );
+    let load_start = std::time::Instant::now();
+    match load_pdb(&mut report_progress) {
+        Err(err) if err.is::<OperationCancelled>() => {
+            log::info!("PDB loading cancelled");
+        }
+        Err(err) => frontend_controller
+            .send_command(FrontendCommand::LoadPDBResult(pdb_slot, Err(err.into())))?,
+        Ok(loaded_pdb_file) => {
+            frontend_controller.send_command(FrontendCommand::LoadPDBResult(pdb_slot, Ok(())))?;
+            log::info!(
+                "'{}' has been loaded successfully! (took {} ms)",
+                loaded_pdb_file.name,
+                load_start.elapsed().as_millis()
+            );
+            pdb_files.insert(pdb_slot, loaded_pdb_file);
+            reconstruction_cache.invalidate(pdb_slot);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reconstruct a type, or retrieve it from `reconstruction_cache` if it has
+/// been reconstructed with the same parameters before.
+fn reconstruct_type_cached(
+    pdb_file: &PdbFile,
+    reconstruction_cache: &mut ReconstructionCache,
+    cache_key: ReconstructionCacheKey,
+) -> Result<String> {
+    if let Some(reconstructed_type) = reconstruction_cache.get(&cache_key) {
+        return Ok(reconstructed_type);
+    }
+
+    let reconstructed_type = reconstruct_type_by_index_command(
+        pdb_file,
+        cache_key.type_index,
+        cache_key.primitives_flavor,
+        cache_key.output_format,
+        cache_key.print_header,
+        cache_key.reconstruct_dependencies,
+        &cache_key.fmt_configuration,
+    )?;
+    reconstruction_cache.insert(cache_key, reconstructed_type.clone());
+
+    Ok(reconstructed_type)
+}
+
+pub(crate) fn reconstruct_type_by_index_command(
     pdb_file: &PdbFile,
     type_index: pdb::TypeIndex,
     primitives_flavor: PrimitiveReconstructionFlavor,
+    output_format: OutputFormat,
     print_header: bool,
     reconstruct_dependencies: bool,
-    print_access_specifiers: bool,
+    fmt_configuration: &DataFormatConfiguration,
 ) -> Result<String> {
     let data = pdb_file.reconstruct_type_by_type_index(
         type_index,
         &primitives_flavor,
+        output_format,
         reconstruct_dependencies,
-        print_access_specifiers,
+        fmt_configuration,
     )?;
-    if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true);
+    // Note: The file header is made of comments, it cannot be prepended to
+    // JSON output
+    if print_header && output_format != OutputFormat::Json {
+        let file_header = generate_file_header(pdb_file, primitives_flavor, output_format);
         Ok(format!("{}{}", file_header, data))
     } else {
         Ok(data)
     }
 }
 
-fn reconstruct_type_by_name_command(
+fn reconstruct_forward_declaration_by_name_command(
     pdb_file: &PdbFile,
     type_name: &str,
     primitives_flavor: PrimitiveReconstructionFlavor,
     print_header: bool,
-    reconstruct_dependencies: bool,
-    print_access_specifiers: bool,
 ) -> Result<String> {
-    let data = pdb_file.reconstruct_type_by_name(
-        type_name,
-        primitives_flavor,
-        reconstruct_dependencies,
-        print_access_specifiers,
-    )?;
+    let data = pdb_file.reconstruct_forward_declaration_by_name(type_name, primitives_flavor)?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true);
-        Ok(format!("{}{}", file_header, data))
+        let file_header = generate_file_header(pdb_file, primitives_flavor, OutputFormat::Cpp);
+        Ok(format!("{}\n{}", file_header, data))
+    } else {
+        Ok(data)
+    }
+}
+
+fn reconstruct_forward_declarations_command(
+    pdb_file: &PdbFile,
+    type_indices: &[pdb::TypeIndex],
+    primitives_flavor: PrimitiveReconstructionFlavor,
+    print_header: bool,
+) -> Result<String> {
+    let data = pdb_file.reconstruct_forward_declarations(type_indices, &primitives_flavor)?;
+    if print_header {
+        let file_header = generate_file_header(pdb_file, primitives_flavor, OutputFormat::Cpp);
+        Ok(format!("{}\n{}", file_header, data))
     } else {
         Ok(data)
     }
 }
 
+fn reconstruct_all_types_command(
+    frontend_controller: &impl FrontendController,
+    cancellation_flag: &AtomicBool,
+    pdb_file: &PdbFile,
+    primitives_flavor: PrimitiveReconstructionFlavor,
+    print_header: bool,
+    reconstruct_dependencies: bool,
+    fmt_configuration: &DataFormatConfiguration,
+) -> Result<()> {
+    if print_header {
+        let file_header = generate_file_header(pdb_file, primitives_flavor, OutputFormat::Cpp);
+        frontend_controller.send_command(FrontendCommand::ReconstructAllTypesChunk(file_header))?;
+    }
+    pdb_file.reconstruct_all_types(
+        primitives_flavor,
+        reconstruct_dependencies,
+        fmt_configuration,
+        |chunk| frontend_controller.send_command(FrontendCommand::ReconstructAllTypesChunk(chunk)),
+        progress_reporter(
+            frontend_controller,
+            cancellation_flag,
+            ProgressStage::ReconstructingTypes,
+        ),
+    )
+}
+
+/// Return a progress callback that forwards progress reports to the frontend,
+/// and aborts the operation once a cancellation has been requested.
+fn progress_reporter<'a>(
+    frontend_controller: &'a impl FrontendController,
+    cancellation_flag: &'a AtomicBool,
+    stage: ProgressStage,
+) -> impl FnMut(usize, usize) -> Result<()> + 'a {
+    move |current, total| {
+        if cancellation_flag.load(Ordering::Relaxed) {
+            return Err(OperationCancelled.into());
+        }
+        frontend_controller.send_command(FrontendCommand::UpdateProgress {
+            current,
+            total,
+            stage,
+        })
+    }
+}
+
 fn generate_file_header(
     pdb_file: &PdbFile,
     primitives_flavor: PrimitiveReconstructionFlavor,
-    include_header_files: bool,
+    output_format: OutputFormat,
 ) -> String {
+    let prelude = match output_format {
+        OutputFormat::Cpp => format!("\n{}", include_headers_for_flavor(primitives_flavor)),
+        OutputFormat::C => format!("\n{}", c_include_headers_for_flavor(primitives_flavor)),
+        OutputFormat::CSharp => {
+            "\nusing System;\nusing System.Runtime.InteropServices;\n".to_string()
+        }
+        OutputFormat::Rust => {
+            "\n#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]\n"
+                .to_string()
+        }
+        OutputFormat::ImHex => "\n#pragma endian little\n".to_string(),
+        OutputFormat::BinaryTemplate => "\nLittleEndian();\n".to_string(),
+        OutputFormat::Go => "\npackage types\n".to_string(),
+        OutputFormat::Ctypes => "\nimport ctypes\n".to_string(),
+        OutputFormat::Json | OutputFormat::WinDbg | OutputFormat::Kaitai => "".to_string(),
+    };
+    if output_format == OutputFormat::C {
+        // C89 doesn't support single-line comments
+        return format!(
+            concat!(
+                "/*\n",
+                " * PDB file: {}\n",
+                " * Image architecture: {}\n",
+                " *\n",
+                " * Information extracted with resym v{}\n",
+                " */\n",
+                "{}"
+            ),
+            pdb_file.name, pdb_file.machine_type, PKG_VERSION, prelude
+        );
+    }
+    if matches!(output_format, OutputFormat::Kaitai | OutputFormat::Ctypes) {
+        return format!(
+            concat!(
+                "#\n",
+                "# PDB file: {}\n",
+                "# Image architecture: {}\n",
+                "#\n",
+                "# Information extracted with resym v{}\n",
+                "#\n",
+                "{}"
+            ),
+            pdb_file.name, pdb_file.machine_type, PKG_VERSION, prelude
+        );
+    }
+
     format!(
         concat!(
             "//\n",
@@ -321,43 +1202,46 @@ fn generate_file_header(
             "//\n",
             "{}"
         ),
-        pdb_file.file_path.display(),
-        pdb_file.machine_type,
-        PKG_VERSION,
-        if include_header_files {
-            format!("\n{}", include_headers_for_flavor(primitives_flavor))
-        } else {
-            "".to_string()
-        }
+        pdb_file.name, pdb_file.machine_type, PKG_VERSION, prelude
     )
 }
 
-fn update_type_filter_command(
+fn list_types_command(
+    frontend_controller: &impl FrontendController,
+    pdb_slot: PDBSlot,
+    pdb_file: &PdbFile,
+) -> Result<()> {
+    // Only references are sorted, names are copied one chunk at a time
+    let mut sorted_type_list = pdb_file.complete_type_list.iter().collect::<Vec<_>>();
+    sorted_type_list.par_sort_by_key(|(_, type_index)| *type_index);
+    for chunk in sorted_type_list.chunks(TYPE_LIST_CHUNK_SIZE) {
+        let chunk = chunk
+            .iter()
+            .map(|(type_name, type_index)| (type_name.clone(), *type_index))
+            .collect();
+        frontend_controller.send_command(FrontendCommand::ListTypesChunk(pdb_slot, chunk))?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn update_type_filter_command(
     pdb_file: &PdbFile,
     search_filter: &str,
     case_insensitive_search: bool,
-    use_regex: bool,
+    match_mode: FilterMatchMode,
     sort_by_index: bool,
-) -> Vec<(String, pdb::TypeIndex)> {
+) -> Result<TypeList> {
     let filter_start = std::time::Instant::now();
 
-    let mut filtered_type_list = if search_filter.is_empty() {
-        // No need to filter
-        pdb_file.complete_type_list.clone()
-    } else if use_regex {
-        filter_types_regex(
-            &pdb_file.complete_type_list,
-            search_filter,
-            case_insensitive_search,
-        )
-    } else {
-        filter_types_regular(
-            &pdb_file.complete_type_list,
-            search_filter,
-            case_insensitive_search,
-        )
-    };
-    if sort_by_index {
+    let mut filtered_type_list = filter_named_list(
+        &pdb_file.complete_type_list,
+        search_filter,
+        case_insensitive_search,
+        match_mode,
+    )?;
+    // Fuzzy matches are ranked instead
+    if sort_by_index && (match_mode != FilterMatchMode::Fuzzy || search_filter.is_empty()) {
         // Order types by type index, so the order is deterministic
         // (i.e., independent from DashMap's hash function)
         filtered_type_list.par_sort_by(|lhs, rhs| lhs.1.cmp(&rhs.1));
@@ -368,46 +1252,295 @@ fn update_type_filter_command(
         filter_start.elapsed().as_millis()
     );
 
-    filtered_type_list
+    Ok(filtered_type_list)
+}
+
+fn filter_types_command(
+    pdb_file: &mut PdbFile,
+    search_filter: &str,
+    case_insensitive_search: bool,
+    match_mode: FilterMatchMode,
+    type_filter: &TypeFilter,
+) -> Result<SizedTypeList> {
+    let mut filtered_type_list = exclude_from_named_list(
+        update_type_filter_command(
+            pdb_file,
+            search_filter,
+            case_insensitive_search,
+            match_mode,
+            true,
+        )?,
+        &type_filter.exclusion_filters,
+        case_insensitive_search,
+        match_mode,
+    )?
+    .into_iter()
+    .filter_map(|(type_name, type_index)| {
+        let type_kind = *pdb_file.complete_type_kinds.get(&type_index)?;
+        let type_size = *pdb_file.complete_type_sizes.get(&type_index)?;
+        type_filter
+            .matches(type_kind, type_size)
+            .then_some((type_name, type_index, type_size))
+    })
+    .collect::<Vec<_>>();
+
+    if type_filter.kinds.contains(&TypeKind::Typedef) {
+        let typedefs = exclude_from_named_list(
+            filter_named_list(
+                pdb_file.typedefs()?,
+                search_filter,
+                case_insensitive_search,
+                match_mode,
+            )?,
+            &type_filter.exclusion_filters,
+            case_insensitive_search,
+            match_mode,
+        )?;
+        filtered_type_list.extend(typedefs.into_iter().filter_map(|(typedef_name, typedef)| {
+            type_filter
+                .matches(TypeKind::Typedef, typedef.size)
+                .then_some((typedef_name, typedef.type_index, typedef.size))
+        }));
+    }
+
+    Ok(filtered_type_list)
+}
+
+fn list_symbols_command(
+    pdb_file: &mut PdbFile,
+    search_filter: &str,
+    case_insensitive_search: bool,
+    match_mode: FilterMatchMode,
+) -> Result<SymbolList> {
+    let public_symbols = pdb_file.public_symbols()?;
+
+    filter_named_list(
+        public_symbols,
+        search_filter,
+        case_insensitive_search,
+        match_mode,
+    )
+}
+
+fn list_globals_command(
+    pdb_file: &mut PdbFile,
+    search_filter: &str,
+    case_insensitive_search: bool,
+    match_mode: FilterMatchMode,
+    primitives_flavor: PrimitiveReconstructionFlavor,
+) -> Result<GlobalList> {
+    let global_variables = pdb_file.global_variables(&primitives_flavor)?;
+
+    filter_named_list(
+        &global_variables,
+        search_filter,
+        case_insensitive_search,
+        match_mode,
+    )
+}
+
+fn find_types_with_field_command(
+    pdb_file: &PdbFile,
+    search_filter: &str,
+    case_insensitive_search: bool,
+    match_mode: FilterMatchMode,
+    primitives_flavor: PrimitiveReconstructionFlavor,
+) -> Result<FieldList> {
+    let fields = pdb_file.fields(&primitives_flavor)?;
+
+    filter_named_list(&fields, search_filter, case_insensitive_search, match_mode)
+}
+
+/// Filter a list of named items (e.g., types or symbols) according to the
+/// given match mode, preserving their order. Fails if the filter is an invalid
+/// regular expression.
+pub fn filter_named_list<T: Clone + Send + Sync>(
+    named_list: &[(String, T)],
+    search_filter: &str,
+    case_insensitive_search: bool,
+    match_mode: FilterMatchMode,
+) -> Result<Vec<(String, T)>> {
+    if search_filter.is_empty() {
+        // No need to filter
+        return Ok(named_list.to_vec());
+    }
+
+    match match_mode {
+        FilterMatchMode::Substring => Ok(filter_types_regular(
+            named_list,
+            search_filter,
+            case_insensitive_search,
+        )),
+        FilterMatchMode::Regex => {
+            filter_types_regex(named_list, search_filter, case_insensitive_search)
+        }
+        FilterMatchMode::Glob => filter_types_regex(
+            named_list,
+            &glob_to_regex(search_filter),
+            case_insensitive_search,
+        ),
+        FilterMatchMode::Fuzzy => Ok(filter_types_fuzzy(
+            named_list,
+            search_filter,
+            case_insensitive_search,
+        )),
+    }
+}
+
+/// Remove the items whose name matches any of the given filters from a list
+/// of named items, preserving their order. Empty filters are ignored.
+fn exclude_from_named_list<T: Clone + Send + Sync>(
+    mut named_list: Vec<(String, T)>,
+    exclusion_filters: &[String],
+    case_insensitive_search: bool,
+    match_mode: FilterMatchMode,
+) -> Result<Vec<(String, T)>> {
+    for exclusion_filter in exclusion_filters {
+        if exclusion_filter.is_empty() {
+            continue;
+        }
+        let excluded_names = filter_named_list(
+            &named_list,
+            exclusion_filter,
+            case_insensitive_search,
+            match_mode,
+        )?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<HashSet<_>>();
+        named_list.retain(|(name, _)| !excluded_names.contains(name));
+    }
+
+    Ok(named_list)
 }
 
 /// Filter type list with a regular expression
-fn filter_types_regex(
-    type_list: &[(String, pdb::TypeIndex)],
+fn filter_types_regex<T: Clone + Send + Sync>(
+    type_list: &[(String, T)],
     search_filter: &str,
     case_insensitive_search: bool,
-) -> Vec<(String, pdb::TypeIndex)> {
-    match regex::RegexBuilder::new(search_filter)
+) -> Result<Vec<(String, T)>> {
+    let regex = regex::RegexBuilder::new(search_filter)
         .case_insensitive(case_insensitive_search)
         .build()
-    {
-        // In case of error, return an empty result
-        Err(_) => vec![],
-        Ok(regex) => type_list
-            .par_iter()
-            .filter(|r| regex.find(&r.0).is_some())
-            .cloned()
-            .collect(),
+        .map_err(ResymError::InvalidRegex)?;
+
+    Ok(filter_list(type_list, |name| regex.is_match(name)))
+}
+
+/// Translate a shell-style glob pattern into an equivalent (anchored) regular
+/// expression
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                // Character class, ends at the next closing bracket
+                let mut class_chars = String::default();
+                let mut terminated = false;
+                for class_char in chars.by_ref() {
+                    if class_char == ']' {
+                        terminated = true;
+                        break;
+                    }
+                    class_chars.push(class_char);
+                }
+                if !terminated {
+                    // Unterminated class, match the characters literally
+                    regex.push_str(&regex::escape(&format!("[{}", class_chars)));
+                    continue;
+                }
+
+                regex.push('[');
+                let class_chars = match class_chars.strip_prefix(['!', '^']) {
+                    Some(negated_class_chars) => {
+                        regex.push('^');
+                        negated_class_chars
+                    }
+                    None => &class_chars,
+                };
+                // Note: Everything but ranges' dashes is escaped, as the regex
+                // crate gives a meaning to sequences such as `&&` or `--` in
+                // classes
+                let class_chars = class_chars.chars().collect::<Vec<_>>();
+                let mut i = 0;
+                while i < class_chars.len() {
+                    regex.push_str(&regex::escape(&class_chars[i].to_string()));
+                    if class_chars.get(i + 1) == Some(&'-') && i + 2 < class_chars.len() {
+                        regex.push('-');
+                        regex.push_str(&regex::escape(&class_chars[i + 2].to_string()));
+                        i += 3;
+                    } else {
+                        i += 1;
+                    }
+                }
+                regex.push(']');
+            }
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
     }
+    regex.push('$');
+
+    regex
 }
 
 /// Filter type list with a plain (sub-)string
-fn filter_types_regular(
-    type_list: &[(String, pdb::TypeIndex)],
+fn filter_types_regular<T: Clone + Send + Sync>(
+    type_list: &[(String, T)],
     search_filter: &str,
     case_insensitive_search: bool,
-) -> Vec<(String, pdb::TypeIndex)> {
+) -> Vec<(String, T)> {
     if case_insensitive_search {
         let search_filter = search_filter.to_lowercase();
-        type_list
-            .par_iter()
-            .filter(|r| r.0.to_lowercase().contains(&search_filter))
-            .cloned()
-            .collect()
+        filter_list(type_list, |name| {
+            name.to_lowercase().contains(&search_filter)
+        })
+    } else {
+        filter_list(type_list, |name| name.contains(search_filter))
+    }
+}
+
+/// Filter type list with a fuzzy matcher, ranking matches from the best to
+/// the worst one (equally good matches are kept in order)
+fn filter_types_fuzzy<T: Clone + Send + Sync>(
+    type_list: &[(String, T)],
+    search_filter: &str,
+    case_insensitive_search: bool,
+) -> Vec<(String, T)> {
+    let matcher = if case_insensitive_search {
+        SkimMatcherV2::default().ignore_case()
+    } else {
+        SkimMatcherV2::default().respect_case()
+    };
+    let score = |r: &(String, T)| {
+        matcher
+            .fuzzy_match(&r.0, search_filter)
+            .map(|score| (score, r.clone()))
+    };
+    let mut scored_list: Vec<(i64, (String, T))> = if type_list.len() < PARALLEL_FILTER_THRESHOLD {
+        type_list.iter().filter_map(score).collect()
+    } else {
+        type_list.par_iter().filter_map(score).collect()
+    };
+    scored_list.par_sort_by(|lhs, rhs| rhs.0.cmp(&lhs.0));
+
+    scored_list.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Keep the items whose name matches, in order. Large lists are filtered in
+/// parallel.
+fn filter_list<T: Clone + Send + Sync>(
+    list: &[(String, T)],
+    is_match: impl Fn(&str) -> bool + Sync,
+) -> Vec<(String, T)> {
+    if list.len() < PARALLEL_FILTER_THRESHOLD {
+        list.iter().filter(|r| is_match(&r.0)).cloned().collect()
     } else {
-        type_list
-            .par_iter()
-            .filter(|r| r.0.contains(search_filter))
+        list.par_iter()
+            .filter(|r| is_match(&r.0))
             .cloned()
             .collect()
     }
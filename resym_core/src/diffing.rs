@@ -1,9 +1,21 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use serde::Serialize;
 use similar::{ChangeTag, TextDiff};
 
-use std::fmt::Write;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Write},
+};
 
-use crate::{pdb_file::PdbFile, pdb_types::PrimitiveReconstructionFlavor, PKG_VERSION};
+use crate::{
+    error::ResymError,
+    pdb_file::PdbFile,
+    pdb_types::{
+        DataFormatConfiguration, OutputFormat, PrimitiveReconstructionFlavor,
+        ReconstructedEnumValueKind, ReconstructedField, ReconstructedType,
+    },
+    PKG_VERSION,
+};
 
 pub type DiffChange = ChangeTag;
 pub type DiffIndices = (Option<usize>, Option<usize>);
@@ -26,7 +38,7 @@ pub fn diff_type_by_name(
     primitives_flavor: PrimitiveReconstructionFlavor,
     print_header: bool,
     reconstruct_dependencies: bool,
-    print_access_specifiers: bool,
+    fmt_configuration: &DataFormatConfiguration,
 ) -> Result<DiffedType> {
     let diff_start = std::time::Instant::now();
     // Prepend header if needed
@@ -43,21 +55,27 @@ pub fn diff_type_by_name(
             .reconstruct_type_by_name(
                 type_name,
                 primitives_flavor,
+                OutputFormat::Cpp,
                 reconstruct_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             )
             .unwrap_or_default();
         let reconstructed_type_to_tmp = pdb_file_to
             .reconstruct_type_by_name(
                 type_name,
                 primitives_flavor,
+                OutputFormat::Cpp,
                 reconstruct_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             )
             .unwrap_or_default();
         if reconstructed_type_from_tmp.is_empty() && reconstructed_type_to_tmp.is_empty() {
             // Make it obvious an error occured
-            return Err(anyhow!("Type not found"));
+            return Err(ResymError::TypeNotFound {
+                type_name: type_name.to_string(),
+                suggestions: vec![],
+            }
+            .into());
         }
         reconstructed_type_from.push_str(&reconstructed_type_from_tmp);
         reconstructed_type_to.push_str(&reconstructed_type_to_tmp);
@@ -88,6 +106,297 @@ pub fn diff_type_by_name(
     })
 }
 
+/// Difference between two versions of a type, found by comparing their
+/// layouts rather than their textual representations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeChange {
+    /// The type only exists in the new PDB
+    TypeAdded,
+    /// The type only exists in the reference PDB
+    TypeRemoved,
+    SizeChanged {
+        old_size: u64,
+        new_size: u64,
+    },
+    FieldAdded {
+        name: String,
+        type_name: String,
+        offset: u64,
+    },
+    FieldRemoved {
+        name: String,
+        type_name: String,
+        offset: u64,
+    },
+    /// Offsets are formatted, as bitfields' offsets include their bit offset
+    FieldMoved {
+        name: String,
+        old_offset: String,
+        new_offset: String,
+    },
+    FieldTypeChanged {
+        name: String,
+        old_type_name: String,
+        new_type_name: String,
+    },
+    EnumValueAdded {
+        name: String,
+        value: ReconstructedEnumValueKind,
+    },
+    EnumValueRemoved {
+        name: String,
+        value: ReconstructedEnumValueKind,
+    },
+    EnumValueChanged {
+        name: String,
+        old_value: ReconstructedEnumValueKind,
+        new_value: ReconstructedEnumValueKind,
+    },
+}
+
+impl fmt::Display for TypeChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeChange::TypeAdded => write!(f, "type added"),
+            TypeChange::TypeRemoved => write!(f, "type removed"),
+            TypeChange::SizeChanged { old_size, new_size } => {
+                write!(f, "size changed from {:#x} to {:#x}", old_size, new_size)
+            }
+            TypeChange::FieldAdded {
+                name,
+                type_name,
+                offset,
+            } => write!(f, "field {} ({}) added at {:#x}", name, type_name, offset),
+            TypeChange::FieldRemoved {
+                name,
+                type_name,
+                offset,
+            } => write!(
+                f,
+                "field {} ({}) removed from {:#x}",
+                name, type_name, offset
+            ),
+            TypeChange::FieldMoved {
+                name,
+                old_offset,
+                new_offset,
+            } => write!(
+                f,
+                "field {} moved from {} to {}",
+                name, old_offset, new_offset
+            ),
+            TypeChange::FieldTypeChanged {
+                name,
+                old_type_name,
+                new_type_name,
+            } => write!(
+                f,
+                "field {} changed type from {} to {}",
+                name, old_type_name, new_type_name
+            ),
+            TypeChange::EnumValueAdded { name, value } => {
+                write!(f, "enumerant {} ({}) added", name, fmt_enum_value(value))
+            }
+            TypeChange::EnumValueRemoved { name, value } => {
+                write!(f, "enumerant {} ({}) removed", name, fmt_enum_value(value))
+            }
+            TypeChange::EnumValueChanged {
+                name,
+                old_value,
+                new_value,
+            } => write!(
+                f,
+                "enumerant {} changed value from {} to {}",
+                name,
+                fmt_enum_value(old_value),
+                fmt_enum_value(new_value)
+            ),
+        }
+    }
+}
+
+/// Compare the layouts of the type named `type_name` in both PDBs. Fields and
+/// enumerants are matched by name.
+pub fn semantic_diff_type_by_name(
+    pdb_file_from: &PdbFile,
+    pdb_file_to: &PdbFile,
+    type_name: &str,
+    primitives_flavor: PrimitiveReconstructionFlavor,
+) -> Result<Vec<TypeChange>> {
+    let find_type = |pdb_file: &PdbFile| {
+        pdb_file
+            .reconstruct_type_model_by_name(type_name, primitives_flavor, false)
+            .ok()
+            .and_then(|types| types.into_iter().find(|t| t.name == type_name))
+    };
+
+    match (find_type(pdb_file_from), find_type(pdb_file_to)) {
+        (None, None) => Err(ResymError::TypeNotFound {
+            type_name: type_name.to_string(),
+            suggestions: vec![],
+        }
+        .into()),
+        (None, Some(_)) => Ok(vec![TypeChange::TypeAdded]),
+        (Some(_), None) => Ok(vec![TypeChange::TypeRemoved]),
+        (Some(type_from), Some(type_to)) => Ok(type_changes(&type_from, &type_to)),
+    }
+}
+
+fn type_changes(type_from: &ReconstructedType, type_to: &ReconstructedType) -> Vec<TypeChange> {
+    let mut changes = vec![];
+    if type_from.size != type_to.size {
+        changes.push(TypeChange::SizeChanged {
+            old_size: type_from.size,
+            new_size: type_to.size,
+        });
+    }
+
+    // Report changes in the order in which fields appear in the new type,
+    // followed with removed fields
+    for field_to in &type_to.fields {
+        match type_from.fields.iter().find(|f| f.name == field_to.name) {
+            None => changes.push(TypeChange::FieldAdded {
+                name: field_to.name.clone(),
+                type_name: field_type_name(field_to),
+                offset: field_to.offset,
+            }),
+            Some(field_from) => {
+                let (old_offset, new_offset) = (field_offset(field_from), field_offset(field_to));
+                if old_offset != new_offset {
+                    changes.push(TypeChange::FieldMoved {
+                        name: field_to.name.clone(),
+                        old_offset,
+                        new_offset,
+                    });
+                }
+                let (old_type_name, new_type_name) =
+                    (field_type_name(field_from), field_type_name(field_to));
+                if old_type_name != new_type_name {
+                    changes.push(TypeChange::FieldTypeChanged {
+                        name: field_to.name.clone(),
+                        old_type_name,
+                        new_type_name,
+                    });
+                }
+            }
+        }
+    }
+    for field_from in &type_from.fields {
+        if !type_to.fields.iter().any(|f| f.name == field_from.name) {
+            changes.push(TypeChange::FieldRemoved {
+                name: field_from.name.clone(),
+                type_name: field_type_name(field_from),
+                offset: field_from.offset,
+            });
+        }
+    }
+
+    for value_to in &type_to.values {
+        match type_from.values.iter().find(|v| v.name == value_to.name) {
+            None => changes.push(TypeChange::EnumValueAdded {
+                name: value_to.name.clone(),
+                value: value_to.value,
+            }),
+            Some(value_from) if value_from.value != value_to.value => {
+                changes.push(TypeChange::EnumValueChanged {
+                    name: value_to.name.clone(),
+                    old_value: value_from.value,
+                    new_value: value_to.value,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for value_from in &type_from.values {
+        if !type_to.values.iter().any(|v| v.name == value_from.name) {
+            changes.push(TypeChange::EnumValueRemoved {
+                name: value_from.name.clone(),
+                value: value_from.value,
+            });
+        }
+    }
+
+    changes
+}
+
+fn field_offset(field: &ReconstructedField) -> String {
+    match field.bit_offset {
+        Some(bit_offset) => format!("{:#x}:{}", field.offset, bit_offset),
+        None => format!("{:#x}", field.offset),
+    }
+}
+
+fn field_type_name(field: &ReconstructedField) -> String {
+    match field.bit_width {
+        Some(bit_width) => format!("{} : {}", field.type_name, bit_width),
+        None => field.type_name.clone(),
+    }
+}
+
+fn fmt_enum_value(value: &ReconstructedEnumValueKind) -> String {
+    match value {
+        ReconstructedEnumValueKind::Signed(value) => value.to_string(),
+        ReconstructedEnumValueKind::Unsigned(value) => value.to_string(),
+    }
+}
+
+/// Difference between the type lists of two PDBs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum TypeListChange {
+    Added {
+        name: String,
+        size: u64,
+    },
+    Removed {
+        name: String,
+        size: u64,
+    },
+    SizeChanged {
+        name: String,
+        old_size: u64,
+        new_size: u64,
+    },
+}
+
+/// Compare the classes, structs and unions of two PDBs by name. Changes are
+/// sorted by type name, the order of the type records doesn't matter.
+pub fn diff_type_lists(
+    pdb_file_from: &PdbFile,
+    pdb_file_to: &PdbFile,
+) -> Result<Vec<TypeListChange>> {
+    let udt_sizes_from = pdb_file_from.udt_sizes()?;
+    let udt_sizes_to = pdb_file_to.udt_sizes()?;
+
+    let type_names = udt_sizes_from
+        .keys()
+        .chain(udt_sizes_to.keys())
+        .collect::<BTreeSet<_>>();
+    Ok(type_names
+        .into_iter()
+        .filter_map(
+            |name| match (udt_sizes_from.get(name), udt_sizes_to.get(name)) {
+                (None, Some(size)) => Some(TypeListChange::Added {
+                    name: name.clone(),
+                    size: *size,
+                }),
+                (Some(size), None) => Some(TypeListChange::Removed {
+                    name: name.clone(),
+                    size: *size,
+                }),
+                (Some(old_size), Some(new_size)) if old_size != new_size => {
+                    Some(TypeListChange::SizeChanged {
+                        name: name.clone(),
+                        old_size: *old_size,
+                        new_size: *new_size,
+                    })
+                }
+                _ => None,
+            },
+        )
+        .collect())
+}
+
 fn generate_diff_header(pdb_file_from: &PdbFile, pdb_file_to: &PdbFile) -> String {
     format!(
         concat!(
@@ -103,9 +412,9 @@ fn generate_diff_header(pdb_file_from: &PdbFile, pdb_file_to: &PdbFile) -> Strin
             "// Information extracted with resym v{}\n",
             "//\n"
         ),
-        pdb_file_from.file_path.display(),
+        pdb_file_from.name,
         pdb_file_from.machine_type,
-        pdb_file_to.file_path.display(),
+        pdb_file_to.name,
         pdb_file_to.machine_type,
         PKG_VERSION,
     )
@@ -0,0 +1,88 @@
+use thiserror::Error;
+
+use std::path::PathBuf;
+
+/// Errors returned by the backend (in its responses and by `Session`).
+/// Errors callers may want to handle differently have their own variant,
+/// others are reported as `Other`.
+#[derive(Debug, Error)]
+pub enum ResymError {
+    /// The PDB file doesn't exist
+    #[error("'{}' does not exist", .0.display())]
+    PdbNotFound(PathBuf),
+    /// The PDB file couldn't be parsed (e.g., it is corrupt or isn't a PDB)
+    #[error("failed to parse PDB: {0}")]
+    PdbParse(#[from] pdb::Error),
+    /// No PDB file has been loaded into the targeted slot
+    #[error("no PDB file has been loaded")]
+    PdbNotLoaded,
+    /// No type has the requested name, types with similar names are
+    /// suggested (from the closest to the farthest one)
+    #[error("type '{type_name}' not found{}", fmt_suggestions(.suggestions))]
+    TypeNotFound {
+        type_name: String,
+        suggestions: Vec<String>,
+    },
+    /// Several distinct types have the requested name, they're identified
+    /// by the given indices (ordered by type index)
+    #[error(
+        "{} types are named '{type_name}' (type indices: {})",
+        .type_indices.len(),
+        fmt_type_indices(.type_indices)
+    )]
+    AmbiguousTypeName {
+        type_name: String,
+        type_indices: Vec<pdb::TypeIndex>,
+    },
+    /// The search filter isn't a valid regular expression
+    #[error("invalid regular expression: {0}")]
+    InvalidRegex(#[source] regex::Error),
+    /// The backend's worker has stopped, commands cannot be processed anymore
+    #[error("the backend has stopped")]
+    BackendStopped,
+    /// Any other error (e.g., I/O errors, or unexpected records)
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+/// Retrieve the `ResymError` wrapped in `err` if there's one, so that errors
+/// propagated as `anyhow::Error`s internally keep their variant.
+impl From<anyhow::Error> for ResymError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<ResymError>() {
+            Ok(err) => err,
+            Err(err) => match err.downcast::<pdb::Error>() {
+                Ok(err) => ResymError::PdbParse(err),
+                Err(err) => ResymError::Other(err),
+            },
+        }
+    }
+}
+
+impl<T> From<crossbeam_channel::SendError<T>> for ResymError {
+    fn from(_: crossbeam_channel::SendError<T>) -> Self {
+        ResymError::BackendStopped
+    }
+}
+
+impl From<crossbeam_channel::RecvError> for ResymError {
+    fn from(_: crossbeam_channel::RecvError) -> Self {
+        ResymError::BackendStopped
+    }
+}
+
+fn fmt_suggestions(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::default()
+    } else {
+        format!("; did you mean: {}?", suggestions.join(", "))
+    }
+}
+
+fn fmt_type_indices(type_indices: &[pdb::TypeIndex]) -> String {
+    type_indices
+        .iter()
+        .map(|type_index| type_index.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
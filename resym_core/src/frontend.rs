@@ -1,14 +1,79 @@
+use std::fmt;
+
 use anyhow::Result;
 
-use crate::{backend::PDBSlot, diffing::DiffedType};
+use crate::{
+    backend::PDBSlot,
+    diffing::{DiffedType, TypeChange, TypeListChange},
+    error::ResymError,
+    pdb_file::{
+        FieldDeclaration, GlobalVariable, Module, PdbStatistics, PublicSymbol, ResolvedAddress,
+        SourceLocation, TypeGraph,
+    },
+    pdb_types::ReconstructedType,
+    pe_file::PdbVerification,
+};
 
 pub type TypeList = Vec<(String, pdb::TypeIndex)>;
+/// Type list along with the sizes of the types, in bytes
+pub type SizedTypeList = Vec<(String, pdb::TypeIndex, u64)>;
+pub type SymbolList = Vec<(String, PublicSymbol)>;
+pub type GlobalList = Vec<(String, GlobalVariable)>;
+pub type FieldList = Vec<(String, FieldDeclaration)>;
+
+/// Step of a long-running backend operation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProgressStage {
+    /// Indexing the types of a PDB (`LoadPDB`), progress is counted in
+    /// type records
+    LoadingPdb,
+    /// Reconstructing all the types of a PDB (`ReconstructAllTypes`),
+    /// progress is counted in types
+    ReconstructingTypes,
+}
+
+impl fmt::Display for ProgressStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProgressStage::LoadingPdb => "Loading PDB".fmt(f),
+            ProgressStage::ReconstructingTypes => "Reconstructing types".fmt(f),
+        }
+    }
+}
 
 pub enum FrontendCommand {
-    LoadPDBResult(Result<PDBSlot>),
-    UpdateFilteredTypes(TypeList),
-    ReconstructTypeResult(Result<String>),
-    DiffTypeResult(Result<DiffedType>),
+    LoadPDBResult(PDBSlot, Result<(), ResymError>),
+    UpdateFilteredTypes(PDBSlot, Result<TypeList, ResymError>),
+    FilterTypesResult(PDBSlot, Result<SizedTypeList, ResymError>),
+    ListTypesChunk(PDBSlot, TypeList),
+    ListTypesResult(PDBSlot, Result<(), ResymError>),
+    ReconstructTypeResult(PDBSlot, Result<String, ResymError>),
+    GetTypeModelResult(PDBSlot, Result<Vec<ReconstructedType>, ResymError>),
+    ReconstructAllTypesChunk(String),
+    ReconstructAllTypesResult(Result<(), ResymError>),
+    DiffTypeResult(Result<DiffedType, ResymError>),
+    SemanticDiffTypeResult(Result<Vec<TypeChange>, ResymError>),
+    DiffTypeListsResult(Result<Vec<TypeListChange>, ResymError>),
+    ListSymbolsResult(PDBSlot, Result<SymbolList, ResymError>),
+    ListGlobalsResult(PDBSlot, Result<GlobalList, ResymError>),
+    ResolveAddressResult(PDBSlot, Result<Option<ResolvedAddress>, ResymError>),
+    ListModulesResult(PDBSlot, Result<Vec<Module>, ResymError>),
+    ComputeStatisticsResult(PDBSlot, Result<PdbStatistics, ResymError>),
+    CheckPDBResult(PDBSlot, Result<(), ResymError>),
+    VerifyPEResult(PDBSlot, Result<PdbVerification, ResymError>),
+    ReconstructComDefinitionsResult(PDBSlot, Result<String, ResymError>),
+    GetSourceInfoResult(PDBSlot, Result<Vec<SourceLocation>, ResymError>),
+    FindTypesWithFieldResult(PDBSlot, Result<FieldList, ResymError>),
+    FindReferencingTypesResult(PDBSlot, Result<Vec<String>, ResymError>),
+    BuildTypeGraphResult(PDBSlot, Result<TypeGraph, ResymError>),
+    /// Acknowledgment of a `BackendCommand::Cancel`
+    Cancelled,
+    /// Periodic report sent while processing long-running commands
+    UpdateProgress {
+        current: usize,
+        total: usize,
+        stage: ProgressStage,
+    },
 }
 
 pub trait FrontendController {
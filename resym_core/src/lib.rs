@@ -1,8 +1,16 @@
+#[cfg(feature = "tokio")]
+pub mod async_backend;
 pub mod backend;
 pub mod diffing;
+pub mod error;
 pub mod frontend;
 pub mod pdb_file;
 pub mod pdb_types;
+pub mod pe_file;
+pub mod session;
 pub mod syntax_highlighting;
 
+pub use error::ResymError;
+pub use session::Session;
+
 const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
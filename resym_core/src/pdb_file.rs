@@ -2,58 +2,459 @@ use anyhow::{anyhow, Result};
 use dashmap::DashMap;
 use pdb::FallibleIterator;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::Serialize;
 
 use std::{
-    collections::BTreeSet,
-    fs::File,
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt, fs,
+    io::{self, Cursor},
+    ops::Range,
     path::{Path, PathBuf},
+    str::FromStr,
     sync::Arc,
 };
 
-use crate::pdb_types::{
-    self, is_unnamed_type, DataFormatConfiguration, PrimitiveReconstructionFlavor,
+use crate::{
+    error::ResymError,
+    pdb_types::{
+        self, is_unnamed_type, resolve_complete_type_index, ComInterface, DataFormatConfiguration,
+        GuidConstant, OutputFormat, PrimitiveReconstructionFlavor, ReconstructedType, TypeAlias,
+        TypeForwarder,
+    },
+    pe_file::{read_guid_at_rva, Guid, PdbReference, PdbVerification},
 };
 
+/// Reader the PDB is parsed from.
+type PdbSource = Cursor<PdbData>;
+
+/// Contents of a PDB. PDB files are mapped into memory when possible, so
+/// only the pages the `pdb` crate reads are loaded.
+enum PdbData {
+    Memory(Arc<[u8]>),
+    /// File read entirely into memory
+    Read(Vec<u8>),
+    Mapped(memmap2::Mmap),
+}
+
+impl AsRef<[u8]> for PdbData {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            PdbData::Memory(data) => data,
+            PdbData::Read(data) => data,
+            PdbData::Mapped(data) => data,
+        }
+    }
+}
+
+impl fmt::Debug for PdbData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdbData::Memory(data) => write!(f, "Memory({} bytes)", data.len()),
+            PdbData::Read(data) => write!(f, "Read({} bytes)", data.len()),
+            PdbData::Mapped(data) => write!(f, "Mapped({} bytes)", data.len()),
+        }
+    }
+}
+
+/// Size (in bytes) above which reconstructed types are flushed to the caller
+/// when reconstructing all the types of a PDB.
+const RECONSTRUCTION_CHUNK_SIZE: usize = 64 * 1024;
+/// Number of reconstructed types above which they're flushed to the caller
+/// (even if `RECONSTRUCTION_CHUNK_SIZE` hasn't been reached), so the first
+/// types are output early.
+const RECONSTRUCTION_CHUNK_TYPE_COUNT: usize = 128;
+/// Number of items (e.g., type records) processed between two progress
+/// reports.
+const PROGRESS_REPORT_INTERVAL: usize = 0x1000;
+/// Maximum number of type names suggested when a type isn't found.
+const MAX_TYPE_NAME_SUGGESTIONS: usize = 5;
+/// Maximum edit distance between a type name and the suggested names.
+const MAX_TYPE_NAME_SUGGESTION_DISTANCE: usize = 3;
+/// Index of the type information (TPI) stream.
+const TPI_STREAM_INDEX: pdb::StreamIndex = pdb::StreamIndex(2);
+/// Kind of the type records describing typedefs, which the `pdb` crate
+/// doesn't parse.
+const LF_ALIAS: u16 = 0x150a;
+
+/// Public symbol, as found in the PDB's global symbol stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicSymbol {
+    /// Name of the symbol, as found in the PDB
+    pub mangled_name: String,
+    /// Relative virtual address of the symbol, if it could be resolved
+    pub rva: Option<u32>,
+}
+
+/// Global (or file-static) variable, as found in the PDB's global symbol
+/// stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalVariable {
+    /// Name of the variable, as found in the PDB
+    pub mangled_name: String,
+    /// Relative virtual address of the variable, if it could be resolved
+    pub rva: Option<u32>,
+    /// Name of the variable's type
+    pub type_name: String,
+    /// Whether the variable is only visible from its module (i.e., it's an
+    /// `S_LDATA32` symbol)
+    pub is_static: bool,
+}
+
+/// Location of an address, relative to the public symbol it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAddress {
+    /// Demangled name of the symbol
+    pub symbol_name: String,
+    pub symbol: PublicSymbol,
+    /// Offset of the address from the start of the symbol
+    pub offset: u32,
+}
+
+/// Location of a definition in the source files the PDB was built from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceLocation {
+    pub file_name: String,
+    pub line: u32,
+}
+
+/// Error returned by operations that were cancelled before completing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperationCancelled;
+
+impl fmt::Display for OperationCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "operation cancelled".fmt(f)
+    }
+}
+
+impl std::error::Error for OperationCancelled {}
+
+/// Module (i.e., compiland) that contributed to the image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    /// Path of the object file (or library) the module comes from
+    pub object_file_name: String,
+    /// Number of symbols in the module's symbol stream, if requested
+    pub symbol_count: Option<usize>,
+}
+
+/// How a type refers to another type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeReferenceKind {
+    /// The type is embedded (as a member, an array element or a base class),
+    /// the referencing type's layout depends on it
+    Embedded,
+    /// The type is pointed to (or referenced) by a member
+    Pointer,
+}
+
+/// Reference from a type to another type of a `TypeGraph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeGraphEdge {
+    /// Index of the referencing type in the graph's types
+    pub from: usize,
+    /// Index of the referenced type in the graph's types
+    pub to: usize,
+    pub kind: TypeReferenceKind,
+}
+
+/// Graph of the classes, structs, unions and enums a type refers to through
+/// its members and base classes, directly or not.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeGraph {
+    /// Names of the graph's types, the root type first (types are ordered by
+    /// distance to the root type)
+    pub type_names: Vec<String>,
+    /// References between the graph's types, a type may refer to another
+    /// both ways
+    pub edges: Vec<TypeGraphEdge>,
+}
+
+/// Kind of a type, as used to filter types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    Struct,
+    /// Class or interface
+    Class,
+    Union,
+    Enum,
+    /// Name given to another type (i.e., a user-defined type symbol whose
+    /// name isn't the name of the type it refers to)
+    Typedef,
+}
+
+impl FromStr for TypeKind {
+    type Err = ParseTypeKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "struct" => Ok(TypeKind::Struct),
+            "class" => Ok(TypeKind::Class),
+            "union" => Ok(TypeKind::Union),
+            "enum" => Ok(TypeKind::Enum),
+            "typedef" => Ok(TypeKind::Typedef),
+            _ => Err(ParseTypeKindError {}),
+        }
+    }
+}
+
+/// An error returned when parsing a `TypeKind` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseTypeKindError {}
+
+impl fmt::Display for ParseTypeKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid type kind".fmt(f)
+    }
+}
+
+/// Typedef, as found in the PDB's global symbol stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Typedef {
+    /// Index of the aliased type
+    pub type_index: pdb::TypeIndex,
+    /// Size of the aliased type in bytes (0 for types without a size, e.g.
+    /// functions)
+    pub size: u64,
+}
+
+/// Member of a user-defined type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FieldDeclaration {
+    /// Name of the type declaring the member
+    pub type_name: String,
+    /// Name of the member's type
+    pub field_type_name: String,
+}
+
+/// Summary of the content of a PDB.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PdbStatistics {
+    pub struct_count: usize,
+    /// Number of classes (and interfaces)
+    pub class_count: usize,
+    pub union_count: usize,
+    pub enum_count: usize,
+    /// Number of user-defined type symbols (i.e., typedefs and tag names)
+    pub typedef_count: usize,
+    pub public_symbol_count: usize,
+    pub module_count: usize,
+    /// Number of records in the type information stream
+    pub type_record_count: usize,
+    /// Largest class, struct or union
+    pub largest_type: Option<TypeSize>,
+    /// Size of pointers on the PDB's target machine, if known
+    pub pointer_size: Option<u64>,
+}
+
+/// Size of a type, identified by its name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeSize {
+    pub name: String,
+    pub size: u64,
+}
+
 pub struct PdbFile<'p> {
+    /// Names and indices of the complete types, ordered by type index
     pub complete_type_list: Vec<(String, pdb::TypeIndex)>,
-    pub forwarder_to_complete_type: Arc<DashMap<pdb::TypeIndex, pdb::TypeIndex>>,
+    /// Indices of the complete types, by name and by unique name. Same-named
+    /// types are ordered by type index.
+    pub type_indices_by_name: HashMap<String, Vec<pdb::TypeIndex>>,
+    /// Sizes of the complete types, in bytes (missing for enums whose
+    /// underlying type couldn't be resolved)
+    pub complete_type_sizes: HashMap<pdb::TypeIndex, u64>,
+    /// Kinds of the complete types
+    pub complete_type_kinds: HashMap<pdb::TypeIndex, TypeKind>,
+    /// Resolves forward references and typedefs
+    pub type_forwarder: TypeForwarder,
     pub machine_type: pdb::MachineType,
+    /// GUID and age of the PDB, which PEs reference their PDB by
+    pub guid: Guid,
+    pub age: u32,
     pub type_information: pdb::TypeInformation<'p>,
-    pub file_path: PathBuf,
-    _pdb: pdb::PDB<'p, File>,
+    /// Name of the PDB, as displayed to the user (i.e., its path for PDBs
+    /// loaded from disk)
+    pub name: String,
+    /// Path of the PDB file, `None` for PDBs loaded from memory
+    pub file_path: Option<PathBuf>,
+    /// Public symbols, by demangled name. Loaded on first use.
+    public_symbols: Option<Vec<(String, PublicSymbol)>>,
+    /// Typedefs, by name. Loaded on first use.
+    typedefs: Option<Vec<(String, Typedef)>>,
+    /// RVA ranges of the section contributions. Loaded on first use.
+    section_contributions: Option<Vec<Range<u32>>>,
+    _pdb: pdb::PDB<'p, PdbSource>,
 }
 
+// Safety: `PdbFile`s are neither `Send` nor `Sync` only because the `pdb`
+// crate keeps the data of streams (e.g., `type_information`) in
+// `Box<dyn SourceView>`s and its reader (`_pdb`) in a `Box<dyn MSF>`, which
+// have no such bounds. PDBs are always read from a `PdbSource`, whose views
+// are owned, immutable byte buffers. The `pdb` crate has no interior
+// mutability, and its reader can only be used through `&mut self`. Lazily
+// loaded fields are only written through `&mut self` as well, so `PdbFile`s
+// can be sent to and read from several threads at once.
+unsafe impl Send for PdbFile<'_> {}
+unsafe impl Sync for PdbFile<'_> {}
+
 impl<'p> PdbFile<'p> {
     pub fn load_from_file(pdb_file_path: &Path) -> Result<PdbFile<'p>> {
-        let file = File::open(pdb_file_path)?;
-        let mut pdb = pdb::PDB::open(file)?;
-        let type_information = pdb.type_information()?;
-        let machine_type = pdb.debug_information()?.machine_type()?;
+        Self::load_from_file_with_progress(pdb_file_path, |_, _| Ok(()))
+    }
+
+    /// Load a PDB file. `report_progress` is periodically called with the
+    /// number of type records processed so far and the total number of
+    /// records. Loading is aborted if it fails (e.g., with
+    /// `OperationCancelled`).
+    ///
+    /// The file is mapped into memory (or read if it cannot be mapped), and
+    /// must not be modified until the returned `PdbFile` is dropped. Files
+    /// that may be rewritten in the meantime (e.g., by a linker) must be
+    /// loaded with `load_from_file_buffered_with_progress` instead.
+    pub fn load_from_file_with_progress(
+        pdb_file_path: &Path,
+        report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<PdbFile<'p>> {
+        let pdb_data = match map_file(pdb_file_path) {
+            Ok(pdb_data) => PdbData::Mapped(pdb_data),
+            Err(err) => {
+                log::debug!(
+                    "Failed to map '{}' into memory, reading it instead: {}",
+                    pdb_file_path.display(),
+                    err
+                );
+                read_file(pdb_file_path)?
+            }
+        };
+        Self::load_from_file_data_with_progress(pdb_file_path, pdb_data, report_progress)
+    }
+
+    pub fn load_from_file_buffered(pdb_file_path: &Path) -> Result<PdbFile<'p>> {
+        Self::load_from_file_buffered_with_progress(pdb_file_path, |_, _| Ok(()))
+    }
+
+    /// Load a PDB file, reading it entirely into memory. The file can then be
+    /// modified while the PDB is loaded. See `load_from_file_with_progress`.
+    pub fn load_from_file_buffered_with_progress(
+        pdb_file_path: &Path,
+        report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<PdbFile<'p>> {
+        let pdb_data = read_file(pdb_file_path)?;
+        Self::load_from_file_data_with_progress(pdb_file_path, pdb_data, report_progress)
+    }
+
+    fn load_from_file_data_with_progress(
+        pdb_file_path: &Path,
+        pdb_data: PdbData,
+        report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<PdbFile<'p>> {
+        let mut pdb_file = Self::load_from_data_with_progress(
+            &pdb_file_path.display().to_string(),
+            pdb_data,
+            report_progress,
+        )?;
+        pdb_file.file_path = Some(pdb_file_path.to_owned());
+
+        Ok(pdb_file)
+    }
+
+    /// Load a PDB from memory. `pdb_name` is used to refer to the PDB (e.g.,
+    /// in file headers).
+    pub fn load_from_bytes(pdb_name: &str, pdb_data: Arc<[u8]>) -> Result<PdbFile<'p>> {
+        Self::load_from_bytes_with_progress(pdb_name, pdb_data, |_, _| Ok(()))
+    }
+
+    /// Load a PDB from memory, see `load_from_file_with_progress`.
+    pub fn load_from_bytes_with_progress(
+        pdb_name: &str,
+        pdb_data: Arc<[u8]>,
+        report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<PdbFile<'p>> {
+        Self::load_from_data_with_progress(pdb_name, PdbData::Memory(pdb_data), report_progress)
+    }
+
+    fn load_from_data_with_progress(
+        pdb_name: &str,
+        pdb_data: PdbData,
+        report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<PdbFile<'p>> {
+        let mut pdb = pdb::PDB::open(Cursor::new(pdb_data)).map_err(ResymError::PdbParse)?;
+        log::trace!("Opened '{}'", pdb_name);
+        let type_information = pdb.type_information().map_err(ResymError::PdbParse)?;
+        log::trace!(
+            "Read the type information stream ({} records)",
+            type_information.len()
+        );
+        let debug_information = pdb.debug_information().map_err(ResymError::PdbParse)?;
+        let machine_type = debug_information
+            .machine_type()
+            .map_err(ResymError::PdbParse)?;
+        log::trace!(
+            "Read the debug information stream (machine type: {:?})",
+            machine_type
+        );
+        let pdb_information = pdb.pdb_information().map_err(ResymError::PdbParse)?;
+        let (data1, data2, data3, data4) = pdb_information.guid.as_fields();
+        let guid = Guid {
+            data1,
+            data2,
+            data3,
+            data4: *data4,
+        };
+        // PEs reference the age stored in the DBI stream, which isn't
+        // necessarily the same as the PDB information stream's
+        let age = debug_information.age().unwrap_or(pdb_information.age);
+        log::trace!(
+            "Read the PDB information stream (GUID: {}, age: {})",
+            guid,
+            age
+        );
 
         let mut pdb_file = PdbFile {
             complete_type_list: vec![],
-            forwarder_to_complete_type: Arc::new(DashMap::default()),
+            type_indices_by_name: HashMap::default(),
+            complete_type_sizes: HashMap::default(),
+            complete_type_kinds: HashMap::default(),
+            type_forwarder: TypeForwarder::default(),
             machine_type,
+            guid,
+            age,
             type_information,
-            file_path: pdb_file_path.to_owned(),
+            name: pdb_name.to_owned(),
+            file_path: None,
+            public_symbols: None,
+            typedefs: None,
+            section_contributions: None,
             _pdb: pdb,
         };
-        pdb_file.load_symbols()?;
+        pdb_file.load_symbols(report_progress)?;
 
         Ok(pdb_file)
     }
 
-    fn load_symbols(&mut self) -> Result<()> {
+    fn load_symbols(
+        &mut self,
+        mut report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<()> {
         // Build the list of complete types
         let complete_symbol_map: DashMap<String, pdb::TypeIndex> = DashMap::default();
         let mut forwarders = vec![];
+        let mut has_type_aliases = false;
         let pdb_start = std::time::Instant::now();
 
+        let type_record_count = self.type_information.len();
         let mut type_finder = self.type_information.finder();
         let mut type_info_iter = self.type_information.iter();
+        let mut processed_record_count = 0;
         while let Some(type_info) = type_info_iter.next()? {
+            if processed_record_count % PROGRESS_REPORT_INTERVAL == 0 {
+                report_progress(processed_record_count, type_record_count)?;
+            }
+            processed_record_count += 1;
             // keep building the index
             type_finder.update(&type_info_iter);
+            has_type_aliases |= type_info.raw_kind() == LF_ALIAS;
 
             let type_index = type_info.index();
             if let Ok(type_data) = type_info.parse() {
@@ -72,7 +473,24 @@ impl<'p> PdbFile<'p> {
                         if is_unnamed_type(&class_name) {
                             class_name = format!("_unnamed_{}", type_index);
                         }
-                        self.complete_type_list.push((class_name, type_index));
+                        self.complete_type_sizes
+                            .insert(type_index, u64::from(data.size));
+                        self.complete_type_kinds.insert(
+                            type_index,
+                            match data.kind {
+                                pdb::ClassKind::Struct => TypeKind::Struct,
+                                pdb::ClassKind::Class | pdb::ClassKind::Interface => {
+                                    TypeKind::Class
+                                }
+                            },
+                        );
+                        add_complete_type(
+                            &mut self.complete_type_list,
+                            &mut self.type_indices_by_name,
+                            class_name,
+                            data.unique_name,
+                            type_index,
+                        );
                     }
                     pdb::TypeData::Union(data) => {
                         let mut class_name = data.name.to_string().into_owned();
@@ -88,7 +506,16 @@ impl<'p> PdbFile<'p> {
                         if is_unnamed_type(&class_name) {
                             class_name = format!("_unnamed_{}", type_index);
                         }
-                        self.complete_type_list.push((class_name, type_index));
+                        self.complete_type_sizes
+                            .insert(type_index, u64::from(data.size));
+                        self.complete_type_kinds.insert(type_index, TypeKind::Union);
+                        add_complete_type(
+                            &mut self.complete_type_list,
+                            &mut self.type_indices_by_name,
+                            class_name,
+                            data.unique_name,
+                            type_index,
+                        );
                     }
                     pdb::TypeData::Enumeration(data) => {
                         let mut class_name = data.name.to_string().into_owned();
@@ -104,22 +531,40 @@ impl<'p> PdbFile<'p> {
                         if is_unnamed_type(&class_name) {
                             class_name = format!("_unnamed_{}", type_index);
                         }
-                        self.complete_type_list.push((class_name, type_index));
+                        if let Ok(size) = pdb_types::type_size(
+                            &type_finder,
+                            &self.type_forwarder,
+                            data.underlying_type,
+                        ) {
+                            self.complete_type_sizes.insert(type_index, size as u64);
+                        }
+                        self.complete_type_kinds.insert(type_index, TypeKind::Enum);
+                        add_complete_type(
+                            &mut self.complete_type_list,
+                            &mut self.type_indices_by_name,
+                            class_name,
+                            data.unique_name,
+                            type_index,
+                        );
                     }
                     _ => {}
                 }
             }
         }
-        log::debug!("PDB loading took {} ms", pdb_start.elapsed().as_millis());
+        report_progress(type_record_count, type_record_count)?;
+        log::debug!(
+            "Type list building took {} ms",
+            pdb_start.elapsed().as_millis()
+        );
 
         // Resolve forwarder references to their corresponding complete type, in parallel
         let fwd_start = std::time::Instant::now();
+        let complete_types = DashMap::default();
         forwarders.par_iter().for_each(|(fwd_name, fwd_type_id)| {
             if let Some(complete_type_index) = complete_symbol_map.get(fwd_name) {
-                self.forwarder_to_complete_type
-                    .insert(*fwd_type_id, *complete_type_index);
+                complete_types.insert(*fwd_type_id, *complete_type_index);
             } else {
-                log::debug!("'{}''s type definition wasn't found", fwd_name);
+                log::trace!("'{}''s type definition wasn't found", fwd_name);
             }
         });
         log::debug!(
@@ -127,115 +572,1093 @@ impl<'p> PdbFile<'p> {
             fwd_start.elapsed().as_millis()
         );
 
+        let type_aliases = if has_type_aliases {
+            self.load_type_aliases()?
+        } else {
+            HashMap::default()
+        };
+        log::trace!("Read {} typedef records", type_aliases.len());
+        self.type_forwarder = TypeForwarder::new(complete_types, type_aliases);
+
         Ok(())
     }
 
-    pub fn reconstruct_type_by_name(
-        &self,
-        type_name: &str,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        reconstruct_dependencies: bool,
-        print_access_specifiers: bool,
-    ) -> Result<String> {
-        // Populate our `TypeFinder` and find the right type index
-        let mut type_index = pdb::TypeIndex::default();
-        let mut type_finder = self.type_information.finder();
+    /// Read the typedefs (`LF_ALIAS` records) of the type information stream.
+    /// The `pdb` crate doesn't expose the data of the records it cannot parse,
+    /// so they're parsed from the raw stream.
+    fn load_type_aliases(&mut self) -> Result<HashMap<pdb::TypeIndex, TypeAlias>> {
+        match self._pdb.raw_stream(TPI_STREAM_INDEX)? {
+            Some(tpi_stream) => parse_type_aliases(tpi_stream.as_slice()),
+            None => Ok(HashMap::default()),
+        }
+    }
+
+    /// Return the public symbols of the PDB, along with their demangled
+    /// names. Symbols are ordered by RVA.
+    pub fn public_symbols(&mut self) -> Result<&[(String, PublicSymbol)]> {
+        if self.public_symbols.is_none() {
+            self.public_symbols = Some(self.load_public_symbols()?);
+        }
+
+        Ok(self.public_symbols.as_deref().unwrap_or_default())
+    }
+
+    fn load_public_symbols(&mut self) -> Result<Vec<(String, PublicSymbol)>> {
+        let symbols_start = std::time::Instant::now();
+        let address_map = self._pdb.address_map()?;
+        let global_symbols = self._pdb.global_symbols()?;
+
+        let mut public_symbols = vec![];
+        let mut symbol_iter = global_symbols.iter();
+        while let Some(symbol) = symbol_iter.next()? {
+            if let Ok(pdb::SymbolData::Public(data)) = symbol.parse() {
+                let mangled_name = data.name.to_string().into_owned();
+                // Names that aren't mangled (e.g., C functions) are kept as is
+                let demangled_name =
+                    msvc_demangler::demangle(&mangled_name, msvc_demangler::DemangleFlags::llvm())
+                        .unwrap_or_else(|_| mangled_name.clone());
+                public_symbols.push((
+                    demangled_name,
+                    PublicSymbol {
+                        mangled_name,
+                        rva: data.offset.to_rva(&address_map).map(|rva| rva.0),
+                    },
+                ));
+            }
+        }
+        // Symbols without a known RVA are placed last
+        public_symbols.sort_by(|lhs, rhs| {
+            (lhs.1.rva.is_none(), lhs.1.rva, &lhs.0).cmp(&(rhs.1.rva.is_none(), rhs.1.rva, &rhs.0))
+        });
+        log::debug!(
+            "Public symbols loading took {} ms",
+            symbols_start.elapsed().as_millis()
+        );
+
+        Ok(public_symbols)
+    }
+
+    /// Return the typedefs of the PDB, along with their names. Typedefs are
+    /// ordered by name.
+    pub fn typedefs(&mut self) -> Result<&[(String, Typedef)]> {
+        if self.typedefs.is_none() {
+            self.typedefs = Some(self.load_typedefs()?);
+        }
+
+        Ok(self.typedefs.as_deref().unwrap_or_default())
+    }
+
+    fn load_typedefs(&mut self) -> Result<Vec<(String, Typedef)>> {
+        let global_symbols = self._pdb.global_symbols()?;
+        let mut udt_symbols = vec![];
+        let mut symbol_iter = global_symbols.iter();
+        while let Some(symbol) = symbol_iter.next()? {
+            if let Ok(pdb::SymbolData::UserDefinedType(data)) = symbol.parse() {
+                udt_symbols.push((data.name.to_string().into_owned(), data.type_index));
+            }
+        }
+
+        let type_finder = self.populated_type_finder()?;
+        let mut typedefs = vec![];
+        for (typedef_name, type_index) in udt_symbols {
+            let type_index = resolve_complete_type_index(&self.type_forwarder, type_index);
+            // Tag names (e.g., `struct S` in C) are declared as types named
+            // after the tag
+            let is_tag_name = match type_finder.find(type_index).and_then(|item| item.parse()) {
+                Ok(pdb::TypeData::Class(data)) => data.name.to_string() == typedef_name,
+                Ok(pdb::TypeData::Union(data)) => data.name.to_string() == typedef_name,
+                Ok(pdb::TypeData::Enumeration(data)) => data.name.to_string() == typedef_name,
+                _ => false,
+            };
+            if !is_tag_name {
+                let size = pdb_types::type_size(&type_finder, &self.type_forwarder, type_index)
+                    .unwrap_or_default();
+                typedefs.push((
+                    typedef_name,
+                    Typedef {
+                        type_index,
+                        size: size as u64,
+                    },
+                ));
+            }
+        }
+        typedefs.sort_by(|lhs, rhs| (&lhs.0, lhs.1.type_index).cmp(&(&rhs.0, rhs.1.type_index)));
+        typedefs.dedup();
+
+        Ok(typedefs)
+    }
+
+    /// Return the global and static variables of the PDB, along with their
+    /// demangled names. Variables are ordered by RVA.
+    pub fn global_variables(
+        &mut self,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<Vec<(String, GlobalVariable)>> {
+        let address_map = self._pdb.address_map()?;
+        let global_symbols = self._pdb.global_symbols()?;
+
+        let mut data_symbols = vec![];
+        let mut symbol_iter = global_symbols.iter();
+        while let Some(symbol) = symbol_iter.next()? {
+            if let Ok(pdb::SymbolData::Data(data)) = symbol.parse() {
+                data_symbols.push((
+                    data.name.to_string().into_owned(),
+                    data.offset.to_rva(&address_map).map(|rva| rva.0),
+                    data.type_index,
+                    !data.global,
+                ));
+            }
+        }
+
+        let type_finder = self.populated_type_finder()?;
+        let mut global_variables = vec![];
+        for (mangled_name, rva, type_index, is_static) in data_symbols {
+            let (type_left, type_right) = pdb_types::type_name(
+                &type_finder,
+                &self.type_forwarder,
+                type_index,
+                primitives_flavor,
+                &mut pdb_types::TypeSet::new(),
+            )?;
+            // Names that aren't mangled (e.g., C variables) are kept as is
+            let demangled_name =
+                msvc_demangler::demangle(&mangled_name, msvc_demangler::DemangleFlags::llvm())
+                    .unwrap_or_else(|_| mangled_name.clone());
+            global_variables.push((
+                demangled_name,
+                GlobalVariable {
+                    mangled_name,
+                    rva,
+                    type_name: format!("{}{}", type_left, type_right),
+                    is_static,
+                },
+            ));
+        }
+        // Variables without a known RVA are placed last
+        global_variables.sort_by(|lhs, rhs| {
+            (lhs.1.rva.is_none(), lhs.1.rva, &lhs.0).cmp(&(rhs.1.rva.is_none(), rhs.1.rva, &rhs.0))
+        });
+        // Static variables can be referenced by several modules
+        global_variables.dedup();
+
+        Ok(global_variables)
+    }
+
+    /// Find the public symbol the given RVA belongs to, i.e. the closest
+    /// symbol preceding it in the same section contribution. Return `None`
+    /// if the RVA isn't part of any known contribution, or if no symbol
+    /// precedes it inside its contribution.
+    pub fn resolve_address(&mut self, rva: u32) -> Result<Option<ResolvedAddress>> {
+        if self.section_contributions.is_none() {
+            self.section_contributions = Some(self.load_section_contributions()?);
+        }
+        let contribution = match self
+            .section_contributions
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find(|contribution| contribution.contains(&rva))
         {
-            let mut type_iter = self.type_information.iter();
-            while let Some(item) = type_iter.next()? {
-                type_finder.update(&type_iter);
+            None => return Ok(None),
+            Some(contribution) => contribution.clone(),
+        };
 
-                let item_type_index = item.index();
-                if let Ok(type_data) = item.parse() {
-                    match type_data {
-                        pdb::TypeData::Class(data) => {
-                            if data.properties.forward_reference() {
-                                // Ignore incomplete type
-                                continue;
-                            }
+        let public_symbols = self.public_symbols()?;
+        // Symbols are ordered by RVA, the ones without an RVA come last
+        let following_symbol_index = public_symbols.partition_point(
+            |(_, symbol)| matches!(symbol.rva, Some(symbol_rva) if symbol_rva <= rva),
+        );
+        let symbol_rva = match following_symbol_index
+            .checked_sub(1)
+            .and_then(|i| public_symbols[i].1.rva)
+        {
+            Some(symbol_rva) if symbol_rva >= contribution.start => symbol_rva,
+            _ => return Ok(None),
+        };
+        // Several symbols may share an address (e.g., folded functions),
+        // pick the first one
+        let symbol_index = public_symbols.partition_point(
+            |(_, symbol)| matches!(symbol.rva, Some(other_rva) if other_rva < symbol_rva),
+        );
+        let (symbol_name, symbol) = &public_symbols[symbol_index];
 
-                            // Rename anonymous tags to something unique
-                            let class_name = data.name.to_string();
-                            if is_unnamed_type(&class_name) {
-                                if type_name == format!("_unnamed_{}", item_type_index) {
-                                    type_index = item_type_index;
-                                }
-                            } else if class_name == type_name {
-                                type_index = item_type_index;
-                            } else if let Some(unique_name) = data.unique_name {
-                                if unique_name.to_string() == type_name {
-                                    type_index = item_type_index;
-                                }
-                            }
-                        }
-                        pdb::TypeData::Union(data) => {
-                            if data.properties.forward_reference() {
-                                // Ignore incomplete type
-                                continue;
-                            }
+        Ok(Some(ResolvedAddress {
+            symbol_name: symbol_name.clone(),
+            symbol: symbol.clone(),
+            offset: rva - symbol_rva,
+        }))
+    }
 
-                            // Rename anonymous tags to something unique
-                            let union_name = data.name.to_string();
-                            if is_unnamed_type(&union_name) {
-                                if type_name == format!("_unnamed_{}", item_type_index) {
-                                    type_index = item_type_index;
-                                }
-                            } else if data.name.to_string() == type_name {
-                                type_index = item_type_index;
-                            } else if let Some(unique_name) = data.unique_name {
-                                if unique_name.to_string() == type_name {
-                                    type_index = item_type_index;
-                                }
-                            }
-                        }
-                        pdb::TypeData::Enumeration(data) => {
-                            if data.properties.forward_reference() {
-                                // Ignore incomplete type
-                                continue;
-                            }
+    /// Return the modules listed in the PDB, in the order they're stored in.
+    /// Symbols of each module are counted if `count_symbols` is true.
+    pub fn modules(&mut self, count_symbols: bool) -> Result<Vec<Module>> {
+        let debug_information = self._pdb.debug_information()?;
 
-                            // Rename anonymous tags to something unique
-                            let enum_name = data.name.to_string();
-                            if is_unnamed_type(&enum_name) {
-                                if type_name == format!("_unnamed_{}", item_type_index) {
-                                    type_index = item_type_index;
-                                }
-                            } else if data.name.to_string() == type_name {
-                                type_index = item_type_index;
-                            } else if let Some(unique_name) = data.unique_name {
-                                if unique_name.to_string() == type_name {
-                                    type_index = item_type_index;
-                                }
+        let mut modules = vec![];
+        let mut module_iter = debug_information.modules()?;
+        while let Some(module) = module_iter.next()? {
+            let symbol_count = if count_symbols {
+                // Some modules (e.g., linker-generated ones) have no symbol
+                // stream
+                match self._pdb.module_info(&module)? {
+                    None => Some(0),
+                    Some(module_info) => Some(module_info.symbols()?.count()?),
+                }
+            } else {
+                None
+            };
+            modules.push(Module {
+                name: module.module_name().into_owned(),
+                object_file_name: module.object_file_name().into_owned(),
+                symbol_count,
+            });
+        }
+
+        Ok(modules)
+    }
+
+    /// Return the locations of the definitions of the types or functions
+    /// named `name`. Types are located through the records the compiler
+    /// emits in the IPI stream, functions through the line information of
+    /// the modules defining them. An empty list is returned if the symbol
+    /// exists but has no source information.
+    pub fn source_info(&mut self, name: &str) -> Result<Vec<SourceLocation>> {
+        let string_table = self._pdb.string_table()?;
+        let mut source_locations = vec![];
+        let mut symbol_found = false;
+
+        if let Some(type_indices) = self.type_indices_by_name.get(name) {
+            symbol_found = true;
+            let id_information = self._pdb.id_information()?;
+            let mut id_finder = id_information.finder();
+            let mut id_iter = id_information.iter();
+            while let Some(id) = id_iter.next()? {
+                id_finder.update(&id_iter);
+                if let Ok(pdb::IdData::UserDefinedTypeSource(source)) = id.parse() {
+                    let udt_index = resolve_complete_type_index(&self.type_forwarder, source.udt);
+                    if !type_indices.contains(&udt_index) {
+                        continue;
+                    }
+                    let file_name = match source.source_file {
+                        pdb::UserDefinedTypeSourceFileRef::Local(id_index) => {
+                            match id_finder.find(id_index)?.parse()? {
+                                pdb::IdData::String(string) => string.name.to_string().into_owned(),
+                                _ => continue,
                             }
                         }
-                        // Ignore
-                        _ => {}
+                        pdb::UserDefinedTypeSourceFileRef::Remote(_, string_ref) => {
+                            string_ref.to_string_lossy(&string_table)?.into_owned()
+                        }
+                    };
+                    source_locations.push(SourceLocation {
+                        file_name,
+                        line: source.line,
+                    });
+                }
+            }
+        }
+
+        let debug_information = self._pdb.debug_information()?;
+        let mut module_iter = debug_information.modules()?;
+        while let Some(module) = module_iter.next()? {
+            let module_info = match self._pdb.module_info(&module)? {
+                Some(module_info) => module_info,
+                None => continue,
+            };
+            let mut line_program = None;
+            let mut symbol_iter = module_info.symbols()?;
+            while let Some(symbol) = symbol_iter.next()? {
+                let procedure = match symbol.parse() {
+                    Ok(pdb::SymbolData::Procedure(procedure)) if procedure.name == name.into() => {
+                        procedure
                     }
+                    _ => continue,
+                };
+                symbol_found = true;
+                if line_program.is_none() {
+                    line_program = Some(module_info.line_program()?);
+                }
+                let line_program = line_program.as_ref().unwrap();
+                // The first line of the function is the one with the lowest
+                // offset inside of it
+                let first_line = line_program
+                    .lines()
+                    .filter(|line| {
+                        Ok(line.offset.section == procedure.offset.section
+                            && line.offset.offset >= procedure.offset.offset
+                            && line.offset.offset - procedure.offset.offset < procedure.len)
+                    })
+                    .min_by_key(|line| Ok(line.offset.offset))?;
+                if let Some(first_line) = first_line {
+                    let file_info = line_program.get_file_info(first_line.file_index)?;
+                    source_locations.push(SourceLocation {
+                        file_name: file_info.name.to_string_lossy(&string_table)?.into_owned(),
+                        line: first_line.line_start,
+                    });
                 }
             }
         }
 
-        if type_index == pdb::TypeIndex::default() {
-            Err(anyhow!("type not found"))
-        } else {
-            self.reconstruct_type_by_type_index_internal(
-                &type_finder,
-                type_index,
-                &primitives_flavor,
-                reconstruct_dependencies,
-                print_access_specifiers,
-            )
+        if !symbol_found {
+            return Err(anyhow::anyhow!("symbol not found"));
+        }
+        source_locations.dedup();
+
+        Ok(source_locations)
+    }
+
+    /// Size of pointers on the PDB's target machine, `None` is returned for
+    /// unknown machine types. Note: Pointer records carry their own size, this
+    /// is the size of pointers in general (e.g., of `void*`).
+    pub fn pointer_size(&self) -> Option<u64> {
+        match self.machine_type {
+            pdb::MachineType::Amd64
+            | pdb::MachineType::Arm64
+            | pdb::MachineType::Ia64
+            | pdb::MachineType::RiscV64 => Some(8),
+            pdb::MachineType::X86
+            | pdb::MachineType::Arm
+            | pdb::MachineType::ArmNT
+            | pdb::MachineType::Thumb
+            | pdb::MachineType::RiscV32 => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Count the types, symbols and modules of the PDB. Forward references
+    /// aren't counted as types.
+    pub fn compute_statistics(&mut self) -> Result<PdbStatistics> {
+        let mut statistics = PdbStatistics {
+            type_record_count: self.type_information.len(),
+            pointer_size: self.pointer_size(),
+            ..Default::default()
+        };
+
+        let mut update_largest_type = |name: pdb::RawString, size: u64| {
+            if statistics
+                .largest_type
+                .as_ref()
+                .is_none_or(|largest_type| size > largest_type.size)
+            {
+                statistics.largest_type = Some(TypeSize {
+                    name: name.to_string().into_owned(),
+                    size,
+                });
+            }
+        };
+        let mut struct_count = 0;
+        let mut class_count = 0;
+        let mut union_count = 0;
+        let mut enum_count = 0;
+        let mut type_info_iter = self.type_information.iter();
+        while let Some(type_info) = type_info_iter.next()? {
+            match type_info.parse() {
+                Ok(pdb::TypeData::Class(data)) if !data.properties.forward_reference() => {
+                    match data.kind {
+                        pdb::ClassKind::Struct => struct_count += 1,
+                        pdb::ClassKind::Class | pdb::ClassKind::Interface => class_count += 1,
+                    }
+                    update_largest_type(data.name, data.size as u64);
+                }
+                Ok(pdb::TypeData::Union(data)) if !data.properties.forward_reference() => {
+                    union_count += 1;
+                    update_largest_type(data.name, data.size as u64);
+                }
+                Ok(pdb::TypeData::Enumeration(data)) if !data.properties.forward_reference() => {
+                    enum_count += 1;
+                }
+                _ => {}
+            }
+        }
+        statistics.struct_count = struct_count;
+        statistics.class_count = class_count;
+        statistics.union_count = union_count;
+        statistics.enum_count = enum_count;
+
+        let global_symbols = self._pdb.global_symbols()?;
+        let mut symbol_iter = global_symbols.iter();
+        while let Some(symbol) = symbol_iter.next()? {
+            if let Ok(pdb::SymbolData::UserDefinedType(_)) = symbol.parse() {
+                statistics.typedef_count += 1;
+            }
+        }
+        statistics.public_symbol_count = self.public_symbols()?.len();
+        statistics.module_count = self._pdb.debug_information()?.modules()?.count()?;
+
+        Ok(statistics)
+    }
+
+    /// Check that the PDB's type information and debug information (i.e.,
+    /// the modules, their symbols and the section contributions) can be
+    /// entirely parsed, as loading the PDB only parses what it needs. Records
+    /// of kinds the `pdb` crate doesn't support aren't considered invalid.
+    pub fn check_integrity(&mut self) -> Result<()> {
+        let check_start = std::time::Instant::now();
+        let mut type_info_iter = self.type_information.iter();
+        while let Some(type_info) = type_info_iter.next().map_err(ResymError::PdbParse)? {
+            match type_info.parse() {
+                Ok(_)
+                | Err(pdb::Error::UnimplementedTypeKind(_))
+                | Err(pdb::Error::UnimplementedFeature(_)) => {}
+                Err(err) => {
+                    return Err(anyhow!(
+                        "failed to parse type record {}: {}",
+                        type_info.index(),
+                        err
+                    ))
+                }
+            }
+        }
+        log::trace!("Checked the type information stream");
+
+        let debug_information = self
+            ._pdb
+            .debug_information()
+            .map_err(ResymError::PdbParse)?;
+        let mut module_iter = debug_information.modules().map_err(ResymError::PdbParse)?;
+        while let Some(module) = module_iter.next().map_err(ResymError::PdbParse)? {
+            // Some modules (e.g., linker-generated ones) have no symbol
+            // stream
+            let module_info = match self._pdb.module_info(&module) {
+                Ok(Some(module_info)) => module_info,
+                Ok(None) => continue,
+                Err(err) => return Err(ResymError::PdbParse(err).into()),
+            };
+            let mut symbol_iter = module_info.symbols().map_err(ResymError::PdbParse)?;
+            while let Some(symbol) = symbol_iter.next().map_err(ResymError::PdbParse)? {
+                match symbol.parse() {
+                    Ok(_)
+                    | Err(pdb::Error::UnimplementedSymbolKind(_))
+                    | Err(pdb::Error::UnimplementedFeature(_)) => {}
+                    Err(err) => {
+                        return Err(anyhow!(
+                            "failed to parse a symbol of module '{}': {}",
+                            module.module_name(),
+                            err
+                        ))
+                    }
+                }
+            }
+        }
+        let mut contribution_iter = debug_information
+            .section_contributions()
+            .map_err(ResymError::PdbParse)?;
+        while contribution_iter
+            .next()
+            .map_err(ResymError::PdbParse)?
+            .is_some()
+        {}
+        log::debug!(
+            "PDB integrity check took {} ms",
+            check_start.elapsed().as_millis()
+        );
+
+        Ok(())
+    }
+
+    /// Return the names of the classes, structs and unions whose members (or
+    /// base classes) refer to the type named `type_name`, directly or through
+    /// pointers and arrays. Only references of the given kind are considered,
+    /// if any.
+    pub fn find_referencing_types(
+        &self,
+        type_name: &str,
+        reference_kind: Option<TypeReferenceKind>,
+    ) -> Result<Vec<String>> {
+        let (type_finder, referenced_type_index) = self.find_type_by_name(type_name)?;
+
+        let mut referencing_types = BTreeSet::new();
+        for (class_name, type_index) in &self.complete_type_list {
+            let is_referencing = udt_field_records(&type_finder, *type_index)?
+                .into_iter()
+                .filter_map(|field| match field {
+                    pdb::TypeData::Member(data) => Some(data.field_type),
+                    pdb::TypeData::BaseClass(data) => Some(data.base_class),
+                    pdb::TypeData::VirtualBaseClass(data) => Some(data.base_class),
+                    _ => None,
+                })
+                .any(|field_type| {
+                    let (type_index, kind) =
+                        self.referenced_type(&type_finder, field_type, TypeReferenceKind::Embedded);
+                    type_index == referenced_type_index
+                        && reference_kind.is_none_or(|reference_kind| kind == reference_kind)
+                });
+            if is_referencing {
+                referencing_types.insert(class_name.clone());
+            }
+        }
+
+        Ok(referencing_types.into_iter().collect())
+    }
+
+    /// Return the graph of the types the type named `type_name` refers to
+    /// through its members and base classes. References are followed up to
+    /// `max_depth` levels from the root type: the types found at that depth
+    /// are part of the graph, but their own references aren't.
+    pub fn type_graph(&self, type_name: &str, max_depth: usize) -> Result<TypeGraph> {
+        let (type_finder, root_type_index) = self.find_type_by_name(type_name)?;
+
+        let mut graph = TypeGraph {
+            type_names: vec![type_name.to_string()],
+            edges: vec![],
+        };
+        let mut type_node_indices = HashMap::from([(root_type_index, 0)]);
+        let mut listed_edges = HashSet::new();
+        // Breadth-first traversal, so that types are listed by distance
+        let mut pending_types = VecDeque::from([(root_type_index, 0)]);
+        while let Some((type_index, depth)) = pending_types.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+
+            let from = type_node_indices[&type_index];
+            for field in udt_field_records(&type_finder, type_index)? {
+                let field_type = match field {
+                    pdb::TypeData::Member(data) => data.field_type,
+                    pdb::TypeData::BaseClass(data) => data.base_class,
+                    pdb::TypeData::VirtualBaseClass(data) => data.base_class,
+                    _ => continue,
+                };
+                let (referenced_type_index, kind) =
+                    self.referenced_type(&type_finder, field_type, TypeReferenceKind::Embedded);
+                // Primitive types and functions aren't part of the graph
+                let referenced_type_name = match udt_name(&type_finder, referenced_type_index) {
+                    Some(referenced_type_name) => referenced_type_name,
+                    None => continue,
+                };
+                let to = match type_node_indices.entry(referenced_type_index) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        graph.type_names.push(referenced_type_name);
+                        pending_types.push_back((referenced_type_index, depth + 1));
+                        *entry.insert(graph.type_names.len() - 1)
+                    }
+                };
+                if listed_edges.insert((from, to, kind)) {
+                    graph.edges.push(TypeGraphEdge { from, to, kind });
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Return the members of every class, struct and union of the PDB, along
+    /// with their names. Members are sorted by the name of the type declaring
+    /// them, and are listed in declaration order within a type. Types defined
+    /// several times identically are only listed once.
+    pub fn fields(
+        &self,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<Vec<(String, FieldDeclaration)>> {
+        let type_finder = self.populated_type_finder()?;
+
+        let mut sorted_type_list = self.complete_type_list.iter().collect::<Vec<_>>();
+        sorted_type_list.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
+        let mut fields = vec![];
+        for (class_name, type_index) in sorted_type_list {
+            for field in udt_field_records(&type_finder, *type_index)? {
+                if let pdb::TypeData::Member(data) = field {
+                    let (type_left, type_right) = pdb_types::type_name(
+                        &type_finder,
+                        &self.type_forwarder,
+                        data.field_type,
+                        primitives_flavor,
+                        &mut pdb_types::TypeSet::new(),
+                    )?;
+                    fields.push((
+                        data.name.to_string().into_owned(),
+                        FieldDeclaration {
+                            type_name: class_name.clone(),
+                            field_type_name: format!("{}{}", type_left, type_right),
+                        },
+                    ));
+                }
+            }
+        }
+        let mut listed_fields = BTreeSet::new();
+        fields.retain(|field| listed_fields.insert(field.clone()));
+
+        Ok(fields)
+    }
+
+    /// Strip modifiers, arrays and pointers from `type_index` and return the
+    /// index of the (complete) type it designates, along with the way it's
+    /// referred to.
+    fn referenced_type(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_index: pdb::TypeIndex,
+        reference_kind: TypeReferenceKind,
+    ) -> (pdb::TypeIndex, TypeReferenceKind) {
+        let type_index = resolve_complete_type_index(&self.type_forwarder, type_index);
+        match type_finder
+            .find(type_index)
+            .and_then(|type_item| type_item.parse())
+        {
+            Ok(pdb::TypeData::Modifier(data)) => {
+                self.referenced_type(type_finder, data.underlying_type, reference_kind)
+            }
+            Ok(pdb::TypeData::Bitfield(data)) => {
+                self.referenced_type(type_finder, data.underlying_type, reference_kind)
+            }
+            Ok(pdb::TypeData::Array(data)) => {
+                self.referenced_type(type_finder, data.element_type, reference_kind)
+            }
+            Ok(pdb::TypeData::Pointer(data)) => self.referenced_type(
+                type_finder,
+                data.underlying_type,
+                TypeReferenceKind::Pointer,
+            ),
+            _ => (type_index, reference_kind),
+        }
+    }
+
+    /// Return the sizes of the named classes, structs and unions of the PDB.
+    /// When several types share a name, the largest size is kept so that the
+    /// result doesn't depend on the order of the type records.
+    pub fn udt_sizes(&self) -> Result<BTreeMap<String, u64>> {
+        let mut udt_sizes = BTreeMap::new();
+        let mut type_info_iter = self.type_information.iter();
+        while let Some(type_info) = type_info_iter.next()? {
+            let (name, size) = match type_info.parse() {
+                Ok(pdb::TypeData::Class(data)) if !data.properties.forward_reference() => {
+                    (data.name, data.size as u64)
+                }
+                Ok(pdb::TypeData::Union(data)) if !data.properties.forward_reference() => {
+                    (data.name, data.size as u64)
+                }
+                _ => continue,
+            };
+            let name = name.to_string();
+            // Names given to unnamed types depend on their type index
+            if is_unnamed_type(&name) {
+                continue;
+            }
+            let largest_size = udt_sizes.entry(name.into_owned()).or_insert(size);
+            *largest_size = std::cmp::max(*largest_size, size);
+        }
+
+        Ok(udt_sizes)
+    }
+
+    fn load_section_contributions(&mut self) -> Result<Vec<Range<u32>>> {
+        let address_map = self._pdb.address_map()?;
+        let debug_information = self._pdb.debug_information()?;
+
+        let mut section_contributions = vec![];
+        let mut contribution_iter = debug_information.section_contributions()?;
+        while let Some(contribution) = contribution_iter.next()? {
+            if let Some(start) = contribution.offset.to_rva(&address_map) {
+                section_contributions.push(start.0..start.0.saturating_add(contribution.size));
+            }
         }
+        section_contributions.sort_by_key(|contribution| contribution.start);
+
+        Ok(section_contributions)
+    }
+
+    pub fn reconstruct_type_by_name(
+        &self,
+        type_name: &str,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        output_format: OutputFormat,
+        reconstruct_dependencies: bool,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let (type_finder, type_index) = self.find_type_by_name(type_name)?;
+        self.reconstruct_type_by_type_index_internal(
+            &type_finder,
+            type_index,
+            &primitives_flavor,
+            output_format,
+            reconstruct_dependencies,
+            fmt_configuration,
+        )
     }
 
     pub fn reconstruct_type_by_type_index(
+        &self,
+        type_index: pdb::TypeIndex,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+        output_format: OutputFormat,
+        reconstruct_dependencies: bool,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let type_finder = self.populated_type_finder()?;
+        self.reconstruct_type_by_type_index_internal(
+            &type_finder,
+            type_index,
+            primitives_flavor,
+            output_format,
+            reconstruct_dependencies,
+            fmt_configuration,
+        )
+    }
+
+    /// Reconstruct a type given its name and return a serializable description
+    /// of it (and of its dependencies, if requested).
+    pub fn reconstruct_type_model_by_name(
+        &self,
+        type_name: &str,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        reconstruct_dependencies: bool,
+    ) -> Result<Vec<ReconstructedType>> {
+        let (type_finder, type_index) = self.find_type_by_name(type_name)?;
+        self.reconstruct_type_model_internal(
+            &type_finder,
+            type_index,
+            &primitives_flavor,
+            reconstruct_dependencies,
+        )
+    }
+
+    /// Reconstruct a type given its type index and return a serializable
+    /// description of it (and of its dependencies, if requested).
+    pub fn reconstruct_type_model_by_type_index(
         &self,
         type_index: pdb::TypeIndex,
         primitives_flavor: &PrimitiveReconstructionFlavor,
         reconstruct_dependencies: bool,
-        print_access_specifiers: bool,
+    ) -> Result<Vec<ReconstructedType>> {
+        let type_finder = self.populated_type_finder()?;
+        self.reconstruct_type_model_internal(
+            &type_finder,
+            type_index,
+            primitives_flavor,
+            reconstruct_dependencies,
+        )
+    }
+
+    /// Reconstruct a C++ forward declaration of the type named `type_name`.
+    pub fn reconstruct_forward_declaration_by_name(
+        &self,
+        type_name: &str,
+        primitives_flavor: PrimitiveReconstructionFlavor,
     ) -> Result<String> {
-        // Populate our `TypeFinder`
+        let (type_finder, type_index) = self.find_type_by_name(type_name)?;
+        let mut forward_declaration = String::new();
+        pdb_types::fmt_forward_declaration(
+            &type_finder,
+            &self.type_forwarder,
+            type_index,
+            &primitives_flavor,
+            &mut forward_declaration,
+        )?;
+
+        Ok(forward_declaration)
+    }
+
+    /// Reconstruct C++ forward declarations of the given types, one per line.
+    /// This doesn't walk the types' members, which makes it much cheaper than
+    /// a full reconstruction.
+    pub fn reconstruct_forward_declarations(
+        &self,
+        type_indices: &[pdb::TypeIndex],
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<String> {
+        let type_finder = self.populated_type_finder()?;
+        let mut forward_declarations = String::new();
+        for type_index in type_indices {
+            pdb_types::fmt_forward_declaration(
+                &type_finder,
+                &self.type_forwarder,
+                *type_index,
+                primitives_flavor,
+                &mut forward_declarations,
+            )?;
+        }
+
+        Ok(forward_declarations)
+    }
+
+    /// Reconstruct all the user-defined types of the PDB. The output is passed
+    /// to `output_chunk` piece by piece (every few types, or once enough
+    /// output has been produced), so that the whole reconstruction never has
+    /// to be held in memory at once.
+    /// If `reconstruct_dependencies` is true, types are ordered so that
+    /// dependencies appear before dependents.
+    pub fn reconstruct_all_types(
+        &self,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        reconstruct_dependencies: bool,
+        fmt_configuration: &DataFormatConfiguration,
+        mut output_chunk: impl FnMut(String) -> Result<()>,
+        mut report_progress: impl FnMut(usize, usize) -> Result<()>,
+    ) -> Result<()> {
+        let type_finder = self.populated_type_finder()?;
+        let mut type_indices = self
+            .complete_type_list
+            .iter()
+            .map(|(_, type_index)| *type_index)
+            .collect::<Vec<_>>();
+        type_indices.sort_unstable();
+
+        let type_forwarder = self
+            .type_forwarder
+            .resolving_type_aliases(fmt_configuration.resolve_typedefs);
+        let reconstruction_start = std::time::Instant::now();
+        let mut chunk = String::new();
+        let mut chunk_type_count = 0;
+        // Types are marked as visited as soon as they're scheduled. This
+        // guarantees termination for mutually-referential types
+        let mut visited_types = BTreeSet::new();
+        let type_count = type_indices.len();
+        for (i, type_index) in type_indices.into_iter().enumerate() {
+            if i % PROGRESS_REPORT_INTERVAL == 0 {
+                report_progress(i, type_count)?;
+            }
+            if !visited_types.insert(type_index) {
+                continue;
+            }
+
+            // Depth-first traversal of the dependency graph, types are output
+            // once all of their dependencies have been output
+            let mut pending_types = vec![self.collect_single_type_data(
+                &type_finder,
+                &type_forwarder,
+                type_index,
+                &primitives_flavor,
+                reconstruct_dependencies,
+            )];
+            while let Some((_, dependencies)) = pending_types.last_mut() {
+                if let Some(dependency) = dependencies.pop() {
+                    if visited_types.insert(dependency) {
+                        pending_types.push(self.collect_single_type_data(
+                            &type_finder,
+                            &type_forwarder,
+                            dependency,
+                            &primitives_flavor,
+                            reconstruct_dependencies,
+                        ));
+                    }
+                    continue;
+                }
+
+                if let Some((type_data, _)) = pending_types.pop() {
+                    type_data.reconstruct(fmt_configuration, &mut chunk)?;
+                    chunk_type_count += 1;
+                    if chunk.len() >= RECONSTRUCTION_CHUNK_SIZE
+                        || chunk_type_count >= RECONSTRUCTION_CHUNK_TYPE_COUNT
+                    {
+                        output_chunk(std::mem::take(&mut chunk))?;
+                        chunk_type_count = 0;
+                    }
+                }
+            }
+        }
+        if !chunk.is_empty() {
+            output_chunk(chunk)?;
+        }
+        report_progress(type_count, type_count)?;
+        log::debug!(
+            "Reconstruction of all types took {} ms",
+            reconstruction_start.elapsed().as_millis()
+        );
+
+        Ok(())
+    }
+
+    /// Return the index of the type named `type_name`. Fails if no type or
+    /// several distinct types have this name.
+    pub fn find_type_index_by_name(&self, type_name: &str) -> Result<pdb::TypeIndex> {
+        let (_, type_index) = self.find_type_by_name(type_name)?;
+        Ok(type_index)
+    }
+
+    /// Populate a `TypeFinder` and find the index of the type named
+    /// `type_name` (or whose unique name is `type_name`). A type can be
+    /// defined several times (e.g., records of outdated definitions are left
+    /// behind by incremental linking), in which case the last definition is
+    /// picked. Distinct types sharing the name (i.e., whose layouts differ)
+    /// are reported rather than picked arbitrarily.
+    fn find_type_by_name(&self, type_name: &str) -> Result<(pdb::TypeFinder<'_>, pdb::TypeIndex)> {
+        let type_indices = match self.type_indices_by_name.get(type_name) {
+            Some(type_indices) => type_indices,
+            None => {
+                let suggested_type_names = self.suggest_type_names(type_name);
+                return Err(ResymError::TypeNotFound {
+                    type_name: type_name.to_string(),
+                    suggestions: suggested_type_names,
+                }
+                .into());
+            }
+        };
+        let type_finder = self.populated_type_finder()?;
+
+        // Keep the last definition of each layout
+        let mut type_indices_by_layout = HashMap::new();
+        for type_index in type_indices {
+            type_indices_by_layout.insert(type_layout(&type_finder, *type_index)?, *type_index);
+        }
+        let mut distinct_type_indices = type_indices_by_layout.into_values().collect::<Vec<_>>();
+        distinct_type_indices.sort();
+        match distinct_type_indices.as_slice() {
+            [] => unreachable!("types are indexed by name once defined"),
+            [type_index] => Ok((type_finder, *type_index)),
+            _ => Err(ResymError::AmbiguousTypeName {
+                type_name: type_name.to_string(),
+                type_indices: distinct_type_indices,
+            }
+            .into()),
+        }
+    }
+
+    /// Check whether this PDB is the one referenced by the PE file at the
+    /// given path.
+    pub fn verify_pe(&self, pe_file_path: &Path) -> Result<PdbVerification> {
+        let pe_reference = PdbReference::from_pe_file(pe_file_path)?;
+        let pdb_file_name = self
+            .name
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or(&self.name)
+            .to_string();
+
+        Ok(PdbVerification {
+            pe_reference,
+            pdb_file_name,
+            pdb_guid: self.guid,
+            pdb_age: self.age,
+        })
+    }
+
+    /// Reconstruct the GUID constants (as `DEFINE_GUID` statements) and the
+    /// virtual function tables of the COM interfaces of the PDB. PDBs don't
+    /// contain the values of variables, the values of GUID constants are read
+    /// from the PE file, if given.
+    pub fn reconstruct_com_definitions(
+        &mut self,
+        pe_file_path: Option<&Path>,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<String> {
+        let guid_constants = self.guid_constants(pe_file_path)?;
+        let interfaces = self.com_interfaces(primitives_flavor)?;
+
+        let mut reconstruction_output = String::new();
+        pdb_types::fmt_com_definitions(&guid_constants, &interfaces, &mut reconstruction_output)?;
+        Ok(reconstruction_output)
+    }
+
+    /// Return the global variables of type `GUID` (e.g., IIDs and CLSIDs).
+    /// Values are only known for the constants generated for `__uuidof`,
+    /// unless the PE file is given.
+    pub fn guid_constants(&mut self, pe_file_path: Option<&Path>) -> Result<Vec<GuidConstant>> {
+        let pe_data = match pe_file_path {
+            Some(pe_file_path) => {
+                // Values read from a different build of the PE would be wrong
+                if !self.verify_pe(pe_file_path)?.is_match() {
+                    return Err(anyhow::anyhow!("the PE file doesn't match the PDB"));
+                }
+                Some(fs::read(pe_file_path)?)
+            }
+            None => None,
+        };
+
+        Ok(self
+            .global_variables(&PrimitiveReconstructionFlavor::Portable)?
+            .into_iter()
+            .filter(|(_, global)| global.type_name.rsplit(' ').next() == Some("_GUID"))
+            .map(|(name, global)| GuidConstant {
+                guid: GuidConstant::guid_from_name(&name)
+                    .or_else(|| read_guid_at_rva(pe_data.as_deref()?, global.rva?).ok()),
+                name,
+                rva: global.rva,
+            })
+            .collect())
+    }
+
+    /// Return the COM interfaces of the PDB, i.e. the classes shaped like
+    /// interfaces that derive from `IUnknown`. Interfaces are sorted by
+    /// name, but base interfaces always come first.
+    pub fn com_interfaces(
+        &self,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<Vec<ComInterface>> {
+        let type_finder = self.populated_type_finder()?;
+
+        let mut candidate_interfaces = BTreeMap::new();
+        for (type_name, type_index) in &self.complete_type_list {
+            // Interfaces have a virtual function table
+            match type_finder.find(*type_index)?.parse() {
+                Ok(pdb::TypeData::Class(data)) if data.vtable_shape.is_some() => {}
+                _ => continue,
+            }
+            let mut type_data = pdb_types::Data::new();
+            if let Err(err) = type_data.add(
+                &type_finder,
+                &self.type_forwarder,
+                *type_index,
+                primitives_flavor,
+                &mut pdb_types::TypeSet::new(),
+            ) {
+                log::error!("Failed to reconstruct type {}: {}", type_index, err);
+                continue;
+            }
+            if let Some(interface) = type_data.com_interface() {
+                candidate_interfaces.insert(type_name.clone(), interface);
+            }
+        }
+
+        let mut interfaces = vec![];
+        let mut listed_interfaces = BTreeSet::new();
+        for interface_name in candidate_interfaces.keys() {
+            let mut interface_chain = vec![];
+            let mut current_name = Some(interface_name);
+            while let Some(interface) = current_name.and_then(|name| candidate_interfaces.get(name))
+            {
+                interface_chain.push(interface);
+                current_name = interface.base_interface_name.as_ref();
+            }
+            let derives_from_iunknown = matches!(
+                interface_chain.last(),
+                Some(root) if root.name == pdb_types::IUNKNOWN_INTERFACE_NAME
+                    && root.base_interface_name.is_none()
+            );
+            if !derives_from_iunknown {
+                continue;
+            }
+            for interface in interface_chain.into_iter().rev() {
+                if listed_interfaces.insert(interface.name.clone()) {
+                    interfaces.push(interface.clone());
+                }
+            }
+        }
+
+        Ok(interfaces)
+    }
+
+    /// Return the names of the types closest to `type_name` (by edit
+    /// distance, ignoring case), from the closest to the farthest one.
+    pub fn suggest_type_names(&self, type_name: &str) -> Vec<String> {
+        let type_name = type_name.to_lowercase();
+        let type_name_length = type_name.chars().count();
+        let mut suggestions = self
+            .complete_type_list
+            .par_iter()
+            .filter_map(|(candidate_name, _)| {
+                // Names whose lengths differ too much cannot be close enough
+                let candidate_length = candidate_name.chars().count();
+                if candidate_length.abs_diff(type_name_length) > MAX_TYPE_NAME_SUGGESTION_DISTANCE {
+                    return None;
+                }
+                let distance = levenshtein_distance(&type_name, &candidate_name.to_lowercase());
+                (distance <= MAX_TYPE_NAME_SUGGESTION_DISTANCE)
+                    .then(|| (distance, candidate_name.clone()))
+            })
+            .collect::<Vec<_>>();
+        suggestions.sort_unstable();
+        suggestions.dedup();
+
+        suggestions
+            .into_iter()
+            .take(MAX_TYPE_NAME_SUGGESTIONS)
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    /// Return a `TypeFinder` that knows about every type of the PDB.
+    fn populated_type_finder(&self) -> Result<pdb::TypeFinder<'_>> {
         let mut type_finder = self.type_information.finder();
         {
             let mut type_iter = self.type_information.iter();
@@ -244,33 +1667,161 @@ impl<'p> PdbFile<'p> {
             }
         }
 
-        self.reconstruct_type_by_type_index_internal(
-            &type_finder,
+        Ok(type_finder)
+    }
+
+    fn reconstruct_type_by_type_index_internal(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_index: pdb::TypeIndex,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+        output_format: OutputFormat,
+        reconstruct_dependencies: bool,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let reconstruction_start = std::time::Instant::now();
+        // ImHex patterns, binary templates and Kaitai specifications must
+        // define the types they embed to be usable
+        let reconstruct_dependencies = reconstruct_dependencies
+            || matches!(
+                output_format,
+                OutputFormat::ImHex | OutputFormat::BinaryTemplate | OutputFormat::Kaitai
+            );
+        let type_data = self.collect_type_data(
+            type_finder,
+            &self
+                .type_forwarder
+                .resolving_type_aliases(fmt_configuration.resolve_typedefs),
             type_index,
             primitives_flavor,
             reconstruct_dependencies,
-            print_access_specifiers,
-        )
+        )?;
+
+        let mut reconstruction_output = String::new();
+        match output_format {
+            OutputFormat::Cpp => {
+                type_data.reconstruct(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::C => {
+                type_data.reconstruct_c(
+                    primitives_flavor,
+                    fmt_configuration,
+                    &mut reconstruction_output,
+                )?;
+            }
+            OutputFormat::CSharp => {
+                type_data.reconstruct_csharp(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::Rust => {
+                type_data.reconstruct_rust(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::WinDbg => {
+                type_data.reconstruct_windbg(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::ImHex => {
+                type_data.reconstruct_imhex(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::BinaryTemplate => {
+                type_data
+                    .reconstruct_binary_template(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::Kaitai => {
+                type_data.reconstruct_kaitai(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::Go => {
+                type_data.reconstruct_go(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::Ctypes => {
+                type_data.reconstruct_ctypes(fmt_configuration, &mut reconstruction_output)?;
+            }
+            OutputFormat::Json => {
+                reconstruction_output = serde_json::to_string_pretty(&type_data.to_model())?;
+            }
+        }
+        log::debug!(
+            "Reconstruction of type {} took {} ms",
+            type_index,
+            reconstruction_start.elapsed().as_millis()
+        );
+
+        Ok(reconstruction_output)
     }
 
-    fn reconstruct_type_by_type_index_internal(
+    fn reconstruct_type_model_internal(
         &self,
         type_finder: &pdb::TypeFinder,
         type_index: pdb::TypeIndex,
         primitives_flavor: &PrimitiveReconstructionFlavor,
         reconstruct_dependencies: bool,
-        print_access_specifiers: bool,
-    ) -> Result<String> {
-        let fmt_configuration = DataFormatConfiguration {
-            print_access_specifiers,
-        };
+    ) -> Result<Vec<ReconstructedType>> {
+        let type_data = self.collect_type_data(
+            type_finder,
+            &self.type_forwarder,
+            type_index,
+            primitives_flavor,
+            reconstruct_dependencies,
+        )?;
+
+        Ok(type_data.to_model())
+    }
+
+    /// Return the `Data` of the requested type, as well as the (complete)
+    /// types it depends on (empty if `collect_dependencies` is false).
+    /// Errors are logged and result in an empty `Data`, so that a single
+    /// invalid type doesn't prevent other types from being reconstructed.
+    fn collect_single_type_data<'t>(
+        &self,
+        type_finder: &pdb::TypeFinder<'t>,
+        type_forwarder: &TypeForwarder,
+        type_index: pdb::TypeIndex,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+        collect_dependencies: bool,
+    ) -> (pdb_types::Data<'t>, Vec<pdb::TypeIndex>) {
+        let mut type_data = pdb_types::Data::new();
+        let mut needed_types = pdb_types::TypeSet::new();
+        if let Err(err) = type_data.add(
+            type_finder,
+            type_forwarder,
+            type_index,
+            primitives_flavor,
+            &mut needed_types,
+        ) {
+            log::error!("Failed to reconstruct type {}: {}", type_index, err);
+            return (pdb_types::Data::new(), vec![]);
+        }
+
+        if !collect_dependencies {
+            return (type_data, vec![]);
+        }
+        // Note: Dependencies are reversed so that they are processed in
+        // ascending type index order
+        let dependencies = needed_types
+            .into_iter()
+            .map(|needed_type_index| resolve_complete_type_index(type_forwarder, needed_type_index))
+            .filter(|needed_type_index| *needed_type_index != type_index)
+            .rev()
+            .collect();
+
+        (type_data, dependencies)
+    }
+
+    /// Return the `Data` of the requested type, including the types it
+    /// depends on if `reconstruct_dependencies` is true.
+    fn collect_type_data<'t>(
+        &self,
+        type_finder: &pdb::TypeFinder<'t>,
+        type_forwarder: &TypeForwarder,
+        type_index: pdb::TypeIndex,
+        primitives_flavor: &PrimitiveReconstructionFlavor,
+        reconstruct_dependencies: bool,
+    ) -> Result<pdb_types::Data<'t>> {
         let mut type_data = pdb_types::Data::new();
         let mut needed_types = pdb_types::TypeSet::new();
 
         // Add the requested type first
         type_data.add(
             type_finder,
-            &self.forwarder_to_complete_type,
+            type_forwarder,
             type_index,
             primitives_flavor,
             &mut needed_types,
@@ -278,13 +1829,15 @@ impl<'p> PdbFile<'p> {
 
         // If dependencies aren't needed, we're done
         if !reconstruct_dependencies {
-            let mut reconstruction_output = String::new();
-            type_data.reconstruct(&fmt_configuration, &mut reconstruction_output)?;
-            return Ok(reconstruction_output);
+            return Ok(type_data);
         }
 
-        // Add all the needed types iteratively until we're done
-        let mut dependencies_data = pdb_types::Data::new();
+        // Add all the needed types iteratively until we're done.
+        // Note: Dependencies are added to the same `Data` as the requested
+        // type, so that definitions can be sorted according to the
+        // dependency graph when reconstructed (the requested type may have to
+        // be defined before some of its dependencies, e.g. when they refer to
+        // it by value).
         let mut processed_types = BTreeSet::from([type_index]);
         let dep_start = std::time::Instant::now();
         loop {
@@ -293,16 +1846,21 @@ impl<'p> PdbFile<'p> {
             match first {
                 None => break,
                 Some(needed_type_index) => {
+                    processed_types.insert(needed_type_index);
+                    // Types must only be defined once, even if several of
+                    // their records are referenced
+                    if type_data.defines_type_named_like(type_finder, needed_type_index) {
+                        continue;
+                    }
+
                     // Add the type
-                    dependencies_data.add(
+                    type_data.add(
                         type_finder,
-                        &self.forwarder_to_complete_type,
+                        type_forwarder,
                         needed_type_index,
                         primitives_flavor,
                         &mut needed_types,
                     )?;
-
-                    processed_types.insert(needed_type_index);
                 }
             }
         }
@@ -311,9 +1869,196 @@ impl<'p> PdbFile<'p> {
             dep_start.elapsed().as_millis()
         );
 
-        let mut reconstruction_output = String::new();
-        dependencies_data.reconstruct(&fmt_configuration, &mut reconstruction_output)?;
-        type_data.reconstruct(&fmt_configuration, &mut reconstruction_output)?;
-        Ok(reconstruction_output)
+        Ok(type_data)
     }
 }
+
+/// Parse the `LF_ALIAS` records of the given type information stream.
+fn parse_type_aliases(tpi_stream: &[u8]) -> Result<HashMap<pdb::TypeIndex, TypeAlias>> {
+    let header_size = read_u32(tpi_stream, 4)? as usize;
+    let minimum_index = read_u32(tpi_stream, 8)?;
+    let records_size = read_u32(tpi_stream, 16)? as usize;
+    let records = tpi_stream
+        .get(header_size..header_size + records_size)
+        .ok_or_else(|| anyhow!("truncated type information stream"))?;
+
+    let mut type_aliases = HashMap::new();
+    let mut type_index = minimum_index;
+    let mut record_offset = 0;
+    while record_offset < records.len() {
+        // Note: Records are prefixed by their size, which doesn't include
+        // the size itself
+        let record_size = read_u16(records, record_offset)? as usize;
+        let record = records
+            .get(record_offset + 2..record_offset + 2 + record_size)
+            .ok_or_else(|| anyhow!("truncated type record"))?;
+        if read_u16(record, 0)? == LF_ALIAS {
+            let underlying_type = pdb::TypeIndex(read_u32(record, 2)?);
+            let name = record[6..].split(|&c| c == 0).next().unwrap_or_default();
+            type_aliases.insert(
+                pdb::TypeIndex(type_index),
+                TypeAlias {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    underlying_type,
+                },
+            );
+        }
+        type_index += 1;
+        record_offset += 2 + record_size;
+    }
+
+    Ok(type_aliases)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| anyhow!("truncated type record"))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| anyhow!("truncated type record"))
+}
+
+/// Map the file at `file_path` into memory.
+fn map_file(file_path: &Path) -> Result<memmap2::Mmap> {
+    let file = fs::File::open(file_path)?;
+    // Safety: the mapping is read-only. Modifying the file while it's mapped is
+    // undefined behavior (e.g., truncating it raises `SIGBUS` on Linux), which
+    // cannot be prevented. Callers of `load_from_file_with_progress` are
+    // responsible for it, files that linkers may rewrite (e.g., reloaded,
+    // watched or browsed ones) are read with `read_file` instead.
+    Ok(unsafe { memmap2::Mmap::map(&file)? })
+}
+
+/// Read the file at `file_path` entirely into memory.
+fn read_file(file_path: &Path) -> Result<PdbData> {
+    let data = fs::read(file_path).map_err(|err| {
+        if err.kind() == io::ErrorKind::NotFound {
+            anyhow::Error::from(ResymError::PdbNotFound(file_path.to_owned()))
+        } else {
+            err.into()
+        }
+    })?;
+
+    Ok(PdbData::Read(data))
+}
+
+/// Add a complete type to the type list, and index it by name (and unique
+/// name).
+fn add_complete_type(
+    complete_type_list: &mut Vec<(String, pdb::TypeIndex)>,
+    type_indices_by_name: &mut HashMap<String, Vec<pdb::TypeIndex>>,
+    type_name: String,
+    unique_name: Option<pdb::RawString>,
+    type_index: pdb::TypeIndex,
+) {
+    if let Some(unique_name) = unique_name {
+        type_indices_by_name
+            .entry(unique_name.to_string().into_owned())
+            .or_default()
+            .push(type_index);
+    }
+    type_indices_by_name
+        .entry(type_name.clone())
+        .or_default()
+        .push(type_index);
+    complete_type_list.push((type_name, type_index));
+}
+
+/// Return the records of the field list of the class or union `type_index`
+/// (empty for other types).
+fn udt_field_records<'t>(
+    type_finder: &pdb::TypeFinder<'t>,
+    type_index: pdb::TypeIndex,
+) -> Result<Vec<pdb::TypeData<'t>>> {
+    let mut field_list_index = match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Class(data) => data.fields,
+        pdb::TypeData::Union(data) => Some(data.fields),
+        _ => None,
+    };
+
+    let mut field_records = vec![];
+    while let Some(fields) = field_list_index {
+        match type_finder.find(fields)?.parse()? {
+            pdb::TypeData::FieldList(field_list) => {
+                field_records.extend(field_list.fields);
+                field_list_index = field_list.continuation;
+            }
+            _ => break,
+        }
+    }
+
+    Ok(field_records)
+}
+
+/// Return the name of the class, union or enum `type_index`, or `None` if it
+/// isn't one. Unnamed types are named after their type index, like in type
+/// lists.
+fn udt_name(type_finder: &pdb::TypeFinder<'_>, type_index: pdb::TypeIndex) -> Option<String> {
+    let name = match type_finder
+        .find(type_index)
+        .and_then(|type_item| type_item.parse())
+    {
+        Ok(pdb::TypeData::Class(data)) => data.name,
+        Ok(pdb::TypeData::Union(data)) => data.name,
+        Ok(pdb::TypeData::Enumeration(data)) => data.name,
+        _ => return None,
+    };
+    let name = name.to_string().into_owned();
+
+    Some(if is_unnamed_type(&name) {
+        format!("_unnamed_{}", type_index)
+    } else {
+        name
+    })
+}
+
+/// Return what determines the layout of the class, union or enum
+/// `type_index`: its size and the names and offsets of its members. Enums are
+/// identified by their field list instead, the linker merges identical field
+/// lists into a single record.
+fn type_layout(
+    type_finder: &pdb::TypeFinder<'_>,
+    type_index: pdb::TypeIndex,
+) -> Result<(u64, Vec<(String, u64)>)> {
+    let size = match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Class(data) => u64::from(data.size),
+        pdb::TypeData::Union(data) => u64::from(data.size),
+        pdb::TypeData::Enumeration(data) => return Ok((u64::from(data.fields.0), vec![])),
+        _ => 0,
+    };
+    let members = udt_field_records(type_finder, type_index)?
+        .into_iter()
+        .filter_map(|field| match field {
+            pdb::TypeData::Member(data) => {
+                Some((data.name.to_string().into_owned(), u64::from(data.offset)))
+            }
+            pdb::TypeData::BaseClass(data) => Some((String::new(), u64::from(data.offset))),
+            _ => None,
+        })
+        .collect();
+
+    Ok((size, members))
+}
+
+/// Compute the Levenshtein distance between two strings.
+fn levenshtein_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs = rhs.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=rhs.len()).collect::<Vec<_>>();
+    let mut current_row = vec![0; rhs.len() + 1];
+    for (i, lhs_char) in lhs.chars().enumerate() {
+        current_row[0] = i + 1;
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution_cost = usize::from(lhs_char != *rhs_char);
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[rhs.len()]
+}
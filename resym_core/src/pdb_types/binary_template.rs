@@ -0,0 +1,459 @@
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, members_layout_extent, struct_members_layout,
+    union::Union,
+    union_members_layout, DataFormatConfiguration, Field, MemberLayout, TypeDescription, TypeSizes,
+};
+
+/// Binary template keywords (and built-in types) that must be escaped when
+/// used as identifiers.
+const BINARY_TEMPLATE_KEYWORDS: &[&str] = &[
+    "break",
+    "byte",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "exists",
+    "false",
+    "float",
+    "for",
+    "function_exists",
+    "if",
+    "int",
+    "local",
+    "parentof",
+    "return",
+    "signed",
+    "sizeof",
+    "startof",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "true",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "while",
+    "wstring",
+];
+
+/// Kind of template struct.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    Struct,
+    Union,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Struct => "struct".fmt(f),
+            ItemKind::Union => "union".fmt(f),
+        }
+    }
+}
+
+/// Member of a template struct, declared on one or several lines.
+struct Member {
+    offset: u64,
+    size: u64,
+    lines: Vec<String>,
+}
+
+/// State shared by the members of a single type (i.e., the type itself and
+/// the unnamed unions and structs it contains).
+struct ItemContext<'a> {
+    type_sizes: &'a TypeSizes<'a>,
+    union_count: usize,
+    struct_count: usize,
+    padding_count: usize,
+}
+
+pub fn fmt_class(
+    class: &Class,
+    type_sizes: &TypeSizes,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let mut context = ItemContext::new(type_sizes);
+    let own_fields_offset = class
+        .fields
+        .iter()
+        .map(|field| field.offset as u64)
+        .min()
+        .unwrap_or(class.size as u64);
+    let mut members = vec![];
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        let base_size = type_sizes
+            .get(base.type_name.as_str())
+            .copied()
+            .unwrap_or(0);
+        members.push(Member {
+            offset: base.offset as u64,
+            size: base_size,
+            lines: vec![format!(
+                "{} __base_{};",
+                flattened_type_name(&base.type_name),
+                i
+            )],
+        });
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        let pointer_size = class
+            .vtable_pointer_size
+            .unwrap_or_else(|| std::cmp::min(own_fields_offset, 8));
+        members.push(Member {
+            offset: 0,
+            size: pointer_size,
+            lines: vec![format!("{} __vftable;", unsigned_type(pointer_size))],
+        });
+    }
+    // Note: Virtual base classes are placed at the end of the object, they're
+    // covered by trailing padding
+
+    let lines = item_lines(
+        &mut context,
+        ItemKind::Struct,
+        members,
+        &struct_members_layout(&class.fields),
+        (0, class.size as u64),
+    );
+    fmt_typedef(ItemKind::Struct, &class.name, &lines, f)
+}
+
+pub fn fmt_union(u: &Union, type_sizes: &TypeSizes, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let mut context = ItemContext::new(type_sizes);
+    let lines = item_lines(
+        &mut context,
+        ItemKind::Union,
+        vec![],
+        &union_members_layout(&u.fields),
+        (0, u.size as u64),
+    );
+    fmt_typedef(ItemKind::Union, &u.name, &lines, f)
+}
+
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "typedef enum <{}> {{",
+        binary_template_type(&e.underlying_type).unwrap_or_else(|| "int32".to_string())
+    )?;
+    for (i, value) in e.values.iter().enumerate() {
+        writeln!(
+            f,
+            "    {} = {}{}",
+            escape_identifier(&value.name.to_string()),
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex),
+            if i + 1 < e.values.len() { "," } else { "" }
+        )?;
+    }
+    writeln!(f, "}} {};", flattened_type_name(&e.name))
+}
+
+impl<'a> ItemContext<'a> {
+    fn new(type_sizes: &'a TypeSizes<'a>) -> Self {
+        Self {
+            type_sizes,
+            union_count: 0,
+            struct_count: 0,
+            padding_count: 0,
+        }
+    }
+
+    fn padding_declaration(&mut self, size: u64) -> String {
+        let declaration = format!("ubyte __padding_{}[{:#x}];", self.padding_count, size);
+        self.padding_count += 1;
+        declaration
+    }
+}
+
+fn fmt_typedef(
+    kind: ItemKind,
+    type_name: &str,
+    lines: &[String],
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "typedef {} {{", kind)?;
+    for line in lines {
+        writeln!(f, "    {}", line)?;
+    }
+    writeln!(f, "}} {};", flattened_type_name(type_name))
+}
+
+/// Return the lines declaring the given members, located at the given offset
+/// (relative to the type being reconstructed) and of the given size.
+/// Templates are read sequentially, so members are laid out one after the
+/// other and explicit padding is inserted to reproduce their offsets.
+fn item_lines(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    mut members: Vec<Member>,
+    layout: &[MemberLayout],
+    (item_offset, item_size): (u64, u64),
+) -> Vec<String> {
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) if field.bitfield.is_some() => {
+                members.push(bitfield_member(context, &[field]));
+            }
+            MemberLayout::Field(field) => members.push(field_member(context, field)),
+            MemberLayout::Bitfields(bitfields) => {
+                members.push(bitfield_member(context, bitfields));
+            }
+            MemberLayout::Union(union_layout) => {
+                let member_name = format!("__union_{}", context.union_count);
+                context.union_count += 1;
+                members.push(nested_item_member(
+                    context,
+                    ItemKind::Union,
+                    &member_name,
+                    union_layout,
+                ));
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let member_name = format!("__struct_{}", context.struct_count);
+                context.struct_count += 1;
+                members.push(nested_item_member(
+                    context,
+                    ItemKind::Struct,
+                    &member_name,
+                    struct_layout,
+                ));
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let mut end_offset = item_offset;
+    for member in members {
+        if kind == ItemKind::Struct && member.offset > end_offset {
+            lines.push(context.padding_declaration(member.offset - end_offset));
+        }
+        lines.extend(member.lines);
+        end_offset = end_offset.max(member.offset + member.size);
+    }
+    // Trailing padding
+    match kind {
+        ItemKind::Struct if end_offset < item_offset + item_size => {
+            lines.push(context.padding_declaration(item_offset + item_size - end_offset));
+        }
+        ItemKind::Union if end_offset < item_offset + item_size => {
+            lines.push(context.padding_declaration(item_size));
+        }
+        _ => {}
+    }
+
+    lines
+}
+
+/// Return the member declaring an unnamed union or struct inline.
+fn nested_item_member(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    member_name: &str,
+    layout: &[MemberLayout],
+) -> Member {
+    let (offset, size) = members_layout_extent(layout);
+    let mut lines = vec![format!("{} {{", kind)];
+    lines.extend(
+        item_lines(context, kind, vec![], layout, (offset, size))
+            .into_iter()
+            .map(|line| format!("    {}", line)),
+    );
+    lines.push(format!("}} {};", member_name));
+
+    Member {
+        offset,
+        size,
+        lines,
+    }
+}
+
+/// Return the member declaring the given bitfields sharing a storage unit.
+/// Bitfields of the same type are packed together, unused bits are declared
+/// explicitly so that following storage units start where they should.
+fn bitfield_member(context: &mut ItemContext, bitfields: &[&Field]) -> Member {
+    let storage_offset = bitfields[0].offset as u64;
+    let storage_size = bitfields.iter().map(|field| field.size).max().unwrap_or(0) as u64;
+    let storage_type = unsigned_type(storage_size);
+
+    // Bits are listed from the least significant one (which is the default
+    // order for little-endian files)
+    let mut lines = vec![];
+    let mut bit_position = 0;
+    for field in bitfields {
+        if let Some(bitfield) = field.bitfield {
+            let position = bitfield.position as u64;
+            if position > bit_position {
+                lines.push(padding_bits_declaration(
+                    context,
+                    storage_type,
+                    position - bit_position,
+                ));
+            }
+            lines.push(format!(
+                "{} {} : {};",
+                storage_type,
+                escape_identifier(&field.name.to_string()),
+                bitfield.length
+            ));
+            bit_position = bit_position.max(position + bitfield.length as u64);
+        }
+    }
+    if bit_position < storage_size * 8 {
+        lines.push(padding_bits_declaration(
+            context,
+            storage_type,
+            storage_size * 8 - bit_position,
+        ));
+    }
+
+    Member {
+        offset: storage_offset,
+        size: storage_size,
+        lines,
+    }
+}
+
+fn padding_bits_declaration(context: &mut ItemContext, storage_type: &str, bits: u64) -> String {
+    let declaration = format!(
+        "{} __padding_{} : {};",
+        storage_type, context.padding_count, bits
+    );
+    context.padding_count += 1;
+    declaration
+}
+
+fn field_member(context: &ItemContext, field: &Field) -> Member {
+    let field_name = escape_identifier(&field.name.to_string());
+    let declaration = match &field.type_description {
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => match binary_template_type(element_type) {
+            // Multi-dimensional arrays aren't supported, flatten them
+            Some(element_type) => format!(
+                "{} {}[{}];",
+                element_type,
+                field_name,
+                dimensions.iter().product::<usize>()
+            ),
+            None => opaque_field_declaration(&field_name, field),
+        },
+        type_description => match binary_template_type(type_description) {
+            Some(type_name) => format!("{} {};", type_name, field_name),
+            None => opaque_field_declaration(&field_name, field),
+        },
+    };
+    // Embedded types' size is known from their definition
+    let size = match field.type_description.strip_modifiers() {
+        TypeDescription::Class(name) | TypeDescription::Union(name) => context
+            .type_sizes
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(field.size as u64),
+        _ => field.size as u64,
+    };
+
+    Member {
+        offset: field.offset as u64,
+        size,
+        lines: vec![declaration],
+    }
+}
+
+/// Types we cannot represent are replaced with raw bytes, keep track of the
+/// original type
+fn opaque_field_declaration(field_name: &str, field: &Field) -> String {
+    format!(
+        "ubyte {}[{}]; // {}{}",
+        field_name, field.size, field.type_left, field.type_right
+    )
+}
+
+/// Return the unsigned integer type of the given size (in bytes).
+fn unsigned_type(size: u64) -> &'static str {
+    match size {
+        1 => "ubyte",
+        2 => "uint16",
+        4 => "uint32",
+        _ => "uint64",
+    }
+}
+
+fn binary_template_type(type_description: &TypeDescription) -> Option<String> {
+    match type_description {
+        TypeDescription::Primitive(kind) => {
+            binary_template_primitive_type(*kind).map(str::to_string)
+        }
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            Some(flattened_type_name(name))
+        }
+        TypeDescription::Enum { name, .. } => Some(flattened_type_name(name)),
+        // Pointers are read as addresses, of the size of the target's
+        // pointers
+        TypeDescription::Pointer { size, .. } => Some(unsigned_type(*size as u64).to_string()),
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => binary_template_type(underlying_type),
+        TypeDescription::Array { .. }
+        | TypeDescription::Function { .. }
+        | TypeDescription::Unknown => None,
+    }
+}
+
+fn binary_template_primitive_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar => "char",
+        pdb::PrimitiveKind::I8 => "byte",
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 | pdb::PrimitiveKind::Bool8 => "ubyte",
+        pdb::PrimitiveKind::WChar | pdb::PrimitiveKind::RChar16 => "wchar_t",
+        pdb::PrimitiveKind::RChar32 => "uint32",
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "int16",
+        pdb::PrimitiveKind::UShort | pdb::PrimitiveKind::U16 | pdb::PrimitiveKind::Bool16 => {
+            "uint16"
+        }
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => "int32",
+        pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 | pdb::PrimitiveKind::Bool32 => {
+            "uint32"
+        }
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => "int64",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 | pdb::PrimitiveKind::Bool64 => {
+            "uint64"
+        }
+        pdb::PrimitiveKind::F32 => "float",
+        pdb::PrimitiveKind::F64 => "double",
+        _ => return None,
+    };
+
+    Some(type_name)
+}
+
+fn escape_identifier(identifier: &str) -> String {
+    if BINARY_TEMPLATE_KEYWORDS.contains(&identifier) {
+        format!("{}_", identifier)
+    } else {
+        identifier.to_string()
+    }
+}
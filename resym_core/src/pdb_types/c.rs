@@ -0,0 +1,357 @@
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, fmt_offset_comment,
+    primitive_types::primitive_kind_as_str,
+    struct_members_layout,
+    union::Union,
+    union_members_layout, unnamed_bitfield_width, DataFormatConfiguration, Field, MemberLayout,
+    PrimitiveReconstructionFlavor, TypeDescription,
+};
+
+/// State shared by the members written for a single type.
+struct TypeContext<'a> {
+    primitive_flavor: &'a PrimitiveReconstructionFlavor,
+    fmt_configuration: &'a DataFormatConfiguration,
+    union_count: usize,
+    struct_count: usize,
+}
+
+pub fn fmt_class(
+    class: &Class,
+    primitive_flavor: &PrimitiveReconstructionFlavor,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "struct {} {{ /* Size={:#x} */",
+        flattened_type_name(&class.name),
+        class.size
+    )?;
+
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        writeln!(
+            f,
+            "  /* {:#06x} */ struct {} __base_{};",
+            base.offset,
+            flattened_type_name(&base.type_name),
+            i
+        )?;
+    }
+    // Virtual base classes are placed at the end of the object, and located
+    // through a virtual base table
+    if let Some(base) = class.base_classes.iter().find(|base| base.is_virtual) {
+        writeln!(f, "  /* {:#06x} */ void* __vbtable;", base.offset)?;
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        writeln!(f, "  /* 0x0000 */ void* __vftable;")?;
+    }
+
+    let mut context = TypeContext::new(primitive_flavor, fmt_configuration);
+    fmt_members(&mut context, &struct_members_layout(&class.fields), 1, f)?;
+
+    writeln!(f, "}};")
+}
+
+pub fn fmt_union(
+    u: &Union,
+    primitive_flavor: &PrimitiveReconstructionFlavor,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "union {} {{ /* Size={:#x} */",
+        flattened_type_name(&u.name),
+        u.size
+    )?;
+
+    let mut context = TypeContext::new(primitive_flavor, fmt_configuration);
+    fmt_members(&mut context, &union_members_layout(&u.fields), 1, f)?;
+
+    writeln!(f, "}};")
+}
+
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "enum {} {{", flattened_type_name(&e.name))?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "  {} = {},",
+            value.name,
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
+        )?;
+    }
+    writeln!(f, "}};")
+}
+
+pub fn fmt_forward_reference(
+    type_keyword: &str,
+    type_name: &str,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "{} {};", type_keyword, flattened_type_name(type_name))
+}
+
+/// C89 has no `static_assert`, declare an array whose size is invalid when the
+/// assertion doesn't hold instead.
+pub fn fmt_size_assert(
+    type_keyword: &str,
+    type_name: &str,
+    type_size: u64,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let type_name = flattened_type_name(type_name);
+    writeln!(
+        f,
+        "typedef char {}_size_check[(sizeof({} {}) == {:#x}) ? 1 : -1];",
+        type_name, type_keyword, type_name, type_size
+    )
+}
+
+impl<'a> TypeContext<'a> {
+    fn new(
+        primitive_flavor: &'a PrimitiveReconstructionFlavor,
+        fmt_configuration: &'a DataFormatConfiguration,
+    ) -> Self {
+        Self {
+            primitive_flavor,
+            fmt_configuration,
+            union_count: 0,
+            struct_count: 0,
+        }
+    }
+}
+
+/// Write the given members. C89 doesn't support unnamed unions and structs, so
+/// they're written as named members.
+fn fmt_members(
+    context: &mut TypeContext,
+    layout: &[MemberLayout],
+    depth: usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let indentation = "  ".repeat(depth);
+    let mut previous_field = None;
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) => fmt_field(context, &indentation, field, f)?,
+            MemberLayout::Bitfields(bitfields) => {
+                for field in bitfields {
+                    if let Some(width) = unnamed_bitfield_width(previous_field, field) {
+                        writeln!(
+                            f,
+                            "{}/* {:#06x} */ {} : {};",
+                            indentation,
+                            field.offset,
+                            c_declaration(context.primitive_flavor, &field.type_description, ""),
+                            width
+                        )?;
+                    }
+                    fmt_field(context, &indentation, field, f)?;
+                    previous_field = Some(*field);
+                }
+                continue;
+            }
+            MemberLayout::Union(union_layout) => {
+                let member_name = format!("__union_{}", context.union_count);
+                context.union_count += 1;
+                writeln!(f, "{}union {{", indentation)?;
+                fmt_members(context, union_layout, depth + 1, f)?;
+                writeln!(f, "{}}} {};", indentation, member_name)?;
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let member_name = format!("__struct_{}", context.struct_count);
+                context.struct_count += 1;
+                writeln!(f, "{}struct {{", indentation)?;
+                fmt_members(context, struct_layout, depth + 1, f)?;
+                writeln!(f, "{}}} {};", indentation, member_name)?;
+            }
+        }
+        previous_field = None;
+    }
+
+    Ok(())
+}
+
+fn fmt_field(
+    context: &TypeContext,
+    indentation: &str,
+    field: &Field,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let field_name = field.name.to_string();
+    let declaration = match field.type_description {
+        // Types we cannot represent are replaced with raw bytes, keep track
+        // of the original type
+        TypeDescription::Unknown => format!(
+            "unsigned char {}[{}]; /* {}{} */",
+            field_name, field.size, field.type_left, field.type_right
+        ),
+        _ => match field.bitfield {
+            Some(bitfield) => format!(
+                "{} : {};",
+                c_declaration(
+                    context.primitive_flavor,
+                    &field.type_description,
+                    &field_name
+                ),
+                bitfield.length
+            ),
+            None => format!(
+                "{};",
+                c_declaration(
+                    context.primitive_flavor,
+                    &field.type_description,
+                    &field_name
+                )
+            ),
+        },
+    };
+    write!(
+        f,
+        "{}/* {:#06x} */ {}",
+        indentation, field.offset, declaration
+    )?;
+    if let Some(offset_radix) = context.fmt_configuration.print_offset_comments {
+        fmt_offset_comment(offset_radix, field.offset as u64, field.bitfield, f)?;
+    }
+    writeln!(f)
+}
+
+/// Return the C declaration of `declarator` with the given type.
+fn c_declaration(
+    primitive_flavor: &PrimitiveReconstructionFlavor,
+    type_description: &TypeDescription,
+    declarator: &str,
+) -> String {
+    match type_description {
+        TypeDescription::Primitive(kind) => {
+            join_declaration(&c_primitive_type(primitive_flavor, *kind), declarator)
+        }
+        TypeDescription::Class(name) => {
+            join_declaration(&format!("struct {}", flattened_type_name(name)), declarator)
+        }
+        TypeDescription::Union(name) => {
+            join_declaration(&format!("union {}", flattened_type_name(name)), declarator)
+        }
+        // The size of C enums cannot be specified, use their underlying type
+        // instead to preserve the layout
+        TypeDescription::Enum {
+            underlying_type, ..
+        } => c_declaration(primitive_flavor, underlying_type, declarator),
+        // Note: References are represented as pointers
+        TypeDescription::Pointer { pointee, .. } => {
+            pointer_declaration(primitive_flavor, pointee, &format!("*{}", declarator))
+        }
+        TypeDescription::Modifier {
+            underlying_type,
+            constant,
+            volatile,
+        } => {
+            let qualifiers = format!(
+                "{}{}",
+                if *constant { "const " } else { "" },
+                if *volatile { "volatile " } else { "" }
+            );
+            match underlying_type.as_ref() {
+                // Qualified pointers
+                TypeDescription::Pointer { pointee, .. } => pointer_declaration(
+                    primitive_flavor,
+                    pointee,
+                    &format!("* {}{}", qualifiers, declarator),
+                ),
+                other => format!(
+                    "{}{}",
+                    qualifiers,
+                    c_declaration(primitive_flavor, other, declarator)
+                ),
+            }
+        }
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => {
+            let dimensions = dimensions
+                .iter()
+                .map(|dim| format!("[{}]", dim))
+                .collect::<String>();
+            c_declaration(
+                primitive_flavor,
+                element_type,
+                &format!("{}{}", declarator, dimensions),
+            )
+        }
+        TypeDescription::Function {
+            return_type,
+            arguments,
+        } => {
+            let arguments = if arguments.is_empty() {
+                "void".to_string()
+            } else {
+                arguments
+                    .iter()
+                    .map(|argument| c_declaration(primitive_flavor, argument, ""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            c_declaration(
+                primitive_flavor,
+                return_type,
+                &format!("{}({})", declarator, arguments),
+            )
+        }
+        TypeDescription::Unknown => join_declaration("void", declarator),
+    }
+}
+
+fn c_primitive_type(
+    primitive_flavor: &PrimitiveReconstructionFlavor,
+    primitive_kind: pdb::PrimitiveKind,
+) -> String {
+    // `char16_t` and `char32_t` are C++11 (and C11) types, use integers of the
+    // same size instead
+    let primitive_kind = match primitive_kind {
+        pdb::PrimitiveKind::RChar16 => pdb::PrimitiveKind::U16,
+        pdb::PrimitiveKind::RChar32 => pdb::PrimitiveKind::U32,
+        other => other,
+    };
+
+    primitive_kind_as_str(primitive_flavor, primitive_kind, false)
+        .unwrap_or_else(|err| err.to_string())
+}
+
+fn pointer_declaration(
+    primitive_flavor: &PrimitiveReconstructionFlavor,
+    pointee: &TypeDescription,
+    declarator: &str,
+) -> String {
+    match pointee.strip_modifiers() {
+        // Pointers to arrays and functions must be parenthesized
+        TypeDescription::Array { .. } | TypeDescription::Function { .. } => {
+            c_declaration(primitive_flavor, pointee, &format!("({})", declarator))
+        }
+        _ => c_declaration(primitive_flavor, pointee, declarator),
+    }
+}
+
+fn join_declaration(type_name: &str, declarator: &str) -> String {
+    if declarator.is_empty() {
+        type_name.to_string()
+    } else {
+        format!("{} {}", type_name, declarator)
+    }
+}
@@ -3,11 +3,13 @@ use std::fmt;
 use anyhow::{anyhow, Result};
 
 use super::{
+    bitfield_info,
     enumeration::Enum,
     field::{FieldAccess, StaticField},
-    fmt_struct_fields_recursive, is_unnamed_type,
+    fields_end_offset, fmt_padding_field, fmt_struct_fields_recursive, is_unnamed_type,
+    packing::Packing,
     primitive_types::PrimitiveReconstructionFlavor,
-    resolve_complete_type_index, type_name, type_size,
+    resolve_complete_type_index, type_description, type_name, type_size, udt_packing,
     union::Union,
     DataFormatConfiguration, Field, Method, TypeForwarder, TypeSet,
 };
@@ -47,9 +49,12 @@ impl fmt::Display for ClassAccess {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BaseClass {
-    type_name: String,
-    offset: u32,
-    access: ClassAccess,
+    pub type_name: String,
+    /// Offset of the base class, or of the virtual base table pointer for
+    /// virtual base classes
+    pub offset: u32,
+    pub access: ClassAccess,
+    pub is_virtual: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,6 +63,8 @@ pub struct Class<'p> {
     pub name: String,
     pub size: u16,
     pub base_classes: Vec<BaseClass>,
+    /// Data members, in declaration order (i.e., as listed in the type's
+    /// field list, which differs from offset order for unnamed unions)
     pub fields: Vec<Field<'p>>,
     pub static_fields: Vec<StaticField<'p>>,
     pub instance_methods: Vec<Method<'p>>,
@@ -65,6 +72,11 @@ pub struct Class<'p> {
     pub nested_classes: Vec<Class<'p>>,
     pub nested_unions: Vec<Union<'p>>,
     pub nested_enums: Vec<Enum<'p>>,
+    /// Size of the pointer to the virtual function table, if the class
+    /// introduces one (i.e., doesn't inherit it from a base class)
+    pub vtable_pointer_size: Option<u64>,
+    /// Packing inferred from the offsets of the members
+    pub packing: Packing,
 }
 
 impl<'p> Class<'p> {
@@ -135,6 +147,8 @@ impl<'p> Class<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    vtable_pointer_size: None,
+                    packing: udt_packing(type_finder, type_forwarder, type_index),
                 };
 
                 if let Some(derived_from) = data.derived_from {
@@ -173,6 +187,7 @@ impl<'p> Class<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    packing: udt_packing(type_finder, type_forwarder, type_index),
                 };
 
                 u.add_fields(
@@ -197,6 +212,8 @@ impl<'p> Class<'p> {
 
                 let mut e = Enum {
                     name,
+                    scoped: data.properties.scoped_definition(),
+                    size: type_size(type_finder, type_forwarder, data.underlying_type)?,
                     underlying_type_name: type_name(
                         type_finder,
                         type_forwarder,
@@ -205,6 +222,11 @@ impl<'p> Class<'p> {
                         needed_types,
                     )?
                     .0,
+                    underlying_type: type_description(
+                        type_finder,
+                        type_forwarder,
+                        data.underlying_type,
+                    )?,
                     values: Vec::new(),
                 };
 
@@ -249,31 +271,33 @@ impl<'p> Class<'p> {
                 let (type_left, type_right) = type_name(
                     type_finder,
                     type_forwarder,
-                    complete_type_index,
+                    data.field_type,
                     primitive_flavor,
                     needed_types,
                 )?;
-                let type_size = type_size(type_finder, complete_type_index)?;
+                let type_size = type_size(type_finder, type_forwarder, complete_type_index)?;
+                let bitfield = bitfield_info(type_finder, complete_type_index)?;
+                let type_description =
+                    type_description(type_finder, type_forwarder, complete_type_index)?;
                 let access = FieldAccess::from_field_attribute(data.attributes.access());
 
                 self.fields.push(Field {
                     type_left,
                     type_right,
+                    type_description,
                     name: data.name,
                     offset: data.offset,
                     size: type_size,
+                    bitfield,
                     access,
                 });
             }
 
             pdb::TypeData::StaticMember(ref data) => {
-                // Resolve the complete type's index, if present in the PDB
-                let complete_type_index =
-                    resolve_complete_type_index(type_forwarder, data.field_type);
                 let (type_left, type_right) = type_name(
                     type_finder,
                     type_forwarder,
-                    complete_type_index,
+                    data.field_type,
                     primitive_flavor,
                     needed_types,
                 )?;
@@ -288,7 +312,7 @@ impl<'p> Class<'p> {
             }
 
             pdb::TypeData::Method(ref data) => {
-                let method = Method::find(
+                let mut method = Method::find(
                     data.name,
                     data.attributes,
                     type_finder,
@@ -297,6 +321,7 @@ impl<'p> Class<'p> {
                     primitive_flavor,
                     needed_types,
                 )?;
+                method.vtable_offset = data.vtable_offset;
                 if data.attributes.is_static() {
                     self.static_methods.push(method);
                 } else {
@@ -312,11 +337,11 @@ impl<'p> Class<'p> {
                         for pdb::MethodListEntry {
                             attributes,
                             method_type,
-                            ..
+                            vtable_offset,
                         } in method_list.methods
                         {
                             // hooray
-                            let method = Method::find(
+                            let mut method = Method::find(
                                 data.name,
                                 attributes,
                                 type_finder,
@@ -325,6 +350,7 @@ impl<'p> Class<'p> {
                                 primitive_flavor,
                                 needed_types,
                             )?;
+                            method.vtable_offset = vtable_offset;
 
                             if attributes.is_static() {
                                 self.static_methods.push(method);
@@ -359,9 +385,13 @@ impl<'p> Class<'p> {
                     .0,
                     offset: data.offset,
                     access: ClassAccess::from_field_attribute(data.attributes.access()),
+                    is_virtual: false,
                 })
             }
 
+            // Indirect virtual base classes are inherited from other base
+            // classes, they aren't part of the type's declaration
+            pdb::TypeData::VirtualBaseClass(ref data) if !data.direct => {}
             pdb::TypeData::VirtualBaseClass(ref data) => {
                 // Resolve the complete type's index, if present in the PDB
                 let complete_base_class_type_index =
@@ -377,12 +407,15 @@ impl<'p> Class<'p> {
                     .0,
                     offset: data.base_pointer_offset,
                     access: ClassAccess::from_field_attribute(data.attributes.access()),
+                    is_virtual: true,
                 })
             }
 
-            pdb::TypeData::VirtualFunctionTablePointer(ref _data) => {
-                // TODO: Display a comment at the beginning of the declaration
-                // to make it obvious a vtable is present?
+            pdb::TypeData::VirtualFunctionTablePointer(ref data) => {
+                // Note: The pointer's record tells its size, which depends on
+                // the target machine
+                self.vtable_pointer_size =
+                    Some(type_size(type_finder, type_forwarder, data.table)? as u64);
             }
 
             // Nested type declaration
@@ -430,18 +463,34 @@ impl<'p> Class<'p> {
                     0 => " :",
                     _ => ",",
                 };
-                write!(f, "{} {} {}", prefix, base.access, base.type_name)?;
+                write!(
+                    f,
+                    "{} {}{} {}",
+                    prefix,
+                    if base.is_virtual { "virtual " } else { "" },
+                    base.access,
+                    base.type_name
+                )?;
             }
         }
 
         writeln!(f, " {{ /* Size={:#x} */", self.size)?;
 
         for base in &self.base_classes {
-            writeln!(
-                f,
-                "  /* {:#06x}: fields for {} */",
-                base.offset, base.type_name
-            )?;
+            if base.is_virtual {
+                // Virtual base classes are placed at the end of the object
+                writeln!(
+                    f,
+                    "  /* {:#06x}: virtual base table pointer for {} */",
+                    base.offset, base.type_name
+                )?;
+            } else {
+                writeln!(
+                    f,
+                    "  /* {:#06x}: fields for {} */",
+                    base.offset, base.type_name
+                )?;
+            }
         }
 
         // Nested declarations
@@ -460,12 +509,40 @@ impl<'p> Class<'p> {
         if !self.nested_enums.is_empty() {
             writeln!(f, "  ")?;
             for e in &self.nested_enums {
-                e.reconstruct(f)?;
+                e.reconstruct(fmt_configuration, f)?;
             }
         }
 
         // Dump fields while detecting unnamed structs and unions
-        fmt_struct_fields_recursive(fmt_configuration, &self.fields, 1, f)?;
+        // Note: If the type has base classes or a virtual table pointer,
+        // we can't know where its own fields are supposed to start
+        let has_hidden_fields = !self.base_classes.is_empty()
+            || self.instance_methods.iter().any(|method| method.is_virtual);
+        let mut padding_index = 0;
+        fmt_struct_fields_recursive(
+            fmt_configuration,
+            &self.fields,
+            1,
+            if has_hidden_fields { None } else { Some(0) },
+            &mut padding_index,
+            f,
+        )?;
+        // Note: Virtual base classes are placed after the type's own fields
+        let has_virtual_bases = self.base_classes.iter().any(|base| base.is_virtual);
+        if fmt_configuration.print_padding_fields && !has_virtual_bases {
+            // Trailing padding
+            let fields_end_offset = fields_end_offset(&self.fields);
+            if !self.fields.is_empty() && fields_end_offset < self.size as u64 {
+                fmt_padding_field(
+                    fmt_configuration,
+                    "  ",
+                    fields_end_offset,
+                    self.size as u64 - fields_end_offset,
+                    &mut padding_index,
+                    f,
+                )?;
+            }
+        }
 
         // Static fields
         for field in &self.static_fields {
@@ -483,10 +560,23 @@ impl<'p> Class<'p> {
             )?;
         }
 
-        if !self.instance_methods.is_empty() {
+        if fmt_configuration.print_methods && !self.instance_methods.is_empty() {
             writeln!(f, "  ")?;
-            for method in &self.instance_methods {
-                writeln!(
+            // Virtual methods come first, in virtual function table order
+            let mut instance_methods = self.instance_methods.iter().collect::<Vec<_>>();
+            instance_methods.sort_by_key(|method| {
+                (!method.is_virtual, method.vtable_offset.unwrap_or(u32::MAX))
+            });
+            for method in instance_methods {
+                // Constructors and destructors must be named after the
+                // sanitized type name
+                let method_name = match method {
+                    _ if !fmt_configuration.sanitize_names => method.name.to_string().into_owned(),
+                    Method { is_ctor: true, .. } => self.name.clone(),
+                    Method { is_dtor: true, .. } => format!("~{}", self.name),
+                    _ => method.name.to_string().into_owned(),
+                };
+                write!(
                     f,
                     "  {}{}{}{}{}({}){}{}{}{};",
                     if fmt_configuration.print_access_specifiers {
@@ -505,20 +595,22 @@ impl<'p> Class<'p> {
                     } else {
                         ""
                     },
-                    &method.name,
+                    method_name,
                     method.arguments.join(", "),
                     method.return_type_name.1,
                     if method.is_const { " const" } else { "" },
                     if method.is_volatile { " volatile" } else { "" },
                     if method.is_pure_virtual { " = 0" } else { "" },
                 )?;
+                method.fmt_annotations(f)?;
+                writeln!(f)?;
             }
         }
 
-        if !self.static_methods.is_empty() {
+        if fmt_configuration.print_methods && !self.static_methods.is_empty() {
             writeln!(f, "  ")?;
             for method in &self.static_methods {
-                writeln!(
+                write!(
                     f,
                     "  {}static {}{}{}({}){}{}{};",
                     if fmt_configuration.print_access_specifiers {
@@ -538,6 +630,8 @@ impl<'p> Class<'p> {
                     if method.is_const { " const" } else { "" },
                     if method.is_volatile { " volatile" } else { "" },
                 )?;
+                method.fmt_annotations(f)?;
+                writeln!(f)?;
             }
         }
 
@@ -0,0 +1,185 @@
+use std::{collections::BTreeMap, fmt};
+
+use super::class::Class;
+use crate::pe_file::Guid;
+
+/// Name of the interface all COM interfaces derive from.
+pub const IUNKNOWN_INTERFACE_NAME: &str = "IUnknown";
+
+/// Entry of the virtual function table of a COM interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComMethod {
+    pub name: String,
+    pub return_type_name: String,
+    pub arguments: Vec<String>,
+    pub calling_convention: Option<&'static str>,
+    /// Offset of the method's entry in the virtual function table
+    pub vtable_offset: u32,
+}
+
+/// Class shaped like a COM interface, i.e. a class without data members,
+/// deriving from at most one (non-virtual) base class, and whose virtual
+/// methods all introduce new virtual function table entries (implementations
+/// override the methods of the interfaces they implement instead).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComInterface {
+    pub name: String,
+    pub base_interface_name: Option<String>,
+    /// Methods introduced by the interface, in virtual function table order
+    pub methods: Vec<ComMethod>,
+}
+
+impl ComInterface {
+    /// Return the interface described by `class`, or `None` if the class
+    /// isn't shaped like a COM interface.
+    pub fn from_class(class: &Class) -> Option<Self> {
+        if !class.fields.is_empty()
+            || class.base_classes.len() > 1
+            || class.base_classes.iter().any(|base| base.is_virtual)
+        {
+            return None;
+        }
+
+        let mut methods = vec![];
+        // Note: Non-virtual methods (e.g., `IUnknown`'s templated
+        // `QueryInterface` helper) aren't part of the interface
+        for method in class
+            .instance_methods
+            .iter()
+            .filter(|method| method.is_virtual)
+        {
+            methods.push(ComMethod {
+                name: method.name.to_string().into_owned(),
+                return_type_name: format!(
+                    "{}{}",
+                    method.return_type_name.0, method.return_type_name.1
+                ),
+                arguments: method.arguments.clone(),
+                calling_convention: method.calling_convention,
+                vtable_offset: method.vtable_offset?,
+            });
+        }
+        if methods.is_empty() {
+            return None;
+        }
+        methods.sort_by_key(|method| method.vtable_offset);
+
+        Some(Self {
+            name: class.name.clone(),
+            base_interface_name: class
+                .base_classes
+                .first()
+                .map(|base| base.type_name.clone()),
+            methods,
+        })
+    }
+}
+
+/// GUID constant (e.g., an IID or a CLSID), as found in the PDB's global
+/// symbol stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuidConstant {
+    pub name: String,
+    /// Relative virtual address of the constant, if it could be resolved
+    pub rva: Option<u32>,
+    /// Value of the constant, if it could be retrieved (PDBs don't contain
+    /// the values of variables)
+    pub guid: Option<Guid>,
+}
+
+impl GuidConstant {
+    /// Retrieve the value of constants generated by the compiler for
+    /// `__uuidof`, which is part of their name (e.g.,
+    /// `_GUID_00000000_0000_0000_c000_000000000046`).
+    pub fn guid_from_name(name: &str) -> Option<Guid> {
+        name.strip_prefix("_GUID_")?.replace('_', "-").parse().ok()
+    }
+}
+
+/// Write the given GUID constants as `DEFINE_GUID` statements, followed by
+/// the virtual function tables of the given interfaces. Interfaces must be
+/// ordered so that base interfaces come first.
+pub fn fmt_com_definitions(
+    guid_constants: &[GuidConstant],
+    interfaces: &[ComInterface],
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    for constant in guid_constants {
+        match constant.guid {
+            Some(guid) => {
+                let d = &guid.data4;
+                writeln!(
+                    f,
+                    "DEFINE_GUID({}, {:#010x}, {:#06x}, {:#06x}, {:#04x}, {:#04x}, {:#04x}, {:#04x}, {:#04x}, {:#04x}, {:#04x}, {:#04x});",
+                    constant.name,
+                    guid.data1,
+                    guid.data2,
+                    guid.data3,
+                    d[0],
+                    d[1],
+                    d[2],
+                    d[3],
+                    d[4],
+                    d[5],
+                    d[6],
+                    d[7]
+                )?;
+            }
+            None => {
+                let rva = constant
+                    .rva
+                    .map_or_else(|| "?".to_string(), |rva| format!("{:#x}", rva));
+                writeln!(
+                    f,
+                    "/* DEFINE_GUID({}, ...); value unknown, RVA {} */",
+                    constant.name, rva
+                )?;
+            }
+        }
+    }
+
+    let interfaces_by_name = interfaces
+        .iter()
+        .map(|interface| (interface.name.as_str(), interface))
+        .collect::<BTreeMap<_, _>>();
+    for (interface_index, interface) in interfaces.iter().enumerate() {
+        // Virtual function tables start with the methods of the base
+        // interfaces, the root interface's first
+        let mut interface_chain = vec![interface];
+        while let Some(base_interface) = interface_chain
+            .last()
+            .and_then(|interface| interface.base_interface_name.as_deref())
+            .and_then(|base_name| interfaces_by_name.get(base_name))
+        {
+            interface_chain.push(base_interface);
+        }
+
+        if interface_index > 0 || !guid_constants.is_empty() {
+            writeln!(f)?;
+        }
+        writeln!(f, "struct {}Vtbl {{", interface.name)?;
+        for chain_interface in interface_chain.iter().rev() {
+            writeln!(f, "  /* {} */", chain_interface.name)?;
+            for method in &chain_interface.methods {
+                let arguments = std::iter::once(format!("{}* This", interface.name))
+                    .chain(method.arguments.iter().cloned())
+                    .collect::<Vec<_>>();
+                writeln!(
+                    f,
+                    "  {} ({}*{})({}); /* vtable+{:#x} */",
+                    method.return_type_name,
+                    method
+                        .calling_convention
+                        .map(|calling_convention| format!("{} ", calling_convention))
+                        .unwrap_or_default(),
+                    method.name,
+                    arguments.join(", "),
+                    method.vtable_offset
+                )?;
+            }
+        }
+        writeln!(f, "}};")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name,
+    union::Union,
+    DataFormatConfiguration, Field, TypeDescription,
+};
+
+/// C# keywords that must be escaped (with '@') when used as identifiers.
+const CSHARP_KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Representation of a member's type in C#
+enum CSharpFieldType {
+    Regular(String),
+    /// `fixed` buffer of the given element type and length
+    FixedBuffer(&'static str, usize),
+    /// Type without C# equivalent, represented as a buffer of the given size
+    Opaque(usize),
+}
+
+pub fn fmt_class(class: &Class, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let indentation = "    ";
+    let has_fixed_buffers = class
+        .fields
+        .iter()
+        .any(|field| !matches!(csharp_field_type(field), CSharpFieldType::Regular(_)));
+    fmt_struct_header(&class.name, class.size as u64, has_fixed_buffers, f)?;
+
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        writeln!(
+            f,
+            "{}[FieldOffset({:#x})] public {} __base_{};",
+            indentation,
+            base.offset,
+            flattened_type_name(&base.type_name),
+            i
+        )?;
+    }
+    // Virtual base classes are placed at the end of the object, and located
+    // through a virtual base table
+    if let Some(base) = class.base_classes.iter().find(|base| base.is_virtual) {
+        writeln!(
+            f,
+            "{}[FieldOffset({:#x})] public IntPtr __vbtable;",
+            indentation, base.offset
+        )?;
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        writeln!(
+            f,
+            "{}[FieldOffset(0x0)] public IntPtr __vftable;",
+            indentation
+        )?;
+    }
+
+    for field in &class.fields {
+        fmt_field(indentation, field, f)?;
+    }
+    writeln!(f, "}}")
+}
+
+pub fn fmt_union(u: &Union, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let indentation = "    ";
+    let has_fixed_buffers = u
+        .fields
+        .iter()
+        .any(|field| !matches!(csharp_field_type(field), CSharpFieldType::Regular(_)));
+    fmt_struct_header(&u.name, u.size as u64, has_fixed_buffers, f)?;
+
+    for field in &u.fields {
+        fmt_field(indentation, field, f)?;
+    }
+    writeln!(f, "}}")
+}
+
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "public enum {} : {}",
+        flattened_type_name(&e.name),
+        enum_base_type(&e.underlying_type, e.size)
+    )?;
+    writeln!(f, "{{")?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "    {} = {},",
+            escape_identifier(&value.name.to_string()),
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
+        )?;
+    }
+    writeln!(f, "}}")
+}
+
+fn fmt_struct_header(
+    type_name: &str,
+    type_size: u64,
+    is_unsafe: bool,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "[StructLayout(LayoutKind.Explicit, Size = {:#x})]",
+        type_size
+    )?;
+    writeln!(
+        f,
+        "public {}struct {}",
+        // Fixed-size buffers can only be declared in an unsafe context
+        if is_unsafe { "unsafe " } else { "" },
+        flattened_type_name(type_name)
+    )?;
+    writeln!(f, "{{")
+}
+
+fn fmt_field(indentation: &str, field: &Field, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let field_name = escape_identifier(&field.name.to_string());
+    match csharp_field_type(field) {
+        CSharpFieldType::Regular(type_name) => write!(
+            f,
+            "{}[FieldOffset({:#x})] public {} {};",
+            indentation, field.offset, type_name, field_name
+        )?,
+        CSharpFieldType::FixedBuffer(_, 0) | CSharpFieldType::Opaque(0) => {
+            // Zero-length arrays cannot be represented with fixed buffers
+            return writeln!(
+                f,
+                "{}// [FieldOffset({:#x})] {} {}{};",
+                indentation, field.offset, field.type_left, field.name, field.type_right
+            );
+        }
+        CSharpFieldType::FixedBuffer(element_type, length) => write!(
+            f,
+            "{}[FieldOffset({:#x})] public fixed {} {}[{}];",
+            indentation, field.offset, element_type, field_name, length
+        )?,
+        CSharpFieldType::Opaque(size) => write!(
+            f,
+            // Keep track of the original type
+            "{}[FieldOffset({:#x})] public fixed byte {}[{}]; // {}{}",
+            indentation, field.offset, field_name, size, field.type_left, field.type_right
+        )?,
+    }
+
+    if let Some(bitfield) = field.bitfield {
+        write!(
+            f,
+            " // Bitfield: bit {}, length {}",
+            bitfield.position, bitfield.length
+        )?;
+    }
+    writeln!(f)
+}
+
+fn csharp_field_type(field: &Field) -> CSharpFieldType {
+    match field.type_description.strip_modifiers() {
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => match element_type.strip_modifiers() {
+            // Fixed-size buffers can only contain primitive types
+            TypeDescription::Primitive(kind) => match csharp_primitive_type(*kind) {
+                Some(element_type) => {
+                    CSharpFieldType::FixedBuffer(element_type, dimensions.iter().product())
+                }
+                None => CSharpFieldType::Opaque(field.size),
+            },
+            _ => CSharpFieldType::Opaque(field.size),
+        },
+        other => match csharp_type(other) {
+            Some(type_name) => CSharpFieldType::Regular(type_name),
+            None => CSharpFieldType::Opaque(field.size),
+        },
+    }
+}
+
+fn csharp_type(type_description: &TypeDescription) -> Option<String> {
+    match type_description {
+        TypeDescription::Primitive(kind) => csharp_primitive_type(*kind).map(str::to_string),
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            Some(flattened_type_name(name))
+        }
+        TypeDescription::Enum { name, .. } => Some(flattened_type_name(name)),
+        TypeDescription::Pointer { .. } | TypeDescription::Function { .. } => {
+            Some("IntPtr".to_string())
+        }
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => csharp_type(underlying_type),
+        TypeDescription::Array { .. } | TypeDescription::Unknown => None,
+    }
+}
+
+fn csharp_primitive_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => "sbyte",
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 => "byte",
+        pdb::PrimitiveKind::WChar | pdb::PrimitiveKind::RChar16 => "char",
+        pdb::PrimitiveKind::RChar32 => "uint",
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "short",
+        pdb::PrimitiveKind::UShort | pdb::PrimitiveKind::U16 | pdb::PrimitiveKind::Bool16 => {
+            "ushort"
+        }
+        pdb::PrimitiveKind::Long
+        | pdb::PrimitiveKind::I32
+        | pdb::PrimitiveKind::Bool32
+        | pdb::PrimitiveKind::HRESULT => "int",
+        pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 => "uint",
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 | pdb::PrimitiveKind::Bool64 => "long",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 => "ulong",
+        pdb::PrimitiveKind::F32 => "float",
+        pdb::PrimitiveKind::F64 => "double",
+        pdb::PrimitiveKind::Bool8 => "bool",
+        _ => return None,
+    };
+
+    Some(type_name)
+}
+
+/// Return the C# type to use as the base of an enum. C# only allows integral
+/// types here, so the underlying type's size is used when it's not one.
+fn enum_base_type(underlying_type: &TypeDescription, size: usize) -> &'static str {
+    if let TypeDescription::Primitive(kind) = underlying_type.strip_modifiers() {
+        if let Some(
+            type_name @ ("sbyte" | "byte" | "short" | "ushort" | "int" | "uint" | "long" | "ulong"),
+        ) = csharp_primitive_type(*kind)
+        {
+            return type_name;
+        }
+    }
+
+    match size {
+        1 => "byte",
+        2 => "ushort",
+        8 => "ulong",
+        _ => "int",
+    }
+}
+
+fn escape_identifier(identifier: &str) -> String {
+    if CSHARP_KEYWORDS.contains(&identifier) {
+        format!("@{}", identifier)
+    } else {
+        identifier.to_string()
+    }
+}
@@ -0,0 +1,559 @@
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, members_layout_extent,
+    packing::Packing,
+    struct_members_layout,
+    union::Union,
+    union_members_layout, DataFormatConfiguration, Field, MemberLayout, TypeDescription, TypeSizes,
+};
+
+/// Kind of ctypes class.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    Struct,
+    Union,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Struct => "ctypes.Structure".fmt(f),
+            ItemKind::Union => "ctypes.Union".fmt(f),
+        }
+    }
+}
+
+/// `ctypes.Structure` (or `ctypes.Union`) subclass.
+/// Classes are declared before their fields are set, so that they can refer
+/// to each other through pointers.
+pub struct Item {
+    name: String,
+    kind: ItemKind,
+    /// `_pack_` value, if natural alignment doesn't reproduce the layout
+    pack: Option<u64>,
+    /// Fields of unnamed unions and structs, whose members are accessed as if
+    /// they were members of this class
+    anonymous_fields: Vec<String>,
+    fields: Vec<String>,
+}
+
+/// Field of a ctypes class, made of one or several entries of `_fields_`.
+struct Member {
+    offset: u64,
+    size: u64,
+    fields: Vec<String>,
+}
+
+/// State shared by the classes generated for a single type (i.e., the type
+/// itself and the unnamed unions and structs it contains).
+struct ItemContext<'a> {
+    type_name: String,
+    type_sizes: &'a TypeSizes<'a>,
+    pack: Option<u64>,
+    /// Whether gaps between members are filled with padding fields
+    explicit_padding: bool,
+    items: Vec<Item>,
+    union_count: usize,
+    struct_count: usize,
+    padding_count: usize,
+}
+
+/// Return the classes describing the given class (i.e., the ones of the
+/// unnamed unions and structs it contains, and then the class').
+pub fn class_items(
+    class: &Class,
+    type_sizes: &TypeSizes,
+    fmt_configuration: &DataFormatConfiguration,
+) -> Vec<Item> {
+    let mut context = ItemContext::new(&class.name, class.packing, type_sizes, fmt_configuration);
+    let mut members = vec![];
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        members.push(Member {
+            offset: base.offset as u64,
+            size: type_sizes
+                .get(base.type_name.as_str())
+                .copied()
+                .unwrap_or(0),
+            fields: vec![format!(
+                "(\"__base_{}\", {}),",
+                i,
+                flattened_type_name(&base.type_name)
+            )],
+        });
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        let own_fields_offset = class
+            .fields
+            .iter()
+            .map(|field| field.offset as u64)
+            .min()
+            .unwrap_or(class.size as u64);
+        let pointer_size = class
+            .vtable_pointer_size
+            .unwrap_or_else(|| std::cmp::min(own_fields_offset, 8));
+        members.push(Member {
+            offset: 0,
+            size: pointer_size,
+            fields: vec![format!(
+                "(\"__vftable\", {}),",
+                pointer_type(pointer_size as usize, "ctypes.c_void_p".to_string())
+            )],
+        });
+    }
+    // Note: Virtual base classes are placed at the end of the object, they're
+    // covered by trailing padding
+
+    let item_name = context.type_name.clone();
+    add_item(
+        &mut context,
+        ItemKind::Struct,
+        item_name,
+        members,
+        &struct_members_layout(&class.fields),
+        (0, class.size as u64),
+    );
+    context.items
+}
+
+/// Return the classes describing the given union (i.e., the ones of the
+/// unnamed structs it contains, and then the union's).
+pub fn union_items(
+    u: &Union,
+    type_sizes: &TypeSizes,
+    fmt_configuration: &DataFormatConfiguration,
+) -> Vec<Item> {
+    let mut context = ItemContext::new(&u.name, u.packing, type_sizes, fmt_configuration);
+    let item_name = context.type_name.clone();
+    add_item(
+        &mut context,
+        ItemKind::Union,
+        item_name,
+        vec![],
+        &union_members_layout(&u.fields),
+        (0, u.size as u64),
+    );
+    context.items
+}
+
+/// Write the declaration of the given class, whose fields are set later on
+/// by `fmt_item_fields`.
+pub fn fmt_item_declaration(item: &Item, f: &mut impl std::fmt::Write) -> fmt::Result {
+    writeln!(f, "class {}({}):", item.name, item.kind)?;
+    writeln!(f, "    pass")
+}
+
+pub fn fmt_item_fields(item: &Item, f: &mut impl std::fmt::Write) -> fmt::Result {
+    // Note: `_pack_` and `_anonymous_` must be set before `_fields_`
+    if let Some(pack) = item.pack {
+        writeln!(f, "{}._pack_ = {}", item.name, pack)?;
+    }
+    if !item.anonymous_fields.is_empty() {
+        writeln!(
+            f,
+            "{}._anonymous_ = ({},)",
+            item.name,
+            item.anonymous_fields
+                .iter()
+                .map(|field_name| format!("\"{}\"", field_name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+    }
+    writeln!(f, "{}._fields_ = [", item.name)?;
+    for field in &item.fields {
+        writeln!(f, "    {}", field)?;
+    }
+    writeln!(f, "]")
+}
+
+/// Enums are represented by an alias of their underlying type and a set of
+/// constants.
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let enum_name = flattened_type_name(&e.name);
+    writeln!(
+        f,
+        "{} = {}",
+        enum_name,
+        ctypes_type(&e.underlying_type).unwrap_or_else(|| "ctypes.c_int32".to_string())
+    )?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "{}_{} = {}",
+            enum_name,
+            value.name,
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Types without definition are represented by classes without fields.
+pub fn fmt_opaque_struct(type_name: &str, f: &mut impl std::fmt::Write) -> fmt::Result {
+    writeln!(
+        f,
+        "class {}(ctypes.Structure):",
+        flattened_type_name(type_name)
+    )?;
+    writeln!(f, "    pass")
+}
+
+pub fn fmt_size_assert(
+    type_name: &str,
+    type_size: u64,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "assert ctypes.sizeof({}) == {:#x}",
+        flattened_type_name(type_name),
+        type_size
+    )
+}
+
+impl<'a> ItemContext<'a> {
+    fn new(
+        type_name: &str,
+        packing: Packing,
+        type_sizes: &'a TypeSizes<'a>,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Self {
+        let (pack, explicit_padding) = match packing {
+            Packing::Natural => (None, fmt_configuration.print_padding_fields),
+            Packing::Packed(packing) => (Some(packing), fmt_configuration.print_padding_fields),
+            // Members are packed and gaps filled explicitly
+            Packing::Unknown => (Some(1), true),
+        };
+
+        Self {
+            type_name: flattened_type_name(type_name),
+            type_sizes,
+            pack,
+            explicit_padding,
+            items: vec![],
+            union_count: 0,
+            struct_count: 0,
+            padding_count: 0,
+        }
+    }
+
+    fn padding_field(&mut self, size: u64) -> String {
+        let field = format!(
+            "(\"_padding_{}\", ctypes.c_uint8 * {:#x}),",
+            self.padding_count, size
+        );
+        self.padding_count += 1;
+        field
+    }
+}
+
+/// Add the class `item_name`, made of the given members, located at the given
+/// offset (relative to the type being reconstructed) and of the given size.
+/// Unnamed unions and structs are described by separate classes (added
+/// before this one), embedded as anonymous fields.
+fn add_item(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    item_name: String,
+    mut members: Vec<Member>,
+    layout: &[MemberLayout],
+    (item_offset, item_size): (u64, u64),
+) {
+    let mut anonymous_fields = vec![];
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) if field.bitfield.is_some() => {
+                members.push(bitfield_member(context, &[field]));
+            }
+            MemberLayout::Field(field) => members.push(field_member(context, field)),
+            MemberLayout::Bitfields(bitfields) => {
+                members.push(bitfield_member(context, bitfields));
+            }
+            MemberLayout::Union(union_layout) => {
+                let union_name = format!("{}__union_{}", context.type_name, context.union_count);
+                let field_name = format!("__union_{}", context.union_count);
+                context.union_count += 1;
+                members.push(nested_item_member(
+                    context,
+                    ItemKind::Union,
+                    union_name,
+                    &field_name,
+                    union_layout,
+                ));
+                anonymous_fields.push(field_name);
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let struct_name = format!("{}__struct_{}", context.type_name, context.struct_count);
+                let field_name = format!("__struct_{}", context.struct_count);
+                context.struct_count += 1;
+                members.push(nested_item_member(
+                    context,
+                    ItemKind::Struct,
+                    struct_name,
+                    &field_name,
+                    struct_layout,
+                ));
+                anonymous_fields.push(field_name);
+            }
+        }
+    }
+
+    let mut fields = vec![];
+    let mut end_offset = item_offset;
+    for member in members {
+        if kind == ItemKind::Struct && context.explicit_padding && member.offset > end_offset {
+            fields.push(context.padding_field(member.offset - end_offset));
+        }
+        fields.extend(member.fields);
+        end_offset = end_offset.max(member.offset + member.size);
+    }
+    // Trailing padding
+    if context.explicit_padding && end_offset < item_offset + item_size {
+        let padding_size = match kind {
+            ItemKind::Struct => item_offset + item_size - end_offset,
+            ItemKind::Union => item_size,
+        };
+        fields.push(context.padding_field(padding_size));
+    }
+
+    // Note: Classes are added after the ones they contain
+    context.items.push(Item {
+        name: item_name,
+        kind,
+        pack: context.pack,
+        anonymous_fields,
+        fields,
+    });
+}
+
+fn nested_item_member(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    item_name: String,
+    field_name: &str,
+    layout: &[MemberLayout],
+) -> Member {
+    let (offset, size) = members_layout_extent(layout);
+    let field = format!("(\"{}\", {}),", field_name, item_name);
+    add_item(context, kind, item_name, vec![], layout, (offset, size));
+
+    Member {
+        offset,
+        size,
+        fields: vec![field],
+    }
+}
+
+/// Return the member declaring the given bitfields sharing a storage unit.
+/// Unused bits are declared explicitly so that the whole storage unit is
+/// consumed, whatever the bitfield allocation rules of the platform.
+fn bitfield_member(context: &mut ItemContext, bitfields: &[&Field]) -> Member {
+    let storage_offset = bitfields[0].offset as u64;
+    let storage_size = bitfields.iter().map(|field| field.size).max().unwrap_or(0) as u64;
+    let storage_type = bitfield_storage_type(bitfields[0], storage_size);
+
+    let mut fields = vec![];
+    let mut bit_position = 0;
+    for field in bitfields {
+        if let Some(bitfield) = field.bitfield {
+            let position = bitfield.position as u64;
+            if position > bit_position {
+                fields.push(padding_bits_field(
+                    context,
+                    &storage_type,
+                    position - bit_position,
+                ));
+            }
+            fields.push(format!(
+                "(\"{}\", {}, {}),",
+                field.name, storage_type, bitfield.length
+            ));
+            bit_position = bit_position.max(position + bitfield.length as u64);
+        }
+    }
+    if bit_position < storage_size * 8 {
+        fields.push(padding_bits_field(
+            context,
+            &storage_type,
+            storage_size * 8 - bit_position,
+        ));
+    }
+
+    Member {
+        offset: storage_offset,
+        size: storage_size,
+        fields,
+    }
+}
+
+fn padding_bits_field(context: &mut ItemContext, storage_type: &str, bits: u64) -> String {
+    let field = format!(
+        "(\"_padding_{}\", {}, {}),",
+        context.padding_count, storage_type, bits
+    );
+    context.padding_count += 1;
+    field
+}
+
+/// Return the integer type used to store the given bitfield, as bitfields can
+/// only be declared with ctypes' integer types.
+fn bitfield_storage_type(field: &Field, storage_size: u64) -> String {
+    let integer_type = match field.type_description.strip_modifiers() {
+        TypeDescription::Primitive(kind) => ctypes_integer_type(*kind),
+        TypeDescription::Enum {
+            underlying_type, ..
+        } => match underlying_type.strip_modifiers() {
+            TypeDescription::Primitive(kind) => ctypes_integer_type(*kind),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    integer_type
+        .unwrap_or_else(|| unsigned_type(storage_size))
+        .to_string()
+}
+
+fn field_member(context: &ItemContext, field: &Field) -> Member {
+    let field_entry = match ctypes_type(&field.type_description) {
+        Some(type_name) => format!("(\"{}\", {}),", field.name, type_name),
+        // Types we cannot represent are replaced with raw bytes, keep track
+        // of the original type
+        None => format!(
+            "(\"{}\", ctypes.c_uint8 * {}),  # {}{}",
+            field.name, field.size, field.type_left, field.type_right
+        ),
+    };
+    // Embedded types' size is known from their definition
+    let size = match field.type_description.strip_modifiers() {
+        TypeDescription::Class(name) | TypeDescription::Union(name) => context
+            .type_sizes
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(field.size as u64),
+        _ => field.size as u64,
+    };
+
+    Member {
+        offset: field.offset as u64,
+        size,
+        fields: vec![field_entry],
+    }
+}
+
+/// Return the type pointers of `size` bytes are represented with.
+/// Only 64-bit pointers are typed, so that the layout of 32-bit targets'
+/// types is preserved when loaded by 64-bit interpreters.
+fn pointer_type(size: usize, typed_pointer: String) -> String {
+    match size {
+        8 => typed_pointer,
+        _ => unsigned_type(size as u64).to_string(),
+    }
+}
+
+/// Return the unsigned integer type of the given size (in bytes).
+fn unsigned_type(size: u64) -> &'static str {
+    match size {
+        1 => "ctypes.c_uint8",
+        2 => "ctypes.c_uint16",
+        4 => "ctypes.c_uint32",
+        _ => "ctypes.c_uint64",
+    }
+}
+
+fn ctypes_type(type_description: &TypeDescription) -> Option<String> {
+    match type_description {
+        TypeDescription::Primitive(kind) => ctypes_primitive_type(*kind).map(str::to_string),
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            Some(flattened_type_name(name))
+        }
+        TypeDescription::Enum { name, .. } => Some(flattened_type_name(name)),
+        TypeDescription::Pointer { pointee, size, .. } => {
+            let typed_pointer = match pointee.strip_modifiers() {
+                TypeDescription::Primitive(pdb::PrimitiveKind::Void)
+                | TypeDescription::Function { .. } => "ctypes.c_void_p".to_string(),
+                pointee => match ctypes_type(pointee) {
+                    Some(pointee_type) => format!("ctypes.POINTER({})", pointee_type),
+                    None => "ctypes.c_void_p".to_string(),
+                },
+            };
+            Some(pointer_type(*size, typed_pointer))
+        }
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => ctypes_type(underlying_type),
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => {
+            // `T * 3 * 2` is an array of 2 arrays of 3 `T`s
+            let element_type = ctypes_type(element_type)?;
+            Some(
+                dimensions
+                    .iter()
+                    .rev()
+                    .fold(element_type, |type_name, dim| {
+                        format!("{} * {}", type_name, dim)
+                    }),
+            )
+        }
+        TypeDescription::Function { .. } | TypeDescription::Unknown => None,
+    }
+}
+
+fn ctypes_primitive_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar => "ctypes.c_char",
+        pdb::PrimitiveKind::F32 => "ctypes.c_float",
+        pdb::PrimitiveKind::F64 => "ctypes.c_double",
+        pdb::PrimitiveKind::Bool8 => "ctypes.c_bool",
+        other => return ctypes_integer_type(other),
+    };
+
+    Some(type_name)
+}
+
+fn ctypes_integer_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => {
+            "ctypes.c_int8"
+        }
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 | pdb::PrimitiveKind::Bool8 => {
+            "ctypes.c_uint8"
+        }
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "ctypes.c_int16",
+        pdb::PrimitiveKind::UShort
+        | pdb::PrimitiveKind::U16
+        | pdb::PrimitiveKind::WChar
+        | pdb::PrimitiveKind::RChar16
+        | pdb::PrimitiveKind::Bool16 => "ctypes.c_uint16",
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => {
+            "ctypes.c_int32"
+        }
+        pdb::PrimitiveKind::ULong
+        | pdb::PrimitiveKind::U32
+        | pdb::PrimitiveKind::RChar32
+        | pdb::PrimitiveKind::Bool32 => "ctypes.c_uint32",
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => "ctypes.c_int64",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 | pdb::PrimitiveKind::Bool64 => {
+            "ctypes.c_uint64"
+        }
+        _ => return None,
+    };
+
+    Some(type_name)
+}
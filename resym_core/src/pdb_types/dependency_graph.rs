@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Types a definition refers to, identified by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeDependencies {
+    /// Types that must be defined beforehand (e.g., base classes and members
+    /// stored by value)
+    pub definitions: BTreeSet<String>,
+    /// Types that only need to be declared beforehand (e.g., pointed-to types)
+    pub declarations: BTreeSet<String>,
+}
+
+impl TypeDependencies {
+    pub fn extend(&mut self, other: TypeDependencies) {
+        self.definitions.extend(other.definitions);
+        self.declarations.extend(other.declarations);
+    }
+}
+
+/// Order in which a set of types must be output. Both lists contain indices
+/// into the slice given to `sort_definitions`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefinitionOrder {
+    /// Types that must be forward declared before any definition
+    pub forward_declarations: Vec<usize>,
+    /// Definitions, ordered so that types are defined before being used
+    pub definitions: Vec<usize>,
+}
+
+/// Sort the given types so that every type is defined after its dependencies.
+///
+/// Types that are part of a dependency cycle (which can only go through
+/// declaration dependencies, e.g. pointers) cannot all be defined before
+/// each other. Such cycles are broken by forward declaring the types that
+/// are used before being defined. Forward declarations are only emitted for
+/// types that are genuinely recursive.
+///
+/// The relative order of `types` is preserved as much as possible, so that
+/// the output is deterministic. Dependencies on types absent from `types`
+/// are ignored.
+pub fn sort_definitions(types: &[(&str, &TypeDependencies)]) -> DefinitionOrder {
+    let mut indices_by_name = BTreeMap::new();
+    for (i, (name, _)) in types.iter().enumerate() {
+        indices_by_name.entry(*name).or_insert(i);
+    }
+    let resolve = |names: &BTreeSet<String>, i: usize| -> Vec<usize> {
+        names
+            .iter()
+            .filter_map(|name| indices_by_name.get(name.as_str()).copied())
+            // Types can always refer to themselves
+            .filter(|&j| j != i)
+            .collect()
+    };
+    let definition_edges = types
+        .iter()
+        .enumerate()
+        .map(|(i, (_, dependencies))| resolve(&dependencies.definitions, i))
+        .collect::<Vec<_>>();
+    let all_edges = types
+        .iter()
+        .enumerate()
+        .map(|(i, (_, dependencies))| {
+            let mut edges = definition_edges[i].clone();
+            edges.extend(
+                resolve(&dependencies.declarations, i)
+                    .into_iter()
+                    .filter(|j| !definition_edges[i].contains(j)),
+            );
+            edges
+        })
+        .collect::<Vec<_>>();
+
+    // Strongly connected components are returned with dependencies first, so
+    // only types of a same component may need forward declarations
+    let mut order = DefinitionOrder::default();
+    let mut in_component = vec![false; types.len()];
+    let mut defined = vec![false; types.len()];
+    for mut component in strongly_connected_components(&all_edges) {
+        if component.len() == 1 {
+            order.definitions.push(component[0]);
+            continue;
+        }
+
+        component.sort_unstable();
+        for &i in &component {
+            in_component[i] = true;
+        }
+        // Definition dependencies cannot be cyclic (types would have an
+        // infinite size), sort the component according to them
+        let component_order = postorder(&component, &definition_edges, &in_component);
+        let mut forward_declared = Vec::new();
+        for &i in &component_order {
+            forward_declared.extend(
+                all_edges[i]
+                    .iter()
+                    .copied()
+                    .filter(|&j| in_component[j] && !defined[j]),
+            );
+            defined[i] = true;
+        }
+        forward_declared.sort_unstable();
+        forward_declared.dedup();
+        order.forward_declarations.extend(forward_declared);
+        order.definitions.extend(component_order);
+
+        for &i in &component {
+            in_component[i] = false;
+        }
+    }
+    order.forward_declarations.sort_unstable();
+
+    order
+}
+
+/// Tarjan's algorithm, implemented iteratively to support deep dependency
+/// chains. Components are returned in reverse topological order (i.e., a
+/// component is returned after all the components it has edges to).
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let node_count = edges.len();
+    let mut next_index = 0;
+    let mut indices: Vec<Option<usize>> = vec![None; node_count];
+    let mut low_links = vec![0; node_count];
+    let mut on_stack = vec![false; node_count];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    for root in 0..node_count {
+        if indices[root].is_some() {
+            continue;
+        }
+
+        // Stack of (node, index of the next edge to visit)
+        let mut call_stack = vec![(root, 0)];
+        indices[root] = Some(next_index);
+        low_links[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some(&(node, edge_position)) = call_stack.last() {
+            if let Some(&successor) = edges[node].get(edge_position) {
+                if let Some(frame) = call_stack.last_mut() {
+                    frame.1 += 1;
+                }
+                match indices[successor] {
+                    None => {
+                        indices[successor] = Some(next_index);
+                        low_links[successor] = next_index;
+                        next_index += 1;
+                        stack.push(successor);
+                        on_stack[successor] = true;
+                        call_stack.push((successor, 0));
+                    }
+                    Some(successor_index) if on_stack[successor] => {
+                        low_links[node] = low_links[node].min(successor_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low_links[parent] = low_links[parent].min(low_links[node]);
+            }
+            if Some(low_links[node]) == indices[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Return `nodes` in depth-first post-order, only following edges to nodes
+/// for which `is_included` is true.
+fn postorder(nodes: &[usize], edges: &[Vec<usize>], is_included: &[bool]) -> Vec<usize> {
+    let mut visited = vec![false; edges.len()];
+    let mut order = Vec::with_capacity(nodes.len());
+    for &root in nodes {
+        if visited[root] {
+            continue;
+        }
+
+        visited[root] = true;
+        let mut call_stack = vec![(root, 0)];
+        while let Some(&(node, edge_position)) = call_stack.last() {
+            if let Some(&successor) = edges[node].get(edge_position) {
+                if let Some(frame) = call_stack.last_mut() {
+                    frame.1 += 1;
+                }
+                if is_included[successor] && !visited[successor] {
+                    visited[successor] = true;
+                    call_stack.push((successor, 0));
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            order.push(node);
+        }
+    }
+
+    order
+}
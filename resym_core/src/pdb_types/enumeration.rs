@@ -2,12 +2,16 @@ use std::fmt;
 
 use anyhow::Result;
 
-use super::TypeSet;
+use super::{DataFormatConfiguration, TypeDescription, TypeSet};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Enum<'p> {
     pub name: String,
+    /// Whether the enum is a scoped enumeration (i.e., `enum class`)
+    pub scoped: bool,
+    pub size: usize,
     pub underlying_type_name: String,
+    pub underlying_type: TypeDescription,
     pub values: Vec<EnumValue<'p>>,
 }
 
@@ -51,24 +55,25 @@ impl<'p> Enum<'p> {
         }
     }
 
-    pub fn reconstruct(&self, f: &mut impl std::fmt::Write) -> fmt::Result {
-        writeln!(f, "enum {} : {} {{", self.name, self.underlying_type_name)?;
+    pub fn reconstruct(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        writeln!(
+            f,
+            "enum {}{} : {} {{",
+            if self.scoped { "class " } else { "" },
+            self.name,
+            self.underlying_type_name
+        )?;
 
         for value in &self.values {
             writeln!(
                 f,
                 "  {} = {},",
                 value.name.to_string(),
-                match value.value {
-                    pdb::Variant::U8(v) => format!("0x{:02x}", v),
-                    pdb::Variant::U16(v) => format!("0x{:04x}", v),
-                    pdb::Variant::U32(v) => format!("0x{:08x}", v),
-                    pdb::Variant::U64(v) => format!("0x{:16x}", v),
-                    pdb::Variant::I8(v) => format!("{}", v),
-                    pdb::Variant::I16(v) => format!("{}", v),
-                    pdb::Variant::I32(v) => format!("{}", v),
-                    pdb::Variant::I64(v) => format!("{}", v),
-                }
+                fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
             )?;
         }
         writeln!(f, "}};")?;
@@ -77,8 +82,34 @@ impl<'p> Enum<'p> {
     }
 }
 
+/// Format an enumerant's value, in decimal or in hexadecimal. Negative values
+/// are written as negated hexadecimal literals (e.g., `-0x1`), so that they
+/// fit in the enum's underlying type.
+pub fn fmt_enum_value(value: pdb::Variant, hex: bool) -> String {
+    let (is_negative, magnitude) = match value {
+        pdb::Variant::U8(v) => (false, v as u64),
+        pdb::Variant::U16(v) => (false, v as u64),
+        pdb::Variant::U32(v) => (false, v as u64),
+        pdb::Variant::U64(v) => (false, v),
+        pdb::Variant::I8(v) => (v < 0, v.unsigned_abs() as u64),
+        pdb::Variant::I16(v) => (v < 0, v.unsigned_abs() as u64),
+        pdb::Variant::I32(v) => (v < 0, v.unsigned_abs() as u64),
+        pdb::Variant::I64(v) => (v < 0, v.unsigned_abs()),
+    };
+
+    format!(
+        "{}{}",
+        if is_negative { "-" } else { "" },
+        if hex {
+            format!("{:#x}", magnitude)
+        } else {
+            format!("{}", magnitude)
+        }
+    )
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnumValue<'p> {
-    name: pdb::RawString<'p>,
-    value: pdb::Variant,
+    pub name: pdb::RawString<'p>,
+    pub value: pdb::Variant,
 }
@@ -1,15 +1,27 @@
 use std::fmt;
 
+use super::TypeDescription;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Field<'p> {
     pub type_left: String,
     pub type_right: String,
+    /// Structured description of the field's type, for non-C++ formatters
+    pub type_description: TypeDescription,
     pub name: pdb::RawString<'p>,
     pub offset: u16,
     pub size: usize,
+    pub bitfield: Option<FieldBitfield>,
     pub access: FieldAccess,
 }
 
+/// Position and width (in bits) of a bitfield member, relative to its offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldBitfield {
+    pub position: u8,
+    pub length: u8,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StaticField<'p> {
     pub type_left: String,
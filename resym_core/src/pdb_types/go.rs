@@ -0,0 +1,411 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, members_layout_extent, struct_members_layout,
+    union::Union,
+    union_members_layout, DataFormatConfiguration, Field, MemberLayout, TypeDescription, TypeSizes,
+};
+
+/// Member of a Go struct, declared on one or several lines.
+struct Member {
+    offset: u64,
+    size: u64,
+    lines: Vec<String>,
+}
+
+/// State shared by the members of a single type (i.e., the type itself and
+/// the unnamed unions and structs it contains).
+struct ItemContext<'a> {
+    type_sizes: &'a TypeSizes<'a>,
+    /// Names of the fields declared so far, which must be unique once
+    /// exported
+    field_names: BTreeSet<String>,
+    union_count: usize,
+    struct_count: usize,
+    bitfield_count: usize,
+}
+
+pub fn fmt_class(
+    class: &Class,
+    type_sizes: &TypeSizes,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let mut context = ItemContext::new(type_sizes);
+    let own_fields_offset = class
+        .fields
+        .iter()
+        .map(|field| field.offset as u64)
+        .min()
+        .unwrap_or(class.size as u64);
+    let mut members = vec![];
+    // Base classes are embedded at the beginning of the struct
+    for base in &class.base_classes {
+        if base.is_virtual {
+            continue;
+        }
+        members.push(Member {
+            offset: base.offset as u64,
+            size: type_sizes
+                .get(base.type_name.as_str())
+                .copied()
+                .unwrap_or(0),
+            lines: vec![flattened_type_name(&base.type_name)],
+        });
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        let pointer_size = class
+            .vtable_pointer_size
+            .unwrap_or_else(|| std::cmp::min(own_fields_offset, 8));
+        members.push(Member {
+            offset: 0,
+            size: pointer_size,
+            lines: vec![format!("Vftable {}", pointer_type(pointer_size as usize))],
+        });
+    }
+    // Note: Virtual base classes are placed at the end of the object, they're
+    // covered by trailing padding
+
+    let lines = struct_lines(
+        &mut context,
+        members,
+        &struct_members_layout(&class.fields),
+        (0, class.size as u64),
+    );
+    writeln!(f, "type {} struct {{", flattened_type_name(&class.name))?;
+    for line in lines {
+        writeln!(f, "\t{}", line)?;
+    }
+    writeln!(f, "}}")
+}
+
+/// Go has no unions, they're represented by byte arrays of their size. How to
+/// access their members is documented in comments.
+pub fn fmt_union(u: &Union, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let union_name = flattened_type_name(&u.name);
+    writeln!(f, "// {} is a union of:", union_name)?;
+    for line in union_accessor_lines(&union_members_layout(&u.fields), 0) {
+        writeln!(f, "{}", line)?;
+    }
+    writeln!(f, "type {} [{}]byte", union_name, u.size)
+}
+
+/// Enums are represented by a named integer type and a set of constants.
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let enum_name = flattened_type_name(&e.name);
+    writeln!(
+        f,
+        "type {} {}",
+        enum_name,
+        go_type(&e.underlying_type).unwrap_or_else(|| "int32".to_string())
+    )?;
+    if e.values.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(f)?;
+    writeln!(f, "const (")?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "\t{}_{} {} = {}",
+            enum_name,
+            value.name,
+            enum_name,
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
+        )?;
+    }
+    writeln!(f, ")")
+}
+
+impl<'a> ItemContext<'a> {
+    fn new(type_sizes: &'a TypeSizes<'a>) -> Self {
+        Self {
+            type_sizes,
+            field_names: BTreeSet::new(),
+            union_count: 0,
+            struct_count: 0,
+            bitfield_count: 0,
+        }
+    }
+
+    /// Return the exported name of the field `name`, along with a comment
+    /// containing the original name when it had to be changed.
+    fn exported_field_name(&mut self, name: &str) -> (String, String) {
+        let mut exported_name = exported_identifier(name);
+        while !self.field_names.insert(exported_name.clone()) {
+            exported_name.push('_');
+        }
+        let comment = if exported_name == name {
+            String::new()
+        } else {
+            format!(" // {}", name)
+        };
+
+        (exported_name, comment)
+    }
+}
+
+/// Return the lines declaring the given struct members, located at the given
+/// offset (relative to the type being reconstructed) and of the given size.
+/// Explicit padding is inserted where members leave gaps, so that the layout
+/// doesn't depend on the alignment of Go types (e.g., unions' byte arrays).
+fn struct_lines(
+    context: &mut ItemContext,
+    mut members: Vec<Member>,
+    layout: &[MemberLayout],
+    (item_offset, item_size): (u64, u64),
+) -> Vec<String> {
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) if field.bitfield.is_some() => {
+                members.push(bitfield_storage_member(context, &[field]));
+            }
+            MemberLayout::Field(field) => members.push(field_member(context, field)),
+            MemberLayout::Bitfields(bitfields) => {
+                members.push(bitfield_storage_member(context, bitfields));
+            }
+            MemberLayout::Union(union_layout) => {
+                let member_name = format!("Union{}", context.union_count);
+                context.union_count += 1;
+                let (offset, size) = members_layout_extent(union_layout);
+                let mut lines = vec![format!("// {} is a union of:", member_name)];
+                lines.extend(union_accessor_lines(union_layout, offset));
+                lines.push(format!("{} [{}]byte", member_name, size));
+                members.push(Member {
+                    offset,
+                    size,
+                    lines,
+                });
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let member_name = format!("Struct{}", context.struct_count);
+                context.struct_count += 1;
+                let (offset, size) = members_layout_extent(struct_layout);
+                let mut lines = vec![format!("{} struct {{", member_name)];
+                lines.extend(
+                    struct_lines(context, vec![], struct_layout, (offset, size))
+                        .into_iter()
+                        .map(|line| format!("\t{}", line)),
+                );
+                lines.push("}".to_string());
+                members.push(Member {
+                    offset,
+                    size,
+                    lines,
+                });
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let mut end_offset = item_offset;
+    for member in members {
+        if member.offset > end_offset {
+            lines.push(format!("_ [{}]byte", member.offset - end_offset));
+        }
+        lines.extend(member.lines);
+        end_offset = end_offset.max(member.offset + member.size);
+    }
+    // Trailing padding
+    if end_offset < item_offset + item_size {
+        lines.push(format!("_ [{}]byte", item_offset + item_size - end_offset));
+    }
+
+    lines
+}
+
+/// Return comment lines listing the members of a union located at
+/// `union_offset`. Members of the structs it contains are listed at their
+/// offset in the union.
+fn union_accessor_lines(layout: &[MemberLayout], union_offset: u64) -> Vec<String> {
+    let mut lines = vec![];
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) => {
+                lines.push(union_accessor_line(field, union_offset));
+            }
+            MemberLayout::Bitfields(bitfields) => lines.extend(
+                bitfields
+                    .iter()
+                    .map(|field| union_accessor_line(field, union_offset)),
+            ),
+            MemberLayout::Union(layout) | MemberLayout::Struct(layout) => {
+                lines.extend(union_accessor_lines(layout, union_offset));
+            }
+        }
+    }
+
+    lines
+}
+
+fn union_accessor_line(field: &Field, union_offset: u64) -> String {
+    let field_type =
+        go_type(&field.type_description).unwrap_or_else(|| format!("[{}]byte", field.size));
+    let bits = match field.bitfield {
+        Some(bitfield) => format!(" (bit {}, length {})", bitfield.position, bitfield.length),
+        None => String::new(),
+    };
+
+    format!(
+        "//\t+{:#05x} {} {}{}",
+        field.offset as u64 - union_offset,
+        field.name,
+        field_type,
+        bits
+    )
+}
+
+/// Return a member covering the storage of the given bitfields, since Go
+/// doesn't support bitfields.
+fn bitfield_storage_member(context: &mut ItemContext, bitfields: &[&Field]) -> Member {
+    let storage_offset = bitfields[0].offset as u64;
+    let storage_size = bitfields.iter().map(|field| field.size).max().unwrap_or(0) as u64;
+    let mut line = format!(
+        "Bitfield{} {} // bitfields:",
+        context.bitfield_count,
+        unsigned_type(storage_size)
+    );
+    context.bitfield_count += 1;
+    for (i, field) in bitfields.iter().enumerate() {
+        if let Some(bitfield) = field.bitfield {
+            line.push_str(&format!(
+                "{} {} (bit {}, length {})",
+                if i > 0 { "," } else { "" },
+                field.name,
+                bitfield.position,
+                bitfield.length
+            ));
+        }
+    }
+
+    Member {
+        offset: storage_offset,
+        size: storage_size,
+        lines: vec![line],
+    }
+}
+
+fn field_member(context: &mut ItemContext, field: &Field) -> Member {
+    let (field_name, comment) = context.exported_field_name(&field.name.to_string());
+    let line = match go_type(&field.type_description) {
+        Some(type_name) => format!("{} {}{}", field_name, type_name, comment),
+        // Types we cannot represent are replaced with raw bytes, keep track
+        // of the original type
+        None => format!(
+            "{} [{}]byte // {}: {}{}",
+            field_name, field.size, field.name, field.type_left, field.type_right
+        ),
+    };
+    // Embedded types' size is known from their definition
+    let size = match field.type_description.strip_modifiers() {
+        TypeDescription::Class(name) | TypeDescription::Union(name) => context
+            .type_sizes
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(field.size as u64),
+        _ => field.size as u64,
+    };
+
+    Member {
+        offset: field.offset as u64,
+        size,
+        lines: vec![line],
+    }
+}
+
+/// Return the integer type pointers of `size` bytes are represented with.
+/// `uintptr` is only used for 64-bit pointers, so that the layout of 32-bit
+/// targets' types is preserved on 64-bit hosts.
+fn pointer_type(size: usize) -> &'static str {
+    match size {
+        8 => "uintptr",
+        _ => unsigned_type(size as u64),
+    }
+}
+
+/// Return the unsigned integer type of the given size (in bytes).
+fn unsigned_type(size: u64) -> &'static str {
+    match size {
+        1 => "uint8",
+        2 => "uint16",
+        4 => "uint32",
+        _ => "uint64",
+    }
+}
+
+fn go_type(type_description: &TypeDescription) -> Option<String> {
+    match type_description {
+        TypeDescription::Primitive(kind) => go_primitive_type(*kind).map(str::to_string),
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            Some(flattened_type_name(name))
+        }
+        TypeDescription::Enum { name, .. } => Some(flattened_type_name(name)),
+        TypeDescription::Pointer { size, .. } => Some(pointer_type(*size).to_string()),
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => go_type(underlying_type),
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => {
+            let element_type = go_type(element_type)?;
+            let dimensions = dimensions
+                .iter()
+                .map(|dim| format!("[{}]", dim))
+                .collect::<String>();
+            Some(format!("{}{}", dimensions, element_type))
+        }
+        TypeDescription::Function { .. } | TypeDescription::Unknown => None,
+    }
+}
+
+fn go_primitive_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => "int8",
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 => "uint8",
+        pdb::PrimitiveKind::WChar | pdb::PrimitiveKind::RChar16 => "uint16",
+        pdb::PrimitiveKind::RChar32 => "rune",
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "int16",
+        pdb::PrimitiveKind::UShort | pdb::PrimitiveKind::U16 | pdb::PrimitiveKind::Bool16 => {
+            "uint16"
+        }
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => "int32",
+        pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 | pdb::PrimitiveKind::Bool32 => {
+            "uint32"
+        }
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => "int64",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 | pdb::PrimitiveKind::Bool64 => {
+            "uint64"
+        }
+        pdb::PrimitiveKind::F32 => "float32",
+        pdb::PrimitiveKind::F64 => "float64",
+        pdb::PrimitiveKind::Bool8 => "bool",
+        _ => return None,
+    };
+
+    Some(type_name)
+}
+
+/// Return the exported (i.e., capitalized) Go identifier corresponding to
+/// the given C++ name.
+fn exported_identifier(name: &str) -> String {
+    let name = flattened_type_name(name);
+    match name.chars().next() {
+        Some(c) if c.is_ascii_lowercase() => {
+            format!("{}{}", c.to_ascii_uppercase(), &name[c.len_utf8()..])
+        }
+        Some(c) if c.is_ascii_uppercase() => name,
+        // Identifiers starting with an underscore (or a digit) cannot be
+        // exported as is
+        _ => format!("X{}", name),
+    }
+}
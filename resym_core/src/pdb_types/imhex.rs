@@ -0,0 +1,438 @@
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, members_layout_extent, struct_members_layout,
+    union::Union,
+    union_members_layout, DataFormatConfiguration, Field, MemberLayout, TypeDescription, TypeSizes,
+};
+
+/// Pattern language keywords (and built-in types) that must be escaped when
+/// used as identifiers.
+const IMHEX_KEYWORDS: &[&str] = &[
+    "addressof",
+    "be",
+    "bitfield",
+    "bool",
+    "break",
+    "char",
+    "char16",
+    "continue",
+    "double",
+    "else",
+    "enum",
+    "false",
+    "float",
+    "fn",
+    "for",
+    "if",
+    "import",
+    "in",
+    "le",
+    "match",
+    "namespace",
+    "null",
+    "out",
+    "padding",
+    "parent",
+    "ref",
+    "return",
+    "s128",
+    "s16",
+    "s24",
+    "s32",
+    "s48",
+    "s64",
+    "s8",
+    "sizeof",
+    "str",
+    "struct",
+    "this",
+    "true",
+    "try",
+    "u128",
+    "u16",
+    "u24",
+    "u32",
+    "u48",
+    "u64",
+    "u8",
+    "union",
+    "using",
+    "while",
+];
+
+/// Kind of pattern type.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    Struct,
+    Union,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Struct => "struct".fmt(f),
+            ItemKind::Union => "union".fmt(f),
+        }
+    }
+}
+
+/// Member of a pattern type.
+struct Member {
+    offset: u64,
+    size: u64,
+    declaration: String,
+}
+
+/// State shared by the items generated for a single type (i.e., the type
+/// itself and the unnamed unions, structs and bitfields it contains).
+struct ItemContext<'a> {
+    type_name: String,
+    type_sizes: &'a TypeSizes<'a>,
+    union_count: usize,
+    struct_count: usize,
+    bitfield_count: usize,
+}
+
+pub fn fmt_class(
+    class: &Class,
+    type_sizes: &TypeSizes,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let mut context = ItemContext::new(&class.name, type_sizes);
+    let own_fields_offset = class
+        .fields
+        .iter()
+        .map(|field| field.offset as u64)
+        .min()
+        .unwrap_or(class.size as u64);
+    let mut members = vec![];
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        let base_size = type_sizes
+            .get(base.type_name.as_str())
+            .copied()
+            .unwrap_or(0);
+        members.push(Member {
+            offset: base.offset as u64,
+            size: base_size,
+            declaration: format!("{} __base_{};", flattened_type_name(&base.type_name), i),
+        });
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        let pointer_size = class
+            .vtable_pointer_size
+            .unwrap_or_else(|| std::cmp::min(own_fields_offset, 8));
+        members.push(Member {
+            offset: 0,
+            size: pointer_size,
+            declaration: format!("u{} __vftable;", pointer_size * 8),
+        });
+    }
+    // Note: Virtual base classes are placed at the end of the object, they're
+    // covered by trailing padding
+
+    let item_name = context.type_name.clone();
+    fmt_item(
+        &mut context,
+        ItemKind::Struct,
+        &item_name,
+        members,
+        &struct_members_layout(&class.fields),
+        (0, class.size as u64),
+        f,
+    )
+}
+
+pub fn fmt_union(u: &Union, type_sizes: &TypeSizes, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let mut context = ItemContext::new(&u.name, type_sizes);
+    let item_name = context.type_name.clone();
+    fmt_item(
+        &mut context,
+        ItemKind::Union,
+        &item_name,
+        vec![],
+        &union_members_layout(&u.fields),
+        (0, u.size as u64),
+        f,
+    )
+}
+
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "enum {} : {} {{",
+        flattened_type_name(&e.name),
+        imhex_type(&e.underlying_type).unwrap_or_else(|| "s32".to_string())
+    )?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "    {} = {},",
+            escape_identifier(&value.name.to_string()),
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
+        )?;
+    }
+    writeln!(f, "}};")
+}
+
+impl<'a> ItemContext<'a> {
+    fn new(type_name: &str, type_sizes: &'a TypeSizes<'a>) -> Self {
+        Self {
+            type_name: flattened_type_name(type_name),
+            type_sizes,
+            union_count: 0,
+            struct_count: 0,
+            bitfield_count: 0,
+        }
+    }
+}
+
+/// Write a pattern type containing the given members, located at the given
+/// offset (relative to the type being reconstructed) and of the given size.
+/// The pattern language doesn't support unnamed unions, structs and
+/// bitfields, so separate types are written (before this one) for each of
+/// them.
+/// Members are laid out one after the other, explicit padding is inserted to
+/// reproduce their offsets.
+fn fmt_item(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    item_name: &str,
+    mut members: Vec<Member>,
+    layout: &[MemberLayout],
+    (item_offset, item_size): (u64, u64),
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) if field.bitfield.is_some() => {
+                members.push(bitfield_member(context, &[field], f)?);
+            }
+            MemberLayout::Field(field) => members.push(field_member(context, field)),
+            MemberLayout::Bitfields(bitfields) => {
+                members.push(bitfield_member(context, bitfields, f)?);
+            }
+            MemberLayout::Union(union_layout) => {
+                let union_name = format!("{}__union_{}", context.type_name, context.union_count);
+                let member_name = format!("__union_{}", context.union_count);
+                context.union_count += 1;
+                let (offset, size) = members_layout_extent(union_layout);
+                fmt_item(
+                    context,
+                    ItemKind::Union,
+                    &union_name,
+                    vec![],
+                    union_layout,
+                    (offset, size),
+                    f,
+                )?;
+                writeln!(f)?;
+                members.push(Member {
+                    offset,
+                    size,
+                    declaration: format!("{} {};", union_name, member_name),
+                });
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let struct_name = format!("{}__struct_{}", context.type_name, context.struct_count);
+                let member_name = format!("__struct_{}", context.struct_count);
+                context.struct_count += 1;
+                let (offset, size) = members_layout_extent(struct_layout);
+                fmt_item(
+                    context,
+                    ItemKind::Struct,
+                    &struct_name,
+                    vec![],
+                    struct_layout,
+                    (offset, size),
+                    f,
+                )?;
+                writeln!(f)?;
+                members.push(Member {
+                    offset,
+                    size,
+                    declaration: format!("{} {};", struct_name, member_name),
+                });
+            }
+        }
+    }
+
+    writeln!(f, "{} {} {{", kind, item_name)?;
+    let mut end_offset = item_offset;
+    for member in members {
+        if kind == ItemKind::Struct && member.offset > end_offset {
+            writeln!(f, "    padding[{:#x}];", member.offset - end_offset)?;
+        }
+        writeln!(f, "    {}", member.declaration)?;
+        end_offset = end_offset.max(member.offset + member.size);
+    }
+    // Trailing padding
+    match kind {
+        ItemKind::Struct if end_offset < item_offset + item_size => {
+            writeln!(
+                f,
+                "    padding[{:#x}];",
+                item_offset + item_size - end_offset
+            )?;
+        }
+        ItemKind::Union if end_offset < item_offset + item_size => {
+            writeln!(f, "    padding[{:#x}];", item_size)?;
+        }
+        _ => {}
+    }
+    writeln!(f, "}};")
+}
+
+/// Write a bitfield type for the given bitfields sharing a storage unit, and
+/// return the member of that type.
+fn bitfield_member(
+    context: &mut ItemContext,
+    bitfields: &[&Field],
+    f: &mut impl std::fmt::Write,
+) -> Result<Member, fmt::Error> {
+    let bitfield_name = format!("{}__bitfield_{}", context.type_name, context.bitfield_count);
+    let member_name = format!("__bitfield_{}", context.bitfield_count);
+    context.bitfield_count += 1;
+    let storage_offset = bitfields[0].offset as u64;
+    let storage_size = bitfields.iter().map(|field| field.size).max().unwrap_or(0) as u64;
+
+    // Bits are listed from the least significant one
+    writeln!(f, "bitfield {} {{", bitfield_name)?;
+    let mut bit_position = 0;
+    for field in bitfields {
+        if let Some(bitfield) = field.bitfield {
+            let position = bitfield.position as u64;
+            if position > bit_position {
+                writeln!(f, "    padding : {};", position - bit_position)?;
+            }
+            writeln!(
+                f,
+                "    {} : {};",
+                escape_identifier(&field.name.to_string()),
+                bitfield.length
+            )?;
+            bit_position = bit_position.max(position + bitfield.length as u64);
+        }
+    }
+    if bit_position < storage_size * 8 {
+        writeln!(f, "    padding : {};", storage_size * 8 - bit_position)?;
+    }
+    writeln!(f, "}};")?;
+    writeln!(f)?;
+
+    Ok(Member {
+        offset: storage_offset,
+        size: storage_size,
+        declaration: format!("{} {};", bitfield_name, member_name),
+    })
+}
+
+fn field_member(context: &ItemContext, field: &Field) -> Member {
+    let field_name = escape_identifier(&field.name.to_string());
+    let declaration = match &field.type_description {
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => match imhex_type(element_type) {
+            // Multi-dimensional arrays aren't supported, flatten them
+            Some(element_type) => format!(
+                "{} {}[{}];",
+                element_type,
+                field_name,
+                dimensions.iter().product::<usize>()
+            ),
+            None => opaque_field_declaration(&field_name, field),
+        },
+        type_description => match imhex_type(type_description) {
+            Some(type_name) => format!("{} {};", type_name, field_name),
+            None => opaque_field_declaration(&field_name, field),
+        },
+    };
+    // Embedded types' size is known from their definition
+    let size = match field.type_description.strip_modifiers() {
+        TypeDescription::Class(name) | TypeDescription::Union(name) => context
+            .type_sizes
+            .get(name.as_str())
+            .copied()
+            .unwrap_or(field.size as u64),
+        _ => field.size as u64,
+    };
+
+    Member {
+        offset: field.offset as u64,
+        size,
+        declaration,
+    }
+}
+
+/// Types we cannot represent are replaced with raw bytes, keep track of the
+/// original type
+fn opaque_field_declaration(field_name: &str, field: &Field) -> String {
+    format!(
+        "u8 {}[{}]; // {}{}",
+        field_name, field.size, field.type_left, field.type_right
+    )
+}
+
+fn imhex_type(type_description: &TypeDescription) -> Option<String> {
+    match type_description {
+        TypeDescription::Primitive(kind) => imhex_primitive_type(*kind).map(str::to_string),
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            Some(flattened_type_name(name))
+        }
+        TypeDescription::Enum { name, .. } => Some(flattened_type_name(name)),
+        // Pointers are read as addresses, of the size of the target's
+        // pointers
+        TypeDescription::Pointer { size, .. } => Some(format!("u{}", size * 8)),
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => imhex_type(underlying_type),
+        TypeDescription::Array { .. }
+        | TypeDescription::Function { .. }
+        | TypeDescription::Unknown => None,
+    }
+}
+
+fn imhex_primitive_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar => "char",
+        pdb::PrimitiveKind::I8 => "s8",
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 => "u8",
+        pdb::PrimitiveKind::WChar | pdb::PrimitiveKind::RChar16 => "char16",
+        pdb::PrimitiveKind::RChar32 => "u32",
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "s16",
+        pdb::PrimitiveKind::UShort | pdb::PrimitiveKind::U16 | pdb::PrimitiveKind::Bool16 => "u16",
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => "s32",
+        pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 | pdb::PrimitiveKind::Bool32 => "u32",
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => "s64",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 | pdb::PrimitiveKind::Bool64 => "u64",
+        pdb::PrimitiveKind::Octa | pdb::PrimitiveKind::I128 => "s128",
+        pdb::PrimitiveKind::UOcta | pdb::PrimitiveKind::U128 => "u128",
+        pdb::PrimitiveKind::F32 => "float",
+        pdb::PrimitiveKind::F64 => "double",
+        pdb::PrimitiveKind::Bool8 => "bool",
+        _ => return None,
+    };
+
+    Some(type_name)
+}
+
+fn escape_identifier(identifier: &str) -> String {
+    if IMHEX_KEYWORDS.contains(&identifier) {
+        format!("{}_", identifier)
+    } else {
+        identifier.to_string()
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, members_layout_extent, struct_members_layout,
+    union::Union,
+    union_members_layout, DataFormatConfiguration, Field, MemberLayout, TypeDescription, TypeSizes,
+};
+
+/// Top-level sections of the documents, in output order
+const SECTIONS: &[&str] = &["types", "enums"];
+
+/// Type specification, which can be referenced by the `type` key of an
+/// attribute.
+pub struct TypeSpec {
+    id: String,
+    /// Attributes read sequentially
+    seq: Vec<Attribute>,
+    /// Attributes read at a given position (i.e., members of unions)
+    instances: Vec<Attribute>,
+}
+
+/// Attribute of a type specification.
+struct Attribute {
+    id: String,
+    /// Keys describing the attribute, in output order
+    keys: Vec<(&'static str, String)>,
+}
+
+/// Member of a type specification, made of one or several attributes.
+struct Member {
+    offset: u64,
+    size: u64,
+    attributes: Vec<Attribute>,
+}
+
+/// Kind of type specification.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    Struct,
+    Union,
+}
+
+/// State shared by the type specifications generated for a single type (i.e.,
+/// the type itself and the unnamed unions and structs it contains).
+struct ItemContext<'a> {
+    type_id: String,
+    type_sizes: &'a TypeSizes<'a>,
+    type_specs: Vec<TypeSpec>,
+    union_count: usize,
+    struct_count: usize,
+    padding_count: usize,
+}
+
+/// Write the document's `meta` section, whose `id` is derived from
+/// `type_name`.
+pub fn fmt_meta(type_name: &str, f: &mut impl std::fmt::Write) -> fmt::Result {
+    writeln!(f, "meta:")?;
+    writeln!(f, "  id: {}", kaitai_identifier(type_name))?;
+    // MSVC targets are little-endian, bitfields are allocated from the least
+    // significant bit
+    writeln!(f, "  endian: le")?;
+    writeln!(f, "  bit-endian: le")
+}
+
+/// Return the type specifications of the given class (i.e., the ones of the
+/// unnamed unions and structs it contains, and then the class').
+pub fn class_type_specs(class: &Class, type_sizes: &TypeSizes) -> Vec<TypeSpec> {
+    let mut context = ItemContext::new(&class.name, type_sizes);
+    let own_fields_offset = class
+        .fields
+        .iter()
+        .map(|field| field.offset as u64)
+        .min()
+        .unwrap_or(class.size as u64);
+    let mut members = vec![];
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        let base_size = type_sizes
+            .get(base.type_name.as_str())
+            .copied()
+            .unwrap_or(0);
+        members.push(Member {
+            offset: base.offset as u64,
+            size: base_size,
+            attributes: vec![Attribute {
+                id: format!("base_{}", i),
+                keys: vec![
+                    ("type", kaitai_identifier(&base.type_name)),
+                    ("size", base_size.to_string()),
+                ],
+            }],
+        });
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        let pointer_size = class
+            .vtable_pointer_size
+            .unwrap_or_else(|| std::cmp::min(own_fields_offset, 8));
+        members.push(Member {
+            offset: 0,
+            size: pointer_size,
+            attributes: vec![Attribute {
+                id: "vftable".to_string(),
+                keys: vec![("type", format!("u{}", pointer_size))],
+            }],
+        });
+    }
+    // Note: Virtual base classes are placed at the end of the object, they're
+    // covered by trailing padding
+
+    let type_id = context.type_id.clone();
+    add_item(
+        &mut context,
+        ItemKind::Struct,
+        type_id,
+        members,
+        &struct_members_layout(&class.fields),
+        (0, class.size as u64),
+    );
+    context.type_specs
+}
+
+/// Return the type specifications of the given union (i.e., the ones of the
+/// unnamed structs it contains, and then the union's).
+pub fn union_type_specs(u: &Union, type_sizes: &TypeSizes) -> Vec<TypeSpec> {
+    let mut context = ItemContext::new(&u.name, type_sizes);
+    let type_id = context.type_id.clone();
+    add_item(
+        &mut context,
+        ItemKind::Union,
+        type_id,
+        vec![],
+        &union_members_layout(&u.fields),
+        (0, u.size as u64),
+    );
+    context.type_specs
+}
+
+/// Write the entry of the `types` section describing `type_spec`.
+pub fn fmt_type_spec(type_spec: &TypeSpec, f: &mut impl std::fmt::Write) -> fmt::Result {
+    writeln!(f, "  {}:", type_spec.id)?;
+    if !type_spec.seq.is_empty() {
+        writeln!(f, "    seq:")?;
+        for attribute in &type_spec.seq {
+            writeln!(f, "      - id: {}", attribute.id)?;
+            for (key, value) in &attribute.keys {
+                writeln!(f, "        {}: {}", key, value)?;
+            }
+        }
+    }
+    if !type_spec.instances.is_empty() {
+        writeln!(f, "    instances:")?;
+        for attribute in &type_spec.instances {
+            writeln!(f, "      {}:", attribute.id)?;
+            for (key, value) in &attribute.keys {
+                writeln!(f, "        {}: {}", key, value)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the entry of the `enums` section describing `e`.
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "  {}:", kaitai_identifier(&e.name))?;
+    // Keys must be unique, aliases are dropped
+    let mut values = BTreeSet::new();
+    for value in &e.values {
+        let fmt_value = fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex);
+        let value_id = kaitai_identifier(&value.name.to_string());
+        if values.insert(fmt_value.clone()) {
+            writeln!(f, "    {}: {}", fmt_value, value_id)?;
+        } else {
+            writeln!(f, "    # {}: {}", fmt_value, value_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Merge the given documents into a single one. The preamble (i.e., comments
+/// and `meta` section) of the first document is kept, and the entries of the
+/// other sections are merged, removing duplicates.
+pub fn merge_kaitai_documents<'d>(documents: impl IntoIterator<Item = &'d str>) -> String {
+    let mut preamble = None;
+    let mut sections: Vec<Vec<(String, String)>> = vec![vec![]; SECTIONS.len()];
+    for document in documents {
+        let mut document_preamble = String::new();
+        let mut current_section = None;
+        for line in document.lines() {
+            if let Some(section) = SECTIONS
+                .iter()
+                .position(|section| line.strip_suffix(':') == Some(*section))
+            {
+                current_section = Some(section);
+                continue;
+            }
+            match current_section {
+                None => {
+                    document_preamble.push_str(line);
+                    document_preamble.push('\n');
+                }
+                Some(section) => {
+                    let entries = &mut sections[section];
+                    let is_entry_start = line.starts_with("  ") && !line.starts_with("   ");
+                    match entries.last_mut() {
+                        Some((_, entry)) if !is_entry_start => {
+                            entry.push_str(line);
+                            entry.push('\n');
+                        }
+                        _ => entries.push((line.trim().to_string(), format!("{}\n", line))),
+                    }
+                }
+            }
+        }
+        preamble.get_or_insert(document_preamble);
+    }
+
+    let mut merged_document = preamble.unwrap_or_default();
+    for (section, entries) in SECTIONS.iter().zip(sections) {
+        if entries.is_empty() {
+            continue;
+        }
+        merged_document.push_str(&format!("{}:\n", section));
+        let mut entry_ids = BTreeSet::new();
+        for (entry_id, entry) in entries {
+            if entry_ids.insert(entry_id) {
+                merged_document.push_str(&entry);
+            }
+        }
+    }
+
+    merged_document
+}
+
+impl<'a> ItemContext<'a> {
+    fn new(type_name: &str, type_sizes: &'a TypeSizes<'a>) -> Self {
+        Self {
+            type_id: kaitai_identifier(type_name),
+            type_sizes,
+            type_specs: vec![],
+            union_count: 0,
+            struct_count: 0,
+            padding_count: 0,
+        }
+    }
+
+    fn padding_id(&mut self) -> String {
+        let padding_id = format!("padding_{}", self.padding_count);
+        self.padding_count += 1;
+        padding_id
+    }
+
+    fn padding_attribute(&mut self, size: u64) -> Attribute {
+        Attribute {
+            id: self.padding_id(),
+            keys: vec![("size", size.to_string())],
+        }
+    }
+}
+
+/// Add the type specification `type_id`, made of the given members, located
+/// at the given offset (relative to the type being reconstructed) and of the
+/// given size. Unnamed unions and structs are described by separate type
+/// specifications.
+/// Members of structs are read one after the other, explicit padding is
+/// inserted to reproduce their offsets. Members of unions are read at their
+/// position, relative to the beginning of the union.
+fn add_item(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    type_id: String,
+    mut members: Vec<Member>,
+    layout: &[MemberLayout],
+    (item_offset, item_size): (u64, u64),
+) {
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) if field.bitfield.is_some() => {
+                members.push(bitfield_member(context, &[field]));
+            }
+            MemberLayout::Field(field) => members.push(field_member(context, field)),
+            MemberLayout::Bitfields(bitfields) => {
+                members.push(bitfield_member(context, bitfields));
+            }
+            MemberLayout::Union(union_layout) => {
+                let member_id = format!("union_{}", context.union_count);
+                context.union_count += 1;
+                members.push(nested_item_member(
+                    context,
+                    ItemKind::Union,
+                    member_id,
+                    union_layout,
+                ));
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let member_id = format!("struct_{}", context.struct_count);
+                context.struct_count += 1;
+                members.push(nested_item_member(
+                    context,
+                    ItemKind::Struct,
+                    member_id,
+                    struct_layout,
+                ));
+            }
+        }
+    }
+
+    let mut type_spec = TypeSpec {
+        id: type_id,
+        seq: vec![],
+        instances: vec![],
+    };
+    let mut end_offset = item_offset;
+    for member in members {
+        match kind {
+            ItemKind::Struct => {
+                if member.offset > end_offset {
+                    let padding = context.padding_attribute(member.offset - end_offset);
+                    type_spec.seq.push(padding);
+                }
+                type_spec.seq.extend(member.attributes);
+            }
+            ItemKind::Union => {
+                for mut attribute in member.attributes {
+                    attribute
+                        .keys
+                        .insert(0, ("pos", (member.offset - item_offset).to_string()));
+                    type_spec.instances.push(attribute);
+                }
+            }
+        }
+        end_offset = end_offset.max(member.offset + member.size);
+    }
+    // Trailing padding (unions are read from a stream of their size, which
+    // covers it)
+    if kind == ItemKind::Struct && end_offset < item_offset + item_size {
+        let padding = context.padding_attribute(item_offset + item_size - end_offset);
+        type_spec.seq.push(padding);
+    }
+
+    // Note: Specifications are added after the ones of the types they contain
+    context.type_specs.push(type_spec);
+}
+
+/// Return the member reading an unnamed union or struct, described by its own
+/// type specification.
+fn nested_item_member(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    member_id: String,
+    layout: &[MemberLayout],
+) -> Member {
+    let type_id = format!("{}_{}", context.type_id, member_id);
+    let (offset, size) = members_layout_extent(layout);
+    add_item(
+        context,
+        kind,
+        type_id.clone(),
+        vec![],
+        layout,
+        (offset, size),
+    );
+
+    Member {
+        offset,
+        size,
+        attributes: vec![Attribute {
+            id: member_id,
+            keys: vec![("type", type_id), ("size", size.to_string())],
+        }],
+    }
+}
+
+/// Return the member reading the given bitfields sharing a storage unit.
+/// Unused bits are read explicitly so that the whole storage unit is consumed.
+fn bitfield_member(context: &mut ItemContext, bitfields: &[&Field]) -> Member {
+    let storage_offset = bitfields[0].offset as u64;
+    let storage_size = bitfields.iter().map(|field| field.size).max().unwrap_or(0) as u64;
+
+    let mut attributes = vec![];
+    let mut bit_position = 0;
+    for field in bitfields {
+        if let Some(bitfield) = field.bitfield {
+            let position = bitfield.position as u64;
+            if position > bit_position {
+                attributes.push(Attribute {
+                    id: context.padding_id(),
+                    keys: vec![("type", format!("b{}", position - bit_position))],
+                });
+            }
+            attributes.push(Attribute {
+                id: kaitai_identifier(&field.name.to_string()),
+                keys: vec![("type", format!("b{}", bitfield.length))],
+            });
+            bit_position = bit_position.max(position + bitfield.length as u64);
+        }
+    }
+    if bit_position < storage_size * 8 {
+        attributes.push(Attribute {
+            id: context.padding_id(),
+            keys: vec![("type", format!("b{}", storage_size * 8 - bit_position))],
+        });
+    }
+
+    Member {
+        offset: storage_offset,
+        size: storage_size,
+        attributes,
+    }
+}
+
+fn field_member(context: &ItemContext, field: &Field) -> Member {
+    let mut attribute = Attribute {
+        id: kaitai_identifier(&field.name.to_string()),
+        keys: vec![],
+    };
+    let size = match &field.type_description {
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => {
+            let element_count = dimensions.iter().product::<usize>() as u64;
+            match type_keys(context, element_type, field) {
+                // Multi-dimensional arrays are flattened
+                Some((keys, element_size)) if element_count > 0 => {
+                    attribute.keys = keys;
+                    attribute.keys.push(("repeat", "expr".to_string()));
+                    attribute
+                        .keys
+                        .push(("repeat-expr", element_count.to_string()));
+                    element_size * element_count
+                }
+                _ => opaque_field(&mut attribute, field),
+            }
+        }
+        type_description => match type_keys(context, type_description, field) {
+            Some((keys, size)) => {
+                attribute.keys = keys;
+                size
+            }
+            None => opaque_field(&mut attribute, field),
+        },
+    };
+
+    Member {
+        offset: field.offset as u64,
+        size,
+        attributes: vec![attribute],
+    }
+}
+
+/// Types we cannot represent are read as raw bytes, keep track of the
+/// original type
+fn opaque_field(attribute: &mut Attribute, field: &Field) -> u64 {
+    attribute.keys = vec![
+        ("size", field.size.to_string()),
+        (
+            "doc",
+            yaml_string(&format!("{}{}", field.type_left, field.type_right)),
+        ),
+    ];
+    field.size as u64
+}
+
+/// Return the keys describing a value of the given type, and its size.
+fn type_keys(
+    context: &ItemContext,
+    type_description: &TypeDescription,
+    field: &Field,
+) -> Option<(Vec<(&'static str, String)>, u64)> {
+    match type_description {
+        TypeDescription::Primitive(kind) => {
+            let (type_name, size) = kaitai_primitive_type(*kind)?;
+            Some((vec![("type", type_name.to_string())], size))
+        }
+        // Embedded types are read from a stream of their size, so that
+        // unions (and trailing padding) are consumed
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            let size = *context.type_sizes.get(name.as_str())?;
+            Some((
+                vec![
+                    ("type", kaitai_identifier(name)),
+                    ("size", size.to_string()),
+                ],
+                size,
+            ))
+        }
+        TypeDescription::Enum {
+            name,
+            underlying_type,
+        } => {
+            let (mut keys, size) = type_keys(context, underlying_type, field)?;
+            keys.push(("enum", kaitai_identifier(name)));
+            Some((keys, size))
+        }
+        // Pointers are read as addresses, of the size of the target's
+        // pointers
+        TypeDescription::Pointer { size, .. } => Some((
+            vec![
+                ("type", format!("u{}", size)),
+                (
+                    "doc",
+                    yaml_string(&format!("{}{}", field.type_left, field.type_right)),
+                ),
+            ],
+            *size as u64,
+        )),
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => type_keys(context, underlying_type, field),
+        TypeDescription::Array { .. }
+        | TypeDescription::Function { .. }
+        | TypeDescription::Unknown => None,
+    }
+}
+
+fn kaitai_primitive_type(kind: pdb::PrimitiveKind) -> Option<(&'static str, u64)> {
+    let primitive_type = match kind {
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => ("s1", 1),
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 | pdb::PrimitiveKind::Bool8 => ("u1", 1),
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => ("s2", 2),
+        pdb::PrimitiveKind::UShort
+        | pdb::PrimitiveKind::U16
+        | pdb::PrimitiveKind::WChar
+        | pdb::PrimitiveKind::RChar16
+        | pdb::PrimitiveKind::Bool16 => ("u2", 2),
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => {
+            ("s4", 4)
+        }
+        pdb::PrimitiveKind::ULong
+        | pdb::PrimitiveKind::U32
+        | pdb::PrimitiveKind::RChar32
+        | pdb::PrimitiveKind::Bool32 => ("u4", 4),
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => ("s8", 8),
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 | pdb::PrimitiveKind::Bool64 => {
+            ("u8", 8)
+        }
+        pdb::PrimitiveKind::F32 => ("f4", 4),
+        pdb::PrimitiveKind::F64 => ("f8", 8),
+        _ => return None,
+    };
+
+    Some(primitive_type)
+}
+
+/// Return the Kaitai identifier (i.e., lower snake case name) corresponding
+/// to the given C++ name (e.g., `ns_struct_test` for `ns::StructTest`).
+fn kaitai_identifier(name: &str) -> String {
+    let chars = flattened_type_name(name).chars().collect::<Vec<_>>();
+    let mut identifier = String::with_capacity(chars.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+            // Word boundary (e.g., `StructTest` or `HTTPRequest`)
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lowercase)
+            {
+                identifier.push('_');
+            }
+        }
+        if c == '_' && identifier.ends_with('_') {
+            continue;
+        }
+        identifier.push(c.to_ascii_lowercase());
+    }
+    let identifier = identifier.trim_matches('_');
+
+    if identifier.starts_with(|c: char| c.is_ascii_lowercase()) {
+        identifier.to_string()
+    } else {
+        format!("t_{}", identifier)
+    }
+}
+
+/// Return the given string as a single-quoted YAML scalar.
+fn yaml_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
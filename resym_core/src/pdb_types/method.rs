@@ -1,3 +1,5 @@
+use std::fmt;
+
 use anyhow::{anyhow, Result};
 
 use super::{
@@ -17,6 +19,10 @@ pub struct Method<'p> {
     pub is_const: bool,
     pub is_volatile: bool,
     pub access: FieldAccess,
+    /// Offset of the method's entry in the virtual function table, for
+    /// virtual methods introducing a new entry
+    pub vtable_offset: Option<u32>,
+    pub calling_convention: Option<&'static str>,
 }
 
 impl<'p> Method<'p> {
@@ -69,6 +75,8 @@ impl<'p> Method<'p> {
                     }
                 },
                 access: FieldAccess::from_field_attribute(attributes.access()),
+                vtable_offset: None,
+                calling_convention: calling_convention_name(data.attributes.calling_convention()),
             }),
 
             other => {
@@ -78,6 +86,25 @@ impl<'p> Method<'p> {
         }
     }
 
+    /// Write a comment containing the method's calling convention and offset
+    /// in the virtual function table, when known.
+    pub fn fmt_annotations(&self, f: &mut impl std::fmt::Write) -> fmt::Result {
+        let annotations = self
+            .calling_convention
+            .map(str::to_string)
+            .into_iter()
+            .chain(
+                self.vtable_offset
+                    .map(|vtable_offset| format!("vtable+{:#x}", vtable_offset)),
+            )
+            .collect::<Vec<_>>();
+        if annotations.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, " /* {} */", annotations.join(", "))
+    }
+
     pub fn find_func_modifier(
         member_func_type: &pdb::MemberFunctionType,
         type_finder: &pdb::TypeFinder<'p>,
@@ -104,3 +131,17 @@ impl<'p> Method<'p> {
         }
     }
 }
+
+/// Return the name of the given CodeView calling convention (`CV_call_e`).
+fn calling_convention_name(calling_convention: u8) -> Option<&'static str> {
+    match calling_convention {
+        0x00 | 0x01 => Some("__cdecl"),
+        0x02 | 0x03 => Some("__pascal"),
+        0x04 | 0x05 => Some("__fastcall"),
+        0x07 | 0x08 => Some("__stdcall"),
+        0x0b => Some("__thiscall"),
+        0x16 => Some("__clrcall"),
+        0x18 => Some("__vectorcall"),
+        _ => None,
+    }
+}
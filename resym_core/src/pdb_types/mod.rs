@@ -1,31 +1,138 @@
+mod binary_template;
+mod c;
 mod class;
+mod com;
+mod csharp;
+mod ctypes;
+mod dependency_graph;
 mod enumeration;
 mod field;
+mod go;
+mod imhex;
+mod kaitai;
 mod method;
+mod model;
+mod name_sanitization;
+mod namespaces;
+mod output_format;
+mod packing;
 mod primitive_types;
+mod rust;
+mod template_aliases;
+mod type_description;
 mod union;
+mod windbg;
 
-use std::collections::{BTreeMap, BTreeSet};
-use std::fmt;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Write as _};
 use std::ops::Range;
+use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
+use dashmap::DashMap;
 
 use class::Class;
+use dependency_graph::{sort_definitions, TypeDependencies};
 use enumeration::Enum;
-use field::{Field, FieldAccess};
+use field::{Field, FieldAccess, FieldBitfield};
 use method::Method;
+use name_sanitization::is_identifier;
+use namespaces::{fmt_in_namespaces, split_namespaces, NamespaceBlocks, ANONYMOUS_NAMESPACE_NAME};
+use packing::{infer_packing, Packing};
 use primitive_types::primitive_kind_as_str;
+use type_description::{type_description, udt_name};
 use union::Union;
 
-pub use primitive_types::{include_headers_for_flavor, PrimitiveReconstructionFlavor};
+pub use com::{
+    fmt_com_definitions, ComInterface, ComMethod, GuidConstant, IUNKNOWN_INTERFACE_NAME,
+};
+pub use kaitai::merge_kaitai_documents;
+pub use model::{
+    ReconstructedEnumValue, ReconstructedEnumValueKind, ReconstructedField, ReconstructedType,
+    ReconstructedTypeKind,
+};
+pub use name_sanitization::sanitize_type_names;
+pub use output_format::{OffsetRadix, OutputFormat};
+pub use primitive_types::{
+    c_include_headers_for_flavor, include_headers_for_flavor, PrimitiveReconstructionFlavor,
+};
+pub use template_aliases::pretty_template_names;
+pub use type_description::TypeDescription;
 
 /// Set of `TypeIndex` objets
 pub type TypeSet = BTreeSet<pdb::TypeIndex>;
 
-pub type TypeForwarder = dashmap::DashMap<pdb::TypeIndex, pdb::TypeIndex>;
+/// Typedef, as described by an `LF_ALIAS` type record (emitted by clang-cl,
+/// MSVC resolves typedefs instead)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAlias {
+    pub name: String,
+    /// Index of the aliased type, which may be another typedef
+    pub underlying_type: pdb::TypeIndex,
+}
+
+/// Resolves the type indices referenced by type records: forward references
+/// to their complete type, and typedefs to the type they alias. Clones share
+/// the same tables.
+#[derive(Debug, Clone, Default)]
+pub struct TypeForwarder {
+    /// Indices of the complete types, by index of their forward references
+    complete_types: Arc<DashMap<pdb::TypeIndex, pdb::TypeIndex>>,
+    /// Typedefs, by type index
+    type_aliases: Arc<HashMap<pdb::TypeIndex, TypeAlias>>,
+    /// Name types after what their typedefs resolve to, instead of after the
+    /// typedefs
+    resolve_type_aliases: bool,
+}
+
+impl TypeForwarder {
+    pub fn new(
+        complete_types: DashMap<pdb::TypeIndex, pdb::TypeIndex>,
+        type_aliases: HashMap<pdb::TypeIndex, TypeAlias>,
+    ) -> Self {
+        Self {
+            complete_types: Arc::new(complete_types),
+            type_aliases: Arc::new(type_aliases),
+            resolve_type_aliases: false,
+        }
+    }
+
+    /// Return a forwarder sharing this one's tables, which names types after
+    /// what their typedefs resolve to if `resolve_type_aliases` is true.
+    pub fn resolving_type_aliases(&self, resolve_type_aliases: bool) -> Self {
+        Self {
+            resolve_type_aliases,
+            ..self.clone()
+        }
+    }
+
+    /// Return the typedef `type_index` refers to, if any.
+    pub fn type_alias(&self, type_index: pdb::TypeIndex) -> Option<&TypeAlias> {
+        self.type_aliases.get(&type_index)
+    }
+
+    /// Return the index of the type the typedef `type_index` ultimately
+    /// aliases (`type_index` itself if it isn't a typedef). Typedefs that
+    /// are part of a cycle are left unresolved.
+    fn resolve_type_alias(&self, type_index: pdb::TypeIndex) -> pdb::TypeIndex {
+        let mut visited_type_indices = vec![];
+        let mut resolved_type_index = type_index;
+        while let Some(type_alias) = self.type_aliases.get(&resolved_type_index) {
+            if visited_type_indices.contains(&resolved_type_index) {
+                return type_index;
+            }
+            visited_type_indices.push(resolved_type_index);
+            resolved_type_index = type_alias.underlying_type;
+        }
+
+        resolved_type_index
+    }
+}
 
 /// Return a pair of strings representing the given `type_index`.
+/// Typedefs are represented by their name, unless `type_forwarder` resolves
+/// them.
 pub fn type_name<'p>(
     type_finder: &pdb::TypeFinder<'p>,
     type_forwarder: &TypeForwarder,
@@ -33,6 +140,27 @@ pub fn type_name<'p>(
     primitive_flavor: &PrimitiveReconstructionFlavor,
     needed_types: &mut TypeSet,
 ) -> Result<(String, String)> {
+    // Resolve the complete type's index, if present in the PDB (callers pass
+    // the indices they reference, so that typedefs can be told apart)
+    let complete_type_index = resolve_complete_type_index(type_forwarder, type_index);
+    if let Some(type_alias) = type_forwarder.type_alias(type_index) {
+        if type_forwarder.type_alias(complete_type_index).is_some() {
+            return Err(anyhow!("typedef '{}' is part of a cycle", type_alias.name));
+        }
+        if !type_forwarder.resolve_type_aliases {
+            // Note: The aliased type is still needed by its users
+            type_name(
+                type_finder,
+                type_forwarder,
+                complete_type_index,
+                primitive_flavor,
+                needed_types,
+            )?;
+            return Ok((type_alias.name.clone(), String::default()));
+        }
+    }
+    let type_index = complete_type_index;
+
     let (type_left, type_right) = match type_finder.find(type_index)?.parse()? {
         pdb::TypeData::Primitive(data) => {
             let name =
@@ -71,20 +199,34 @@ pub fn type_name<'p>(
         }
 
         pdb::TypeData::Pointer(data) => {
-            // Resolve the complete type's index, if present in the PDB
-            let complete_underlying_type_index =
-                resolve_complete_type_index(type_forwarder, data.underlying_type);
             let (type_left, type_right) = type_name(
                 type_finder,
                 type_forwarder,
-                complete_underlying_type_index,
+                data.underlying_type,
                 primitive_flavor,
                 needed_types,
             )?;
-            if data.attributes.is_reference() {
-                (format!("{}&", type_left), type_right)
+            let declarator = match data.attributes.pointer_mode() {
+                pdb::PointerMode::LValueReference => "&",
+                pdb::PointerMode::RValueReference => "&&",
+                _ => "*",
+            };
+            // Note: Qualifiers of the pointer itself (e.g., `T* const`) are
+            // usually stored in its attributes rather than in a modifier
+            let declarator =
+                match cv_qualifiers(data.attributes.is_const(), data.attributes.is_volatile()) {
+                    "" => declarator.to_string(),
+                    qualifiers => format!("{} {}", declarator, qualifiers),
+                };
+            if type_right.starts_with('[') {
+                // Pointers to arrays must be parenthesized, to tell them
+                // apart from arrays of pointers
+                (
+                    format!("{} ({}", type_left, declarator),
+                    format!("){}", type_right),
+                )
             } else {
-                (format!("{}*", type_left), type_right)
+                (format!("{}{}", type_left, declarator), type_right)
             }
         }
 
@@ -95,18 +237,28 @@ pub fn type_name<'p>(
             let (type_left, type_right) = type_name(
                 type_finder,
                 type_forwarder,
-                complete_underlying_type_index,
+                data.underlying_type,
                 primitive_flavor,
                 needed_types,
             )?;
 
-            if data.constant {
-                (format!("const {}", type_left), type_right)
-            } else if data.volatile {
-                (format!("volatile {}", type_left), type_right)
-            } else {
-                // ?
+            let qualifiers = cv_qualifiers(data.constant, data.volatile);
+            // Note: The left part of pointers doesn't necessarily end with
+            // their declarator (e.g., `T* volatile`), the underlying record
+            // tells whether a pointer is being qualified
+            let is_pointer = match type_finder.find(complete_underlying_type_index)?.parse()? {
+                pdb::TypeData::Pointer(_) => true,
+                pdb::TypeData::Primitive(data) => data.indirection.is_some(),
+                _ => false,
+            };
+            if qualifiers.is_empty() {
                 (type_left, type_right)
+            } else if is_pointer {
+                // Qualifiers of pointers follow the declarator (inside of the
+                // parentheses of pointers to arrays and functions)
+                (format!("{} {}", type_left, qualifiers), type_right)
+            } else {
+                (format!("{} {}", qualifiers, type_left), type_right)
             }
         }
 
@@ -114,53 +266,56 @@ pub fn type_name<'p>(
             // Resolve the complete type's index, if present in the PDB
             let complete_element_type_index =
                 resolve_complete_type_index(type_forwarder, data.element_type);
-            let (base_name, mut dimensions) = array_base_name(
+            // Note: Arrays of arrays are nested, the element type's right
+            // part contains the inner dimensions (or the argument list of
+            // function pointers)
+            let (element_type_left, element_type_right) = type_name(
                 type_finder,
                 type_forwarder,
-                complete_element_type_index,
+                data.element_type,
                 primitive_flavor,
                 needed_types,
             )?;
-            let type_size = u32::try_from(type_size(type_finder, complete_element_type_index)?)?;
+            let type_size = u32::try_from(type_size(
+                type_finder,
+                type_forwarder,
+                complete_element_type_index,
+            )?)?;
             let mut divider = if type_size == 0 {
                 log::warn!(
                     "'{}' has invalid size (0), array dimensions might be incorrect",
-                    base_name
+                    element_type_left
                 );
                 1
             } else {
                 type_size
             };
 
-            let mut dimensions_elem_count = data
+            // Note: Dimensions are expressed in bytes, from the innermost to
+            // the outermost one
+            let dimensions_elem_count = data
                 .dimensions
                 .into_iter()
                 .map(|dim_size| {
                     let result = dim_size / divider;
-                    divider = dim_size;
+                    divider = std::cmp::max(dim_size, 1);
                     result as usize
                 })
                 .collect::<Vec<_>>();
-            dimensions.append(&mut dimensions_elem_count);
-
-            let mut dimensions_str = String::default();
-            // Note: Dimensions are collected in reverse order so we have to use
-            // a reverse iterator
-            for dim in dimensions.iter().rev() {
-                dimensions_str = format!("{}[{}]", dimensions_str, dim);
-            }
+            let dimensions_str = dimensions_elem_count
+                .iter()
+                .rev()
+                .map(|dim| format!("[{}]", dim))
+                .collect::<String>();
 
-            (base_name, dimensions_str)
+            (element_type_left, dimensions_str + &element_type_right)
         }
 
         pdb::TypeData::Bitfield(data) => {
-            // Resolve the complete type's index, if present in the PDB
-            let complete_underlying_type_index =
-                resolve_complete_type_index(type_forwarder, data.underlying_type);
             let (type_left, type_right) = type_name(
                 type_finder,
                 type_forwarder,
-                complete_underlying_type_index,
+                data.underlying_type,
                 primitive_flavor,
                 needed_types,
             )?;
@@ -170,13 +325,10 @@ pub fn type_name<'p>(
         pdb::TypeData::Procedure(data) => {
             // TODO: Parse and display attributes
             let (ret_type_left, ret_type_right) = if let Some(return_type) = data.return_type {
-                // Resolve the complete type's index, if present in the PDB
-                let complete_return_type_index =
-                    resolve_complete_type_index(type_forwarder, return_type);
                 type_name(
                     type_finder,
                     type_forwarder,
-                    complete_return_type_index,
+                    return_type,
                     primitive_flavor,
                     needed_types,
                 )?
@@ -198,23 +350,18 @@ pub fn type_name<'p>(
         }
 
         pdb::TypeData::MemberFunction(data) => {
-            // Resolve the complete type's index, if present in the PDB
-            let complete_return_type_index =
-                resolve_complete_type_index(type_forwarder, data.return_type);
-            let complete_class_type_index =
-                resolve_complete_type_index(type_forwarder, data.class_type);
             // // TODO: Parse and display attributes
             let (ret_type_left, ret_type_right) = type_name(
                 type_finder,
                 type_forwarder,
-                complete_return_type_index,
+                data.return_type,
                 primitive_flavor,
                 needed_types,
             )?;
             let (class_type_left, _) = type_name(
                 type_finder,
                 type_forwarder,
-                complete_class_type_index,
+                data.class_type,
                 primitive_flavor,
                 needed_types,
             )?;
@@ -247,60 +394,14 @@ pub fn type_name<'p>(
     Ok((type_left, type_right))
 }
 
-fn array_base_name<'p>(
-    type_finder: &pdb::TypeFinder<'p>,
-    type_forwarder: &TypeForwarder,
-    type_index: pdb::TypeIndex,
-    primitive_flavor: &PrimitiveReconstructionFlavor,
-    needed_types: &mut TypeSet,
-) -> Result<(String, Vec<usize>)> {
-    match type_finder.find(type_index)?.parse()? {
-        pdb::TypeData::Array(data) => {
-            // Resolve the complete type's index, if present in the PDB
-            let complete_element_type_index =
-                resolve_complete_type_index(type_forwarder, data.element_type);
-            let (base_name, mut base_dimensions) = array_base_name(
-                type_finder,
-                type_forwarder,
-                complete_element_type_index,
-                primitive_flavor,
-                needed_types,
-            )?;
-            let type_size = u32::try_from(type_size(type_finder, complete_element_type_index)?)?;
-            let mut divider = if type_size == 0 {
-                log::warn!(
-                    "'{}' has invalid size (0), array dimensions might be incorrect",
-                    base_name
-                );
-                1
-            } else {
-                type_size
-            };
-
-            let mut dimensions_elem_count = data
-                .dimensions
-                .into_iter()
-                .map(|dim_size| {
-                    let result = dim_size / divider;
-                    divider = dim_size;
-                    result as usize
-                })
-                .collect::<Vec<_>>();
-            base_dimensions.append(&mut dimensions_elem_count);
-
-            Ok((base_name, base_dimensions))
-        }
-        _ => Ok((
-            type_name(
-                type_finder,
-                type_forwarder,
-                type_index,
-                primitive_flavor,
-                needed_types,
-            )?
-            .0,
-            vec![],
-        )),
+/// Return the C++ spelling of the given cv-qualifiers (empty if there are
+/// none).
+fn cv_qualifiers(constant: bool, volatile: bool) -> &'static str {
+    match (constant, volatile) {
+        (true, true) => "const volatile",
+        (true, false) => "const",
+        (false, true) => "volatile",
+        (false, false) => "",
     }
 }
 
@@ -333,7 +434,12 @@ pub fn argument_list<'p>(
 }
 
 /// Return the type's size in bytes.
-pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> Result<usize> {
+pub fn type_size(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<usize> {
+    let type_index = type_forwarder.resolve_type_alias(type_index);
     let size = match type_finder.find(type_index)?.parse()? {
         pdb::TypeData::Primitive(data) => {
             let mut size = match data.kind {
@@ -358,7 +464,8 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
                 | pdb::PrimitiveKind::U32
                 | pdb::PrimitiveKind::ULong
                 | pdb::PrimitiveKind::F32
-                | pdb::PrimitiveKind::Bool32 => 4,
+                | pdb::PrimitiveKind::Bool32
+                | pdb::PrimitiveKind::HRESULT => 4,
 
                 pdb::PrimitiveKind::I64
                 | pdb::PrimitiveKind::Quad
@@ -386,7 +493,9 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
 
         pdb::TypeData::Class(data) => data.size as usize,
 
-        pdb::TypeData::Enumeration(data) => type_size(type_finder, data.underlying_type)?,
+        pdb::TypeData::Enumeration(data) => {
+            type_size(type_finder, type_forwarder, data.underlying_type)?
+        }
 
         pdb::TypeData::Union(data) => data.size as usize,
 
@@ -406,16 +515,36 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
             pdb::PointerKind::Ptr64 => 8,
         },
 
-        pdb::TypeData::Modifier(data) => type_size(type_finder, data.underlying_type)?,
+        pdb::TypeData::Modifier(data) => {
+            type_size(type_finder, type_forwarder, data.underlying_type)?
+        }
 
         pdb::TypeData::Array(data) => *data.dimensions.iter().last().unwrap_or(&0) as usize,
 
+        pdb::TypeData::Bitfield(data) => {
+            type_size(type_finder, type_forwarder, data.underlying_type)?
+        }
+
         _ => 0,
     };
 
     Ok(size)
 }
 
+/// Return the bitfield information of the given type, if it is a bitfield.
+pub fn bitfield_info(
+    type_finder: &pdb::TypeFinder,
+    type_index: pdb::TypeIndex,
+) -> Result<Option<FieldBitfield>> {
+    match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Bitfield(data) => Ok(Some(FieldBitfield {
+            position: data.position,
+            length: data.length,
+        })),
+        _ => Ok(None),
+    }
+}
+
 /// Indicate if the given `type_name` is the name of an anonymous type.
 pub fn is_unnamed_type(type_name: &str) -> bool {
     type_name.contains("<anonymous-")
@@ -423,6 +552,18 @@ pub fn is_unnamed_type(type_name: &str) -> bool {
         || type_name.contains("__unnamed")
 }
 
+/// Return an identifier usable in languages that don't support namespaces
+/// nor templates, derived from the given C++ `type_name`.
+/// Scope separators are replaced with underscores, as are characters that
+/// aren't valid in identifiers.
+pub fn flattened_type_name(type_name: &str) -> String {
+    type_name
+        .replace("::", "_")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
 /// Struct that represent a set of reconstructed types (forward declarations,
 /// classes/structs, enums and unions)
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -431,6 +572,28 @@ pub struct Data<'p> {
     classes: Vec<Class<'p>>,
     enums: Vec<Enum<'p>>,
     unions: Vec<Union<'p>>,
+    /// Types referenced by the types defined in this `Data`, by type name
+    dependencies: BTreeMap<String, TypeDependencies>,
+    /// Names of the types declared inside of another type
+    nested_types: BTreeSet<String>,
+}
+
+/// Reference to a type defined in a `Data`
+#[derive(Debug, Clone, Copy)]
+enum Definition<'a, 'p> {
+    Enum(&'a Enum<'p>),
+    Class(&'a Class<'p>),
+    Union(&'a Union<'p>),
+}
+
+impl Definition<'_, '_> {
+    fn name(&self) -> &str {
+        match self {
+            Definition::Enum(e) => &e.name,
+            Definition::Class(class) => &class.name,
+            Definition::Union(u) => &u.name,
+        }
+    }
 }
 
 impl Data<'_> {
@@ -439,34 +602,514 @@ impl Data<'_> {
         fmt_configuration: &DataFormatConfiguration,
         f: &mut impl std::fmt::Write,
     ) -> fmt::Result {
-        // Types without definition
-        if !self.forward_references.is_empty() {
+        if !fmt_configuration.pretty_templates
+            && !fmt_configuration.print_namespaces
+            && !fmt_configuration.sanitize_names
+        {
+            return self.reconstruct_definitions(fmt_configuration, f);
+        }
+
+        let mut reconstruction = String::new();
+        self.reconstruct_definitions(fmt_configuration, &mut reconstruction)?;
+        if fmt_configuration.pretty_templates {
+            reconstruction = pretty_template_names(&reconstruction);
+        }
+        // Types declared in anonymous namespaces are visible from their
+        // enclosing namespace
+        if fmt_configuration.print_namespaces {
+            reconstruction = reconstruction.replace(&format!("{}::", ANONYMOUS_NAMESPACE_NAME), "");
+        }
+        if fmt_configuration.sanitize_names {
+            reconstruction = sanitize_type_names(&reconstruction);
+        }
+
+        f.write_str(&reconstruction)
+    }
+
+    fn reconstruct_definitions(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        let (forward_declarations, definitions) = self.ordered_definitions();
+        // Types are declared with their unqualified name inside of their
+        // namespaces if requested
+        let type_names = self.type_names();
+        let split_name = |type_name| {
+            if fmt_configuration.print_namespaces {
+                split_namespaces(
+                    type_name,
+                    self.nested_types.contains(type_name),
+                    &type_names,
+                )
+            } else {
+                (Vec::new(), type_name)
+            }
+        };
+
+        // Types without definition and types used before being defined
+        if !self.forward_references.is_empty() || !forward_declarations.is_empty() {
+            writeln!(f)?;
+            for e in &self.forward_references {
+                let (namespaces, name) = split_name(&e.name);
+                fmt_in_namespaces(&namespaces, f, |f| {
+                    ForwardReference {
+                        kind: e.kind,
+                        name: name.to_string(),
+                    }
+                    .reconstruct(f)
+                })?;
+            }
+            if !forward_declarations.is_empty() {
+                writeln!(f, "// Declared beforehand to break dependency cycles")?;
+            }
+            for definition in &forward_declarations {
+                let (namespaces, name) = split_name(definition.name());
+                match definition {
+                    Definition::Class(class) => fmt_in_namespaces(&namespaces, f, |f| {
+                        ForwardReference {
+                            kind: class.kind,
+                            name: name.to_string(),
+                        }
+                        .reconstruct(f)
+                    })?,
+                    Definition::Union(_) => {
+                        fmt_in_namespaces(&namespaces, f, |f| writeln!(f, "union {};", name))?
+                    }
+                    Definition::Enum(_) => {}
+                }
+            }
+        }
+
+        // Enum, class/struct and union definitions
+        let mut namespace_blocks = NamespaceBlocks::default();
+        for definition in &definitions {
+            let (namespaces, name) = split_name(definition.name());
+            namespace_blocks.enter(&namespaces, f)?;
+            writeln!(f)?;
+            // Keep track of the original name of sanitized types
+            if fmt_configuration.sanitize_names && !is_identifier(name) {
+                writeln!(f, "// {}", definition.name())?;
+            }
+            match definition {
+                Definition::Enum(e) => with_name(*e, &e.name, name, |e, name| e.name = name)
+                    .reconstruct(fmt_configuration, f)?,
+                Definition::Class(class) => {
+                    let class =
+                        with_name(*class, &class.name, name, |class, name| class.name = name);
+                    fmt_packed_definition(class.packing, fmt_configuration, f, |cfg, f| {
+                        class.reconstruct(cfg, f)
+                    })?;
+                    if fmt_configuration.print_size_asserts {
+                        fmt_size_assert(&class.name, class.size as u64, f)?;
+                    }
+                }
+                Definition::Union(u) => {
+                    let u = with_name(*u, &u.name, name, |u, name| u.name = name);
+                    fmt_packed_definition(u.packing, fmt_configuration, f, |cfg, f| {
+                        u.reconstruct(cfg, f)
+                    })?;
+                    if fmt_configuration.print_size_asserts {
+                        fmt_size_assert(&u.name, u.size as u64, f)?;
+                    }
+                }
+            }
+        }
+        namespace_blocks.close_all(f)?;
+
+        Ok(())
+    }
+
+    /// Indicate whether a type named like the type `type_index` has already
+    /// been defined (e.g., from another record of a type defined several
+    /// times).
+    pub fn defines_type_named_like(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_index: pdb::TypeIndex,
+    ) -> bool {
+        match defined_type_name(type_finder, type_index) {
+            Some(type_name) => {
+                self.classes.iter().any(|class| class.name == type_name)
+                    || self.unions.iter().any(|u| u.name == type_name)
+                    || self.enums.iter().any(|e| e.name == type_name)
+            }
+            None => false,
+        }
+    }
+
+    /// Return the names of the types declared in this `Data`.
+    fn type_names(&self) -> BTreeSet<&str> {
+        self.forward_references
+            .iter()
+            .map(|e| e.name.as_str())
+            .chain(self.classes.iter().map(|class| class.name.as_str()))
+            .chain(self.unions.iter().map(|u| u.name.as_str()))
+            .chain(self.enums.iter().map(|e| e.name.as_str()))
+            .collect()
+    }
+
+    /// Reconstruct the types as plain C declarations. Types are output in the
+    /// same order as with `reconstruct`.
+    pub fn reconstruct_c(
+        &self,
+        primitive_flavor: &PrimitiveReconstructionFlavor,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        let (forward_declarations, definitions) = self.ordered_definitions();
+        if !self.forward_references.is_empty() || !forward_declarations.is_empty() {
             writeln!(f)?;
             for e in &self.forward_references {
-                e.reconstruct(f)?;
+                c::fmt_forward_reference("struct", &e.name, f)?;
+            }
+            if !forward_declarations.is_empty() {
+                writeln!(f, "/* Declared beforehand to break dependency cycles */")?;
+            }
+            for definition in &forward_declarations {
+                match definition {
+                    Definition::Class(class) => c::fmt_forward_reference("struct", &class.name, f)?,
+                    Definition::Union(u) => c::fmt_forward_reference("union", &u.name, f)?,
+                    Definition::Enum(_) => {}
+                }
+            }
+        }
+        for definition in definitions {
+            writeln!(f)?;
+            match definition {
+                Definition::Enum(e) => c::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => {
+                    fmt_packed_definition(class.packing, fmt_configuration, f, |cfg, f| {
+                        c::fmt_class(class, primitive_flavor, cfg, f)
+                    })?;
+                    if fmt_configuration.print_size_asserts {
+                        c::fmt_size_assert("struct", &class.name, class.size as u64, f)?;
+                    }
+                }
+                Definition::Union(u) => {
+                    fmt_packed_definition(u.packing, fmt_configuration, f, |cfg, f| {
+                        c::fmt_union(u, primitive_flavor, cfg, f)
+                    })?;
+                    if fmt_configuration.print_size_asserts {
+                        c::fmt_size_assert("union", &u.name, u.size as u64, f)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct the types as C# structs with an explicit layout. Types are
+    /// output in the same order as with `reconstruct`.
+    pub fn reconstruct_csharp(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        // Note: Declaration order doesn't matter in C#
+        let (_, definitions) = self.ordered_definitions();
+        for definition in definitions {
+            writeln!(f)?;
+            match definition {
+                Definition::Enum(e) => csharp::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => csharp::fmt_class(class, f)?,
+                Definition::Union(u) => csharp::fmt_union(u, f)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct the types as ImHex patterns. Types are output in the same
+    /// order as with `reconstruct`, which lets patterns refer to the types
+    /// defined before them.
+    pub fn reconstruct_imhex(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        // Note: Pointers are represented by addresses, forward declarations
+        // aren't needed
+        let (_, definitions) = self.ordered_definitions();
+        let type_sizes = definition_sizes(&definitions);
+        for definition in definitions {
+            writeln!(f)?;
+            match definition {
+                Definition::Enum(e) => imhex::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => imhex::fmt_class(class, &type_sizes, f)?,
+                Definition::Union(u) => imhex::fmt_union(u, &type_sizes, f)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct the types as 010 Editor binary template structs. Types are
+    /// output in the same order as with `reconstruct`, which lets templates
+    /// refer to the types defined before them.
+    pub fn reconstruct_binary_template(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        // Note: Pointers are represented by addresses, forward declarations
+        // aren't needed
+        let (_, definitions) = self.ordered_definitions();
+        let type_sizes = definition_sizes(&definitions);
+        for definition in definitions {
+            writeln!(f)?;
+            match definition {
+                Definition::Enum(e) => binary_template::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => binary_template::fmt_class(class, &type_sizes, f)?,
+                Definition::Union(u) => binary_template::fmt_union(u, &type_sizes, f)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct the types as a Kaitai Struct specification, whose `types`
+    /// (and `enums`) describe the types defined in this `Data`.
+    pub fn reconstruct_kaitai(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        let (_, definitions) = self.ordered_definitions();
+        let type_sizes = definition_sizes(&definitions);
+        // The document is named after the reconstructed type, which comes
+        // after its dependencies
+        if let Some(root_definition) = definitions.last() {
+            kaitai::fmt_meta(root_definition.name(), f)?;
+        }
+        let type_specs = definitions
+            .iter()
+            .flat_map(|definition| match definition {
+                Definition::Class(class) => kaitai::class_type_specs(class, &type_sizes),
+                Definition::Union(u) => kaitai::union_type_specs(u, &type_sizes),
+                Definition::Enum(_) => vec![],
+            })
+            .collect::<Vec<_>>();
+        if !type_specs.is_empty() {
+            writeln!(f, "types:")?;
+            for type_spec in &type_specs {
+                kaitai::fmt_type_spec(type_spec, f)?;
+            }
+        }
+        if !self.enums.is_empty() {
+            writeln!(f, "enums:")?;
+            for definition in &definitions {
+                if let Definition::Enum(e) = definition {
+                    kaitai::fmt_enum(e, fmt_configuration, f)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct the layout of the types, as displayed by WinDbg's `dt`
+    /// command. Types embedded in others are expanded when they're defined
+    /// in this `Data`. Types are output in the same order as with
+    /// `reconstruct`.
+    pub fn reconstruct_windbg(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        let (_, definitions) = self.ordered_definitions();
+        let embedded_definitions = definitions
+            .iter()
+            .map(|definition| (definition.name(), *definition))
+            .collect();
+        for definition in &definitions {
+            writeln!(f)?;
+            match definition {
+                Definition::Enum(e) => windbg::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => windbg::fmt_class(class, &embedded_definitions, f)?,
+                Definition::Union(u) => windbg::fmt_union(u, &embedded_definitions, f)?,
             }
         }
 
-        // Enum definitions
-        for e in &self.enums {
+        Ok(())
+    }
+
+    /// Reconstruct the types as `#[repr(C)]` Rust items. Types are output in
+    /// the same order as with `reconstruct`.
+    pub fn reconstruct_rust(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        for e in &self.forward_references {
             writeln!(f)?;
-            e.reconstruct(f)?;
+            rust::fmt_opaque_struct(&e.name, f)?;
+        }
+        // Note: Declaration order doesn't matter in Rust
+        let (_, definitions) = self.ordered_definitions();
+        for definition in definitions {
+            writeln!(f)?;
+            match definition {
+                Definition::Enum(e) => rust::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => {
+                    rust::fmt_class(class, f)?;
+                    if fmt_configuration.print_size_asserts {
+                        rust::fmt_size_assert(&class.name, class.size as u64, f)?;
+                    }
+                }
+                Definition::Union(u) => {
+                    rust::fmt_union(u, f)?;
+                    if fmt_configuration.print_size_asserts {
+                        rust::fmt_size_assert(&u.name, u.size as u64, f)?;
+                    }
+                }
+            }
         }
 
-        // Class/struct definitions
-        for class in &self.classes {
+        Ok(())
+    }
+
+    /// Reconstruct the types as Python `ctypes` classes. All the classes are
+    /// declared first, and their fields set in the same order as with
+    /// `reconstruct`, so that classes can refer to each other through
+    /// pointers.
+    pub fn reconstruct_ctypes(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        for e in &self.forward_references {
+            writeln!(f)?;
+            ctypes::fmt_opaque_struct(&e.name, f)?;
+        }
+        let (_, definitions) = self.ordered_definitions();
+        let type_sizes = definition_sizes(&definitions);
+        // Enums are aliases of their underlying type, which can be defined
+        // up front
+        for definition in &definitions {
+            if let Definition::Enum(e) = definition {
+                writeln!(f)?;
+                ctypes::fmt_enum(e, fmt_configuration, f)?;
+            }
+        }
+        let items = definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Class(class) => Some((
+                    definition,
+                    ctypes::class_items(class, &type_sizes, fmt_configuration),
+                )),
+                Definition::Union(u) => Some((
+                    definition,
+                    ctypes::union_items(u, &type_sizes, fmt_configuration),
+                )),
+                Definition::Enum(_) => None,
+            })
+            .collect::<Vec<_>>();
+        for item in items.iter().flat_map(|(_, items)| items) {
             writeln!(f)?;
-            class.reconstruct(fmt_configuration, f)?;
+            ctypes::fmt_item_declaration(item, f)?;
         }
+        for (definition, items) in &items {
+            writeln!(f)?;
+            for item in items {
+                ctypes::fmt_item_fields(item, f)?;
+            }
+            if fmt_configuration.print_size_asserts {
+                if let Some(type_size) = type_sizes.get(definition.name()) {
+                    ctypes::fmt_size_assert(definition.name(), *type_size, f)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
 
-        // Union definitions
-        for u in &self.unions {
+    /// Reconstruct the types as Go structs. Types are output in the same
+    /// order as with `reconstruct`.
+    pub fn reconstruct_go(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        // Note: Pointers are represented by addresses, forward declarations
+        // aren't needed
+        let (_, definitions) = self.ordered_definitions();
+        let type_sizes = definition_sizes(&definitions);
+        for definition in definitions {
             writeln!(f)?;
-            u.reconstruct(fmt_configuration, f)?;
+            match definition {
+                Definition::Enum(e) => go::fmt_enum(e, fmt_configuration, f)?,
+                Definition::Class(class) => go::fmt_class(class, &type_sizes, f)?,
+                Definition::Union(u) => go::fmt_union(u, f)?,
+            }
         }
 
         Ok(())
     }
+
+    /// Return a serializable description of the types contained in this
+    /// `Data`, in the order in which `reconstruct` outputs them.
+    pub fn to_model(&self) -> Vec<ReconstructedType> {
+        let (_, definitions) = self.ordered_definitions();
+        definitions
+            .into_iter()
+            .map(|definition| match definition {
+                Definition::Enum(e) => ReconstructedType::from(e),
+                Definition::Class(class) => ReconstructedType::from(class),
+                Definition::Union(u) => ReconstructedType::from(u),
+            })
+            .collect()
+    }
+
+    /// Return the COM interface described by the first class contained in
+    /// this `Data`, if it's shaped like one.
+    pub fn com_interface(&self) -> Option<ComInterface> {
+        self.classes.first().and_then(ComInterface::from_class)
+    }
+
+    /// Return the types that must be forward declared, followed with the
+    /// definitions of all the types, sorted so that types are defined after
+    /// their dependencies. Types that don't depend on each other are sorted
+    /// by name, so that the output doesn't depend on the order in which
+    /// types were added.
+    fn ordered_definitions(&self) -> (Vec<Definition<'_, '_>>, Vec<Definition<'_, '_>>) {
+        let mut definitions = self
+            .enums
+            .iter()
+            .map(Definition::Enum)
+            .chain(self.classes.iter().map(Definition::Class))
+            .chain(self.unions.iter().map(Definition::Union))
+            .collect::<Vec<_>>();
+        definitions.sort_by(|lhs, rhs| lhs.name().cmp(rhs.name()));
+        let no_dependencies = TypeDependencies::default();
+        let types = definitions
+            .iter()
+            .map(|definition| {
+                (
+                    definition.name(),
+                    self.dependencies
+                        .get(definition.name())
+                        .unwrap_or(&no_dependencies),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let order = sort_definitions(&types);
+        (
+            order
+                .forward_declarations
+                .into_iter()
+                .map(|i| definitions[i])
+                .collect(),
+            order
+                .definitions
+                .into_iter()
+                .map(|i| definitions[i])
+                .collect(),
+        )
+    }
 }
 
 impl<'p> Default for Data<'p> {
@@ -482,6 +1125,8 @@ impl<'p> Data<'p> {
             classes: Vec::new(),
             enums: Vec::new(),
             unions: Vec::new(),
+            dependencies: BTreeMap::new(),
+            nested_types: BTreeSet::new(),
         }
     }
 
@@ -502,6 +1147,9 @@ impl<'p> Data<'p> {
                 } else {
                     name_str.into_owned()
                 };
+                if data.properties.is_nested_type() {
+                    self.nested_types.insert(name.clone());
+                }
 
                 if data.properties.forward_reference() {
                     self.forward_references.push(ForwardReference {
@@ -524,10 +1172,13 @@ impl<'p> Data<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    vtable_pointer_size: None,
+                    packing: udt_packing(type_finder, type_forwarder, type_index),
                 };
 
+                let mut referenced_types = TypeSet::new();
                 if let Some(derived_from) = data.derived_from {
-                    class.add_derived_from(type_finder, derived_from, needed_types)?;
+                    class.add_derived_from(type_finder, derived_from, &mut referenced_types)?;
                 }
 
                 if let Some(fields) = data.fields {
@@ -540,7 +1191,7 @@ impl<'p> Data<'p> {
                         type_forwarder,
                         fields,
                         primitive_flavor,
-                        needed_types,
+                        &mut referenced_types,
                     ) {
                         log::error!(
                             "Error encountered while reconstructing '{}': {}",
@@ -550,6 +1201,19 @@ impl<'p> Data<'p> {
                     }
                 }
 
+                self.add_dependencies(
+                    &class.name,
+                    definition_dependencies(
+                        type_finder,
+                        class
+                            .base_classes
+                            .iter()
+                            .map(|base| base.type_name.as_str()),
+                        &class.fields,
+                        &referenced_types,
+                    ),
+                );
+                needed_types.extend(referenced_types);
                 self.classes.insert(0, class);
             }
 
@@ -561,6 +1225,9 @@ impl<'p> Data<'p> {
                 } else {
                     name_str.into_owned()
                 };
+                if data.properties.is_nested_type() {
+                    self.nested_types.insert(name.clone());
+                }
 
                 let mut u = Union {
                     name,
@@ -572,14 +1239,16 @@ impl<'p> Data<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    packing: udt_packing(type_finder, type_forwarder, type_index),
                 };
 
+                let mut referenced_types = TypeSet::new();
                 if let Err(err) = u.add_fields(
                     type_finder,
                     type_forwarder,
                     data.fields,
                     primitive_flavor,
-                    needed_types,
+                    &mut referenced_types,
                 ) {
                     log::error!(
                         "Error encountered while reconstructing '{}': {}",
@@ -588,6 +1257,16 @@ impl<'p> Data<'p> {
                     );
                 }
 
+                self.add_dependencies(
+                    &u.name,
+                    definition_dependencies(
+                        type_finder,
+                        std::iter::empty(),
+                        &u.fields,
+                        &referenced_types,
+                    ),
+                );
+                needed_types.extend(referenced_types);
                 self.unions.insert(0, u);
             }
 
@@ -599,9 +1278,14 @@ impl<'p> Data<'p> {
                 } else {
                     name_str.into_owned()
                 };
+                if data.properties.is_nested_type() {
+                    self.nested_types.insert(name.clone());
+                }
 
                 let mut e = Enum {
                     name,
+                    scoped: data.properties.scoped_definition(),
+                    size: type_size(type_finder, type_forwarder, data.underlying_type)?,
                     underlying_type_name: type_name(
                         type_finder,
                         type_forwarder,
@@ -610,6 +1294,11 @@ impl<'p> Data<'p> {
                         needed_types,
                     )?
                     .0,
+                    underlying_type: type_description(
+                        type_finder,
+                        type_forwarder,
+                        data.underlying_type,
+                    )?,
                     values: Vec::new(),
                 };
 
@@ -630,22 +1319,211 @@ impl<'p> Data<'p> {
 
         Ok(())
     }
+    /// Record the dependencies of the type named `type_name`. Dependencies of
+    /// types sharing the same name are merged.
+    fn add_dependencies(&mut self, type_name: &str, dependencies: TypeDependencies) {
+        self.dependencies
+            .entry(type_name.to_string())
+            .or_default()
+            .extend(dependencies);
+    }
+}
+
+/// Return the dependencies of a type with the given base classes and fields.
+/// `referenced_types` contains all the types referenced by the type.
+fn definition_dependencies<'a>(
+    type_finder: &pdb::TypeFinder,
+    base_class_names: impl Iterator<Item = &'a str>,
+    fields: &[Field],
+    referenced_types: &TypeSet,
+) -> TypeDependencies {
+    let mut dependencies = TypeDependencies::default();
+    // Base classes and members stored by value require a complete type
+    dependencies
+        .definitions
+        .extend(base_class_names.map(str::to_string));
+    dependencies.definitions.extend(
+        fields
+            .iter()
+            .filter_map(|field| by_value_type_name(&field.type_description))
+            .map(str::to_string),
+    );
+    // Other references (e.g., pointers or method arguments) only require a
+    // declaration
+    dependencies.declarations.extend(
+        referenced_types
+            .iter()
+            .filter_map(|type_index| defined_type_name(type_finder, *type_index))
+            .filter(|type_name| !dependencies.definitions.contains(type_name)),
+    );
+
+    dependencies
+}
+
+/// Return the name of the user-defined type stored in a member of the given
+/// type, if any.
+fn by_value_type_name(type_description: &TypeDescription) -> Option<&str> {
+    match type_description.strip_modifiers() {
+        TypeDescription::Class(name) | TypeDescription::Union(name) => Some(name),
+        TypeDescription::Enum { name, .. } => Some(name),
+        TypeDescription::Array { element_type, .. } => by_value_type_name(element_type),
+        _ => None,
+    }
+}
+
+/// Return the name under which the type `type_index` is defined, if it's a
+/// user-defined type.
+fn defined_type_name(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> Option<String> {
+    let name = match type_finder.find(type_index).ok()?.parse().ok()? {
+        pdb::TypeData::Class(data) => data.name,
+        pdb::TypeData::Union(data) => data.name,
+        pdb::TypeData::Enumeration(data) => data.name,
+        _ => return None,
+    };
+
+    Some(udt_name(&name.to_string(), type_index))
+}
+
+/// Write a C++ forward declaration of the user-defined type `type_index`.
+/// Unlike full reconstructions, this doesn't walk the type's members.
+pub fn fmt_forward_declaration(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+    primitive_flavor: &PrimitiveReconstructionFlavor,
+    f: &mut impl std::fmt::Write,
+) -> Result<()> {
+    match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Class(data) => writeln!(
+            f,
+            "{} {};",
+            match data.kind {
+                pdb::ClassKind::Class => "class",
+                // Note: Interfaces are declared as structs, as `__interface`
+                // is a Microsoft extension
+                pdb::ClassKind::Struct | pdb::ClassKind::Interface => "struct",
+            },
+            udt_name(&data.name.to_string(), type_index)
+        )?,
+        pdb::TypeData::Union(data) => {
+            writeln!(f, "union {};", udt_name(&data.name.to_string(), type_index))?
+        }
+        // Enums can only be forward declared along with their underlying type
+        pdb::TypeData::Enumeration(data) => writeln!(
+            f,
+            "enum {}{} : {};",
+            if data.properties.scoped_definition() {
+                "class "
+            } else {
+                ""
+            },
+            udt_name(&data.name.to_string(), type_index),
+            type_name(
+                type_finder,
+                type_forwarder,
+                data.underlying_type,
+                primitive_flavor,
+                &mut TypeSet::new(),
+            )?
+            .0
+        )?,
+        other => return Err(anyhow!("cannot forward declare {:?}", other)),
+    }
+
+    Ok(())
+}
+
+/// Infer the packing of the given user-defined type. Errors are logged and
+/// result in `Packing::Unknown`.
+fn udt_packing(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Packing {
+    infer_packing(type_finder, type_forwarder, type_index).unwrap_or_else(|err| {
+        log::warn!("Failed to infer packing of type {}: {}", type_index, err);
+        Packing::Unknown
+    })
+}
+
+/// Return `definition`, renamed from `original_name` to `name` if they differ.
+fn with_name<'d, T: Clone>(
+    definition: &'d T,
+    original_name: &str,
+    name: &str,
+    set_name: impl FnOnce(&mut T, String),
+) -> Cow<'d, T> {
+    if original_name == name {
+        return Cow::Borrowed(definition);
+    }
+
+    let mut definition = definition.clone();
+    set_name(&mut definition, name.to_string());
+    Cow::Owned(definition)
 }
 
+/// Write the definition of a type with the given packing, as written by
+/// `fmt_definition`. When `DataFormatConfiguration::infer_packing` is set,
+/// the definition is wrapped in `#pragma pack` directives if needed, or
+/// written with explicit padding fields if the packing couldn't be inferred.
+fn fmt_packed_definition<W: std::fmt::Write>(
+    packing: Packing,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut W,
+    fmt_definition: impl FnOnce(&DataFormatConfiguration, &mut W) -> fmt::Result,
+) -> fmt::Result {
+    if !fmt_configuration.infer_packing {
+        return fmt_definition(fmt_configuration, f);
+    }
+
+    match packing {
+        Packing::Natural => fmt_definition(fmt_configuration, f),
+        Packing::Packed(packing) => {
+            writeln!(f, "#pragma pack(push, {})", packing)?;
+            fmt_definition(fmt_configuration, f)?;
+            writeln!(f, "#pragma pack(pop)")
+        }
+        Packing::Unknown => fmt_definition(
+            &DataFormatConfiguration {
+                print_padding_fields: true,
+                ..fmt_configuration.clone()
+            },
+            f,
+        ),
+    }
+}
+
+/// Return the index of the complete type `type_index` refers to. Typedefs
+/// are always resolved, only `type_name` names types after their typedefs.
 pub fn resolve_complete_type_index(
-    forwarder_to_complete_type: &dashmap::DashMap<pdb::TypeIndex, pdb::TypeIndex>,
+    type_forwarder: &TypeForwarder,
     type_index: pdb::TypeIndex,
 ) -> pdb::TypeIndex {
-    match forwarder_to_complete_type.get(&type_index) {
+    let type_index = type_forwarder.resolve_type_alias(type_index);
+    match type_forwarder.complete_types.get(&type_index) {
         Some(d) => *d.value(),
         None => type_index,
     }
 }
 
+fn fmt_size_assert(type_name: &str, type_size: u64, f: &mut impl std::fmt::Write) -> fmt::Result {
+    writeln!(
+        f,
+        "static_assert(sizeof({}) == {:#x}, \"{} has an unexpected size\");",
+        type_name, type_size, type_name
+    )
+}
+
+/// Format the given struct fields.
+/// `start_offset` is the offset at which the first field is expected to be, if
+/// known. It's used to detect leading padding when
+/// `DataFormatConfiguration::print_padding_fields` is set.
 fn fmt_struct_fields_recursive(
     fmt_configuration: &DataFormatConfiguration,
     fields: &[Field],
     depth: usize,
+    start_offset: Option<u64>,
+    padding_index: &mut usize,
     f: &mut impl std::fmt::Write,
 ) -> fmt::Result {
     if fields.is_empty() {
@@ -655,27 +1533,50 @@ fn fmt_struct_fields_recursive(
     let unions_found = find_unnamed_unions_in_struct(fields);
     // Write fields into the `Formatter`
     let indentation = "  ".repeat(depth);
+    let mut next_offset = start_offset;
+    let mut previous_field = None;
     for union_range in unions_found {
+        if fmt_configuration.print_padding_fields {
+            // Fill gaps between consecutive members
+            let range_fields =
+                &fields[union_range.start..std::cmp::max(union_range.end, union_range.start + 1)];
+            let range_offset = range_fields[0].offset as u64;
+            if let Some(next_offset) = next_offset {
+                if range_offset > next_offset {
+                    fmt_padding_field(
+                        fmt_configuration,
+                        &indentation,
+                        next_offset,
+                        range_offset - next_offset,
+                        padding_index,
+                        f,
+                    )?;
+                }
+            }
+            next_offset = Some(std::cmp::max(
+                next_offset.unwrap_or_default(),
+                fields_end_offset(range_fields),
+            ));
+        }
+
         // Fields out of unnamed unions are represented by "empty" unions
         if union_range.is_empty() {
             let field = &fields[union_range.start];
-            writeln!(
-                f,
-                "{}/* {:#06x} */ {}{} {}{};",
-                &indentation,
-                field.offset,
-                if fmt_configuration.print_access_specifiers {
-                    &field.access
-                } else {
-                    &FieldAccess::None
-                },
-                field.type_left,
-                field.name.to_string(),
-                field.type_right,
-            )?;
+            if let Some(width) = unnamed_bitfield_width(previous_field, field) {
+                fmt_unnamed_bitfield(fmt_configuration, &indentation, field, width, f)?;
+            }
+            fmt_field(fmt_configuration, &indentation, field, f)?;
+            previous_field = Some(field);
         } else {
+            previous_field = None;
             writeln!(f, "{}union {{", &indentation)?;
-            fmt_union_fields_recursive(fmt_configuration, &fields[union_range], depth + 1, f)?;
+            fmt_union_fields_recursive(
+                fmt_configuration,
+                &fields[union_range],
+                depth + 1,
+                padding_index,
+                f,
+            )?;
             writeln!(f, "{}}};", &indentation)?;
         }
     }
@@ -683,7 +1584,194 @@ fn fmt_struct_fields_recursive(
     Ok(())
 }
 
+fn fmt_field(
+    fmt_configuration: &DataFormatConfiguration,
+    indentation: &str,
+    field: &Field,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}/* {:#06x} */ {}{} {}{};",
+        indentation,
+        field.offset,
+        if fmt_configuration.print_access_specifiers {
+            &field.access
+        } else {
+            &FieldAccess::None
+        },
+        field.type_left,
+        field.name.to_string(),
+        field.type_right,
+    )?;
+    if let Some(offset_radix) = fmt_configuration.print_offset_comments {
+        fmt_offset_comment(offset_radix, field.offset as u64, field.bitfield, f)?;
+    }
+    writeln!(f)
+}
+
+/// Write an unnamed bitfield of the given width, preceding the bitfield
+/// `field` in its storage unit.
+fn fmt_unnamed_bitfield(
+    fmt_configuration: &DataFormatConfiguration,
+    indentation: &str,
+    field: &Field,
+    width: u8,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "{}/* {:#06x} */ {}{} : {};",
+        indentation,
+        field.offset,
+        if fmt_configuration.print_access_specifiers {
+            &field.access
+        } else {
+            &FieldAccess::None
+        },
+        field.type_left,
+        width
+    )
+}
+
+/// Write a trailing comment containing the given offset (and bit position,
+/// in the case of bitfields), in the requested radix. Member lines already
+/// start with their hexadecimal offset, so only bit positions are written
+/// with the hexadecimal radix (and nothing for other members).
+fn fmt_offset_comment(
+    offset_radix: OffsetRadix,
+    offset: u64,
+    bitfield: Option<FieldBitfield>,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    match (offset_radix, bitfield) {
+        (OffsetRadix::Hexadecimal, None) => Ok(()),
+        (OffsetRadix::Hexadecimal, Some(bitfield)) => {
+            write!(f, " /* bit {} */", bitfield.position)
+        }
+        (OffsetRadix::Decimal, None) => write!(f, " /* {} */", offset),
+        (OffsetRadix::Decimal, Some(bitfield)) => {
+            write!(f, " /* {}, bit {} */", offset, bitfield.position)
+        }
+    }
+}
+
+/// Write a synthetic field covering `size` bytes of padding at `offset`.
+/// Padding fields are numbered in declaration order so that their names are
+/// deterministic and unique within a type.
+fn fmt_padding_field(
+    fmt_configuration: &DataFormatConfiguration,
+    indentation: &str,
+    offset: u64,
+    size: u64,
+    padding_index: &mut usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}/* {:#06x} */ uint8_t _padding_{}[{}];",
+        indentation, offset, padding_index, size
+    )?;
+    if let Some(offset_radix) = fmt_configuration.print_offset_comments {
+        fmt_offset_comment(offset_radix, offset, None, f)?;
+    }
+    writeln!(f)?;
+    *padding_index += 1;
+
+    Ok(())
+}
+
+/// Return the offset of the first byte that follows the given fields.
+fn fields_end_offset(fields: &[Field]) -> u64 {
+    fields
+        .iter()
+        .map(|field| field.offset as u64 + field.size as u64)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Return the ranges of fields sharing a storage unit (i.e., consecutive
+/// bitfields). Other fields have a storage unit of their own.
+fn storage_units(fields: &[Field]) -> Vec<Range<usize>> {
+    let mut units: Vec<Range<usize>> = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        match units.last_mut() {
+            Some(unit) if shares_storage_unit(&fields[unit.end - 1], field) => unit.end = i + 1,
+            _ => units.push(i..i + 1),
+        }
+    }
+
+    units
+}
+
+/// Check whether `field` is a bitfield stored in the same storage unit as
+/// `previous_field`, the field declared right before it.
+fn shares_storage_unit(previous_field: &Field, field: &Field) -> bool {
+    match (previous_field.bitfield, field.bitfield) {
+        (Some(previous_bitfield), Some(bitfield)) => {
+            previous_field.offset == field.offset
+                && previous_field.size == field.size
+                && bitfield.position >= previous_bitfield.position + previous_bitfield.length
+        }
+        _ => false,
+    }
+}
+
+/// Return the width of the unnamed bitfield that must be declared before the
+/// bitfield `field` to reproduce its position, if any. `previous_field` is
+/// the field declared right before it. A width of 0 means `field` doesn't
+/// use the storage unit left with free bits by `previous_field`.
+fn unnamed_bitfield_width(previous_field: Option<&Field>, field: &Field) -> Option<u8> {
+    let bitfield = field.bitfield?;
+    let previous_field = previous_field.filter(|previous_field| previous_field.bitfield.is_some());
+    match previous_field {
+        Some(previous_field) if shares_storage_unit(previous_field, field) => {
+            let previous_bitfield = previous_field.bitfield?;
+            let expected_position = previous_bitfield.position + previous_bitfield.length;
+            (bitfield.position > expected_position).then(|| bitfield.position - expected_position)
+        }
+        _ if bitfield.position > 0 => Some(bitfield.position),
+        // Consecutive bitfields of the same size go into the same storage
+        // unit as long as they fit
+        Some(previous_field) => {
+            let previous_bitfield = previous_field.bitfield?;
+            let free_bits = (previous_field.size * 8) as u64
+                - (previous_bitfield.position + previous_bitfield.length) as u64;
+            let is_next_unit =
+                field.offset as u64 == previous_field.offset as u64 + previous_field.size as u64;
+            (previous_field.size == field.size
+                && is_next_unit
+                && free_bits >= bitfield.length as u64)
+                .then_some(0)
+        }
+        None => None,
+    }
+}
+
+/// Find the unnamed unions declared in the given struct fields. Fields out of
+/// unnamed unions are represented by empty ranges.
 fn find_unnamed_unions_in_struct(fields: &[Field]) -> Vec<Range<usize>> {
+    let units = storage_units(fields);
+    let unit_fields = units
+        .iter()
+        .map(|unit| &fields[unit.start])
+        .collect::<Vec<_>>();
+
+    let mut unions_found = vec![];
+    for union_range in find_unnamed_unions_in_units(&unit_fields) {
+        if union_range.is_empty() {
+            // Bitfields sharing a storage unit are declared one after the
+            // other
+            unions_found.extend(units[union_range.start].clone().map(|i| i..i));
+        } else {
+            unions_found.push(units[union_range.start].start..units[union_range.end - 1].end);
+        }
+    }
+
+    unions_found
+}
+
+fn find_unnamed_unions_in_units(fields: &[&Field]) -> Vec<Range<usize>> {
     let mut unions_found: Vec<Range<usize>> = vec![];
     // Temporary map of unions and fields that'll be used to compute the list
     // of unnamed unions which are in the struct.
@@ -758,6 +1846,7 @@ fn fmt_union_fields_recursive(
     fmt_configuration: &DataFormatConfiguration,
     fields: &[Field],
     depth: usize,
+    padding_index: &mut usize,
     f: &mut impl std::fmt::Write,
 ) -> fmt::Result {
     if fields.is_empty() {
@@ -769,24 +1858,24 @@ fn fmt_union_fields_recursive(
     for struct_range in structs_found {
         // Fields out of unnamed structs are represented by "empty" structs
         if struct_range.is_empty() {
-            let field = &fields[struct_range.start];
-            writeln!(
-                f,
-                "{}/* {:#06x} */ {}{} {}{};",
+            fmt_field(
+                fmt_configuration,
                 &indentation,
-                field.offset,
-                if fmt_configuration.print_access_specifiers {
-                    &field.access
-                } else {
-                    &FieldAccess::None
-                },
-                field.type_left,
-                field.name.to_string(),
-                field.type_right,
+                &fields[struct_range.start],
+                f,
             )?;
         } else {
             writeln!(f, "{}struct {{", &indentation)?;
-            fmt_struct_fields_recursive(fmt_configuration, &fields[struct_range], depth + 1, f)?;
+            // Note: Unnamed structs start at the union's offset, so there's
+            // no leading padding to detect here
+            fmt_struct_fields_recursive(
+                fmt_configuration,
+                &fields[struct_range],
+                depth + 1,
+                None,
+                padding_index,
+                f,
+            )?;
             writeln!(f, "{}}};", &indentation)?;
         }
     }
@@ -794,7 +1883,32 @@ fn fmt_union_fields_recursive(
     Ok(())
 }
 
+/// Find the unnamed structs declared in the given union fields. Fields out of
+/// unnamed structs are represented by empty ranges.
 fn find_unnamed_structs_in_unions(fields: &[Field]) -> Vec<Range<usize>> {
+    let units = storage_units(fields);
+    let unit_fields = units
+        .iter()
+        .map(|unit| &fields[unit.start])
+        .collect::<Vec<_>>();
+
+    find_unnamed_structs_in_union_units(&unit_fields)
+        .into_iter()
+        .map(|struct_range| {
+            let last_unit = std::cmp::max(struct_range.end, struct_range.start + 1) - 1;
+            let fields_range = units[struct_range.start].start..units[last_unit].end;
+            // Bitfields sharing a storage unit must be wrapped into a struct
+            // to be laid out one after the other
+            if struct_range.is_empty() && fields_range.len() == 1 {
+                fields_range.start..fields_range.start
+            } else {
+                fields_range
+            }
+        })
+        .collect()
+}
+
+fn find_unnamed_structs_in_union_units(fields: &[&Field]) -> Vec<Range<usize>> {
     let mut structs_found: Vec<Range<usize>> = vec![];
 
     let field_count = fields.len();
@@ -839,6 +1953,109 @@ fn find_unnamed_structs_in_unions(fields: &[Field]) -> Vec<Range<usize>> {
     structs_found
 }
 
+/// Sizes of the types that can be embedded in others, by type name.
+type TypeSizes<'a> = BTreeMap<&'a str, u64>;
+
+/// Return the sizes of the given class and union definitions.
+fn definition_sizes<'a>(definitions: &[Definition<'a, '_>]) -> TypeSizes<'a> {
+    definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Class(class) => Some((class.name.as_str(), class.size as u64)),
+            Definition::Union(u) => Some((u.name.as_str(), u.size as u64)),
+            Definition::Enum(_) => None,
+        })
+        .collect()
+}
+
+/// Members of a struct or union, with the unnamed unions and structs found in
+/// between them made explicit (as they are in C++ reconstructions).
+enum MemberLayout<'a, 'p> {
+    Field(&'a Field<'p>),
+    /// Consecutive bitfields sharing a storage unit
+    Bitfields(Vec<&'a Field<'p>>),
+    Union(Vec<MemberLayout<'a, 'p>>),
+    Struct(Vec<MemberLayout<'a, 'p>>),
+}
+
+/// Return the layout of the given struct fields.
+fn struct_members_layout<'a, 'p>(fields: &'a [Field<'p>]) -> Vec<MemberLayout<'a, 'p>> {
+    if fields.is_empty() {
+        return vec![];
+    }
+
+    let mut layout = vec![];
+    for union_range in find_unnamed_unions_in_struct(fields) {
+        // Fields out of unnamed unions are represented by "empty" unions
+        if !union_range.is_empty() {
+            layout.push(MemberLayout::Union(union_members_layout(
+                &fields[union_range],
+            )));
+            continue;
+        }
+
+        let field = &fields[union_range.start];
+        match layout.last_mut() {
+            Some(MemberLayout::Bitfields(bitfields))
+                if shares_storage_unit(bitfields[bitfields.len() - 1], field) =>
+            {
+                bitfields.push(field)
+            }
+            _ if field.bitfield.is_some() => layout.push(MemberLayout::Bitfields(vec![field])),
+            _ => layout.push(MemberLayout::Field(field)),
+        }
+    }
+
+    layout
+}
+
+/// Return the layout of the given union fields.
+fn union_members_layout<'a, 'p>(fields: &'a [Field<'p>]) -> Vec<MemberLayout<'a, 'p>> {
+    if fields.is_empty() {
+        return vec![];
+    }
+
+    find_unnamed_structs_in_unions(fields)
+        .into_iter()
+        .map(|struct_range| {
+            // Fields out of unnamed structs are represented by "empty" structs
+            if struct_range.is_empty() {
+                MemberLayout::Field(&fields[struct_range.start])
+            } else {
+                MemberLayout::Struct(struct_members_layout(&fields[struct_range]))
+            }
+        })
+        .collect()
+}
+
+/// Return the offset and size of the region covered by the given members.
+fn members_layout_extent(layout: &[MemberLayout]) -> (u64, u64) {
+    let field_extent = |field: &Field| (field.offset as u64, field.size as u64);
+    let (start_offset, end_offset) = layout
+        .iter()
+        .flat_map(|member| match member {
+            MemberLayout::Field(field) => vec![field_extent(field)],
+            MemberLayout::Bitfields(bitfields) => {
+                bitfields.iter().map(|field| field_extent(field)).collect()
+            }
+            MemberLayout::Union(layout) | MemberLayout::Struct(layout) => {
+                vec![members_layout_extent(layout)]
+            }
+        })
+        .fold(
+            (u64::MAX, 0),
+            |(start_offset, end_offset), (offset, size)| {
+                (start_offset.min(offset), end_offset.max(offset + size))
+            },
+        );
+
+    if start_offset > end_offset {
+        (0, 0)
+    } else {
+        (start_offset, end_offset - start_offset)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ForwardReference {
     kind: pdb::ClassKind,
@@ -860,15 +2077,54 @@ impl ForwardReference {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DataFormatConfiguration {
     pub print_access_specifiers: bool,
+    /// Print the declarations of member functions
+    pub print_methods: bool,
+    /// Emit a `static_assert` checking the size of each reconstructed
+    /// struct/class/union
+    pub print_size_asserts: bool,
+    /// Insert explicit padding fields in gaps between struct members
+    pub print_padding_fields: bool,
+    /// Append a comment containing its offset to each member, in the given
+    /// radix. Only bitfields' bit positions are appended in hexadecimal, as
+    /// member lines already start with their hexadecimal offset
+    pub print_offset_comments: Option<OffsetRadix>,
+    /// Replace instantiations of common STL templates with their friendly
+    /// aliases (e.g., `std::string`) in C++ output
+    pub pretty_templates: bool,
+    /// Wrap types whose layout isn't reproduced by natural alignment in
+    /// `#pragma pack` directives (C and C++ output)
+    pub infer_packing: bool,
+    /// Write enumerants' values in hexadecimal instead of decimal
+    pub print_enum_values_in_hex: bool,
+    /// Declare types inside of blocks for the namespaces they belong to,
+    /// under their unqualified name, in C++ output
+    pub print_namespaces: bool,
+    /// Rewrite type names that aren't valid identifiers (e.g., templates or
+    /// qualified names) into identifiers in C++ output. Other formats always
+    /// flatten names.
+    pub sanitize_names: bool,
+    /// Name the types of members after what their typedefs ultimately
+    /// resolve to, instead of after the typedefs
+    pub resolve_typedefs: bool,
 }
 
 impl Default for DataFormatConfiguration {
     fn default() -> Self {
         Self {
             print_access_specifiers: true,
+            print_methods: true,
+            print_size_asserts: false,
+            print_padding_fields: false,
+            print_offset_comments: None,
+            pretty_templates: false,
+            infer_packing: false,
+            print_enum_values_in_hex: false,
+            print_namespaces: false,
+            sanitize_names: false,
+            resolve_typedefs: false,
         }
     }
 }
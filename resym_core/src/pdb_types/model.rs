@@ -0,0 +1,129 @@
+use serde::Serialize;
+
+use super::{class::Class, enumeration::Enum, field::Field, union::Union};
+
+/// Serializable description of a reconstructed type. This is meant for
+/// programmatic consumers that cannot rely on the C++ representation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconstructedType {
+    pub name: String,
+    pub kind: ReconstructedTypeKind,
+    /// Size of the type in bytes
+    pub size: u64,
+    /// Data members, in declaration order
+    pub fields: Vec<ReconstructedField>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<ReconstructedEnumValue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReconstructedTypeKind {
+    Struct,
+    Class,
+    Interface,
+    Union,
+    Enum,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconstructedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// Offset of the field in bytes, from the start of the type
+    pub offset: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_offset: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_width: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReconstructedEnumValue {
+    pub name: String,
+    pub value: ReconstructedEnumValueKind,
+}
+
+/// Enumerants can be either signed or unsigned, depending on the
+/// enumeration's underlying type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ReconstructedEnumValueKind {
+    Signed(i64),
+    Unsigned(u64),
+}
+
+impl From<&Field<'_>> for ReconstructedField {
+    fn from(field: &Field<'_>) -> Self {
+        Self {
+            name: field.name.to_string().into_owned(),
+            // Bitfields' widths are part of `type_right`, they're exposed
+            // separately
+            type_name: if field.bitfield.is_some() {
+                field.type_left.clone()
+            } else {
+                format!("{}{}", field.type_left, field.type_right)
+            },
+            offset: field.offset as u64,
+            bit_offset: field.bitfield.map(|b| b.position),
+            bit_width: field.bitfield.map(|b| b.length),
+        }
+    }
+}
+
+impl From<&Class<'_>> for ReconstructedType {
+    fn from(class: &Class<'_>) -> Self {
+        Self {
+            name: class.name.clone(),
+            kind: match class.kind {
+                pdb::ClassKind::Class => ReconstructedTypeKind::Class,
+                pdb::ClassKind::Struct => ReconstructedTypeKind::Struct,
+                pdb::ClassKind::Interface => ReconstructedTypeKind::Interface,
+            },
+            size: class.size as u64,
+            fields: class.fields.iter().map(ReconstructedField::from).collect(),
+            values: vec![],
+        }
+    }
+}
+
+impl From<&Union<'_>> for ReconstructedType {
+    fn from(u: &Union<'_>) -> Self {
+        Self {
+            name: u.name.clone(),
+            kind: ReconstructedTypeKind::Union,
+            size: u.size as u64,
+            fields: u.fields.iter().map(ReconstructedField::from).collect(),
+            values: vec![],
+        }
+    }
+}
+
+impl From<&Enum<'_>> for ReconstructedType {
+    fn from(e: &Enum<'_>) -> Self {
+        Self {
+            name: e.name.clone(),
+            kind: ReconstructedTypeKind::Enum,
+            size: e.size as u64,
+            fields: vec![],
+            values: e
+                .values
+                .iter()
+                .map(|value| ReconstructedEnumValue {
+                    name: value.name.to_string().into_owned(),
+                    value: match value.value {
+                        pdb::Variant::U8(v) => ReconstructedEnumValueKind::Unsigned(v as u64),
+                        pdb::Variant::U16(v) => ReconstructedEnumValueKind::Unsigned(v as u64),
+                        pdb::Variant::U32(v) => ReconstructedEnumValueKind::Unsigned(v as u64),
+                        pdb::Variant::U64(v) => ReconstructedEnumValueKind::Unsigned(v),
+                        pdb::Variant::I8(v) => ReconstructedEnumValueKind::Signed(v as i64),
+                        pdb::Variant::I16(v) => ReconstructedEnumValueKind::Signed(v as i64),
+                        pdb::Variant::I32(v) => ReconstructedEnumValueKind::Signed(v as i64),
+                        pdb::Variant::I64(v) => ReconstructedEnumValueKind::Signed(v),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+/// Rewrite the type names found in the C++ `text` that aren't valid
+/// identifiers (e.g., `std::vector<int>`, `` `anonymous namespace'::Foo `` or
+/// `<lambda_1>`) into identifiers. Names are rewritten deterministically, so
+/// that references to a type and its definition still match. Comments and
+/// string literals are left untouched.
+pub fn sanitize_type_names(text: &str) -> String {
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let mut output = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        let end = match c {
+            '/' if matches!(chars.get(i + 1), Some((_, '/'))) => {
+                skip_until(&chars, i, |c, _| c == '\n')
+            }
+            '/' if matches!(chars.get(i + 1), Some((_, '*'))) => {
+                skip_until(&chars, i + 2, |c, previous| {
+                    previous == Some('*') && c == '/'
+                })
+                .map(|end| end + 1)
+            }
+            '"' => skip_until(&chars, i + 1, |c, previous| {
+                c == '"' && previous != Some('\\')
+            })
+            .map(|end| end + 1),
+            // Numbers aren't names (e.g., `0x10`)
+            c if c.is_ascii_digit() => skip_until(&chars, i, |c, _| !is_name_char(c)),
+            _ => match name_end(&chars, i) {
+                Some(end) => {
+                    let name = &text[start..char_offset(text, &chars, end)];
+                    if is_identifier(name) {
+                        output.push_str(name);
+                    } else {
+                        output.push_str(&sanitized_name(name));
+                    }
+                    i = end;
+                    continue;
+                }
+                None => Some(i + 1),
+            },
+        }
+        .unwrap_or(chars.len());
+
+        output.push_str(&text[start..char_offset(text, &chars, end)]);
+        i = end;
+    }
+
+    output
+}
+
+/// Return an identifier derived from `name`. Runs of characters that aren't
+/// valid in identifiers are replaced with a single underscore (leading and
+/// trailing ones are removed), and pointers and references are spelled out so
+/// that e.g. `Foo<int *>` and `Foo<int>` don't collide.
+fn sanitized_name(name: &str) -> String {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in name.chars() {
+        let spelled_out_word = match c {
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                word.push(c);
+                continue;
+            }
+            '*' => Some("ptr"),
+            '&' => Some("ref"),
+            _ => None,
+        };
+        words.push(std::mem::take(&mut word));
+        words.extend(spelled_out_word.map(str::to_string));
+    }
+    words.push(word);
+
+    let sanitized_name = words
+        .into_iter()
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if sanitized_name.is_empty() || sanitized_name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", sanitized_name)
+    } else {
+        sanitized_name
+    }
+}
+
+/// Check whether `name` is a valid C/C++ identifier.
+pub fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Characters found in names, including MSVC-specific ones
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '@' | '?')
+}
+
+/// Return the position following the (possibly qualified or templated) name
+/// starting at `position`, if any.
+fn name_end(chars: &[(usize, char)], position: usize) -> Option<usize> {
+    let mut end = position;
+    loop {
+        let (component_end, is_word) = name_component_end(chars, end)?;
+        end = component_end;
+        // Template arguments (but not `operator<`)
+        if is_word
+            && !component_is(
+                chars,
+                position.max(end_of_scope(chars, end)),
+                end,
+                "operator",
+            )
+        {
+            if let Some(arguments_end) = template_arguments_end(chars, end) {
+                end = arguments_end;
+            }
+        }
+        // Qualified names
+        let is_scope_separator = matches!(chars.get(end), Some((_, ':')))
+            && matches!(chars.get(end + 1), Some((_, ':')));
+        if !is_scope_separator || name_component_end(chars, end + 2).is_none() {
+            return Some(end);
+        }
+        end += 2;
+    }
+}
+
+/// Return the position following the unqualified name starting at `position`
+/// (an identifier, `` `anonymous namespace' `` or a tag like `<lambda_1>`),
+/// if any, and whether it's an identifier.
+fn name_component_end(chars: &[(usize, char)], position: usize) -> Option<(usize, bool)> {
+    match chars.get(position)?.1 {
+        c if is_name_char(c) && !c.is_ascii_digit() => {
+            skip_until(chars, position, |c, _| !is_name_char(c))
+                .or(Some(chars.len()))
+                .map(|end| (end, true))
+        }
+        '`' => {
+            let end = skip_until(chars, position + 1, |c, _| c == '\'' || c == '\n')?;
+            (chars[end].1 == '\'').then_some((end + 1, false))
+        }
+        '<' => {
+            let end = skip_until(chars, position + 1, |c, _| !(is_name_char(c) || c == '-'))?;
+            (end > position + 1 && chars[end].1 == '>').then_some((end + 1, false))
+        }
+        _ => None,
+    }
+}
+
+/// Return the position following the template arguments starting at
+/// `position`, if any. Arguments cannot span several lines nor statements.
+fn template_arguments_end(chars: &[(usize, char)], position: usize) -> Option<usize> {
+    if chars.get(position)?.1 != '<' {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    for (i, (_, c)) in chars.iter().enumerate().skip(position) {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            '\n' | ';' | '{' | '}' => return None,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Return the position of the beginning of the name component ending at `end`.
+fn end_of_scope(chars: &[(usize, char)], end: usize) -> usize {
+    chars[..end]
+        .iter()
+        .rposition(|(_, c)| !is_name_char(*c))
+        .map_or(0, |position| position + 1)
+}
+
+fn component_is(chars: &[(usize, char)], start: usize, end: usize, word: &str) -> bool {
+    chars[start..end].iter().map(|(_, c)| *c).eq(word.chars())
+}
+
+/// Return the position of the first character at or after `position` for
+/// which `predicate` (given the character and the previous one) is true.
+fn skip_until(
+    chars: &[(usize, char)],
+    position: usize,
+    predicate: impl Fn(char, Option<char>) -> bool,
+) -> Option<usize> {
+    (position..chars.len()).find(|i| {
+        let previous = i.checked_sub(1).map(|previous| chars[previous].1);
+        predicate(chars[*i].1, previous)
+    })
+}
+
+/// Return the byte offset of the character at `position`.
+fn char_offset(text: &str, chars: &[(usize, char)], position: usize) -> usize {
+    chars
+        .get(position)
+        .map_or(text.len(), |(offset, _)| *offset)
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// Name MSVC gives to anonymous namespaces
+pub const ANONYMOUS_NAMESPACE_NAME: &str = "`anonymous namespace'";
+
+/// Split the qualified C++ `type_name` into the namespaces it's declared in
+/// and its name in the innermost namespace (e.g., `ns::Foo<ns::Bar>` into
+/// `["ns"]` and `Foo<ns::Bar>`).
+/// Scopes that are types (i.e., the enclosing scope of `is_nested` types, or
+/// names from `type_names`) aren't namespaces, types declared in them keep
+/// their qualified name (e.g., `Outer::Inner`).
+pub fn split_namespaces<'n>(
+    type_name: &'n str,
+    is_nested: bool,
+    type_names: &BTreeSet<&str>,
+) -> (Vec<&'n str>, &'n str) {
+    let mut scope_ends = scope_separator_positions(type_name);
+    if is_nested {
+        scope_ends.pop();
+    }
+
+    let mut namespaces = Vec::new();
+    let mut name_start = 0;
+    for scope_end in scope_ends {
+        if type_names.contains(&type_name[..scope_end]) {
+            break;
+        }
+        namespaces.push(&type_name[name_start..scope_end]);
+        name_start = scope_end + 2;
+    }
+
+    (namespaces, &type_name[name_start..])
+}
+
+/// Return the positions of the `::` separating the scopes of `type_name`,
+/// ignoring the ones found in template arguments.
+fn scope_separator_positions(type_name: &str) -> Vec<usize> {
+    let bytes = type_name.as_bytes();
+    let mut positions = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'<' | b'(' => depth += 1,
+            b'>' | b')' => depth = depth.saturating_sub(1),
+            b':' if depth == 0 && bytes.get(i + 1) == Some(&b':') => {
+                positions.push(i);
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    positions
+}
+
+/// Write the declaration written by `fmt_declaration` on a single line,
+/// inside of the given namespaces.
+pub fn fmt_in_namespaces(
+    namespaces: &[&str],
+    f: &mut impl std::fmt::Write,
+    fmt_declaration: impl FnOnce(&mut String) -> fmt::Result,
+) -> fmt::Result {
+    let mut declaration = String::new();
+    fmt_declaration(&mut declaration)?;
+    if namespaces.is_empty() {
+        return f.write_str(&declaration);
+    }
+
+    for namespace in namespaces {
+        write!(f, "{} ", namespace_opening(namespace))?;
+    }
+    write!(f, "{}", declaration.trim_end())?;
+    writeln!(f, "{}", " }".repeat(namespaces.len()))
+}
+
+/// Namespace blocks currently opened in the output, so that consecutive
+/// definitions declared in the same namespaces share the same blocks.
+#[derive(Default)]
+pub struct NamespaceBlocks {
+    namespaces: Vec<String>,
+}
+
+impl NamespaceBlocks {
+    /// Close and open namespace blocks so that the following definitions are
+    /// declared in `namespaces`.
+    pub fn enter(&mut self, namespaces: &[&str], f: &mut impl std::fmt::Write) -> fmt::Result {
+        let common_depth = self
+            .namespaces
+            .iter()
+            .zip(namespaces)
+            .take_while(|(opened, namespace)| opened == *namespace)
+            .count();
+        while self.namespaces.len() > common_depth {
+            self.close_innermost(f)?;
+        }
+        for namespace in &namespaces[common_depth..] {
+            writeln!(f)?;
+            writeln!(f, "{}", namespace_opening(namespace))?;
+            self.namespaces.push(namespace.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Close all the opened namespace blocks.
+    pub fn close_all(&mut self, f: &mut impl std::fmt::Write) -> fmt::Result {
+        while !self.namespaces.is_empty() {
+            self.close_innermost(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn close_innermost(&mut self, f: &mut impl std::fmt::Write) -> fmt::Result {
+        if let Some(namespace) = self.namespaces.pop() {
+            writeln!(f)?;
+            if namespace == ANONYMOUS_NAMESPACE_NAME {
+                writeln!(f, "}} // namespace")?;
+            } else {
+                writeln!(f, "}} // namespace {}", namespace)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn namespace_opening(namespace: &str) -> String {
+    if namespace == ANONYMOUS_NAMESPACE_NAME {
+        "namespace {".to_string()
+    } else {
+        format!("namespace {} {{", namespace)
+    }
+}
@@ -0,0 +1,86 @@
+use std::{fmt, str::FromStr};
+
+/// Representation used to output reconstructed types.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum OutputFormat {
+    Cpp,
+    /// Plain C, without C++ constructs
+    C,
+    Json,
+    /// C# structs with an explicit layout
+    CSharp,
+    /// `#[repr(C)]` Rust items
+    Rust,
+    /// Layout listing, as displayed by WinDbg's `dt` command
+    WinDbg,
+    /// ImHex pattern language types
+    ImHex,
+    /// 010 Editor binary template structs
+    BinaryTemplate,
+    /// Kaitai Struct specification (YAML)
+    Kaitai,
+    /// Go structs
+    Go,
+    /// Python `ctypes` classes
+    Ctypes,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ParseOutputFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cpp" | "c++" => Ok(OutputFormat::Cpp),
+            "c" => Ok(OutputFormat::C),
+            "json" => Ok(OutputFormat::Json),
+            "csharp" | "c#" | "cs" => Ok(OutputFormat::CSharp),
+            "rust" | "rs" => Ok(OutputFormat::Rust),
+            "windbg" => Ok(OutputFormat::WinDbg),
+            "imhex" | "hexpat" => Ok(OutputFormat::ImHex),
+            "010" | "bt" => Ok(OutputFormat::BinaryTemplate),
+            "kaitai" | "ksy" => Ok(OutputFormat::Kaitai),
+            "go" | "golang" => Ok(OutputFormat::Go),
+            "ctypes" | "python" | "py" => Ok(OutputFormat::Ctypes),
+            _ => Err(ParseOutputFormatError {}),
+        }
+    }
+}
+
+/// An error returned when parsing an `OutputFormat` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOutputFormatError {}
+
+impl fmt::Display for ParseOutputFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid output format".fmt(f)
+    }
+}
+
+/// Radix used to print member offsets in comments.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum OffsetRadix {
+    Hexadecimal,
+    Decimal,
+}
+
+impl FromStr for OffsetRadix {
+    type Err = ParseOffsetRadixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hex" | "hexadecimal" => Ok(OffsetRadix::Hexadecimal),
+            "dec" | "decimal" => Ok(OffsetRadix::Decimal),
+            _ => Err(ParseOffsetRadixError {}),
+        }
+    }
+}
+
+/// An error returned when parsing an `OffsetRadix` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOffsetRadixError {}
+
+impl fmt::Display for ParseOffsetRadixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid offset radix".fmt(f)
+    }
+}
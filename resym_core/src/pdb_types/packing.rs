@@ -0,0 +1,251 @@
+use anyhow::Result;
+
+use super::{resolve_complete_type_index, type_size, TypeForwarder};
+
+/// Packing values `#pragma pack` is tried with, from the largest to the
+/// smallest one.
+const PACKING_CANDIDATES: &[u64] = &[8, 4, 2, 1];
+
+/// Packing of a user-defined type, as inferred from its members' offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Packing {
+    /// Natural alignment reproduces the type's layout
+    Natural,
+    /// The type's layout is reproduced with `#pragma pack(N)`
+    Packed(u64),
+    /// No packing value reproduces the type's layout
+    Unknown,
+}
+
+/// Placement of a member (or base class) in a user-defined type.
+struct MemberPlacement {
+    offset: u64,
+    size: u64,
+    alignment: u64,
+}
+
+/// Layout of a user-defined type, as described in the PDB.
+struct UdtLayout {
+    size: u64,
+    is_union: bool,
+    members: Vec<MemberPlacement>,
+    /// Virtual base classes are placed after the other members and their
+    /// offsets aren't recorded in the PDB, the type's size cannot be
+    /// predicted when present
+    has_virtual_bases: bool,
+}
+
+/// Infer the packing of the class or union `type_index`, by looking for the
+/// `#pragma pack` value that reproduces the offsets of its members as well
+/// as its size.
+pub fn infer_packing(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<Packing> {
+    let layout = match udt_layout(type_finder, type_forwarder, type_index)? {
+        None => return Ok(Packing::Natural),
+        Some(layout) => layout,
+    };
+
+    Ok(layout_packing(&layout))
+}
+
+fn layout_packing(layout: &UdtLayout) -> Packing {
+    if layout.members.is_empty() || is_reproduced_by_packing(layout, None) {
+        return Packing::Natural;
+    }
+
+    // Packing values greater than or equal to the natural alignment of the
+    // members have no effect
+    let natural_alignment = natural_alignment(layout);
+    PACKING_CANDIDATES
+        .iter()
+        .copied()
+        .filter(|packing| *packing < natural_alignment)
+        .find(|packing| is_reproduced_by_packing(layout, Some(*packing)))
+        .map_or(Packing::Unknown, Packing::Packed)
+}
+
+/// Return the alignment of the type `type_index`, taking its packing into
+/// account for user-defined types.
+fn type_alignment(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<u64> {
+    let type_index = resolve_complete_type_index(type_forwarder, type_index);
+    let alignment = match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Class(_) | pdb::TypeData::Union(_) => {
+            match udt_layout(type_finder, type_forwarder, type_index)? {
+                None => 1,
+                Some(layout) => {
+                    let natural_alignment = natural_alignment(&layout);
+                    match layout_packing(&layout) {
+                        Packing::Natural => natural_alignment,
+                        Packing::Packed(packing) => std::cmp::min(natural_alignment, packing),
+                        // Use the greatest alignment compatible with the
+                        // type's size
+                        Packing::Unknown => {
+                            let mut alignment = natural_alignment;
+                            while alignment > 1 && layout.size % alignment != 0 {
+                                alignment /= 2;
+                            }
+                            alignment
+                        }
+                    }
+                }
+            }
+        }
+        pdb::TypeData::Enumeration(data) => {
+            type_alignment(type_finder, type_forwarder, data.underlying_type)?
+        }
+        pdb::TypeData::Modifier(data) => {
+            type_alignment(type_finder, type_forwarder, data.underlying_type)?
+        }
+        pdb::TypeData::Bitfield(data) => {
+            type_alignment(type_finder, type_forwarder, data.underlying_type)?
+        }
+        pdb::TypeData::Array(data) => {
+            type_alignment(type_finder, type_forwarder, data.element_type)?
+        }
+        // Primitive types and pointers are aligned on their size
+        _ => type_size(type_finder, type_forwarder, type_index)? as u64,
+    };
+
+    Ok(std::cmp::max(alignment, 1))
+}
+
+/// Return the layout of the class or union `type_index`, or `None` if it
+/// isn't a complete user-defined type.
+fn udt_layout(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<Option<UdtLayout>> {
+    let type_index = resolve_complete_type_index(type_forwarder, type_index);
+    let (size, is_union, fields) = match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Class(data) if !data.properties.forward_reference() => match data.fields {
+            None => return Ok(None),
+            Some(fields) => (data.size as u64, false, fields),
+        },
+        pdb::TypeData::Union(data) if !data.properties.forward_reference() => {
+            (data.size as u64, true, data.fields)
+        }
+        _ => return Ok(None),
+    };
+
+    let mut layout = UdtLayout {
+        size,
+        is_union,
+        members: Vec::new(),
+        has_virtual_bases: false,
+    };
+    add_member_placements(type_finder, type_forwarder, fields, &mut layout)?;
+    layout.members.sort_by_key(|member| member.offset);
+
+    Ok(Some(layout))
+}
+
+fn add_member_placements(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    fields: pdb::TypeIndex,
+    layout: &mut UdtLayout,
+) -> Result<()> {
+    let field_list = match type_finder.find(fields)?.parse()? {
+        pdb::TypeData::FieldList(field_list) => field_list,
+        _ => return Ok(()),
+    };
+
+    for field in &field_list.fields {
+        let (offset, field_type) = match field {
+            pdb::TypeData::Member(data) => (data.offset as u64, data.field_type),
+            pdb::TypeData::BaseClass(data) => (data.offset as u64, data.base_class),
+            // Virtual base table pointer
+            pdb::TypeData::VirtualBaseClass(data) => {
+                layout.has_virtual_bases = true;
+                (data.base_pointer_offset as u64, data.base_pointer)
+            }
+            // Virtual function table pointer, located at the beginning of
+            // the type
+            pdb::TypeData::VirtualFunctionTablePointer(data) => (0, data.table),
+            _ => continue,
+        };
+        let field_type = resolve_complete_type_index(type_forwarder, field_type);
+        layout.members.push(MemberPlacement {
+            offset,
+            size: type_size(type_finder, type_forwarder, field_type)? as u64,
+            alignment: type_alignment(type_finder, type_forwarder, field_type)?,
+        });
+    }
+
+    if let Some(continuation) = field_list.continuation {
+        add_member_placements(type_finder, type_forwarder, continuation, layout)?;
+    }
+
+    Ok(())
+}
+
+fn natural_alignment(layout: &UdtLayout) -> u64 {
+    layout
+        .members
+        .iter()
+        .map(|member| member.alignment)
+        .max()
+        .unwrap_or(1)
+}
+
+/// Check whether laying out the members with the given packing (or natural
+/// alignment if `None`) reproduces the observed layout.
+fn is_reproduced_by_packing(layout: &UdtLayout, packing: Option<u64>) -> bool {
+    let effective_alignment = |alignment: u64| match packing {
+        Some(packing) => std::cmp::min(alignment, packing),
+        None => alignment,
+    };
+
+    let mut end_offset = 0;
+    let mut members = layout.members.iter().peekable();
+    while let Some(member) = members.next() {
+        // Members sharing an offset start a common region (e.g., an unnamed
+        // union, or bitfields sharing a storage unit)
+        let mut region_alignment = effective_alignment(member.alignment);
+        let mut region_end_offset = member.offset + member.size;
+        while let Some(next_member) = members.next_if(|m| m.offset == member.offset) {
+            region_alignment = region_alignment.max(effective_alignment(next_member.alignment));
+            region_end_offset = region_end_offset.max(next_member.offset + next_member.size);
+        }
+
+        if member.offset % region_alignment != 0 {
+            return false;
+        }
+        // Members placed after the previous ones must directly follow them.
+        // Others overlap with the previous members (e.g., inside an unnamed
+        // union).
+        if !layout.is_union
+            && member.offset >= end_offset
+            && member.offset != round_up(end_offset, region_alignment)
+        {
+            return false;
+        }
+        end_offset = end_offset.max(region_end_offset);
+    }
+
+    if layout.has_virtual_bases {
+        return true;
+    }
+    let alignment = layout
+        .members
+        .iter()
+        .map(|member| effective_alignment(member.alignment))
+        .max()
+        .unwrap_or(1);
+    round_up(end_offset, alignment) == layout.size
+}
+
+fn round_up(value: u64, alignment: u64) -> u64 {
+    match value % alignment {
+        0 => value,
+        remainder => value + alignment - remainder,
+    }
+}
@@ -2,7 +2,7 @@ use std::{fmt, str::FromStr};
 
 use anyhow::{anyhow, Result};
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum PrimitiveReconstructionFlavor {
     Portable,
     Microsoft,
@@ -41,6 +41,18 @@ pub fn include_headers_for_flavor(flavor: PrimitiveReconstructionFlavor) -> Stri
     .to_string()
 }
 
+/// Same as `include_headers_for_flavor`, for C output.
+pub fn c_include_headers_for_flavor(flavor: PrimitiveReconstructionFlavor) -> String {
+    match flavor {
+        PrimitiveReconstructionFlavor::Portable => {
+            "#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n"
+        }
+        PrimitiveReconstructionFlavor::Microsoft => "#include <Windows.h>\n",
+        PrimitiveReconstructionFlavor::Raw => "",
+    }
+    .to_string()
+}
+
 pub fn primitive_kind_as_str(
     flavor: &PrimitiveReconstructionFlavor,
     primitive_kind: pdb::PrimitiveKind,
@@ -0,0 +1,337 @@
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    flattened_type_name, struct_members_layout,
+    union::Union,
+    union_members_layout, DataFormatConfiguration, Field, MemberLayout, TypeDescription,
+};
+
+/// Rust keywords that must be escaped when used as identifiers.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "union", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+/// Keywords that cannot be used as raw identifiers.
+const RUST_RESERVED_IDENTIFIERS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Kind of `#[repr(C)]` item.
+#[derive(Clone, Copy)]
+enum ItemKind {
+    Struct,
+    Union,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Struct => "struct".fmt(f),
+            ItemKind::Union => "union".fmt(f),
+        }
+    }
+}
+
+/// State shared by the items generated for a single type (i.e., the type
+/// itself and the unnamed unions and structs it contains).
+struct ItemContext {
+    type_name: String,
+    union_count: usize,
+    struct_count: usize,
+    bitfield_count: usize,
+}
+
+pub fn fmt_class(class: &Class, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let mut context = ItemContext::new(&class.name);
+    let mut members = vec![];
+    // Base classes are embedded at the beginning of the struct
+    for (i, base) in class.base_classes.iter().enumerate() {
+        if base.is_virtual {
+            continue;
+        }
+        members.push(format!(
+            "pub __base_{}: {},",
+            i,
+            flattened_type_name(&base.type_name)
+        ));
+    }
+    // Virtual base classes are placed at the end of the object, and located
+    // through a virtual base table
+    if class.base_classes.iter().any(|base| base.is_virtual) {
+        members.push("pub __vbtable: *const core::ffi::c_void,".to_string());
+    }
+    // Classes with virtual methods (and no base to inherit it from) start
+    // with a pointer to their virtual table
+    if class.base_classes.is_empty() && class.instance_methods.iter().any(|m| m.is_virtual) {
+        members.push("pub __vftable: *const core::ffi::c_void,".to_string());
+    }
+
+    let item_name = context.type_name.clone();
+    fmt_item(
+        &mut context,
+        ItemKind::Struct,
+        &item_name,
+        members,
+        &struct_members_layout(&class.fields),
+        f,
+    )
+}
+
+pub fn fmt_union(u: &Union, f: &mut impl std::fmt::Write) -> fmt::Result {
+    let mut context = ItemContext::new(&u.name);
+    let item_name = context.type_name.clone();
+    fmt_item(
+        &mut context,
+        ItemKind::Union,
+        &item_name,
+        vec![],
+        &union_members_layout(&u.fields),
+        f,
+    )
+}
+
+/// Enums are represented by a type alias and a set of constants, as Rust
+/// enums cannot hold values that aren't explicitly declared.
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let enum_name = flattened_type_name(&e.name);
+    writeln!(
+        f,
+        "pub type {} = {};",
+        enum_name,
+        rust_type(&e.underlying_type).unwrap_or_else(|| "i32".to_string())
+    )?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "pub const {}_{}: {} = {};",
+            enum_name,
+            value.name,
+            enum_name,
+            fmt_enum_value(value.value, fmt_configuration.print_enum_values_in_hex)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Types without definition are represented by opaque structs.
+pub fn fmt_opaque_struct(type_name: &str, f: &mut impl std::fmt::Write) -> fmt::Result {
+    writeln!(f, "#[repr(C)]")?;
+    writeln!(f, "#[derive(Clone, Copy)]")?;
+    writeln!(f, "pub struct {} {{", flattened_type_name(type_name))?;
+    writeln!(f, "    _opaque: [u8; 0],")?;
+    writeln!(f, "}}")
+}
+
+pub fn fmt_size_assert(
+    type_name: &str,
+    type_size: u64,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "const _: () = assert!(core::mem::size_of::<{}>() == {:#x});",
+        flattened_type_name(type_name),
+        type_size
+    )
+}
+
+impl ItemContext {
+    fn new(type_name: &str) -> Self {
+        Self {
+            type_name: flattened_type_name(type_name),
+            union_count: 0,
+            struct_count: 0,
+            bitfield_count: 0,
+        }
+    }
+}
+
+/// Write a `#[repr(C)]` item containing the given members. Since Rust doesn't
+/// support unnamed unions and structs, separate items are written (before
+/// this one) for each of them.
+fn fmt_item(
+    context: &mut ItemContext,
+    kind: ItemKind,
+    item_name: &str,
+    mut members: Vec<String>,
+    layout: &[MemberLayout],
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    for member in layout {
+        match member {
+            MemberLayout::Field(field) if field.bitfield.is_some() => {
+                members.push(bitfield_storage_member(context, &[field]));
+            }
+            MemberLayout::Field(field) => members.push(field_member(field)),
+            MemberLayout::Bitfields(bitfields) => {
+                members.push(bitfield_storage_member(context, bitfields));
+            }
+            MemberLayout::Union(union_layout) => {
+                let union_name = format!("{}__union_{}", context.type_name, context.union_count);
+                let member_name = format!("__union_{}", context.union_count);
+                context.union_count += 1;
+                fmt_item(
+                    context,
+                    ItemKind::Union,
+                    &union_name,
+                    vec![],
+                    union_layout,
+                    f,
+                )?;
+                writeln!(f)?;
+                members.push(format!("pub {}: {},", member_name, union_name));
+            }
+            MemberLayout::Struct(struct_layout) => {
+                let struct_name = format!("{}__struct_{}", context.type_name, context.struct_count);
+                let member_name = format!("__struct_{}", context.struct_count);
+                context.struct_count += 1;
+                fmt_item(
+                    context,
+                    ItemKind::Struct,
+                    &struct_name,
+                    vec![],
+                    struct_layout,
+                    f,
+                )?;
+                writeln!(f)?;
+                members.push(format!("pub {}: {},", member_name, struct_name));
+            }
+        }
+    }
+
+    writeln!(f, "#[repr(C)]")?;
+    writeln!(f, "#[derive(Clone, Copy)]")?;
+    writeln!(f, "pub {} {} {{", kind, item_name)?;
+    for member in members {
+        writeln!(f, "    {}", member)?;
+    }
+    writeln!(f, "}}")
+}
+
+/// Return a member covering the storage of the given bitfields. Bitfields
+/// aren't translated, as their layout is implementation-defined.
+fn bitfield_storage_member(context: &mut ItemContext, bitfields: &[&Field]) -> String {
+    let storage_offset = bitfields[0].offset;
+    let storage_size = bitfields.iter().map(|field| field.size).max().unwrap_or(0);
+    let mut member = format!(
+        "pub _bitfield_{}: {}, // TODO: bitfields in bytes {:#x}..{:#x}:",
+        context.bitfield_count,
+        rust_type(&bitfields[0].type_description)
+            .unwrap_or_else(|| format!("[u8; {}]", storage_size)),
+        storage_offset,
+        storage_offset as usize + storage_size
+    );
+    context.bitfield_count += 1;
+    for (i, field) in bitfields.iter().enumerate() {
+        if let Some(bitfield) = field.bitfield {
+            member.push_str(&format!(
+                "{} {} (bit {}, length {})",
+                if i > 0 { "," } else { "" },
+                field.name,
+                bitfield.position,
+                bitfield.length
+            ));
+        }
+    }
+
+    member
+}
+
+fn field_member(field: &Field) -> String {
+    let field_name = escape_identifier(&field.name.to_string());
+    match rust_type(&field.type_description) {
+        Some(type_name) => format!("pub {}: {},", field_name, type_name),
+        // Types we cannot represent are replaced with raw bytes, keep track
+        // of the original type
+        None => format!(
+            "pub {}: [u8; {}], // {}{}",
+            field_name, field.size, field.type_left, field.type_right
+        ),
+    }
+}
+
+fn rust_type(type_description: &TypeDescription) -> Option<String> {
+    match type_description {
+        TypeDescription::Primitive(kind) => rust_primitive_type(*kind).map(str::to_string),
+        TypeDescription::Class(name) | TypeDescription::Union(name) => {
+            Some(flattened_type_name(name))
+        }
+        TypeDescription::Enum { name, .. } => Some(flattened_type_name(name)),
+        TypeDescription::Pointer { pointee, .. } => {
+            let is_const = matches!(
+                pointee.as_ref(),
+                TypeDescription::Modifier { constant: true, .. }
+            );
+            let pointee_type = match pointee.strip_modifiers() {
+                // Function pointers are kept opaque
+                TypeDescription::Function { .. } => "core::ffi::c_void".to_string(),
+                other => rust_type(other).unwrap_or_else(|| "core::ffi::c_void".to_string()),
+            };
+            Some(format!(
+                "*{} {}",
+                if is_const { "const" } else { "mut" },
+                pointee_type
+            ))
+        }
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => rust_type(underlying_type),
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => {
+            let element_type = rust_type(element_type)?;
+            Some(
+                dimensions
+                    .iter()
+                    .rev()
+                    .fold(element_type, |type_name, dim| {
+                        format!("[{}; {}]", type_name, dim)
+                    }),
+            )
+        }
+        TypeDescription::Function { .. } | TypeDescription::Unknown => None,
+    }
+}
+
+fn rust_primitive_type(kind: pdb::PrimitiveKind) -> Option<&'static str> {
+    let type_name = match kind {
+        pdb::PrimitiveKind::Void => "core::ffi::c_void",
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => "i8",
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 => "u8",
+        pdb::PrimitiveKind::WChar | pdb::PrimitiveKind::RChar16 => "u16",
+        pdb::PrimitiveKind::RChar32 => "u32",
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "i16",
+        pdb::PrimitiveKind::UShort | pdb::PrimitiveKind::U16 | pdb::PrimitiveKind::Bool16 => "u16",
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => "i32",
+        pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 | pdb::PrimitiveKind::Bool32 => "u32",
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => "i64",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 | pdb::PrimitiveKind::Bool64 => "u64",
+        pdb::PrimitiveKind::Octa | pdb::PrimitiveKind::I128 => "i128",
+        pdb::PrimitiveKind::UOcta | pdb::PrimitiveKind::U128 => "u128",
+        pdb::PrimitiveKind::F32 => "f32",
+        pdb::PrimitiveKind::F64 => "f64",
+        pdb::PrimitiveKind::Bool8 => "bool",
+        _ => return None,
+    };
+
+    Some(type_name)
+}
+
+fn escape_identifier(identifier: &str) -> String {
+    if RUST_RESERVED_IDENTIFIERS.contains(&identifier) {
+        format!("{}_", identifier)
+    } else if RUST_KEYWORDS.contains(&identifier) {
+        format!("r#{}", identifier)
+    } else {
+        identifier.to_string()
+    }
+}
@@ -0,0 +1,289 @@
+/// Friendly aliases of common STL template instantiations, as pairs of
+/// (pattern, alias). `$N` placeholders match a single template argument and
+/// must designate the same type everywhere they appear in a pattern.
+/// Whitespace is insignificant in patterns.
+const STL_TEMPLATE_ALIASES: &[(&str, &str)] = &[
+    // Strings
+    (
+        "std::basic_string<char,std::char_traits<char>,std::allocator<char>>",
+        "std::string",
+    ),
+    (
+        "std::basic_string<wchar_t,std::char_traits<wchar_t>,std::allocator<wchar_t>>",
+        "std::wstring",
+    ),
+    (
+        "std::basic_string<char16_t,std::char_traits<char16_t>,std::allocator<char16_t>>",
+        "std::u16string",
+    ),
+    (
+        "std::basic_string<char32_t,std::char_traits<char32_t>,std::allocator<char32_t>>",
+        "std::u32string",
+    ),
+    (
+        "std::basic_string_view<char,std::char_traits<char>>",
+        "std::string_view",
+    ),
+    (
+        "std::basic_string_view<wchar_t,std::char_traits<wchar_t>>",
+        "std::wstring_view",
+    ),
+    // Sequence containers
+    ("std::vector<$0,std::allocator<$0>>", "std::vector<$0>"),
+    ("std::list<$0,std::allocator<$0>>", "std::list<$0>"),
+    ("std::forward_list<$0,std::allocator<$0>>", "std::forward_list<$0>"),
+    ("std::deque<$0,std::allocator<$0>>", "std::deque<$0>"),
+    ("std::queue<$0,std::deque<$0>>", "std::queue<$0>"),
+    ("std::stack<$0,std::deque<$0>>", "std::stack<$0>"),
+    // Associative containers
+    (
+        "std::map<$0,$1,std::less<$0>,std::allocator<std::pair<$0 const,$1>>>",
+        "std::map<$0,$1>",
+    ),
+    (
+        "std::multimap<$0,$1,std::less<$0>,std::allocator<std::pair<$0 const,$1>>>",
+        "std::multimap<$0,$1>",
+    ),
+    ("std::set<$0,std::less<$0>,std::allocator<$0>>", "std::set<$0>"),
+    (
+        "std::multiset<$0,std::less<$0>,std::allocator<$0>>",
+        "std::multiset<$0>",
+    ),
+    (
+        "std::unordered_map<$0,$1,std::hash<$0>,std::equal_to<$0>,std::allocator<std::pair<$0 const,$1>>>",
+        "std::unordered_map<$0,$1>",
+    ),
+    (
+        "std::unordered_multimap<$0,$1,std::hash<$0>,std::equal_to<$0>,std::allocator<std::pair<$0 const,$1>>>",
+        "std::unordered_multimap<$0,$1>",
+    ),
+    (
+        "std::unordered_set<$0,std::hash<$0>,std::equal_to<$0>,std::allocator<$0>>",
+        "std::unordered_set<$0>",
+    ),
+    (
+        "std::unordered_multiset<$0,std::hash<$0>,std::equal_to<$0>,std::allocator<$0>>",
+        "std::unordered_multiset<$0>",
+    ),
+    // Smart pointers
+    (
+        "std::unique_ptr<$0,std::default_delete<$0>>",
+        "std::unique_ptr<$0>",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Word,
+    Punctuation,
+    /// `$N`, only found in patterns
+    Placeholder(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    /// Position of the token in the tokenized string
+    start: usize,
+    end: usize,
+}
+
+/// Replace instantiations of common STL templates found in `text` with their
+/// friendly aliases (e.g., `std::vector<int,std::allocator<int> >` becomes
+/// `std::vector<int>`). Template arguments are preserved (and prettified as
+/// well), the rest of the text is left untouched.
+pub fn pretty_template_names(text: &str) -> String {
+    // Parse patterns once
+    thread_local! {
+        static ALIASES: Vec<(Vec<Token<'static>>, &'static str)> = STL_TEMPLATE_ALIASES
+            .iter()
+            .map(|(pattern, alias)| (tokenize(pattern, true), *alias))
+            .collect();
+    }
+
+    ALIASES.with(|aliases| replace_aliases(text, aliases))
+}
+
+fn replace_aliases(text: &str, aliases: &[(Vec<Token>, &str)]) -> String {
+    let tokens = tokenize(text, false);
+    let mut output = String::with_capacity(text.len());
+    let mut copied_until = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        // Only match full names (i.e., not `ns::std::vector`)
+        let is_name_start = i == 0 || tokens[i - 1].text != "::";
+        let matched_alias = if is_name_start {
+            aliases.iter().find_map(|(pattern, alias)| {
+                let mut bindings = Vec::new();
+                match_tokens(pattern, &tokens, i, &mut bindings).map(|end| (end, alias, bindings))
+            })
+        } else {
+            None
+        };
+
+        match matched_alias {
+            Some((end, alias, bindings)) => {
+                output.push_str(&text[copied_until..tokens[i].start]);
+                output.push_str(&expand_alias(text, alias, &bindings, aliases));
+                copied_until = tokens[end - 1].end;
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+    output.push_str(&text[copied_until..]);
+
+    output
+}
+
+/// Replace placeholders in `alias` with the text they're bound to.
+fn expand_alias(
+    text: &str,
+    alias: &str,
+    bindings: &[Option<(usize, usize)>],
+    aliases: &[(Vec<Token>, &str)],
+) -> String {
+    let mut expanded_alias = String::with_capacity(alias.len());
+    let mut chars = alias.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek().and_then(|c| c.to_digit(10))) {
+            ('$', Some(placeholder)) => {
+                chars.next();
+                if let Some(Some((start, end))) = bindings.get(placeholder as usize) {
+                    // Template arguments may be instantiations themselves
+                    expanded_alias.push_str(&replace_aliases(&text[*start..*end], aliases));
+                }
+            }
+            _ => expanded_alias.push(c),
+        }
+    }
+
+    expanded_alias
+}
+
+/// Match `pattern` against `tokens`, starting at `position`. Return the
+/// position following the match if successful. Bound placeholders are stored
+/// in `bindings`, as ranges of `text`.
+fn match_tokens(
+    pattern: &[Token],
+    tokens: &[Token],
+    position: usize,
+    bindings: &mut Vec<Option<(usize, usize)>>,
+) -> Option<usize> {
+    let (pattern_token, remaining_pattern) = match pattern.split_first() {
+        None => return Some(position),
+        Some(split) => split,
+    };
+
+    match pattern_token.kind {
+        TokenKind::Placeholder(placeholder) => {
+            if bindings.len() <= placeholder {
+                bindings.resize(placeholder + 1, None);
+            }
+            let previous_binding = bindings[placeholder];
+            // Try candidates from the shortest to the longest one. Template
+            // arguments cannot contain unbalanced brackets or top-level
+            // commas.
+            let mut depth = 0usize;
+            for (end, token) in tokens.iter().enumerate().skip(position) {
+                match token.text {
+                    "<" | "(" | "[" => depth += 1,
+                    ">" | ")" | "]" if depth == 0 => return None,
+                    ">" | ")" | "]" => depth -= 1,
+                    "," if depth == 0 => return None,
+                    _ => {}
+                }
+                if depth != 0 {
+                    continue;
+                }
+
+                let candidate = &tokens[position..=end];
+                let is_consistent = match previous_binding {
+                    None => true,
+                    Some((start, bound_end)) => {
+                        tokens_text(tokens, start, bound_end).eq(candidate.iter().map(|t| t.text))
+                    }
+                };
+                if !is_consistent {
+                    continue;
+                }
+
+                bindings[placeholder] = Some((candidate[0].start, token.end));
+                if let Some(match_end) = match_tokens(remaining_pattern, tokens, end + 1, bindings)
+                {
+                    return Some(match_end);
+                }
+                bindings[placeholder] = previous_binding;
+            }
+
+            None
+        }
+        _ => match tokens.get(position) {
+            Some(token) if token.text == pattern_token.text => {
+                match_tokens(remaining_pattern, tokens, position + 1, bindings)
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Return the text of the tokens contained in the `start..end` range of the
+/// tokenized string.
+fn tokens_text<'a>(
+    tokens: &'a [Token<'a>],
+    start: usize,
+    end: usize,
+) -> impl Iterator<Item = &'a str> {
+    tokens
+        .iter()
+        .skip_while(move |token| token.start < start)
+        .take_while(move |token| token.end <= end)
+        .map(|token| token.text)
+}
+
+fn tokenize(text: &str, is_pattern: bool) -> Vec<Token<'_>> {
+    let is_word_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '@' | '?');
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        let kind = if c.is_whitespace() {
+            continue;
+        } else if is_pattern && c == '$' {
+            let digit = chars.peek().and_then(|(_, c)| c.to_digit(10));
+            match digit {
+                Some(placeholder) => {
+                    chars.next();
+                    end += 1;
+                    TokenKind::Placeholder(placeholder as usize)
+                }
+                None => TokenKind::Punctuation,
+            }
+        } else if is_word_char(c) {
+            while let Some((position, c)) = chars.peek().copied() {
+                if !is_word_char(c) {
+                    break;
+                }
+                chars.next();
+                end = position + c.len_utf8();
+            }
+            TokenKind::Word
+        } else if c == ':' && matches!(chars.peek(), Some((_, ':'))) {
+            chars.next();
+            end += 1;
+            TokenKind::Punctuation
+        } else {
+            TokenKind::Punctuation
+        };
+
+        tokens.push(Token {
+            kind,
+            text: &text[start..end],
+            start,
+            end,
+        });
+    }
+
+    tokens
+}
@@ -0,0 +1,246 @@
+use anyhow::{anyhow, Result};
+
+use super::{is_unnamed_type, resolve_complete_type_index, type_size, TypeForwarder};
+
+/// Language-agnostic description of a type, used by formatters that cannot
+/// rely on C++ type names (e.g., C#).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDescription {
+    Primitive(pdb::PrimitiveKind),
+    /// Class, struct or interface, identified by its name
+    Class(String),
+    /// Union, identified by its name
+    Union(String),
+    /// Enum, identified by its name
+    Enum {
+        name: String,
+        underlying_type: Box<TypeDescription>,
+    },
+    /// Pointer (or reference) of `size` bytes
+    Pointer {
+        pointee: Box<TypeDescription>,
+        size: usize,
+        is_reference: bool,
+    },
+    Modifier {
+        underlying_type: Box<TypeDescription>,
+        constant: bool,
+        volatile: bool,
+    },
+    /// Array of `element_type`, dimensions are ordered from the outermost to
+    /// the innermost one (i.e., in declaration order)
+    Array {
+        element_type: Box<TypeDescription>,
+        dimensions: Vec<usize>,
+    },
+    /// Procedure or member function, only reachable through pointers
+    Function {
+        return_type: Box<TypeDescription>,
+        arguments: Vec<TypeDescription>,
+    },
+    Unknown,
+}
+
+impl TypeDescription {
+    /// Return the description with top-level modifiers (i.e., `const` and
+    /// `volatile`) removed.
+    pub fn strip_modifiers(&self) -> &TypeDescription {
+        match self {
+            TypeDescription::Modifier {
+                underlying_type, ..
+            } => underlying_type.strip_modifiers(),
+            other => other,
+        }
+    }
+}
+
+/// Return a `TypeDescription` of the given `type_index`.
+/// Bitfields are described by their underlying type, and typedefs by the
+/// type they alias.
+pub fn type_description(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<TypeDescription> {
+    let type_index = type_forwarder.resolve_type_alias(type_index);
+    let description = match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Primitive(data) => {
+            let primitive = TypeDescription::Primitive(data.kind);
+            if let Some(indirection) = data.indirection {
+                TypeDescription::Pointer {
+                    pointee: Box::new(primitive),
+                    size: match indirection {
+                        pdb::Indirection::Near16
+                        | pdb::Indirection::Far16
+                        | pdb::Indirection::Huge16 => 2,
+                        pdb::Indirection::Near32 | pdb::Indirection::Far32 => 4,
+                        pdb::Indirection::Near64 => 8,
+                        pdb::Indirection::Near128 => 16,
+                    },
+                    is_reference: false,
+                }
+            } else {
+                primitive
+            }
+        }
+
+        pdb::TypeData::Class(data) => {
+            TypeDescription::Class(udt_name(&data.name.to_string(), type_index))
+        }
+
+        pdb::TypeData::Union(data) => {
+            TypeDescription::Union(udt_name(&data.name.to_string(), type_index))
+        }
+
+        pdb::TypeData::Enumeration(data) => TypeDescription::Enum {
+            name: udt_name(&data.name.to_string(), type_index),
+            underlying_type: Box::new(type_description(
+                type_finder,
+                type_forwarder,
+                data.underlying_type,
+            )?),
+        },
+
+        pdb::TypeData::Pointer(data) => {
+            // Resolve the complete type's index, if present in the PDB
+            let complete_underlying_type_index =
+                resolve_complete_type_index(type_forwarder, data.underlying_type);
+            TypeDescription::Pointer {
+                pointee: Box::new(type_description(
+                    type_finder,
+                    type_forwarder,
+                    complete_underlying_type_index,
+                )?),
+                size: type_size(type_finder, type_forwarder, type_index)?,
+                is_reference: data.attributes.is_reference(),
+            }
+        }
+
+        pdb::TypeData::Modifier(data) => {
+            // Resolve the complete type's index, if present in the PDB
+            let complete_underlying_type_index =
+                resolve_complete_type_index(type_forwarder, data.underlying_type);
+            TypeDescription::Modifier {
+                underlying_type: Box::new(type_description(
+                    type_finder,
+                    type_forwarder,
+                    complete_underlying_type_index,
+                )?),
+                constant: data.constant,
+                volatile: data.volatile,
+            }
+        }
+
+        pdb::TypeData::Array(_) => {
+            let (element_type, mut dimensions) =
+                array_description(type_finder, type_forwarder, type_index)?;
+            // Note: Dimensions are collected from the innermost to the
+            // outermost one
+            dimensions.reverse();
+            TypeDescription::Array {
+                element_type: Box::new(element_type),
+                dimensions,
+            }
+        }
+
+        pdb::TypeData::Bitfield(data) => {
+            // Resolve the complete type's index, if present in the PDB
+            let complete_underlying_type_index =
+                resolve_complete_type_index(type_forwarder, data.underlying_type);
+            type_description(type_finder, type_forwarder, complete_underlying_type_index)?
+        }
+
+        pdb::TypeData::Procedure(data) => TypeDescription::Function {
+            return_type: Box::new(match data.return_type {
+                Some(return_type) => type_description(
+                    type_finder,
+                    type_forwarder,
+                    resolve_complete_type_index(type_forwarder, return_type),
+                )?,
+                None => TypeDescription::Primitive(pdb::PrimitiveKind::Void),
+            }),
+            arguments: argument_list_description(type_finder, type_forwarder, data.argument_list)?,
+        },
+
+        // Note: The implicit `this` argument isn't part of the description
+        pdb::TypeData::MemberFunction(data) => TypeDescription::Function {
+            return_type: Box::new(type_description(
+                type_finder,
+                type_forwarder,
+                resolve_complete_type_index(type_forwarder, data.return_type),
+            )?),
+            arguments: argument_list_description(type_finder, type_forwarder, data.argument_list)?,
+        },
+
+        _ => TypeDescription::Unknown,
+    };
+
+    Ok(description)
+}
+
+/// Return the element type of the given array, as well as its dimensions
+/// (from the innermost to the outermost one).
+fn array_description(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<(TypeDescription, Vec<usize>)> {
+    match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Array(data) => {
+            // Resolve the complete type's index, if present in the PDB
+            let complete_element_type_index =
+                resolve_complete_type_index(type_forwarder, data.element_type);
+            let (element_type, mut dimensions) =
+                array_description(type_finder, type_forwarder, complete_element_type_index)?;
+            let element_size = u32::try_from(type_size(
+                type_finder,
+                type_forwarder,
+                complete_element_type_index,
+            )?)?;
+            // Note: Dimensions are expressed in bytes, convert them into
+            // element counts
+            let mut divider = std::cmp::max(element_size, 1);
+            dimensions.extend(data.dimensions.into_iter().map(|dim_size| {
+                let result = dim_size / divider;
+                divider = std::cmp::max(dim_size, 1);
+                result as usize
+            }));
+
+            Ok((element_type, dimensions))
+        }
+        _ => Ok((
+            type_description(type_finder, type_forwarder, type_index)?,
+            vec![],
+        )),
+    }
+}
+
+fn argument_list_description(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+) -> Result<Vec<TypeDescription>> {
+    match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::ArgumentList(data) => data
+            .arguments
+            .into_iter()
+            .map(|argument_type| {
+                type_description(
+                    type_finder,
+                    type_forwarder,
+                    resolve_complete_type_index(type_forwarder, argument_type),
+                )
+            })
+            .collect(),
+        _ => Err(anyhow!("argument list of non-argument-list type")),
+    }
+}
+
+pub(super) fn udt_name(name: &str, type_index: pdb::TypeIndex) -> String {
+    // Rename unnamed anonymous tags to something unique
+    if is_unnamed_type(name) {
+        format!("_unnamed_{}", type_index)
+    } else {
+        name.to_string()
+    }
+}
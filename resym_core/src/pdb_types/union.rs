@@ -3,19 +3,22 @@ use std::fmt;
 use anyhow::{anyhow, Result};
 
 use super::{
+    bitfield_info,
     class::Class,
     enumeration::Enum,
     field::{FieldAccess, StaticField},
     fmt_union_fields_recursive, is_unnamed_type,
+    packing::Packing,
     primitive_types::PrimitiveReconstructionFlavor,
-    resolve_complete_type_index, type_name, type_size, DataFormatConfiguration, Field, Method,
-    TypeForwarder, TypeSet,
+    resolve_complete_type_index, type_description, type_name, type_size, udt_packing,
+    DataFormatConfiguration, Field, Method, TypeForwarder, TypeSet,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Union<'p> {
     pub name: String,
     pub size: u32,
+    /// Data members, in declaration order
     pub fields: Vec<Field<'p>>,
     pub static_fields: Vec<StaticField<'p>>,
     pub instance_methods: Vec<Method<'p>>,
@@ -23,6 +26,8 @@ pub struct Union<'p> {
     pub nested_classes: Vec<Class<'p>>,
     pub nested_unions: Vec<Union<'p>>,
     pub nested_enums: Vec<Enum<'p>>,
+    /// Packing inferred from the offsets of the members
+    pub packing: Packing,
 }
 
 impl<'p> Union<'p> {
@@ -82,6 +87,8 @@ impl<'p> Union<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    vtable_pointer_size: None,
+                    packing: udt_packing(type_finder, type_forwarder, type_index),
                 };
 
                 if let Some(derived_from) = data.derived_from {
@@ -120,6 +127,7 @@ impl<'p> Union<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    packing: udt_packing(type_finder, type_forwarder, type_index),
                 };
 
                 u.add_fields(
@@ -144,6 +152,8 @@ impl<'p> Union<'p> {
 
                 let mut e = Enum {
                     name,
+                    scoped: data.properties.scoped_definition(),
+                    size: type_size(type_finder, type_forwarder, data.underlying_type)?,
                     underlying_type_name: type_name(
                         type_finder,
                         type_forwarder,
@@ -152,6 +162,11 @@ impl<'p> Union<'p> {
                         needed_types,
                     )?
                     .0,
+                    underlying_type: type_description(
+                        type_finder,
+                        type_forwarder,
+                        data.underlying_type,
+                    )?,
                     values: Vec::new(),
                 };
 
@@ -192,31 +207,34 @@ impl<'p> Union<'p> {
                 let (type_left, type_right) = type_name(
                     type_finder,
                     type_forwarder,
-                    complete_type_index,
+                    data.field_type,
                     primitive_flavor,
                     needed_types,
                 )?;
-                let type_size = type_size(type_finder, complete_type_index)?;
+                let type_size = type_size(type_finder, type_forwarder, complete_type_index)?;
+                let bitfield = bitfield_info(type_finder, complete_type_index)?;
+                let type_description =
+                    type_description(type_finder, type_forwarder, complete_type_index)?;
                 let access = FieldAccess::from_field_attribute(data.attributes.access());
 
                 // TODO: attributes (static, virtual, etc.)
                 self.fields.push(Field {
                     type_left,
                     type_right,
+                    type_description,
                     name: data.name,
                     offset: data.offset,
                     size: type_size,
+                    bitfield,
                     access,
                 });
             }
 
             pdb::TypeData::StaticMember(ref data) => {
-                let complete_type_index =
-                    resolve_complete_type_index(type_forwarder, data.field_type);
                 let (type_left, type_right) = type_name(
                     type_finder,
                     type_forwarder,
-                    complete_type_index,
+                    data.field_type,
                     primitive_flavor,
                     needed_types,
                 )?;
@@ -231,7 +249,7 @@ impl<'p> Union<'p> {
             }
 
             pdb::TypeData::Method(ref data) => {
-                let method = Method::find(
+                let mut method = Method::find(
                     data.name,
                     data.attributes,
                     type_finder,
@@ -240,6 +258,7 @@ impl<'p> Union<'p> {
                     primitive_flavor,
                     needed_types,
                 )?;
+                method.vtable_offset = data.vtable_offset;
                 if data.attributes.is_static() {
                     self.static_methods.push(method);
                 } else {
@@ -268,11 +287,11 @@ impl<'p> Union<'p> {
                         for pdb::MethodListEntry {
                             attributes,
                             method_type,
-                            ..
+                            vtable_offset,
                         } in method_list.methods
                         {
                             // hooray
-                            let method = Method::find(
+                            let mut method = Method::find(
                                 data.name,
                                 attributes,
                                 type_finder,
@@ -281,6 +300,7 @@ impl<'p> Union<'p> {
                                 primitive_flavor,
                                 needed_types,
                             )?;
+                            method.vtable_offset = vtable_offset;
 
                             if attributes.is_static() {
                                 self.static_methods.push(method);
@@ -332,12 +352,12 @@ impl<'p> Union<'p> {
         if !self.nested_enums.is_empty() {
             writeln!(f, "  ")?;
             for e in &self.nested_enums {
-                e.reconstruct(f)?;
+                e.reconstruct(fmt_configuration, f)?;
             }
         }
 
         // Dump fields while detecting unnamed structs and unions
-        fmt_union_fields_recursive(fmt_configuration, &self.fields, 1, f)?;
+        fmt_union_fields_recursive(fmt_configuration, &self.fields, 1, &mut 0, f)?;
 
         // Static fields
         for field in &self.static_fields {
@@ -355,10 +375,10 @@ impl<'p> Union<'p> {
             )?;
         }
 
-        if !self.instance_methods.is_empty() {
+        if fmt_configuration.print_methods && !self.instance_methods.is_empty() {
             writeln!(f, "  ")?;
             for method in &self.instance_methods {
-                writeln!(
+                write!(
                     f,
                     "  {}{}{}{}{}({}){}{}{}{};",
                     if fmt_configuration.print_access_specifiers {
@@ -384,13 +404,15 @@ impl<'p> Union<'p> {
                     if method.is_volatile { " volatile" } else { "" },
                     if method.is_pure_virtual { " = 0" } else { "" },
                 )?;
+                method.fmt_annotations(f)?;
+                writeln!(f)?;
             }
         }
 
-        if !self.static_methods.is_empty() {
+        if fmt_configuration.print_methods && !self.static_methods.is_empty() {
             writeln!(f, "  ")?;
             for method in &self.static_methods {
-                writeln!(
+                write!(
                     f,
                     "  {}{}static {}{}{}({}){}{}{};",
                     if fmt_configuration.print_access_specifiers {
@@ -411,6 +433,8 @@ impl<'p> Union<'p> {
                     if method.is_const { " const" } else { "" },
                     if method.is_volatile { " volatile" } else { "" },
                 )?;
+                method.fmt_annotations(f)?;
+                writeln!(f)?;
             }
         }
 
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::{
+    class::Class,
+    enumeration::{fmt_enum_value, Enum},
+    primitive_types::primitive_kind_as_str,
+    union::Union,
+    DataFormatConfiguration, Definition, Field, PrimitiveReconstructionFlavor, TypeDescription,
+};
+
+/// Width member names are padded to, as done by WinDbg's `dt`
+const MEMBER_NAME_WIDTH: usize = 16;
+/// Embedded types are expanded up to this depth
+const MAX_EXPANSION_DEPTH: usize = 8;
+
+/// Definitions of the types that can be expanded in the layout of the types
+/// that embed them, by type name.
+pub type EmbeddedDefinitions<'a, 'p> = BTreeMap<&'a str, Definition<'a, 'p>>;
+
+pub fn fmt_class(
+    class: &Class,
+    definitions: &EmbeddedDefinitions,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "{}", class.name)?;
+    fmt_class_members(class, definitions, 1, f)
+}
+
+pub fn fmt_union(
+    u: &Union,
+    definitions: &EmbeddedDefinitions,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "{}", u.name)?;
+    fmt_fields(&u.fields, definitions, 1, f)
+}
+
+pub fn fmt_enum(
+    e: &Enum,
+    fmt_configuration: &DataFormatConfiguration,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    writeln!(f, "{}", e.name)?;
+    for value in &e.values {
+        writeln!(
+            f,
+            "   {} = {}",
+            value.name,
+            if fmt_configuration.print_enum_values_in_hex {
+                fmt_enum_value(value.value, true)
+            } else {
+                // WinDbg's prefix for decimal numbers
+                format!("0n{}", fmt_enum_value(value.value, false))
+            }
+        )?;
+    }
+
+    Ok(())
+}
+
+fn fmt_class_members(
+    class: &Class,
+    definitions: &EmbeddedDefinitions,
+    depth: usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let indentation = "   ".repeat(depth);
+    for base in &class.base_classes {
+        if base.is_virtual {
+            continue;
+        }
+        writeln!(
+            f,
+            "{}+{:#05x} __BaseClass {}",
+            indentation, base.offset, base.type_name
+        )?;
+        fmt_embedded_type(&base.type_name, definitions, depth + 1, f)?;
+    }
+
+    fmt_fields(&class.fields, definitions, depth, f)
+}
+
+/// Write the given fields, in declaration order. Members of unnamed unions
+/// and structs are listed along with the other members, at their offset.
+fn fmt_fields(
+    fields: &[Field],
+    definitions: &EmbeddedDefinitions,
+    depth: usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let indentation = "   ".repeat(depth);
+    for field in fields {
+        let member_type = match (field.bitfield, &field.type_description) {
+            (Some(bitfield), _) => format!(
+                "Pos {}, {} Bit{}",
+                bitfield.position,
+                bitfield.length,
+                if bitfield.length == 1 { "" } else { "s" }
+            ),
+            (None, TypeDescription::Unknown) => format!("{}{}", field.type_left, field.type_right),
+            (None, type_description) => windbg_type(type_description),
+        };
+        writeln!(
+            f,
+            "{}+{:#05x} {:<width$} : {}",
+            indentation,
+            field.offset,
+            field.name.to_string(),
+            member_type,
+            width = MEMBER_NAME_WIDTH
+        )?;
+
+        // Expand embedded types (but not the ones accessed through pointers)
+        if field.bitfield.is_none() {
+            match field.type_description.strip_modifiers() {
+                TypeDescription::Class(name) | TypeDescription::Union(name) => {
+                    fmt_embedded_type(name, definitions, depth + 1, f)?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the members of the embedded type `type_name` if it's defined.
+/// Offsets are relative to the beginning of the embedded type.
+fn fmt_embedded_type(
+    type_name: &str,
+    definitions: &EmbeddedDefinitions,
+    depth: usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    if depth > MAX_EXPANSION_DEPTH {
+        return Ok(());
+    }
+
+    match definitions.get(type_name) {
+        Some(Definition::Class(class)) => fmt_class_members(class, definitions, depth, f),
+        Some(Definition::Union(u)) => fmt_fields(&u.fields, definitions, depth, f),
+        _ => Ok(()),
+    }
+}
+
+/// Return the name WinDbg gives to the given type.
+fn windbg_type(type_description: &TypeDescription) -> String {
+    match type_description {
+        TypeDescription::Primitive(kind) => windbg_primitive_type(*kind),
+        TypeDescription::Class(name) | TypeDescription::Union(name) => name.clone(),
+        TypeDescription::Enum { name, .. } => name.clone(),
+        // Note: References are represented as pointers
+        TypeDescription::Pointer { pointee, size, .. } => format!(
+            "Ptr{} {}",
+            size * 8,
+            match pointee.strip_modifiers() {
+                TypeDescription::Function { .. } => "Function".to_string(),
+                pointee => windbg_type(pointee),
+            }
+        ),
+        // Qualifiers aren't displayed
+        TypeDescription::Modifier {
+            underlying_type, ..
+        } => windbg_type(underlying_type),
+        TypeDescription::Array {
+            element_type,
+            dimensions,
+        } => {
+            let dimensions = dimensions
+                .iter()
+                .map(|dim| format!("[{}] ", dim))
+                .collect::<String>();
+            format!("{}{}", dimensions, windbg_type(element_type))
+        }
+        TypeDescription::Function { .. } => "Function".to_string(),
+        TypeDescription::Unknown => "Void".to_string(),
+    }
+}
+
+fn windbg_primitive_type(primitive_kind: pdb::PrimitiveKind) -> String {
+    match primitive_kind {
+        pdb::PrimitiveKind::NoType | pdb::PrimitiveKind::Void => "Void",
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => "Char",
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 => "UChar",
+        pdb::PrimitiveKind::WChar | pdb::PrimitiveKind::RChar16 => "Wchar",
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => "Int2B",
+        pdb::PrimitiveKind::UShort | pdb::PrimitiveKind::U16 => "Uint2B",
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => "Int4B",
+        pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 | pdb::PrimitiveKind::RChar32 => {
+            "Uint4B"
+        }
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => "Int8B",
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 => "Uint8B",
+        pdb::PrimitiveKind::Octa | pdb::PrimitiveKind::I128 => "Int16B",
+        pdb::PrimitiveKind::UOcta | pdb::PrimitiveKind::U128 => "Uint16B",
+        pdb::PrimitiveKind::F32 => "Float",
+        pdb::PrimitiveKind::F64 => "Double",
+        pdb::PrimitiveKind::Bool8 => "Bool",
+        other => {
+            return primitive_kind_as_str(&PrimitiveReconstructionFlavor::Raw, other, false)
+                .unwrap_or_else(|err| err.to_string())
+        }
+    }
+    .to_string()
+}
@@ -0,0 +1,304 @@
+use anyhow::{anyhow, Result};
+
+use std::{fmt, fs, path::Path, str::FromStr};
+
+const IMAGE_DOS_SIGNATURE: &[u8] = b"MZ";
+const IMAGE_NT_SIGNATURE: &[u8] = b"PE\0\0";
+const IMAGE_NT_OPTIONAL_HDR32_MAGIC: u16 = 0x10b;
+const IMAGE_NT_OPTIONAL_HDR64_MAGIC: u16 = 0x20b;
+const IMAGE_DIRECTORY_ENTRY_DEBUG: usize = 6;
+const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+const COFF_FILE_HEADER_SIZE: usize = 20;
+const SECTION_HEADER_SIZE: usize = 40;
+const DEBUG_DIRECTORY_ENTRY_SIZE: usize = 28;
+const CODEVIEW_PDB70_SIGNATURE: &[u8] = b"RSDS";
+
+/// GUID, as stored in PEs and PDBs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guid {
+    pub data1: u32,
+    pub data2: u16,
+    pub data3: u16,
+    pub data4: [u8; 8],
+}
+
+impl Guid {
+    /// Read a GUID stored in memory (i.e., with its first three fields in
+    /// little endian). `None` is returned if less than 16 bytes are given.
+    pub fn from_le_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut data4 = [0; 8];
+        data4.copy_from_slice(bytes.get(8..16)?);
+
+        Some(Guid {
+            data1: read_u32(bytes, 0).ok()?,
+            data2: read_u16(bytes, 4).ok()?,
+            data3: read_u16(bytes, 6).ok()?,
+            data4,
+        })
+    }
+
+    /// Format the GUID as 32 uppercase hexadecimal digits, without
+    /// separators (as found in symbol servers' paths).
+    pub fn to_simple_string(&self) -> String {
+        let mut guid_str = format!("{:08X}{:04X}{:04X}", self.data1, self.data2, self.data3);
+        for byte in self.data4 {
+            guid_str.push_str(&format!("{:02X}", byte));
+        }
+
+        guid_str
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let d = &self.data4;
+        write!(
+            f,
+            "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+            self.data1, self.data2, self.data3, d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7]
+        )
+    }
+}
+
+impl FromStr for Guid {
+    type Err = ParseGuidError;
+
+    /// Parse a GUID written with or without separators (and braces).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .trim_start_matches('{')
+            .trim_end_matches('}')
+            .replace('-', "");
+        if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseGuidError {});
+        }
+
+        let parse_u8 = |i: usize| u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16);
+        let mut data4 = [0; 8];
+        for (i, byte) in data4.iter_mut().enumerate() {
+            *byte = parse_u8(8 + i).map_err(|_| ParseGuidError {})?;
+        }
+        Ok(Guid {
+            data1: u32::from_str_radix(&digits[0..8], 16).map_err(|_| ParseGuidError {})?,
+            data2: u16::from_str_radix(&digits[8..12], 16).map_err(|_| ParseGuidError {})?,
+            data3: u16::from_str_radix(&digits[12..16], 16).map_err(|_| ParseGuidError {})?,
+            data4,
+        })
+    }
+}
+
+/// An error returned when parsing a `Guid` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseGuidError {}
+
+impl fmt::Display for ParseGuidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid GUID".fmt(f)
+    }
+}
+
+/// Reference to the PDB of a PE, as found in its CodeView debug directory
+/// entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdbReference {
+    /// Path of the PDB when the PE was linked
+    pub pdb_path: String,
+    pub guid: Guid,
+    pub age: u32,
+}
+
+impl PdbReference {
+    /// Read the reference to the PDB of the PE file at the given path.
+    pub fn from_pe_file(pe_file_path: &Path) -> Result<Self> {
+        Self::from_pe_data(&fs::read(pe_file_path)?)
+    }
+
+    /// Read the reference to the PDB of a PE, given its content.
+    pub fn from_pe_data(pe_data: &[u8]) -> Result<Self> {
+        let pe_headers = PeHeaders::parse(pe_data)?;
+        if pe_headers.directory_count <= IMAGE_DIRECTORY_ENTRY_DEBUG {
+            return Err(anyhow!("the PE has no debug directory"));
+        }
+        let debug_directory_offset =
+            pe_headers.data_directories_offset + IMAGE_DIRECTORY_ENTRY_DEBUG * 8;
+        let debug_directory_rva = read_u32(pe_data, debug_directory_offset)?;
+        let debug_directory_size = read_u32(pe_data, debug_directory_offset + 4)? as usize;
+        if debug_directory_rva == 0 || debug_directory_size == 0 {
+            return Err(anyhow!("the PE has no debug directory"));
+        }
+
+        // Locate the debug directory in the file
+        let debug_directory_file_offset = pe_headers
+            .rva_to_file_offset(pe_data, debug_directory_rva)
+            .ok_or_else(|| anyhow!("the debug directory isn't part of any section"))?;
+
+        for entry_index in 0..debug_directory_size / DEBUG_DIRECTORY_ENTRY_SIZE {
+            let entry_offset =
+                debug_directory_file_offset + entry_index * DEBUG_DIRECTORY_ENTRY_SIZE;
+            if read_u32(pe_data, entry_offset + 12)? != IMAGE_DEBUG_TYPE_CODEVIEW {
+                continue;
+            }
+            let codeview_size = read_u32(pe_data, entry_offset + 16)? as usize;
+            let codeview_offset = read_u32(pe_data, entry_offset + 24)? as usize;
+            let codeview_data = pe_data
+                .get(codeview_offset..codeview_offset + codeview_size)
+                .ok_or_else(|| anyhow!("truncated CodeView record"))?;
+
+            return Self::from_codeview_record(codeview_data);
+        }
+
+        Err(anyhow!("the PE has no CodeView debug information"))
+    }
+
+    fn from_codeview_record(codeview_data: &[u8]) -> Result<Self> {
+        if codeview_data.get(..4) != Some(CODEVIEW_PDB70_SIGNATURE) {
+            return Err(anyhow!("unsupported CodeView record format"));
+        }
+        let guid = Guid::from_le_bytes(&codeview_data[4..])
+            .ok_or_else(|| anyhow!("truncated CodeView record"))?;
+        let age = read_u32(codeview_data, 20)?;
+        let pdb_path = codeview_data[24..]
+            .split(|byte| *byte == 0)
+            .next()
+            .unwrap_or_default();
+
+        Ok(PdbReference {
+            pdb_path: String::from_utf8_lossy(pdb_path).into_owned(),
+            guid,
+            age,
+        })
+    }
+
+    /// Name of the PDB file, without the directories of its path.
+    pub fn pdb_file_name(&self) -> &str {
+        self.pdb_path
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or(&self.pdb_path)
+    }
+
+    /// Identifier of the PDB on symbol servers (i.e., its GUID followed by
+    /// its age).
+    pub fn symbol_server_id(&self) -> String {
+        format!("{}{:X}", self.guid.to_simple_string(), self.age)
+    }
+
+    /// Parse an identifier formatted like `symbol_server_id`'s into a
+    /// GUID and an age.
+    pub fn parse_symbol_server_id(id: &str) -> Result<(Guid, u32)> {
+        let digits = id.trim_start_matches('{').replace(['-', '}'], "");
+        if digits.len() <= 32 || !digits.is_ascii() {
+            return Err(anyhow!("invalid PDB identifier '{}'", id));
+        }
+        let (guid_str, age_str) = digits.split_at(32);
+        let guid = guid_str
+            .parse()
+            .map_err(|_| anyhow!("invalid PDB identifier '{}'", id))?;
+        let age = u32::from_str_radix(age_str, 16)
+            .map_err(|_| anyhow!("invalid PDB identifier '{}'", id))?;
+
+        Ok((guid, age))
+    }
+}
+
+/// Result of the comparison of a PDB with the PDB a PE references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdbVerification {
+    pub pe_reference: PdbReference,
+    /// Name of the PDB file, without the directories of its path
+    pub pdb_file_name: String,
+    pub pdb_guid: Guid,
+    pub pdb_age: u32,
+}
+
+impl PdbVerification {
+    /// Whether the PDB is the one the PE references (i.e., their GUIDs and
+    /// ages are the same).
+    pub fn is_match(&self) -> bool {
+        self.pe_reference.guid == self.pdb_guid && self.pe_reference.age == self.pdb_age
+    }
+
+    /// Whether the PDB file has the name the PE references it by. PDBs can
+    /// be renamed, a different name doesn't imply a mismatch.
+    pub fn is_name_match(&self) -> bool {
+        self.pe_reference
+            .pdb_file_name()
+            .eq_ignore_ascii_case(&self.pdb_file_name)
+    }
+}
+
+/// Read the GUID stored at the given RVA of a PE, given its content.
+pub fn read_guid_at_rva(pe_data: &[u8], rva: u32) -> Result<Guid> {
+    let pe_headers = PeHeaders::parse(pe_data)?;
+    pe_headers
+        .rva_to_file_offset(pe_data, rva)
+        .and_then(|file_offset| Guid::from_le_bytes(pe_data.get(file_offset..)?))
+        .ok_or_else(|| anyhow!("RVA {:#x} isn't part of the PE's content", rva))
+}
+
+/// Location of the PE headers needed to find data in a PE's content.
+struct PeHeaders {
+    data_directories_offset: usize,
+    directory_count: usize,
+    section_table_offset: usize,
+    section_count: usize,
+}
+
+impl PeHeaders {
+    fn parse(pe_data: &[u8]) -> Result<Self> {
+        if pe_data.get(..2) != Some(IMAGE_DOS_SIGNATURE) {
+            return Err(anyhow!("not a PE file"));
+        }
+        let nt_headers_offset = read_u32(pe_data, 0x3C)? as usize;
+        if pe_data.get(nt_headers_offset..nt_headers_offset + 4) != Some(IMAGE_NT_SIGNATURE) {
+            return Err(anyhow!("not a PE file"));
+        }
+
+        let file_header_offset = nt_headers_offset + 4;
+        let section_count = read_u16(pe_data, file_header_offset + 2)? as usize;
+        let optional_header_size = read_u16(pe_data, file_header_offset + 16)? as usize;
+        let optional_header_offset = file_header_offset + COFF_FILE_HEADER_SIZE;
+        let (directory_count_offset, data_directories_offset) =
+            match read_u16(pe_data, optional_header_offset)? {
+                IMAGE_NT_OPTIONAL_HDR32_MAGIC => (92, 96),
+                IMAGE_NT_OPTIONAL_HDR64_MAGIC => (108, 112),
+                magic => return Err(anyhow!("invalid optional header magic: {:#x}", magic)),
+            };
+
+        Ok(Self {
+            data_directories_offset: optional_header_offset + data_directories_offset,
+            directory_count: read_u32(pe_data, optional_header_offset + directory_count_offset)?
+                as usize,
+            section_table_offset: optional_header_offset + optional_header_size,
+            section_count,
+        })
+    }
+
+    /// Return the offset in the file of the data stored at the given RVA,
+    /// `None` is returned if the RVA isn't part of any section.
+    fn rva_to_file_offset(&self, pe_data: &[u8], rva: u32) -> Option<usize> {
+        (0..self.section_count)
+            .map(|i| self.section_table_offset + i * SECTION_HEADER_SIZE)
+            .find_map(|section_header_offset| {
+                let virtual_size = read_u32(pe_data, section_header_offset + 8).ok()?;
+                let virtual_address = read_u32(pe_data, section_header_offset + 12).ok()?;
+                let raw_data_size = read_u32(pe_data, section_header_offset + 16).ok()?;
+                let raw_data_offset = read_u32(pe_data, section_header_offset + 20).ok()?;
+                let section_size = virtual_size.max(raw_data_size);
+                (rva >= virtual_address && rva - virtual_address < section_size)
+                    .then(|| raw_data_offset as usize + (rva - virtual_address) as usize)
+            })
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| anyhow!("truncated PE file"))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| anyhow!("truncated PE file"))
+}
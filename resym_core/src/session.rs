@@ -0,0 +1,169 @@
+use std::{path::Path, sync::Arc};
+
+use crate::{
+    backend::{reconstruct_type_by_index_command, update_type_filter_command, FilterMatchMode},
+    error::ResymError,
+    frontend::TypeList,
+    pdb_file::PdbFile,
+    pdb_types::{
+        DataFormatConfiguration, OutputFormat, PrimitiveReconstructionFlavor, ReconstructedType,
+    },
+};
+
+/// Options used to reconstruct types with a `Session`.
+#[derive(Debug, Clone)]
+pub struct ReconstructionOptions {
+    pub primitives_flavor: PrimitiveReconstructionFlavor,
+    pub output_format: OutputFormat,
+    /// Prepend a header (i.e., a comment and the required includes) to the
+    /// reconstructed type
+    pub print_header: bool,
+    /// Also reconstruct the types the type depends on
+    pub reconstruct_dependencies: bool,
+    pub fmt_configuration: DataFormatConfiguration,
+}
+
+impl Default for ReconstructionOptions {
+    fn default() -> Self {
+        Self {
+            primitives_flavor: PrimitiveReconstructionFlavor::Portable,
+            output_format: OutputFormat::Cpp,
+            print_header: false,
+            reconstruct_dependencies: false,
+            fmt_configuration: DataFormatConfiguration::default(),
+        }
+    }
+}
+
+/// Options used to list types with a `Session`.
+#[derive(Debug, Clone)]
+pub struct FilterOptions {
+    pub case_insensitive: bool,
+    pub match_mode: FilterMatchMode,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        Self {
+            case_insensitive: false,
+            match_mode: FilterMatchMode::Substring,
+        }
+    }
+}
+
+/// Synchronous interface to a single PDB file, for library consumers that
+/// don't need the channel-based API used by the GUI. Calls are processed on
+/// the calling thread, and return once they're done.
+///
+/// Sessions are `Send` and `Sync`. Once a session has been opened, it can be
+/// shared between threads (e.g., in an `Arc`): types can be reconstructed
+/// and listed from several threads at once, calls made concurrently don't
+/// wait for each other. Reloading the PDB requires exclusive access to the
+/// session (`&mut self`), so it cannot happen while other calls are being
+/// processed.
+pub struct Session {
+    /// Parsed PDB, which is only read once it has been loaded
+    pdb_file: Arc<PdbFile<'static>>,
+}
+
+impl Session {
+    /// Load the PDB file at the given path. The file is read entirely into
+    /// memory, so that it can be rewritten (e.g., by a linker) while the
+    /// session is open.
+    pub fn open(pdb_file_path: impl AsRef<Path>) -> Result<Self, ResymError> {
+        let pdb_file = PdbFile::load_from_file_buffered(pdb_file_path.as_ref())?;
+
+        Ok(Self {
+            pdb_file: Arc::new(pdb_file),
+        })
+    }
+
+    /// Load the PDB file again, from the same path. The previously loaded
+    /// PDB is kept if loading fails.
+    pub fn reload(&mut self) -> Result<(), ResymError> {
+        // Note: Sessions are always opened from a file
+        if let Some(pdb_file_path) = self.pdb_file.file_path.clone() {
+            self.pdb_file = Arc::new(PdbFile::load_from_file_buffered(&pdb_file_path)?);
+        }
+
+        Ok(())
+    }
+
+    /// Reconstruct the type of the given name.
+    pub fn reconstruct_type(
+        &self,
+        type_name: &str,
+        options: &ReconstructionOptions,
+    ) -> Result<String, ResymError> {
+        let type_index = self.pdb_file.find_type_index_by_name(type_name)?;
+        Ok(reconstruct_type_by_index_command(
+            &self.pdb_file,
+            type_index,
+            options.primitives_flavor,
+            options.output_format,
+            options.print_header,
+            options.reconstruct_dependencies,
+            &options.fmt_configuration,
+        )?)
+    }
+
+    /// Retrieve a serializable description of the type of the given name,
+    /// followed by the types it depends on if `reconstruct_dependencies` is
+    /// set in the options.
+    pub fn type_model(
+        &self,
+        type_name: &str,
+        options: &ReconstructionOptions,
+    ) -> Result<Vec<ReconstructedType>, ResymError> {
+        Ok(self.pdb_file.reconstruct_type_model_by_name(
+            type_name,
+            options.primitives_flavor,
+            options.reconstruct_dependencies,
+        )?)
+    }
+
+    /// List the types whose name matches the given filter (an empty filter
+    /// matches all the types).
+    pub fn list_types(
+        &self,
+        filter: &str,
+        options: &FilterOptions,
+    ) -> Result<TypeList, ResymError> {
+        Ok(update_type_filter_command(
+            &self.pdb_file,
+            filter,
+            options.case_insensitive,
+            options.match_mode,
+            true,
+        )?)
+    }
+
+    /// Iterate over all the types of the PDB, ordered by type index. Type
+    /// names are borrowed from the session, nothing is allocated.
+    pub fn iter_types(&self) -> TypeIter<'_> {
+        TypeIter {
+            type_list: self.pdb_file.complete_type_list.iter(),
+        }
+    }
+}
+
+/// Iterator over the types of a PDB, returned by `Session::iter_types`.
+pub struct TypeIter<'s> {
+    // Note: Types are listed in the order of the type stream, i.e., by type
+    // index
+    type_list: std::slice::Iter<'s, (String, pdb::TypeIndex)>,
+}
+
+impl<'s> Iterator for TypeIter<'s> {
+    type Item = (pdb::TypeIndex, &'s str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.type_list
+            .next()
+            .map(|(type_name, type_index)| (*type_index, type_name.as_str()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.type_list.size_hint()
+    }
+}
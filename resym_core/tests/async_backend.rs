@@ -0,0 +1,60 @@
+#![cfg(feature = "tokio")]
+
+use std::{path::PathBuf, sync::Arc};
+
+use resym_core::{
+    async_backend::{AsyncBackend, AsyncFrontendController},
+    backend::BackendCommand,
+    frontend::FrontendCommand,
+    pdb_types::{DataFormatConfiguration, OutputFormat, PrimitiveReconstructionFlavor},
+};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+async fn recv_result(rx_ui: &mut UnboundedReceiver<FrontendCommand>) -> Option<FrontendCommand> {
+    while let Some(command) = rx_ui.recv().await {
+        if !matches!(command, FrontendCommand::UpdateProgress { .. }) {
+            return Some(command);
+        }
+    }
+
+    None
+}
+
+#[tokio::test]
+async fn test_async_backend_reconstruct_type() {
+    let (frontend_controller, mut rx_ui) = AsyncFrontendController::new();
+    let backend = AsyncBackend::new(Arc::new(frontend_controller)).expect("backend creation");
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .await
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&mut rx_ui).await,
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    backend
+        .send_command(BackendCommand::ReconstructTypeByName(
+            0,
+            "resym_test::StructTest".to_string(),
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            false,
+            false,
+            DataFormatConfiguration::default(),
+        ))
+        .await
+        .expect("send command");
+    match recv_result(&mut rx_ui).await {
+        Some(FrontendCommand::ReconstructTypeResult(0, Ok(reconstructed_type))) => {
+            assert!(reconstructed_type.contains("struct resym_test::StructTest"));
+        }
+        _ => panic!("unexpected response"),
+    }
+}
@@ -0,0 +1,819 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Result;
+use crossbeam_channel::{Receiver, Sender};
+use resym_core::{
+    backend::{filter_named_list, Backend, BackendCommand, FilterMatchMode, TypeFilter},
+    frontend::{FrontendCommand, FrontendController, ProgressStage},
+    pdb_file::{OperationCancelled, PdbFile, TypeKind},
+    pdb_types::{
+        DataFormatConfiguration, OutputFormat, PrimitiveReconstructionFlavor, ReconstructedTypeKind,
+    },
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct TestFrontendController {
+    tx_ui: Sender<FrontendCommand>,
+}
+
+impl FrontendController for TestFrontendController {
+    fn send_command(&self, command: FrontendCommand) -> Result<()> {
+        Ok(self.tx_ui.send(command)?)
+    }
+}
+
+fn start_backend() -> (Backend, Receiver<FrontendCommand>) {
+    let (tx_ui, rx_ui) = crossbeam_channel::unbounded();
+    let backend =
+        Backend::new(Arc::new(TestFrontendController { tx_ui })).expect("backend creation");
+
+    (backend, rx_ui)
+}
+
+fn start_backend_with_cache_size(cache_size: usize) -> (Backend, Receiver<FrontendCommand>) {
+    let (tx_ui, rx_ui) = crossbeam_channel::unbounded();
+    let backend = Backend::with_cache_size(Arc::new(TestFrontendController { tx_ui }), cache_size)
+        .expect("backend creation");
+
+    (backend, rx_ui)
+}
+
+#[test]
+fn test_cancelled_pdb_loading() {
+    let result = PdbFile::load_from_file_with_progress(Path::new(TEST_PDB_FILE_PATH), |_, _| {
+        Err(OperationCancelled.into())
+    });
+    match result {
+        Ok(_) => panic!("loading should have been cancelled"),
+        Err(err) => assert!(err.is::<OperationCancelled>()),
+    }
+}
+
+#[test]
+fn test_backend_cancellation_acknowledgment() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::Cancel)
+        .expect("send command");
+    assert!(matches!(
+        rx_ui.recv_timeout(RESPONSE_TIMEOUT),
+        Ok(FrontendCommand::Cancelled)
+    ));
+
+    // Commands sent after the cancellation are processed normally
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+}
+
+#[test]
+fn test_backend_progress_reports() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    let mut progress_reports = vec![];
+    while let Ok(command) = rx_ui.recv_timeout(RESPONSE_TIMEOUT) {
+        match command {
+            FrontendCommand::UpdateProgress {
+                current,
+                total,
+                stage,
+            } => progress_reports.push((current, total, stage)),
+            FrontendCommand::LoadPDBResult(_, result) => {
+                assert!(result.is_ok());
+                break;
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+
+    // Progress goes from 0 to the total number of type records
+    let total = progress_reports.first().expect("progress reports").1;
+    assert!(progress_reports
+        .iter()
+        .all(|report| report.1 == total && report.2 == ProgressStage::LoadingPdb));
+    assert_eq!(progress_reports.first().map(|report| report.0), Some(0));
+    assert_eq!(progress_reports.last().map(|report| report.0), Some(total));
+    assert!(progress_reports
+        .windows(2)
+        .all(|pair| pair[0].0 < pair[1].0));
+}
+
+/// Wait for the next response that isn't a progress report.
+fn recv_result(rx_ui: &Receiver<FrontendCommand>) -> Option<FrontendCommand> {
+    while let Ok(command) = rx_ui.recv_timeout(RESPONSE_TIMEOUT) {
+        if !matches!(command, FrontendCommand::UpdateProgress { .. }) {
+            return Some(command);
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_backend_reload_pdb() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    backend
+        .send_command(BackendCommand::ReloadPDB(0))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+}
+
+#[test]
+fn test_backend_rewritten_pdb() {
+    let pdb_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("rewritten.pdb");
+    let pdb_data = std::fs::read(TEST_PDB_FILE_PATH).expect("read test PDB");
+    let (backend, rx_ui) = start_backend();
+    let reconstruct_type = || {
+        backend
+            .send_command(BackendCommand::ReconstructTypeByName(
+                0,
+                "resym_test::StructTest".to_string(),
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                false,
+                false,
+                DataFormatConfiguration::default(),
+            ))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::ReconstructTypeResult(0, result)) => result.is_ok(),
+            _ => panic!("unexpected response"),
+        }
+    };
+    // Note: This reads streams the `pdb` crate hasn't read while loading
+    let check_pdb = || {
+        backend
+            .send_command(BackendCommand::CheckPDB(0))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::CheckPDBResult(0, result)) => result.is_ok(),
+            _ => panic!("unexpected response"),
+        }
+    };
+
+    // Files loaded with `LoadPDBBuffered` and reloaded ones can be truncated
+    // and rewritten in place while they're loaded
+    std::fs::write(&pdb_file_path, &pdb_data).expect("write PDB");
+    for load_command in [
+        BackendCommand::LoadPDBBuffered(0, pdb_file_path.clone()),
+        BackendCommand::ReloadPDB(0),
+    ] {
+        backend.send_command(load_command).expect("send command");
+        assert!(matches!(
+            recv_result(&rx_ui),
+            Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+        ));
+
+        std::fs::write(&pdb_file_path, &pdb_data[..pdb_data.len() / 8]).expect("truncate PDB");
+        assert!(reconstruct_type());
+        assert!(check_pdb());
+        std::fs::write(&pdb_file_path, &pdb_data).expect("rewrite PDB");
+        assert!(reconstruct_type());
+        assert!(check_pdb());
+    }
+}
+
+#[test]
+fn test_backend_reload_pdb_without_loaded_pdb() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::ReloadPDB(0))
+        .expect("send command");
+    match recv_result(&rx_ui) {
+        Some(FrontendCommand::LoadPDBResult(0, Err(err))) => {
+            assert_eq!(err.to_string(), "no PDB file has been loaded")
+        }
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[test]
+fn test_backend_load_pdb_bytes() {
+    let (backend, rx_ui) = start_backend();
+    let pdb_data = std::fs::read(TEST_PDB_FILE_PATH).expect("read test PDB");
+
+    backend
+        .send_command(BackendCommand::LoadPDBBytes(
+            0,
+            "test.pdb".to_string(),
+            pdb_data.into(),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    // PDBs loaded from memory aren't backed by a file
+    backend
+        .send_command(BackendCommand::ReloadPDB(0))
+        .expect("send command");
+    match recv_result(&rx_ui) {
+        Some(FrontendCommand::LoadPDBResult(0, Err(err))) => assert_eq!(
+            err.to_string(),
+            "PDB files loaded from memory cannot be reloaded"
+        ),
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[test]
+fn test_load_pdb_bytes() {
+    let pdb_data = std::fs::read(TEST_PDB_FILE_PATH).expect("read test PDB");
+    let pdb_file_from_bytes =
+        PdbFile::load_from_bytes("test.pdb", pdb_data.into()).expect("load test PDB from memory");
+    let pdb_file_from_path =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test PDB");
+
+    assert_eq!(pdb_file_from_bytes.name, "test.pdb");
+    assert_eq!(pdb_file_from_bytes.file_path, None);
+    assert_eq!(
+        pdb_file_from_path.file_path.as_deref(),
+        Some(Path::new(TEST_PDB_FILE_PATH))
+    );
+    assert_eq!(
+        pdb_file_from_bytes.complete_type_list,
+        pdb_file_from_path.complete_type_list
+    );
+}
+
+#[test]
+fn test_backend_concurrent_pdbs() {
+    const TEST_DIFF_FROM_PDB_FILE_PATH: &str = "tests/data/test_diff_from.pdb";
+    let (backend, rx_ui) = start_backend();
+
+    for (pdb_slot, pdb_file_path) in [(0, TEST_PDB_FILE_PATH), (1, TEST_DIFF_FROM_PDB_FILE_PATH)] {
+        backend
+            .send_command(BackendCommand::LoadPDB(
+                pdb_slot,
+                PathBuf::from(pdb_file_path),
+            ))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::LoadPDBResult(loaded_slot, result)) => {
+                assert_eq!(loaded_slot, pdb_slot);
+                assert!(result.is_ok());
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+
+    // Each response carries the slot of the PDB it concerns
+    let mut type_lists = vec![];
+    for pdb_slot in [1, 0] {
+        backend
+            .send_command(BackendCommand::UpdateTypeFilter(
+                pdb_slot,
+                String::default(),
+                false,
+                FilterMatchMode::Substring,
+            ))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::UpdateFilteredTypes(filtered_slot, result)) => {
+                assert_eq!(filtered_slot, pdb_slot);
+                type_lists.push(result.expect("type list"));
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+    assert_ne!(type_lists[0], type_lists[1]);
+}
+
+#[test]
+fn test_backend_find_types_with_field() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    backend
+        .send_command(BackendCommand::FindTypesWithField(
+            0,
+            "^FLINK$".to_string(),
+            true,
+            FilterMatchMode::Regex,
+            PrimitiveReconstructionFlavor::Portable,
+        ))
+        .expect("send command");
+    match recv_result(&rx_ui) {
+        Some(FrontendCommand::FindTypesWithFieldResult(0, Ok(field_list))) => {
+            let field_list = field_list
+                .into_iter()
+                .map(|(name, field)| (field.type_name, name, field.field_type_name))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                field_list,
+                vec![(
+                    "_LIST_ENTRY".to_string(),
+                    "Flink".to_string(),
+                    "_LIST_ENTRY*".to_string()
+                )]
+            );
+        }
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[test]
+fn test_backend_get_type_model() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    backend
+        .send_command(BackendCommand::GetTypeModel(
+            0,
+            "_LIST_ENTRY".to_string(),
+            PrimitiveReconstructionFlavor::Portable,
+            false,
+        ))
+        .expect("send command");
+    match recv_result(&rx_ui) {
+        Some(FrontendCommand::GetTypeModelResult(0, Ok(type_model))) => {
+            assert_eq!(type_model.len(), 1);
+            assert_eq!(type_model[0].name, "_LIST_ENTRY");
+            assert_eq!(type_model[0].kind, ReconstructedTypeKind::Struct);
+            assert_eq!(
+                type_model[0]
+                    .fields
+                    .iter()
+                    .map(|field| (field.name.as_str(), field.offset))
+                    .collect::<Vec<_>>(),
+                vec![("Flink", 0), ("Blink", 8)]
+            );
+        }
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[test]
+fn test_backend_reconstruction_cache() {
+    // Cached and uncached reconstructions must match
+    for cache_size in [0, 1, 64] {
+        let (backend, rx_ui) = start_backend_with_cache_size(cache_size);
+
+        backend
+            .send_command(BackendCommand::LoadPDB(
+                0,
+                PathBuf::from(TEST_PDB_FILE_PATH),
+            ))
+            .expect("send command");
+        assert!(matches!(
+            recv_result(&rx_ui),
+            Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+        ));
+        backend
+            .send_command(BackendCommand::UpdateTypeFilter(
+                0,
+                "^_LIST_ENTRY$".to_string(),
+                false,
+                FilterMatchMode::Regex,
+            ))
+            .expect("send command");
+        let type_index = match recv_result(&rx_ui) {
+            Some(FrontendCommand::UpdateFilteredTypes(0, Ok(type_list))) => type_list[0].1,
+            _ => panic!("unexpected response"),
+        };
+
+        let reconstruct_type = |output_format| {
+            backend
+                .send_command(BackendCommand::ReconstructTypeByIndex(
+                    0,
+                    type_index,
+                    PrimitiveReconstructionFlavor::Portable,
+                    output_format,
+                    false,
+                    false,
+                    DataFormatConfiguration::default(),
+                ))
+                .expect("send command");
+            match recv_result(&rx_ui) {
+                Some(FrontendCommand::ReconstructTypeResult(0, result)) => {
+                    result.expect("reconstructed type")
+                }
+                _ => panic!("unexpected response"),
+            }
+        };
+
+        let reconstruct_type_by_name = |type_name: &str| {
+            backend
+                .send_command(BackendCommand::ReconstructTypeByName(
+                    0,
+                    type_name.to_string(),
+                    PrimitiveReconstructionFlavor::Portable,
+                    OutputFormat::Cpp,
+                    false,
+                    false,
+                    DataFormatConfiguration::default(),
+                ))
+                .expect("send command");
+            match recv_result(&rx_ui) {
+                Some(FrontendCommand::ReconstructTypeResult(0, result)) => result,
+                _ => panic!("unexpected response"),
+            }
+        };
+
+        let reconstructed_type = reconstruct_type(OutputFormat::Cpp);
+        assert_eq!(reconstruct_type(OutputFormat::Cpp), reconstructed_type);
+        // Reconstruction flags are part of the cache key
+        assert_ne!(reconstruct_type(OutputFormat::C), reconstructed_type);
+        assert_eq!(reconstruct_type(OutputFormat::Cpp), reconstructed_type);
+        // Types reconstructed by name share the entries of their index
+        for _ in 0..2 {
+            assert_eq!(
+                reconstruct_type_by_name("_LIST_ENTRY").expect("reconstructed type"),
+                reconstructed_type
+            );
+        }
+        assert!(reconstruct_type_by_name("_LIST_ENTRY_").is_err());
+
+        backend
+            .send_command(BackendCommand::ClearCache)
+            .expect("send command");
+        assert_eq!(reconstruct_type(OutputFormat::Cpp), reconstructed_type);
+    }
+}
+
+#[test]
+fn test_filter_named_list_order() {
+    // Large enough to be filtered in parallel
+    let named_list = (0..100_000)
+        .map(|i| (format!("Type_{}", i), i))
+        .collect::<Vec<_>>();
+
+    let expected_list = named_list
+        .iter()
+        .filter(|(name, _)| name.contains('7'))
+        .cloned()
+        .collect::<Vec<_>>();
+    for (match_mode, search_filter) in [
+        (FilterMatchMode::Substring, "7"),
+        (FilterMatchMode::Regex, "7"),
+        (FilterMatchMode::Glob, "*7*"),
+    ] {
+        let filtered_list =
+            filter_named_list(&named_list, search_filter, false, match_mode).expect("filter list");
+        assert_eq!(filtered_list, expected_list);
+    }
+}
+
+#[test]
+fn test_filter_named_list_fuzzy() {
+    let named_list = [
+        "ContextSocket",
+        "SocketContext",
+        "Socket",
+        "SockCtx",
+        "sock_ctx_t",
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, name)| (name.to_string(), i))
+    .collect::<Vec<_>>();
+
+    let filtered_list = filter_named_list(&named_list, "sockctx", true, FilterMatchMode::Fuzzy)
+        .expect("filter list");
+    let filtered_names = filtered_list
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    // Closer matches come first
+    assert_eq!(
+        filtered_names,
+        vec!["sock_ctx_t", "SockCtx", "SocketContext"]
+    );
+
+    let filtered_list = filter_named_list(&named_list, "SocCtx", false, FilterMatchMode::Fuzzy)
+        .expect("filter list");
+    let filtered_names = filtered_list
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(filtered_names, vec!["SockCtx", "SocketContext"]);
+}
+
+#[test]
+fn test_filter_named_list_glob_classes() {
+    let named_list = ["x&", "x-", "x~", "xa", "xb", "xc", "x[", "x\\"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_string(), i))
+        .collect::<Vec<_>>();
+
+    for (search_filter, expected_names) in [
+        // Class set operations of the regex crate are matched literally
+        ("x[&-&&b]", vec!["x&", "xb"]),
+        ("x[~~&&-]", vec!["x&", "x-", "x~"]),
+        ("x[!&&~~]", vec!["x-", "xa", "xb", "xc", "x[", "x\\"]),
+        // Dashes are part of ranges unless they start or end the class
+        ("x[a-b-]", vec!["x-", "xa", "xb"]),
+        ("x[-c]", vec!["x-", "xc"]),
+        ("x[[\\]", vec!["x[", "x\\"]),
+    ] {
+        let filtered_list =
+            filter_named_list(&named_list, search_filter, false, FilterMatchMode::Glob)
+                .expect("filter list");
+        let filtered_names = filtered_list
+            .iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(filtered_names, expected_names, "{}", search_filter);
+    }
+}
+
+#[test]
+fn test_backend_update_type_filter_fuzzy() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    backend
+        .send_command(BackendCommand::UpdateTypeFilter(
+            0,
+            "EnumTest2".to_string(),
+            false,
+            FilterMatchMode::Fuzzy,
+        ))
+        .expect("send command");
+    match recv_result(&rx_ui) {
+        Some(FrontendCommand::UpdateFilteredTypes(0, Ok(type_list))) => {
+            let type_names = type_list
+                .iter()
+                .map(|(type_name, _)| type_name.as_str())
+                .collect::<Vec<_>>();
+            // Ranked, rather than ordered by type index
+            assert_eq!(
+                type_names,
+                vec!["resym_test::EnumTest2", "resym_test::EnumClassTest2"]
+            );
+            assert!(type_list[0].1 > type_list[1].1);
+        }
+        _ => panic!("unexpected response"),
+    }
+}
+
+#[test]
+fn test_backend_filter_types_by_size() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    // `resym_test::ClassAccessTest` and `resym_test::StructAccessTest` are
+    // 0x10 bytes long, `resym_test::UnionAccessTest` is 4 bytes long
+    let class_access_test = ("resym_test::ClassAccessTest", 0x10);
+    let struct_access_test = ("resym_test::StructAccessTest", 0x10);
+    let union_access_test = ("resym_test::UnionAccessTest", 0x4);
+    let size_filter_cases = [
+        (
+            None,
+            None,
+            vec![class_access_test, struct_access_test, union_access_test],
+        ),
+        (
+            Some(0x10),
+            Some(0x10),
+            vec![class_access_test, struct_access_test],
+        ),
+        (
+            Some(0x4),
+            Some(0x10),
+            vec![class_access_test, struct_access_test, union_access_test],
+        ),
+        (Some(0x5), None, vec![class_access_test, struct_access_test]),
+        (None, Some(0xf), vec![union_access_test]),
+        (Some(0x11), None, vec![]),
+        (Some(0x10), Some(0x4), vec![]),
+    ];
+    for (min_size, max_size, expected_types) in size_filter_cases {
+        backend
+            .send_command(BackendCommand::FilterTypes(
+                0,
+                "^resym_test::[A-Za-z]+AccessTest$".to_string(),
+                false,
+                FilterMatchMode::Regex,
+                TypeFilter {
+                    min_size,
+                    max_size,
+                    ..Default::default()
+                },
+            ))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::FilterTypesResult(0, Ok(type_list))) => {
+                let type_list = type_list
+                    .iter()
+                    .map(|(type_name, _, type_size)| (type_name.as_str(), *type_size))
+                    .collect::<Vec<_>>();
+                assert_eq!(type_list, expected_types, "{:?}-{:?}", min_size, max_size);
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+}
+
+#[test]
+fn test_backend_filter_types_by_kind() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    let search_filter = "^(BYTE|CONTEXT|_CONTEXT|resym_test::[A-Za-z]+AccessTest)$";
+    let kind_filter_cases = [
+        (
+            vec![],
+            vec![
+                "resym_test::ClassAccessTest",
+                "resym_test::StructAccessTest",
+                "resym_test::UnionAccessTest",
+                "_CONTEXT",
+            ],
+        ),
+        (
+            vec![TypeKind::Struct],
+            vec!["resym_test::StructAccessTest", "_CONTEXT"],
+        ),
+        (vec![TypeKind::Class], vec!["resym_test::ClassAccessTest"]),
+        (
+            vec![TypeKind::Union, TypeKind::Enum],
+            vec!["resym_test::UnionAccessTest"],
+        ),
+        // Structs named after their tag (i.e., `struct _CONTEXT`) aren't
+        // typedefs
+        (vec![TypeKind::Typedef], vec!["BYTE", "CONTEXT"]),
+        (
+            vec![TypeKind::Typedef, TypeKind::Union],
+            vec!["resym_test::UnionAccessTest", "BYTE", "CONTEXT"],
+        ),
+    ];
+    for (kinds, expected_types) in kind_filter_cases {
+        backend
+            .send_command(BackendCommand::FilterTypes(
+                0,
+                search_filter.to_string(),
+                false,
+                FilterMatchMode::Regex,
+                TypeFilter {
+                    kinds: kinds.clone(),
+                    ..Default::default()
+                },
+            ))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::FilterTypesResult(0, Ok(type_list))) => {
+                let type_names = type_list
+                    .iter()
+                    .map(|(type_name, _, _)| type_name.as_str())
+                    .collect::<Vec<_>>();
+                assert_eq!(type_names, expected_types, "{:?}", kinds);
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+}
+
+#[test]
+fn test_backend_filter_types_exclusions() {
+    let (backend, rx_ui) = start_backend();
+
+    backend
+        .send_command(BackendCommand::LoadPDB(
+            0,
+            PathBuf::from(TEST_PDB_FILE_PATH),
+        ))
+        .expect("send command");
+    assert!(matches!(
+        recv_result(&rx_ui),
+        Some(FrontendCommand::LoadPDBResult(0, Ok(())))
+    ));
+
+    let exclusion_cases = [
+        (
+            "resym_test::*AccessTest",
+            false,
+            FilterMatchMode::Glob,
+            vec!["*Class*", "", "*Union*"],
+            vec!["resym_test::StructAccessTest"],
+        ),
+        (
+            "^resym_test::[a-z]+accesstest$",
+            true,
+            FilterMatchMode::Regex,
+            vec!["^resym_test::s", "UNION"],
+            vec!["resym_test::ClassAccessTest"],
+        ),
+        (
+            "AccessTest",
+            false,
+            FilterMatchMode::Substring,
+            vec!["resym_test::"],
+            vec![],
+        ),
+    ];
+    for (search_filter, case_insensitive, match_mode, exclusion_filters, expected_types) in
+        exclusion_cases
+    {
+        backend
+            .send_command(BackendCommand::FilterTypes(
+                0,
+                search_filter.to_string(),
+                case_insensitive,
+                match_mode,
+                TypeFilter {
+                    exclusion_filters: exclusion_filters.iter().map(|s| s.to_string()).collect(),
+                    ..Default::default()
+                },
+            ))
+            .expect("send command");
+        match recv_result(&rx_ui) {
+            Some(FrontendCommand::FilterTypesResult(0, Ok(type_list))) => {
+                let type_names = type_list
+                    .iter()
+                    .map(|(type_name, _, _)| type_name.as_str())
+                    .collect::<Vec<_>>();
+                assert_eq!(type_names, expected_types, "{}", search_filter);
+            }
+            _ => panic!("unexpected response"),
+        }
+    }
+}
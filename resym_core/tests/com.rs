@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use resym_core::{
+    pdb_file::PdbFile,
+    pdb_types::{
+        fmt_com_definitions, ComInterface, ComMethod, GuidConstant, PrimitiveReconstructionFlavor,
+    },
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+fn com_method(name: &str, arguments: &[&str], vtable_offset: u32) -> ComMethod {
+    ComMethod {
+        name: name.to_string(),
+        return_type_name: "LONG".to_string(),
+        arguments: arguments
+            .iter()
+            .map(|argument| argument.to_string())
+            .collect(),
+        calling_convention: Some("__stdcall"),
+        vtable_offset,
+    }
+}
+
+#[test]
+fn test_com_interfaces_require_iunknown() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // `resym_test::PureVirtualClass` is shaped like an interface, but doesn't
+    // derive from `IUnknown`
+    let interfaces = pdb_file
+        .com_interfaces(&PrimitiveReconstructionFlavor::Microsoft)
+        .expect("COM interfaces");
+    assert!(interfaces.is_empty());
+}
+
+#[test]
+fn test_guid_from_name() {
+    let guid =
+        GuidConstant::guid_from_name("_GUID_00000000_0000_0000_c000_000000000046").expect("GUID");
+    assert_eq!(guid.to_string(), "00000000-0000-0000-C000-000000000046");
+    assert_eq!(GuidConstant::guid_from_name("IID_IUnknown"), None);
+}
+
+#[test]
+fn test_fmt_com_definitions() {
+    let guid_constants = vec![
+        GuidConstant {
+            name: "IID_IUnknown".to_string(),
+            rva: Some(0x1000),
+            guid: GuidConstant::guid_from_name("_GUID_00000000_0000_0000_c000_000000000046"),
+        },
+        GuidConstant {
+            name: "IID_IStream".to_string(),
+            rva: Some(0x1010),
+            guid: None,
+        },
+    ];
+    let interfaces = vec![
+        ComInterface {
+            name: "IUnknown".to_string(),
+            base_interface_name: None,
+            methods: vec![
+                com_method("QueryInterface", &["const _GUID&", "void**"], 0x0),
+                com_method("AddRef", &[], 0x8),
+                com_method("Release", &[], 0x10),
+            ],
+        },
+        ComInterface {
+            name: "ISequentialStream".to_string(),
+            base_interface_name: Some("IUnknown".to_string()),
+            methods: vec![com_method("Read", &["void*", "ULONG", "ULONG*"], 0x18)],
+        },
+    ];
+
+    let mut output = String::new();
+    fmt_com_definitions(&guid_constants, &interfaces, &mut output).expect("format");
+    assert_eq!(
+        output,
+        "DEFINE_GUID(IID_IUnknown, 0x00000000, 0x0000, 0x0000, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46);
+/* DEFINE_GUID(IID_IStream, ...); value unknown, RVA 0x1010 */
+
+struct IUnknownVtbl {
+  /* IUnknown */
+  LONG (__stdcall *QueryInterface)(IUnknown* This, const _GUID&, void**); /* vtable+0x0 */
+  LONG (__stdcall *AddRef)(IUnknown* This); /* vtable+0x8 */
+  LONG (__stdcall *Release)(IUnknown* This); /* vtable+0x10 */
+};
+
+struct ISequentialStreamVtbl {
+  /* IUnknown */
+  LONG (__stdcall *QueryInterface)(ISequentialStream* This, const _GUID&, void**); /* vtable+0x0 */
+  LONG (__stdcall *AddRef)(ISequentialStream* This); /* vtable+0x8 */
+  LONG (__stdcall *Release)(ISequentialStream* This); /* vtable+0x10 */
+  /* ISequentialStream */
+  LONG (__stdcall *Read)(ISequentialStream* This, void*, ULONG, ULONG*); /* vtable+0x18 */
+};
+"
+    );
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use resym_core::{
+    pdb_file::{FieldDeclaration, PdbFile},
+    pdb_types::PrimitiveReconstructionFlavor,
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_field_listing() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let fields = pdb_file
+        .fields(&PrimitiveReconstructionFlavor::Portable)
+        .expect("list fields");
+    let struct_test_fields = fields
+        .iter()
+        .filter(|(_, field)| field.type_name == "resym_test::StructTest")
+        .cloned()
+        .collect::<Vec<_>>();
+    // Members are listed once, in declaration order
+    assert_eq!(
+        struct_test_fields,
+        [
+            ("u1", "unsigned char"),
+            ("u2", "uint16_t"),
+            ("u3", "uint32_t"),
+            ("u4", "uint64_t")
+        ]
+        .into_iter()
+        .map(|(name, field_type_name)| (
+            name.to_string(),
+            FieldDeclaration {
+                type_name: "resym_test::StructTest".to_string(),
+                field_type_name: field_type_name.to_string(),
+            }
+        ))
+        .collect::<Vec<_>>()
+    );
+
+    // Members are sorted by the name of their type
+    assert!(fields
+        .windows(2)
+        .all(|pair| pair[0].1.type_name <= pair[1].1.type_name));
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use resym_core::{pdb_file::PdbFile, pdb_types::PrimitiveReconstructionFlavor};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_global_variables() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let global_variables = pdb_file
+        .global_variables(&PrimitiveReconstructionFlavor::Portable)
+        .expect("global variables");
+
+    let (_, static_member) = global_variables
+        .iter()
+        .find(|(name, _)| name == "resym_test::ClassWithRefsAndStaticsTest::sint")
+        .expect("static member");
+    assert_eq!(static_member.type_name, "int32_t");
+    assert!(!static_member.is_static);
+    assert!(static_member.rva.is_some());
+
+    // File-static variables are listed too
+    let (_, static_variable) = global_variables
+        .iter()
+        .find(|(name, _)| name == "pre_cpp_initializer")
+        .expect("static variable");
+    assert_eq!(static_variable.type_name, "void (*)()");
+    assert!(static_variable.is_static);
+}
+
+#[test]
+fn test_global_variables_primitives_flavor() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let global_variables = pdb_file
+        .global_variables(&PrimitiveReconstructionFlavor::Microsoft)
+        .expect("global variables");
+
+    assert!(global_variables.iter().any(|(name, global)| name
+        == "resym_test::ClassWithRefsAndStaticsTest::sint"
+        && global.type_name == "LONG"));
+}
+
+#[test]
+fn test_global_variables_ordered_by_rva() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let global_variables = pdb_file
+        .global_variables(&PrimitiveReconstructionFlavor::Portable)
+        .expect("global variables");
+
+    let rvas = global_variables
+        .iter()
+        .filter_map(|(_, global)| global.rva)
+        .collect::<Vec<_>>();
+    assert!(!rvas.is_empty());
+    assert!(rvas.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn test_global_variables_arrays_of_function_pointers() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let global_variables = pdb_file
+        .global_variables(&PrimitiveReconstructionFlavor::Portable)
+        .expect("global variables");
+
+    // Dimensions are placed inside of the pointer's parentheses, before the
+    // argument list
+    assert!(global_variables
+        .iter()
+        .any(|(name, global)| name == "__xc_a" && global.type_name == "void (*[1])()"));
+    assert!(global_variables
+        .iter()
+        .any(|(name, global)| name == "__xi_a" && global.type_name == "int32_t (*[1])()"));
+}
+
+#[test]
+fn test_global_variables_qualified_pointers() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let global_variables = pdb_file
+        .global_variables(&PrimitiveReconstructionFlavor::Portable)
+        .expect("global variables");
+    let global_type_name = |global_name: &str| {
+        global_variables
+            .iter()
+            .find(|(name, _)| name == global_name)
+            .map(|(_, global)| global.type_name.as_str())
+            .unwrap_or_else(|| panic!("global variable {}", global_name))
+    };
+
+    // Qualifiers of pointers follow their declarator
+    assert_eq!(
+        global_type_name("_RTC_ErrorMessages"),
+        "const char* const[6]"
+    );
+    assert_eq!(
+        global_type_name("resym_test::StructTest::`vftable'"),
+        "void (* const[2])()"
+    );
+}
@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use resym_core::pdb_file::PdbFile;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_module_listing() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let modules = pdb_file.modules(false).expect("modules");
+    let test_module = modules
+        .iter()
+        .find(|module| module.name.ends_with("symbol_zoo.obj"))
+        .expect("test module");
+    assert_eq!(test_module.object_file_name, test_module.name);
+    assert_eq!(test_module.symbol_count, None);
+    // Modules coming from static libraries refer to their library
+    assert!(modules
+        .iter()
+        .any(|module| module.object_file_name.ends_with("MSVCRTD.lib")));
+}
+
+#[test]
+fn test_module_listing_with_symbol_counts() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let modules = pdb_file.modules(true).expect("modules");
+    assert!(modules.iter().all(|module| module.symbol_count.is_some()));
+    let test_module = modules
+        .iter()
+        .find(|module| module.name.ends_with("symbol_zoo.obj"))
+        .expect("test module");
+    assert!(test_module.symbol_count.unwrap_or_default() > 0);
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+
+use resym_core::{
+    pdb_file::PdbFile,
+    pe_file::{read_guid_at_rva, Guid, PdbReference},
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+/// GUID and age of tests/data/test.pdb
+const TEST_PDB_GUID: &str = "04C3AA1B-A45B-4125-827B-3770512917DB";
+const TEST_PDB_AGE: u32 = 6;
+
+/// Build a minimal PE32+ image, whose debug directory references a PDB.
+fn build_pe(guid: &Guid, age: u32, pdb_path: &str) -> Vec<u8> {
+    let mut pe = vec![0u8; 0x400];
+    let mut write = |offset: usize, bytes: &[u8]| {
+        pe[offset..offset + bytes.len()].copy_from_slice(bytes);
+    };
+
+    // DOS header
+    write(0, b"MZ");
+    write(0x3C, &0x40u32.to_le_bytes());
+    // NT headers
+    write(0x40, b"PE\0\0");
+    write(0x44, &0x8664u16.to_le_bytes());
+    write(0x46, &1u16.to_le_bytes());
+    write(0x54, &240u16.to_le_bytes());
+    // Optional header, with 16 data directories
+    write(0x58, &0x20bu16.to_le_bytes());
+    write(0x58 + 108, &16u32.to_le_bytes());
+    write(0x58 + 112 + 6 * 8, &0x1000u32.to_le_bytes());
+    write(0x58 + 112 + 6 * 8 + 4, &28u32.to_le_bytes());
+    // Section table
+    let section_header_offset = 0x58 + 240;
+    write(section_header_offset, b".rdata\0\0");
+    write(section_header_offset + 8, &0x100u32.to_le_bytes());
+    write(section_header_offset + 12, &0x1000u32.to_le_bytes());
+    write(section_header_offset + 16, &0x200u32.to_le_bytes());
+    write(section_header_offset + 20, &0x200u32.to_le_bytes());
+    // Debug directory
+    let mut codeview_record = b"RSDS".to_vec();
+    codeview_record.extend_from_slice(&guid.data1.to_le_bytes());
+    codeview_record.extend_from_slice(&guid.data2.to_le_bytes());
+    codeview_record.extend_from_slice(&guid.data3.to_le_bytes());
+    codeview_record.extend_from_slice(&guid.data4);
+    codeview_record.extend_from_slice(&age.to_le_bytes());
+    codeview_record.extend_from_slice(pdb_path.as_bytes());
+    codeview_record.push(0);
+    write(0x200 + 12, &2u32.to_le_bytes());
+    write(0x200 + 16, &(codeview_record.len() as u32).to_le_bytes());
+    write(0x200 + 20, &0x101Cu32.to_le_bytes());
+    write(0x200 + 24, &0x21Cu32.to_le_bytes());
+    write(0x21C, &codeview_record);
+
+    pe
+}
+
+#[test]
+fn test_pdb_reference_from_pe() {
+    let guid: Guid = TEST_PDB_GUID.parse().expect("parse GUID");
+    let pe = build_pe(&guid, TEST_PDB_AGE, "C:\\build\\Release\\test.pdb");
+
+    let pdb_reference = PdbReference::from_pe_data(&pe).expect("read PDB reference");
+    assert_eq!(pdb_reference.guid, guid);
+    assert_eq!(pdb_reference.age, TEST_PDB_AGE);
+    assert_eq!(pdb_reference.pdb_path, "C:\\build\\Release\\test.pdb");
+    assert_eq!(pdb_reference.pdb_file_name(), "test.pdb");
+    assert_eq!(
+        pdb_reference.symbol_server_id(),
+        "04C3AA1BA45B4125827B3770512917DB6"
+    );
+}
+
+#[test]
+fn test_pdb_reference_from_invalid_pe() {
+    assert!(PdbReference::from_pe_data(b"").is_err());
+    assert!(PdbReference::from_pe_data(&[0u8; 0x400]).is_err());
+
+    let guid: Guid = TEST_PDB_GUID.parse().expect("parse GUID");
+    let mut pe = build_pe(&guid, TEST_PDB_AGE, "test.pdb");
+    pe.truncate(0x220);
+    assert!(PdbReference::from_pe_data(&pe).is_err());
+}
+
+#[test]
+fn test_read_guid_at_rva() {
+    let guid: Guid = TEST_PDB_GUID.parse().expect("parse GUID");
+    let pe = build_pe(&guid, TEST_PDB_AGE, "test.pdb");
+
+    // The GUID of the CodeView record, which is mapped at RVA 0x101C
+    assert_eq!(read_guid_at_rva(&pe, 0x1020).expect("read GUID"), guid);
+    assert!(read_guid_at_rva(&pe, 0x2000).is_err());
+}
+
+#[test]
+fn test_guid_formatting() {
+    let guid: Guid = "{04c3aa1b-a45b-4125-827b-3770512917db}"
+        .parse()
+        .expect("parse GUID");
+    assert_eq!(guid.to_string(), TEST_PDB_GUID);
+    assert_eq!(guid.to_simple_string(), "04C3AA1BA45B4125827B3770512917DB");
+    assert!("04C3AA1B".parse::<Guid>().is_err());
+}
+
+#[test]
+fn test_parse_symbol_server_id() {
+    let (guid, age) = PdbReference::parse_symbol_server_id("04C3AA1BA45B4125827B3770512917DB1A")
+        .expect("parse identifier");
+    assert_eq!(guid.to_string(), TEST_PDB_GUID);
+    assert_eq!(age, 0x1A);
+    assert!(PdbReference::parse_symbol_server_id("04C3AA1BA45B4125827B3770512917DB").is_err());
+    assert!(PdbReference::parse_symbol_server_id("04C3AA1BA45B4125827B3770512917DBXY").is_err());
+}
+
+#[test]
+fn test_verify_pe() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load PDB");
+    let guid: Guid = TEST_PDB_GUID.parse().expect("parse GUID");
+    assert_eq!(pdb_file.guid, guid);
+    assert_eq!(pdb_file.age, TEST_PDB_AGE);
+
+    let verify_pe = |file_name: &str, pe: Vec<u8>| {
+        let pe_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(file_name);
+        std::fs::write(&pe_file_path, pe).expect("write PE");
+        pdb_file.verify_pe(&pe_file_path).expect("verify PE")
+    };
+
+    let verification = verify_pe(
+        "verify_match.exe",
+        build_pe(&guid, TEST_PDB_AGE, "C:\\build\\test.pdb"),
+    );
+    assert!(verification.is_match());
+    assert!(verification.is_name_match());
+
+    // Renamed PDB
+    let verification = verify_pe(
+        "verify_renamed.exe",
+        build_pe(&guid, TEST_PDB_AGE, "C:\\build\\renamed.pdb"),
+    );
+    assert!(verification.is_match());
+    assert!(!verification.is_name_match());
+
+    // Stale PDB
+    let verification = verify_pe(
+        "verify_stale.exe",
+        build_pe(&guid, TEST_PDB_AGE + 1, "C:\\build\\test.pdb"),
+    );
+    assert!(!verification.is_match());
+
+    // PDB of another build
+    let other_guid = Guid { data1: 0, ..guid };
+    let verification = verify_pe(
+        "verify_other.exe",
+        build_pe(&other_guid, TEST_PDB_AGE, "C:\\build\\test.pdb"),
+    );
+    assert!(!verification.is_match());
+}
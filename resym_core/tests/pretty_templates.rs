@@ -0,0 +1,54 @@
+use resym_core::pdb_types::pretty_template_names;
+
+#[test]
+fn test_pretty_template_names() {
+    const TEST_CASES: &[(&str, &str)] = &[
+        (
+            "std::basic_string<char,std::char_traits<char>,std::allocator<char> >",
+            "std::string",
+        ),
+        (
+            "const std::basic_string<wchar_t,std::char_traits<wchar_t>,std::allocator<wchar_t> >&",
+            "const std::wstring&",
+        ),
+        (
+            "std::vector<MyStruct *,std::allocator<MyStruct *> >",
+            "std::vector<MyStruct *>",
+        ),
+        (
+            "std::map<int,std::basic_string<char,std::char_traits<char>,std::allocator<char> >,std::less<int>,std::allocator<std::pair<int const ,std::basic_string<char,std::char_traits<char>,std::allocator<char> > > > >",
+            "std::map<int,std::string>",
+        ),
+        (
+            "std::vector<std::vector<int,std::allocator<int> >,std::allocator<std::vector<int,std::allocator<int> > > >",
+            "std::vector<std::vector<int>>",
+        ),
+        (
+            "std::unique_ptr<Foo,std::default_delete<Foo> > m_foo[2];",
+            "std::unique_ptr<Foo> m_foo[2];",
+        ),
+        // Non-default arguments must be preserved
+        (
+            "std::vector<int,MyAllocator<int> >",
+            "std::vector<int,MyAllocator<int> >",
+        ),
+        (
+            "std::map<int,int,MyLess,std::allocator<std::pair<int const ,int> > >",
+            "std::map<int,int,MyLess,std::allocator<std::pair<int const ,int> > >",
+        ),
+        // Only fully-qualified STL names are aliased
+        (
+            "other::std::vector<int,std::allocator<int> >",
+            "other::std::vector<int,std::allocator<int> >",
+        ),
+    ];
+
+    for (type_name, expected_pretty_type_name) in TEST_CASES {
+        assert_eq!(
+            pretty_template_names(type_name),
+            *expected_pretty_type_name,
+            "{}",
+            type_name
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use resym_core::pdb_types::sanitize_type_names;
+
+#[test]
+fn test_sanitize_type_names() {
+    const TEST_CASES: &[(&str, &str)] = &[
+        (
+            "struct resym_test::StructTest;",
+            "struct resym_test_StructTest;",
+        ),
+        (
+            "std::vector<int,std::allocator<int> > m_values;",
+            "std_vector_int_std_allocator_int m_values;",
+        ),
+        (
+            "Foo<char const *> a; Foo<char const> b;",
+            "Foo_char_const_ptr a; Foo_char_const b;",
+        ),
+        (
+            "struct `anonymous namespace'::Foo {",
+            "struct anonymous_namespace_Foo {",
+        ),
+        ("class <lambda_1> {", "class lambda_1 {"),
+        // Valid identifiers, numbers and operators are left untouched
+        (
+            "  /* 0x0008 */ uint32_t u3[0x10];",
+            "  /* 0x0008 */ uint32_t u3[0x10];",
+        ),
+        (
+            "  bool operator<(const Foo&);",
+            "  bool operator<(const Foo&);",
+        ),
+        // Comments and strings keep the original names
+        (
+            "// ns::Foo<int>\nstruct ns::Foo<int> {};",
+            "// ns::Foo<int>\nstruct ns_Foo_int {};",
+        ),
+        (
+            "static_assert(sizeof(ns::Foo) == 0x4, \"ns::Foo has an unexpected size\");",
+            "static_assert(sizeof(ns_Foo) == 0x4, \"ns::Foo has an unexpected size\");",
+        ),
+    ];
+
+    for (text, expected_sanitized_text) in TEST_CASES {
+        assert_eq!(
+            sanitize_type_names(text),
+            *expected_sanitized_text,
+            "{}",
+            text
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, Barrier},
+    thread,
+};
+
+use resym_core::{
+    backend::FilterMatchMode,
+    pdb_types::{DataFormatConfiguration, OutputFormat},
+    session::{FilterOptions, ReconstructionOptions},
+    ResymError, Session,
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_session_open_invalid_path() {
+    let err = Session::open("tests/data/inexistent.pdb")
+        .err()
+        .expect("open should fail");
+    assert!(matches!(err, ResymError::PdbNotFound(_)));
+}
+
+#[test]
+fn test_session_open_invalid_pdb() {
+    let err = Session::open("tests/data/test.cpp")
+        .err()
+        .expect("open should fail");
+    assert!(matches!(err, ResymError::PdbParse(_)));
+}
+
+#[test]
+fn test_session_list_types() {
+    let session = Session::open(TEST_PDB_FILE_PATH).expect("open session");
+
+    let all_types = session
+        .list_types("", &FilterOptions::default())
+        .expect("list types");
+    assert!(!all_types.is_empty());
+
+    let filtered_types = session
+        .list_types(
+            "resym_test::*class*",
+            &FilterOptions {
+                case_insensitive: true,
+                match_mode: FilterMatchMode::Glob,
+            },
+        )
+        .expect("list types");
+    assert!(!filtered_types.is_empty());
+    assert!(filtered_types.len() < all_types.len());
+    assert!(filtered_types
+        .iter()
+        .all(|(type_name, _)| type_name.to_lowercase().contains("class")));
+
+    let err = session
+        .list_types(
+            "(",
+            &FilterOptions {
+                case_insensitive: false,
+                match_mode: FilterMatchMode::Regex,
+            },
+        )
+        .expect_err("filtering should fail");
+    assert!(matches!(err, ResymError::InvalidRegex(_)));
+}
+
+#[test]
+fn test_session_reconstruct_type() {
+    let session = Session::open(TEST_PDB_FILE_PATH).expect("open session");
+
+    let reconstructed_type = session
+        .reconstruct_type("resym_test::StructTest", &ReconstructionOptions::default())
+        .expect("reconstruct type");
+    assert!(reconstructed_type.contains("struct resym_test::StructTest"));
+
+    let reconstructed_type = session
+        .reconstruct_type(
+            "resym_test::StructTest",
+            &ReconstructionOptions {
+                output_format: OutputFormat::Rust,
+                ..Default::default()
+            },
+        )
+        .expect("reconstruct type");
+    assert!(reconstructed_type.contains("#[repr(C)]"));
+
+    let err = session
+        .reconstruct_type("resym_test::Inexistent", &ReconstructionOptions::default())
+        .expect_err("reconstruction should fail");
+    match &err {
+        ResymError::TypeNotFound { type_name, .. } => {
+            assert_eq!(type_name, "resym_test::Inexistent")
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+    assert_eq!(err.to_string(), "type 'resym_test::Inexistent' not found");
+}
+
+#[test]
+fn test_session_reconstruct_ambiguous_type() {
+    let session = Session::open(TEST_PDB_FILE_PATH).expect("open session");
+
+    // Distinct unnamed types share this (unique) name
+    let err = session
+        .reconstruct_type(".?AU<unnamed-tag>@@", &ReconstructionOptions::default())
+        .expect_err("reconstruction should fail");
+    match &err {
+        ResymError::AmbiguousTypeName { type_indices, .. } => {
+            assert_eq!(
+                type_indices,
+                &[pdb::TypeIndex(0x1191), pdb::TypeIndex(0x140d)]
+            )
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    // Definitions of `StructTest` only differ by their methods, the last
+    // one is picked
+    let reconstructed_type = session
+        .reconstruct_type(
+            "resym_test::StructTest",
+            &ReconstructionOptions {
+                fmt_configuration: DataFormatConfiguration {
+                    print_methods: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .expect("reconstruct type");
+    assert!(reconstructed_type.contains("~StructTest()"));
+}
+
+#[test]
+fn test_session_type_model() {
+    let session = Session::open(TEST_PDB_FILE_PATH).expect("open session");
+
+    let type_model = session
+        .type_model("_LIST_ENTRY", &ReconstructionOptions::default())
+        .expect("type model");
+    assert_eq!(type_model.len(), 1);
+    assert_eq!(type_model[0].size, 16);
+    assert_eq!(type_model[0].fields.len(), 2);
+}
+
+#[test]
+fn test_session_iter_types() {
+    let session = Session::open(TEST_PDB_FILE_PATH).expect("open session");
+    let type_count = session
+        .list_types("", &FilterOptions::default())
+        .expect("list types")
+        .len();
+
+    let type_iter = session.iter_types();
+    assert_eq!(type_iter.size_hint(), (type_count, Some(type_count)));
+    let type_list = type_iter.collect::<Vec<_>>();
+    assert_eq!(type_list.len(), type_count);
+    assert!(type_list.windows(2).all(|types| types[0].0 < types[1].0));
+
+    // Other calls can be made while iterating
+    let mut type_iter = session.iter_types();
+    let first_types = type_iter.by_ref().take(10).collect::<Vec<_>>();
+    assert_eq!(first_types.as_slice(), &type_list[..10]);
+    assert!(session
+        .reconstruct_type(type_list[0].1, &ReconstructionOptions::default())
+        .is_ok());
+    assert_eq!(type_iter.next().as_ref(), type_list.get(10));
+}
+
+#[test]
+fn test_session_concurrent_reconstruction() {
+    let session = Arc::new(Session::open(TEST_PDB_FILE_PATH).expect("open session"));
+    let type_names = session
+        .list_types("resym_test::", &FilterOptions::default())
+        .expect("list types")
+        .into_iter()
+        .map(|(type_name, _)| type_name)
+        .collect::<Vec<_>>();
+    let expected_types = type_names
+        .iter()
+        .map(|type_name| {
+            session
+                .reconstruct_type(type_name, &ReconstructionOptions::default())
+                .expect("reconstruct type")
+        })
+        .collect::<Vec<_>>();
+
+    let threads = (0..4)
+        .map(|_| {
+            let session = session.clone();
+            let type_names = type_names.clone();
+            thread::spawn(move || {
+                type_names
+                    .iter()
+                    .map(|type_name| {
+                        session
+                            .reconstruct_type(type_name, &ReconstructionOptions::default())
+                            .expect("reconstruct type")
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        assert_eq!(thread.join().expect("join thread"), expected_types);
+    }
+}
+
+#[test]
+fn test_session_simultaneous_calls() {
+    const THREAD_COUNT: usize = 4;
+    let session = Arc::new(Session::open(TEST_PDB_FILE_PATH).expect("open session"));
+    let barrier = Arc::new(Barrier::new(THREAD_COUNT));
+
+    // Every thread waits for the others while it's iterating over the types,
+    // which requires calls to be processed at the same time
+    let threads = (0..THREAD_COUNT)
+        .map(|_| {
+            let session = session.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut type_iter = session.iter_types();
+                let (_, first_type_name) = type_iter.next().expect("type");
+                barrier.wait();
+                let reconstructed_type = session
+                    .reconstruct_type("resym_test::StructTest", &ReconstructionOptions::default())
+                    .expect("reconstruct type");
+                barrier.wait();
+                let type_list = session
+                    .list_types("resym_test::", &FilterOptions::default())
+                    .expect("list types");
+
+                (
+                    first_type_name.to_string(),
+                    type_iter.count(),
+                    reconstructed_type,
+                    type_list,
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    let results = threads
+        .into_iter()
+        .map(|thread| thread.join().expect("join thread"))
+        .collect::<Vec<_>>();
+    assert!(results.windows(2).all(|results| results[0] == results[1]));
+}
+
+#[test]
+fn test_session_reload() {
+    let pdb_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("session_reload.pdb");
+    fs::copy(TEST_PDB_FILE_PATH, &pdb_file_path).expect("copy PDB");
+    let mut session = Session::open(&pdb_file_path).expect("open session");
+
+    // The PDB is kept if reloading fails
+    fs::write(&pdb_file_path, "not a PDB").expect("overwrite PDB");
+    assert!(matches!(session.reload(), Err(ResymError::PdbParse(_))));
+    assert!(session
+        .reconstruct_type("resym_test::StructTest", &ReconstructionOptions::default())
+        .is_ok());
+
+    fs::copy("tests/data/test_diff_from.pdb", &pdb_file_path).expect("copy PDB");
+    session.reload().expect("reload PDB");
+    assert!(session
+        .reconstruct_type("RemovedStruct", &ReconstructionOptions::default())
+        .is_ok());
+}
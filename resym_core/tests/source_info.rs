@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use resym_core::pdb_file::PdbFile;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_source_info_type() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let source_locations = pdb_file
+        .source_info("resym_test::StructTest")
+        .expect("source info");
+    assert!(!source_locations.is_empty());
+    assert!(source_locations
+        .iter()
+        .all(|location| location.file_name.ends_with("symbol_zoo.cpp")));
+}
+
+#[test]
+fn test_source_info_function() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let source_locations = pdb_file
+        .source_info("resym_test::StructTest::StructTest")
+        .expect("source info");
+    assert_eq!(source_locations.len(), 1);
+    assert!(source_locations[0].file_name.ends_with("symbol_zoo.cpp"));
+    assert_eq!(source_locations[0].line, 93);
+}
+
+#[test]
+fn test_source_info_unavailable() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // Nested types have no source record
+    let source_locations = pdb_file
+        .source_info("resym_test::ClassWithNestedDeclarationsTest::NestedStruct")
+        .expect("source info");
+    assert!(source_locations.is_empty());
+}
+
+#[test]
+fn test_source_info_unknown_symbol() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    assert!(pdb_file.source_info("resym_test::NonExistent").is_err());
+}
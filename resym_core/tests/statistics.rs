@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use resym_core::pdb_file::PdbFile;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_statistics() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let statistics = pdb_file.compute_statistics().expect("statistics");
+    insta::assert_debug_snapshot!(statistics);
+}
+
+#[test]
+fn test_pointer_size() {
+    // test.pdb has been generated for x64
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    assert_eq!(pdb_file.pointer_size(), Some(8));
+}
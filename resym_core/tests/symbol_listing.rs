@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use resym_core::pdb_file::PdbFile;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_public_symbols_demangling() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let public_symbols = pdb_file.public_symbols().expect("public symbols");
+
+    let (demangled_name, symbol) = public_symbols
+        .iter()
+        .find(|(_, symbol)| symbol.mangled_name == "??0StructTest@resym_test@@QEAA@XZ")
+        .expect("StructTest's constructor");
+    assert_eq!(
+        demangled_name,
+        "public: __cdecl resym_test::StructTest::StructTest(void)"
+    );
+    assert!(symbol.rva.is_some());
+
+    // Names that aren't mangled are kept as is
+    assert!(public_symbols
+        .iter()
+        .any(|(demangled_name, symbol)| demangled_name == "main" && symbol.mangled_name == "main"));
+}
+
+#[test]
+fn test_public_symbols_ordered_by_rva() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let public_symbols = pdb_file.public_symbols().expect("public symbols");
+
+    let rvas = public_symbols
+        .iter()
+        .filter_map(|(_, symbol)| symbol.rva)
+        .collect::<Vec<_>>();
+    assert!(!rvas.is_empty());
+    assert!(rvas.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn test_address_resolution() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let main_rva = pdb_file
+        .public_symbols()
+        .expect("public symbols")
+        .iter()
+        .find(|(name, _)| name == "main")
+        .and_then(|(_, symbol)| symbol.rva)
+        .expect("main's RVA");
+
+    let resolved_address = pdb_file
+        .resolve_address(main_rva)
+        .expect("address resolution")
+        .expect("main");
+    assert_eq!(resolved_address.symbol_name, "main");
+    assert_eq!(resolved_address.offset, 0);
+
+    let resolved_address = pdb_file
+        .resolve_address(main_rva + 0x10)
+        .expect("address resolution")
+        .expect("main+0x10");
+    assert_eq!(resolved_address.symbol_name, "main");
+    assert_eq!(resolved_address.offset, 0x10);
+}
+
+#[test]
+fn test_address_resolution_outside_contributions() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // Image headers and addresses past the end of the image don't belong
+    // to any symbol
+    for rva in [0x0, 0x500, 0x2000_0000] {
+        assert_eq!(
+            pdb_file.resolve_address(rva).expect("address resolution"),
+            None,
+            "{:#x}",
+            rva
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use std::path::Path;
 
 use resym_core::{
-    diffing::diff_type_by_name, pdb_file::PdbFile, pdb_types::PrimitiveReconstructionFlavor,
+    diffing::{diff_type_by_name, diff_type_lists, semantic_diff_type_by_name, TypeListChange},
+    pdb_file::PdbFile,
+    pdb_types::{DataFormatConfiguration, PrimitiveReconstructionFlavor},
 };
 
 const TEST_PDB_FROM_FILE_PATH: &str = "tests/data/test_diff_from.pdb";
@@ -29,7 +31,10 @@ fn test_struct_diffing() {
             PrimitiveReconstructionFlavor::Portable,
             false,
             false,
-            false,
+            &DataFormatConfiguration {
+                print_access_specifiers: false,
+                ..Default::default()
+            },
         )
         .expect("diff generation");
         insta::assert_snapshot!(diffed_type.data);
@@ -50,7 +55,93 @@ fn test_struct_diffing_inexistent_type() {
         PrimitiveReconstructionFlavor::Portable,
         false,
         false,
-        false,
+        &DataFormatConfiguration {
+            print_access_specifiers: false,
+            ..Default::default()
+        },
+    )
+    .is_err());
+}
+
+#[test]
+fn test_struct_semantic_diffing() {
+    let pdb_file_from = PdbFile::load_from_file(Path::new(TEST_PDB_FROM_FILE_PATH))
+        .expect("load test_diff_from.pdb");
+    let pdb_file_to =
+        PdbFile::load_from_file(Path::new(TEST_PDB_TO_FILE_PATH)).expect("load test_diff_to.pdb");
+
+    for test_case_type_name in TEST_CASES {
+        let type_changes = semantic_diff_type_by_name(
+            &pdb_file_from,
+            &pdb_file_to,
+            test_case_type_name,
+            PrimitiveReconstructionFlavor::Portable,
+        )
+        .expect("semantic diff generation");
+        let type_changes = type_changes
+            .iter()
+            .map(|type_change| format!("{}\n", type_change))
+            .collect::<String>();
+        insta::assert_snapshot!(type_changes);
+    }
+}
+
+#[test]
+fn test_struct_semantic_diffing_inexistent_type() {
+    const INEXISTENT_TYPE_NAME: &str = "TypeNotFound";
+    let pdb_file_from = PdbFile::load_from_file(Path::new(TEST_PDB_FROM_FILE_PATH))
+        .expect("load test_diff_from.pdb");
+    let pdb_file_to =
+        PdbFile::load_from_file(Path::new(TEST_PDB_TO_FILE_PATH)).expect("load test_diff_to.pdb");
+    assert!(semantic_diff_type_by_name(
+        &pdb_file_from,
+        &pdb_file_to,
+        INEXISTENT_TYPE_NAME,
+        PrimitiveReconstructionFlavor::Portable,
     )
     .is_err());
 }
+
+#[test]
+fn test_type_list_diffing() {
+    let pdb_file_from = PdbFile::load_from_file(Path::new(TEST_PDB_FROM_FILE_PATH))
+        .expect("load test_diff_from.pdb");
+    let pdb_file_to =
+        PdbFile::load_from_file(Path::new(TEST_PDB_TO_FILE_PATH)).expect("load test_diff_to.pdb");
+
+    let type_list_changes =
+        diff_type_lists(&pdb_file_from, &pdb_file_to).expect("type list diff generation");
+    assert_eq!(
+        type_list_changes,
+        vec![
+            TypeListChange::Added {
+                name: "NewStruct".to_string(),
+                size: 0x4
+            },
+            TypeListChange::Removed {
+                name: "RemovedStruct".to_string(),
+                size: 0x4
+            },
+            TypeListChange::SizeChanged {
+                name: "UserStructAdd".to_string(),
+                old_size: 0x10,
+                new_size: 0x18
+            },
+            TypeListChange::SizeChanged {
+                name: "UserStructAddAndReplace".to_string(),
+                old_size: 0x10,
+                new_size: 0x28
+            },
+            TypeListChange::SizeChanged {
+                name: "UserStructRemove".to_string(),
+                old_size: 0x18,
+                new_size: 0x10
+            },
+        ]
+    );
+
+    // Identical PDBs have no differences
+    let type_list_changes =
+        diff_type_lists(&pdb_file_from, &pdb_file_from).expect("type list diff generation");
+    assert!(type_list_changes.is_empty());
+}
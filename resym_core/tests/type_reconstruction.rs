@@ -1,8 +1,16 @@
 use std::path::Path;
 
-use resym_core::{pdb_file::PdbFile, pdb_types::PrimitiveReconstructionFlavor};
+use resym_core::{
+    pdb_file::PdbFile,
+    pdb_types::{
+        merge_kaitai_documents, DataFormatConfiguration, OffsetRadix, OutputFormat,
+        PrimitiveReconstructionFlavor,
+    },
+};
 
 const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const TEST_DECLARATORS_PDB_FILE_PATH: &str = "tests/data/test_declarators.pdb";
+const TEST_TYPEDEFS_PDB_FILE_PATH: &str = "tests/data/test_typedefs.pdb";
 const TEST_CASES: &[&str] = &[
     "resym_test::PrimitiveTypesTest",
     "resym_test::ArrayTest",
@@ -27,8 +35,12 @@ fn test_type_reconstruction_portable_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_portable_access_specifiers",
         PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Cpp,
         false,
-        true,
+        &DataFormatConfiguration {
+            print_access_specifiers: true,
+            ..Default::default()
+        },
     );
 }
 
@@ -37,8 +49,12 @@ fn test_type_reconstruction_microsoft_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_microsoft_access_specifiers",
         PrimitiveReconstructionFlavor::Microsoft,
+        OutputFormat::Cpp,
         false,
-        true,
+        &DataFormatConfiguration {
+            print_access_specifiers: true,
+            ..Default::default()
+        },
     );
 }
 
@@ -47,16 +63,527 @@ fn test_type_reconstruction_raw_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_raw_access_specifiers",
         PrimitiveReconstructionFlavor::Raw,
+        OutputFormat::Cpp,
+        false,
+        &DataFormatConfiguration {
+            print_access_specifiers: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_no_methods() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_no_methods",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Cpp,
         false,
+        &DataFormatConfiguration {
+            print_methods: false,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_size_asserts() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_size_asserts",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Cpp,
         true,
+        &DataFormatConfiguration {
+            print_size_asserts: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_padding_fields() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_padding_fields",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Cpp,
+        false,
+        &DataFormatConfiguration {
+            print_padding_fields: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_offset_comments() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_offset_comments",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Cpp,
+        false,
+        &DataFormatConfiguration {
+            print_offset_comments: Some(OffsetRadix::Decimal),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_type_reconstruction_hex_offset_comments() {
+    // Members already start with their hexadecimal offset, only bit
+    // positions are appended
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (output_format, format_name) in [(OutputFormat::Cpp, "cpp"), (OutputFormat::C, "c")] {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                "resym_test::StructTest",
+                PrimitiveReconstructionFlavor::Portable,
+                output_format,
+                false,
+                &DataFormatConfiguration {
+                    print_offset_comments: Some(OffsetRadix::Hexadecimal),
+                    ..Default::default()
+                },
+            )
+            .expect("reconstruct type: resym_test::StructTest");
+
+        let snapshot_name = format!("type_reconstruction_hex_offset_comments_{}", format_name);
+        insta::assert_snapshot!(snapshot_name, reconstructed_type);
+    }
+}
+
+#[test]
+fn test_type_reconstruction_portable_c() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_c",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::C,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_csharp() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_csharp",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::CSharp,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_rust() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_rust",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Rust,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_go() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_go",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Go,
+        false,
+        &DataFormatConfiguration::default(),
     );
 }
 
+#[test]
+fn test_type_reconstruction_portable_ctypes() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_ctypes",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Ctypes,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_windbg() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_windbg",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::WinDbg,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_imhex() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_imhex",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::ImHex,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_binary_template() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_binary_template",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::BinaryTemplate,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_portable_kaitai() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_portable_kaitai",
+        PrimitiveReconstructionFlavor::Portable,
+        OutputFormat::Kaitai,
+        false,
+        &DataFormatConfiguration::default(),
+    );
+}
+
+#[test]
+fn test_type_reconstruction_kaitai_merge() {
+    // Both types embed `_UNWIND_CODE`
+    const MERGE_TEST_CASES: &[&str] = &["_UNWIND_INFO", "_UNWIND_CODE", "resym_test::EnumTest1"];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let documents = MERGE_TEST_CASES
+        .iter()
+        .map(|test_case_type_name| {
+            pdb_file
+                .reconstruct_type_by_name(
+                    test_case_type_name,
+                    PrimitiveReconstructionFlavor::Portable,
+                    OutputFormat::Kaitai,
+                    false,
+                    &DataFormatConfiguration::default(),
+                )
+                .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name))
+        })
+        .collect::<Vec<_>>();
+
+    let merged_document = merge_kaitai_documents(documents.iter().map(String::as_str));
+    let count_lines = |expected_line: &str| {
+        merged_document
+            .lines()
+            .filter(|line| *line == expected_line)
+            .count()
+    };
+    assert_eq!(count_lines("meta:"), 1);
+    assert_eq!(count_lines("  id: unwind_info"), 1);
+    assert_eq!(count_lines("types:"), 1);
+    assert_eq!(count_lines("  unwind_code:"), 1);
+    assert_eq!(count_lines("  unwind_info:"), 1);
+    assert_eq!(count_lines("enums:"), 1);
+    assert_eq!(count_lines("  resym_test_enum_test1:"), 1);
+    // Sections follow the preamble
+    assert!(merged_document.find("types:") < merged_document.find("enums:"));
+}
+
+#[test]
+fn test_type_reconstruction_recursive_dependencies() {
+    // Types that refer to each other through pointers
+    const RECURSIVE_TEST_CASES: &[&str] = &[
+        "_s__RTTIClassHierarchyDescriptor",
+        "_s__RTTIBaseClassDescriptor",
+    ];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in RECURSIVE_TEST_CASES.iter().enumerate() {
+        for (output_format, format_name) in [(OutputFormat::Cpp, "cpp"), (OutputFormat::C, "c")] {
+            let reconstructed_type = pdb_file
+                .reconstruct_type_by_name(
+                    test_case_type_name,
+                    PrimitiveReconstructionFlavor::Portable,
+                    output_format,
+                    true,
+                    &DataFormatConfiguration::default(),
+                )
+                .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
+
+            // Every type is defined exactly once, after the forward
+            // declarations breaking the cycle
+            let forward_declarations_end = reconstructed_type
+                .find("break dependency cycles")
+                .expect("forward declarations");
+            for type_name in RECURSIVE_TEST_CASES {
+                let definition = format!("struct {} {{", type_name);
+                assert_eq!(reconstructed_type.matches(&definition).count(), 1);
+                assert!(reconstructed_type.find(&definition) > Some(forward_declarations_end));
+            }
+
+            let snapshot_name = format!(
+                "type_reconstruction_recursive_dependencies_{}-{}",
+                format_name, i
+            );
+            insta::assert_snapshot!(snapshot_name, reconstructed_type);
+        }
+    }
+}
+
+#[test]
+fn test_type_reconstruction_dependencies_defined_once() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let count_definitions = |reconstructed_type: &str, type_name: &str| {
+        reconstructed_type
+            .matches(&format!("struct {} {{", type_name))
+            .count()
+    };
+
+    // `PrimitiveTypesTest` is used by several members
+    let reconstructed_type = pdb_file
+        .reconstruct_type_by_name(
+            "resym_test::ArrayTest",
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            true,
+            &DataFormatConfiguration::default(),
+        )
+        .expect("reconstruct type");
+    assert_eq!(
+        count_definitions(&reconstructed_type, "resym_test::PrimitiveTypesTest"),
+        1
+    );
+
+    // Outdated records of `StructTest` refer to its latest record
+    let reconstructed_type = pdb_file
+        .reconstruct_type_by_type_index(
+            pdb::TypeIndex(0x1109),
+            &PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            true,
+            &DataFormatConfiguration::default(),
+        )
+        .expect("reconstruct type");
+    assert_eq!(
+        count_definitions(&reconstructed_type, "resym_test::StructTest"),
+        1
+    );
+}
+
+#[test]
+fn test_type_reconstruction_union_with_nested_struct() {
+    // Union containing both an unnamed struct and a named one
+    const UNION_TEST_CASE: &str = "_LARGE_INTEGER";
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (output_format, format_name) in [
+        (OutputFormat::Cpp, "cpp"),
+        (OutputFormat::C, "c"),
+        (OutputFormat::Go, "go"),
+        (OutputFormat::WinDbg, "windbg"),
+    ] {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                UNION_TEST_CASE,
+                PrimitiveReconstructionFlavor::Portable,
+                output_format,
+                true,
+                &DataFormatConfiguration::default(),
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type: {}", UNION_TEST_CASE));
+
+        let snapshot_name = format!(
+            "type_reconstruction_union_with_nested_struct_{}",
+            format_name
+        );
+        insta::assert_snapshot!(snapshot_name, reconstructed_type);
+    }
+}
+
+#[test]
+fn test_type_reconstruction_bitfields() {
+    // Bitfields mixed with regular members, across several storage units
+    const BITFIELDS_TEST_CASES: &[&str] = &["_UNWIND_INFO", "_UNWIND_CODE"];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in BITFIELDS_TEST_CASES.iter().enumerate() {
+        for (output_format, format_name) in [
+            (OutputFormat::Cpp, "cpp"),
+            (OutputFormat::C, "c"),
+            (OutputFormat::Rust, "rust"),
+            (OutputFormat::Go, "go"),
+            (OutputFormat::Ctypes, "ctypes"),
+            (OutputFormat::WinDbg, "windbg"),
+            (OutputFormat::ImHex, "imhex"),
+            (OutputFormat::BinaryTemplate, "binary_template"),
+            (OutputFormat::Kaitai, "kaitai"),
+        ] {
+            let reconstructed_type = pdb_file
+                .reconstruct_type_by_name(
+                    test_case_type_name,
+                    PrimitiveReconstructionFlavor::Portable,
+                    output_format,
+                    false,
+                    &DataFormatConfiguration {
+                        print_offset_comments: Some(OffsetRadix::Hexadecimal),
+                        ..Default::default()
+                    },
+                )
+                .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
+
+            let snapshot_name = format!("type_reconstruction_bitfields_{}-{}", format_name, i);
+            insta::assert_snapshot!(snapshot_name, reconstructed_type);
+        }
+    }
+}
+
+#[test]
+fn test_type_reconstruction_infer_packing() {
+    // Types declared with `#pragma pack`
+    const PACKED_TEST_CASES: &[&str] = &["_s__RTTIBaseClassDescriptor", "lfClass"];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in PACKED_TEST_CASES.iter().enumerate() {
+        for (output_format, format_name) in [
+            (OutputFormat::Cpp, "cpp"),
+            (OutputFormat::C, "c"),
+            (OutputFormat::Ctypes, "ctypes"),
+        ] {
+            let reconstructed_type = pdb_file
+                .reconstruct_type_by_name(
+                    test_case_type_name,
+                    PrimitiveReconstructionFlavor::Portable,
+                    output_format,
+                    false,
+                    &DataFormatConfiguration {
+                        infer_packing: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
+
+            let snapshot_name = format!("type_reconstruction_infer_packing_{}-{}", format_name, i);
+            insta::assert_snapshot!(snapshot_name, reconstructed_type);
+        }
+    }
+}
+
+#[test]
+fn test_type_reconstruction_enum_values_in_hex() {
+    const ENUM_TEST_CASES: &[&str] = &[
+        "resym_test::EnumTest1",
+        "resym_test::EnumTest2",
+        // Negative values
+        "tagCLSCTX",
+        // Duplicate values
+        "ReplacesCorHdrNumericDefines",
+    ];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in ENUM_TEST_CASES.iter().enumerate() {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                test_case_type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                false,
+                &DataFormatConfiguration {
+                    print_enum_values_in_hex: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
+
+        let snapshot_name = format!("type_reconstruction_enum_values_in_hex-{}", i);
+        insta::assert_snapshot!(snapshot_name, reconstructed_type);
+    }
+}
+
+#[test]
+fn test_type_reconstruction_sanitize_names() {
+    const SANITIZED_TEST_CASES: &[&str] = &[
+        "resym_test::StructTest",
+        "resym_test::PureVirtualClassSpecialized",
+        "__vcrt_va_list_is_reference<char const * const>",
+    ];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in SANITIZED_TEST_CASES.iter().enumerate() {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                test_case_type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                true,
+                &DataFormatConfiguration {
+                    print_size_asserts: true,
+                    sanitize_names: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
+
+        let snapshot_name = format!("type_reconstruction_sanitize_names-{}", i);
+        insta::assert_snapshot!(snapshot_name, reconstructed_type);
+    }
+}
+
+#[test]
+fn test_type_reconstruction_namespaces() {
+    const NAMESPACED_TEST_CASES: &[&str] = &[
+        "resym_test::PureVirtualClassSpecialized",
+        // Nested in a class, not in a namespace
+        "resym_test::ClassWithNestedDeclarationsTest::NestedStruct",
+        // Types declared in the global namespace, some forward declared
+        "_s__RTTIClassHierarchyDescriptor",
+    ];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in NAMESPACED_TEST_CASES.iter().enumerate() {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                test_case_type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                true,
+                &DataFormatConfiguration {
+                    print_size_asserts: true,
+                    print_namespaces: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
+
+        let snapshot_name = format!("type_reconstruction_namespaces-{}", i);
+        insta::assert_snapshot!(snapshot_name, reconstructed_type);
+    }
+}
+
+#[test]
+fn test_forward_declarations_reconstruction() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let mut forward_declarations = String::new();
+    for test_case_type_name in TEST_CASES {
+        forward_declarations.push_str(
+            &pdb_file
+                .reconstruct_forward_declaration_by_name(
+                    test_case_type_name,
+                    PrimitiveReconstructionFlavor::Portable,
+                )
+                .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name)),
+        );
+    }
+
+    insta::assert_snapshot!("forward_declarations_reconstruction", forward_declarations);
+}
+
 fn test_type_reconstruction_internal(
     test_name: &str,
     primitives_flavor: PrimitiveReconstructionFlavor,
+    output_format: OutputFormat,
     reconstruct_dependencies: bool,
-    print_access_specifiers: bool,
+    fmt_configuration: &DataFormatConfiguration,
 ) {
     let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
     for (i, test_case_type_name) in TEST_CASES.iter().enumerate() {
@@ -64,12 +591,417 @@ fn test_type_reconstruction_internal(
             .reconstruct_type_by_name(
                 test_case_type_name,
                 primitives_flavor,
+                output_format,
                 reconstruct_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             )
-            .expect(format!("reconstruct type: {}", test_case_type_name).as_str());
+            .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name));
 
         let snapshot_name = format!("{}-{}", test_name, i);
         insta::assert_snapshot!(snapshot_name, reconstructed_type);
     }
 }
+
+#[test]
+fn test_type_model_reconstruction() {
+    const MODEL_TEST_CASES: &[&str] = &[
+        "resym_test::BitFieldsTest2",
+        "resym_test::UnionTest",
+        "resym_test::EnumTest2",
+    ];
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, test_case_type_name) in MODEL_TEST_CASES.iter().enumerate() {
+        let reconstructed_type_model = pdb_file
+            .reconstruct_type_model_by_name(
+                test_case_type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                false,
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type model: {}", test_case_type_name));
+        let serialized_type_model =
+            serde_json::to_string_pretty(&reconstructed_type_model).expect("serialize model");
+
+        let snapshot_name = format!("type_model_reconstruction-{}", i);
+        insta::assert_snapshot!(snapshot_name, serialized_type_model);
+    }
+}
+
+#[test]
+fn test_type_reconstruction_multidimensional_arrays() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let reconstructed_type = pdb_file
+        .reconstruct_type_by_name(
+            "resym_test::ArrayTest",
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            false,
+            &DataFormatConfiguration::default(),
+        )
+        .expect("reconstruct type");
+
+    // Dimensions are listed from the outermost to the innermost one
+    assert!(reconstructed_type.contains(" char array4[1][2][3][4][5];"));
+    assert!(reconstructed_type.contains(" int32_t array5[1][2][3][4][5];"));
+    assert!(reconstructed_type.contains(" resym_test::PrimitiveTypesTest array6[1][2][3][4][5];"));
+}
+
+#[test]
+fn test_type_reconstruction_cv_qualifiers() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let reconstruct_type = |type_name: &str| {
+        pdb_file
+            .reconstruct_type_by_name(
+                type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                false,
+                &DataFormatConfiguration {
+                    print_methods: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type: {}", type_name))
+    };
+
+    // Pointer to const data
+    let reconstructed_type = reconstruct_type("resym_test::ClassWithRefsAndStaticsTest");
+    assert!(reconstructed_type.contains(" const int32_t* ciptr;"));
+    assert!(reconstructed_type.contains(" const int32_t& ciref;"));
+
+    // Const pointer to const data
+    let reconstructed_type = reconstruct_type("std::exception");
+    assert!(reconstructed_type.contains(" exception(const char* const);"));
+}
+
+#[test]
+fn test_type_reconstruction_qualified_pointers_to_arrays_and_functions() {
+    // Qualifiers of pointers to arrays and functions go inside of the
+    // declarator's parentheses, whether they're stored in the pointers'
+    // attributes or in modifiers
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_DECLARATORS_PDB_FILE_PATH))
+        .expect("load test_declarators.pdb");
+    let reconstructed_type = pdb_file
+        .reconstruct_type_by_name(
+            "resym_test::QualifiedPointersTest",
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            false,
+            &DataFormatConfiguration::default(),
+        )
+        .expect("reconstruct type: resym_test::QualifiedPointersTest");
+
+    insta::assert_snapshot!(reconstructed_type);
+}
+
+#[test]
+fn test_type_reconstruction_typedefs() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_TYPEDEFS_PDB_FILE_PATH))
+        .expect("load test_typedefs.pdb");
+    for resolve_typedefs in [false, true] {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                "resym_test::TypedefsTest",
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                false,
+                &DataFormatConfiguration {
+                    resolve_typedefs,
+                    ..Default::default()
+                },
+            )
+            .expect("reconstruct type: resym_test::TypedefsTest");
+
+        let snapshot_name = format!("type_reconstruction_typedefs-{}", resolve_typedefs);
+        insta::assert_snapshot!(snapshot_name, reconstructed_type);
+    }
+}
+
+#[test]
+fn test_type_reconstruction_cyclic_typedefs() {
+    // Members typed with typedefs that never resolve to a type are reported
+    // rather than followed endlessly
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_TYPEDEFS_PDB_FILE_PATH))
+        .expect("load test_typedefs.pdb");
+    for resolve_typedefs in [false, true] {
+        let reconstructed_type = pdb_file
+            .reconstruct_type_by_name(
+                "resym_test::CyclicTypedefsTest",
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                false,
+                &DataFormatConfiguration {
+                    resolve_typedefs,
+                    ..Default::default()
+                },
+            )
+            .expect("reconstruct type: resym_test::CyclicTypedefsTest");
+        assert!(!reconstructed_type.contains("cyclic"));
+    }
+}
+
+#[test]
+fn test_type_reconstruction_reference_parameters() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let reconstructed_type = pdb_file
+        .reconstruct_type_by_name(
+            "resym_test::PureVirtualClass",
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            false,
+            &DataFormatConfiguration {
+                print_methods: true,
+                ..Default::default()
+            },
+        )
+        .expect("reconstruct type");
+
+    // Copy and move special member functions take lvalue and rvalue
+    // references respectively
+    assert!(reconstructed_type.contains(" PureVirtualClass(const resym_test::PureVirtualClass&);"));
+    assert!(reconstructed_type.contains(" PureVirtualClass(resym_test::PureVirtualClass&&);"));
+    assert!(reconstructed_type
+        .contains(" resym_test::PureVirtualClass& operator=(resym_test::PureVirtualClass&&);"));
+}
+
+#[test]
+fn test_type_reconstruction_declaration_order() {
+    // Members of unnamed unions overlap, so the declaration order of these
+    // types' members differs from their offset order
+    const DECLARATION_ORDER_TEST_CASES: &[&str] = &[
+        "resym_test::BitFieldsTest1",
+        "resym_test::BitFieldsTest2",
+        "resym_test::StructUnnamedUdtTest1",
+        "resym_test::StructUnnamedUdtTest3",
+    ];
+    use pdb::FallibleIterator;
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let mut pdb = pdb::PDB::open(std::fs::File::open(TEST_PDB_FILE_PATH).expect("open test.pdb"))
+        .expect("parse test.pdb");
+    let type_information = pdb.type_information().expect("type information");
+    for test_case_type_name in DECLARATION_ORDER_TEST_CASES {
+        // Read the members' names straight from the type's field list
+        let mut type_finder = type_information.finder();
+        let mut type_iter = type_information.iter();
+        let mut field_list_index = None;
+        while let Some(type_item) = type_iter.next().expect("type record") {
+            type_finder.update(&type_iter);
+            if let Ok(pdb::TypeData::Class(data)) = type_item.parse() {
+                if data.name.to_string() == *test_case_type_name && data.fields.is_some() {
+                    field_list_index = data.fields;
+                }
+            }
+        }
+        let mut declared_member_names = vec![];
+        let mut next_field_list_index = field_list_index;
+        while let Some(field_list_index) = next_field_list_index {
+            let field_list = match type_finder.find(field_list_index).unwrap().parse() {
+                Ok(pdb::TypeData::FieldList(field_list)) => field_list,
+                _ => panic!("field list of {}", test_case_type_name),
+            };
+            for field in &field_list.fields {
+                if let pdb::TypeData::Member(data) = field {
+                    declared_member_names.push(data.name.to_string().into_owned());
+                }
+            }
+            next_field_list_index = field_list.continuation;
+        }
+
+        let reconstructed_type_model = pdb_file
+            .reconstruct_type_model_by_name(
+                test_case_type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                false,
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type model: {}", test_case_type_name));
+        let reconstructed_member_names = reconstructed_type_model[0]
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(reconstructed_member_names, declared_member_names);
+    }
+
+    // Members are written in declaration order as well, even though `i5`
+    // is placed before `i3`
+    let reconstructed_type = pdb_file
+        .reconstruct_type_by_name(
+            "resym_test::StructUnnamedUdtTest1",
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            false,
+            &DataFormatConfiguration::default(),
+        )
+        .expect("reconstruct type");
+    let member_position = |member_name: &str| {
+        reconstructed_type
+            .find(&format!(" {};", member_name))
+            .unwrap_or_else(|| panic!("declaration of {}", member_name))
+    };
+    assert!(member_position("i3") < member_position("i5"));
+    assert!(member_position("i5") < member_position("i21"));
+}
+
+#[test]
+fn test_type_reconstruction_determinism() {
+    let reconstruct_test_types = || {
+        // Each run loads the PDB again, so that no state is shared
+        let pdb_file =
+            PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+        let mut reconstructed_types = vec![];
+        for test_case_type_name in TEST_CASES
+            .iter()
+            .chain(&["_s__RTTIClassHierarchyDescriptor"])
+        {
+            reconstructed_types.push(
+                pdb_file
+                    .reconstruct_type_by_name(
+                        test_case_type_name,
+                        PrimitiveReconstructionFlavor::Portable,
+                        OutputFormat::Cpp,
+                        true,
+                        &DataFormatConfiguration::default(),
+                    )
+                    .unwrap_or_else(|_| panic!("reconstruct type: {}", test_case_type_name)),
+            );
+        }
+        pdb_file
+            .reconstruct_all_types(
+                PrimitiveReconstructionFlavor::Portable,
+                true,
+                &DataFormatConfiguration::default(),
+                |chunk| {
+                    reconstructed_types.push(chunk);
+                    Ok(())
+                },
+                |_, _| Ok(()),
+            )
+            .expect("reconstruct all types");
+
+        reconstructed_types
+    };
+
+    assert_eq!(reconstruct_test_types(), reconstruct_test_types());
+}
+
+#[test]
+fn test_all_types_reconstruction() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let mut chunks = vec![];
+    pdb_file
+        .reconstruct_all_types(
+            PrimitiveReconstructionFlavor::Portable,
+            true,
+            &DataFormatConfiguration::default(),
+            |chunk| {
+                chunks.push(chunk);
+                Ok(())
+            },
+            |_, _| Ok(()),
+        )
+        .expect("reconstruct all types");
+    // The output is streamed
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+    let reconstructed_types = chunks.concat();
+
+    // Every test type must be part of the output, and be output only after the
+    // types it depends on
+    for test_case_type_name in TEST_CASES {
+        let type_definition_position =
+            type_definition_line(&reconstructed_types, test_case_type_name)
+                .unwrap_or_else(|| panic!("definition of {}", test_case_type_name));
+        let type_model = pdb_file
+            .reconstruct_type_model_by_name(
+                test_case_type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                true,
+            )
+            .unwrap_or_else(|_| panic!("reconstruct type model: {}", test_case_type_name));
+        for dependency in type_model
+            .iter()
+            .filter(|t| t.name.as_str() != *test_case_type_name)
+        {
+            let dependency_definition_position =
+                type_definition_line(&reconstructed_types, &dependency.name)
+                    .unwrap_or_else(|| panic!("definition of {}", dependency.name));
+            assert!(
+                dependency_definition_position < type_definition_position,
+                "{} is defined after {}",
+                dependency.name,
+                test_case_type_name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_type_reconstruction_type_name_suggestions() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    assert_eq!(
+        pdb_file.suggest_type_names("resym_test::StructTst"),
+        vec!["resym_test::StructTest"]
+    );
+    // Case is ignored, closest names come first
+    assert_eq!(
+        pdb_file.suggest_type_names("resym_test::enumtest"),
+        vec!["resym_test::EnumTest1", "resym_test::EnumTest2"]
+    );
+    assert!(pdb_file.suggest_type_names("NoSuchTypeAnywhere").is_empty());
+
+    let err = pdb_file
+        .reconstruct_type_by_name(
+            "resym_test::StructTst",
+            PrimitiveReconstructionFlavor::Portable,
+            OutputFormat::Cpp,
+            false,
+            &DataFormatConfiguration::default(),
+        )
+        .expect_err("inexistent type");
+    assert_eq!(
+        err.to_string(),
+        "type 'resym_test::StructTst' not found; did you mean: resym_test::StructTest?"
+    );
+}
+
+/// Return the line number of the first definition of `type_name` in `output`.
+fn type_definition_line(output: &str, type_name: &str) -> Option<usize> {
+    output.lines().position(|line| {
+        let mut words = line.split_whitespace();
+        !line.starts_with(' ')
+            && line.contains('{')
+            && matches!(
+                words.next(),
+                Some("struct" | "class" | "interface" | "union" | "enum")
+            )
+            && words.next() == Some(type_name)
+    })
+}
+
+#[test]
+fn test_type_reconstruction_by_unique_name() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    for (type_name, type_index) in &pdb_file.complete_type_list {
+        assert!(pdb_file.type_indices_by_name[type_name].contains(type_index));
+    }
+
+    let reconstruct_type = |type_name| {
+        pdb_file
+            .reconstruct_type_by_name(
+                type_name,
+                PrimitiveReconstructionFlavor::Portable,
+                OutputFormat::Cpp,
+                false,
+                &DataFormatConfiguration::default(),
+            )
+            .expect("reconstruct type")
+    };
+    assert_eq!(
+        reconstruct_type(".?AUStructTest@resym_test@@"),
+        reconstruct_type("resym_test::StructTest")
+    );
+}
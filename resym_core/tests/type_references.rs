@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use resym_core::pdb_file::{PdbFile, TypeReferenceKind};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_find_referencing_types() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // `_LIST_ENTRY` points to itself and is embedded in `_PEB_LDR_DATA`
+    assert_eq!(
+        pdb_file
+            .find_referencing_types("_LIST_ENTRY", None)
+            .expect("find referencing types"),
+        vec!["_LIST_ENTRY", "_PEB_LDR_DATA"]
+    );
+    assert_eq!(
+        pdb_file
+            .find_referencing_types("_LIST_ENTRY", Some(TypeReferenceKind::Embedded))
+            .expect("find referencing types"),
+        vec!["_PEB_LDR_DATA"]
+    );
+    assert_eq!(
+        pdb_file
+            .find_referencing_types("_LIST_ENTRY", Some(TypeReferenceKind::Pointer))
+            .expect("find referencing types"),
+        vec!["_LIST_ENTRY"]
+    );
+}
+
+#[test]
+fn test_find_referencing_types_base_classes() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    assert_eq!(
+        pdb_file
+            .find_referencing_types(
+                "resym_test::PureVirtualClass",
+                Some(TypeReferenceKind::Embedded)
+            )
+            .expect("find referencing types"),
+        vec![
+            "resym_test::InterfaceImplClass",
+            "resym_test::PureVirtualClassSpecialized"
+        ]
+    );
+}
+
+#[test]
+fn test_find_referencing_types_inexistent_type() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    assert!(pdb_file
+        .find_referencing_types("TypeNotFound", None)
+        .is_err());
+}
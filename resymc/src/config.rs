@@ -0,0 +1,429 @@
+use anyhow::{anyhow, Result};
+use resym_core::pdb_types::{OffsetRadix, PrimitiveReconstructionFlavor};
+use serde::{de, Deserialize, Deserializer};
+use structopt::StructOpt;
+
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    dump_format::DumpFormat, highlight_theme::HighlightTheme, report_format::ReportFormat,
+    ResymOptions,
+};
+
+/// Name of the configuration file looked up when none is given with
+/// `--config`.
+pub const CONFIG_FILE_NAME: &str = "resym.toml";
+/// Name of the directory of the configuration file, in the user's
+/// configuration directory.
+const CONFIG_DIR_NAME: &str = "resym";
+
+/// Default values of the options of `resymc`'s commands. Keys are named
+/// after the options' long names (e.g., `print-dependencies = true`), in a
+/// table named after the commands they apply to (`[dump]` for `dump` and
+/// `dump-all`, `[list]` for `list`).
+///
+/// Options given on the command line take precedence over the file's values:
+/// flags enabled in the file are disabled with `--no-<flag>` (see
+/// `DisabledDumpFlags` and `DisabledListFlags`), and the file's values which
+/// conflict with the options given on the command line are ignored. Values
+/// of the file which conflict with each other are rejected when loading it.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    dump: DumpConfig,
+    list: ListConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct DumpConfig {
+    #[serde(deserialize_with = "deserialize_from_str")]
+    primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+    /// Only applies to `dump`
+    #[serde(deserialize_with = "deserialize_from_str")]
+    format: Option<DumpFormat>,
+    print_header: bool,
+    print_dependencies: bool,
+    print_access_specifiers: bool,
+    methods: bool,
+    emit_size_asserts: bool,
+    emit_padding: bool,
+    offsets: bool,
+    #[serde(deserialize_with = "deserialize_from_str")]
+    offsets_radix: Option<OffsetRadix>,
+    infer_packing: bool,
+    pretty_templates: bool,
+    namespaces: bool,
+    sanitize_names: bool,
+    resolve_typedefs: bool,
+    enum_hex: bool,
+    highlight_syntax: bool,
+    /// Only applies when output is highlighted
+    #[serde(deserialize_with = "deserialize_from_str")]
+    theme: Option<HighlightTheme>,
+    /// Only applies when output is highlighted
+    theme_file: Option<PathBuf>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct ListConfig {
+    case_insensitive: bool,
+    /// Match modes only apply when none is given on the command line
+    use_regex: bool,
+    glob: bool,
+    fuzzy: bool,
+    #[serde(deserialize_with = "deserialize_from_str")]
+    format: Option<ReportFormat>,
+    #[serde(deserialize_with = "deserialize_from_str")]
+    primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+}
+
+/// Flags of `dump` and `dump-all` disabling the flags enabled by the
+/// configuration file.
+#[derive(Debug, StructOpt)]
+pub struct DisabledDumpFlags {
+    /// Don't print header, even if the configuration file enables it
+    #[structopt(long = "no-print-header", conflicts_with = "print-header")]
+    no_print_header: bool,
+    /// Don't print (or order types by) dependencies, even if the
+    /// configuration file enables it
+    #[structopt(long = "no-print-dependencies", conflicts_with = "print-dependencies")]
+    no_print_dependencies: bool,
+    /// Don't print C++ access specifiers, even if the configuration file
+    /// enables it
+    #[structopt(
+        long = "no-print-access-specifiers",
+        conflicts_with = "print-access-specifiers"
+    )]
+    no_print_access_specifiers: bool,
+    /// Don't print member function declarations, even if the configuration
+    /// file enables it
+    #[structopt(long = "no-methods", conflicts_with = "print-methods")]
+    no_print_methods: bool,
+    /// Don't emit static_asserts, even if the configuration file enables it
+    #[structopt(long = "no-emit-size-asserts", conflicts_with = "print-size-asserts")]
+    no_print_size_asserts: bool,
+    /// Don't insert padding fields, even if the configuration file enables it
+    #[structopt(long = "no-emit-padding", conflicts_with = "print-padding-fields")]
+    no_print_padding_fields: bool,
+    /// Don't append offset comments, even if the configuration file enables
+    /// it
+    #[structopt(long = "no-offsets", conflicts_with = "print-offset-comments")]
+    no_print_offset_comments: bool,
+    /// Don't infer struct packing, even if the configuration file enables it
+    #[structopt(long = "no-infer-packing", conflicts_with = "infer-packing")]
+    no_infer_packing: bool,
+    /// Don't collapse STL template instantiations, even if the configuration
+    /// file enables it
+    #[structopt(long = "no-pretty-templates", conflicts_with = "pretty-templates")]
+    no_pretty_templates: bool,
+    /// Don't declare types inside of namespace blocks, even if the
+    /// configuration file enables it
+    #[structopt(long = "no-namespaces", conflicts_with = "namespaces")]
+    no_namespaces: bool,
+    /// Don't rewrite type names, even if the configuration file enables it
+    #[structopt(long = "no-sanitize-names", conflicts_with = "sanitize-names")]
+    no_sanitize_names: bool,
+    /// Don't resolve typedefs, even if the configuration file enables it
+    #[structopt(long = "no-resolve-typedefs", conflicts_with = "resolve-typedefs")]
+    no_resolve_typedefs: bool,
+    /// Write enumerants' values in decimal, even if the configuration file
+    /// enables --enum-hex
+    #[structopt(long = "no-enum-hex", conflicts_with = "enum-hex")]
+    no_enum_hex: bool,
+    /// Don't highlight output, even if the configuration file enables it
+    #[structopt(long = "no-highlight-syntax", conflicts_with = "highlight-syntax")]
+    no_highlight_syntax: bool,
+}
+
+/// Flags of `list` disabling the flags enabled by the configuration file.
+#[derive(Debug, StructOpt)]
+pub struct DisabledListFlags {
+    /// Match case, even if the configuration file enables --case-insensitive
+    #[structopt(long = "no-case-insensitive", conflicts_with = "case-insensitive")]
+    no_case_insensitive: bool,
+    /// Don't use regular expressions, even if the configuration file enables
+    /// them
+    #[structopt(long = "no-use-regex", conflicts_with = "use-regex")]
+    no_use_regex: bool,
+    /// Don't use glob patterns, even if the configuration file enables them
+    #[structopt(long = "no-glob", conflicts_with = "use-glob")]
+    no_use_glob: bool,
+    /// Don't use fuzzy matching, even if the configuration file enables it
+    #[structopt(long = "no-fuzzy", conflicts_with = "use-fuzzy-matching")]
+    no_use_fuzzy_matching: bool,
+}
+
+impl Config {
+    /// Load the configuration from the given file or, if none is given, from
+    /// the first `resym.toml` file found in the working directory, then in
+    /// `$XDG_CONFIG_HOME/resym` (`~/.config/resym` by default). The default
+    /// configuration is returned if no file is found.
+    pub fn load(config_file_path: Option<&Path>) -> Result<Self> {
+        let config_file_path = match config_file_path {
+            Some(config_file_path) => config_file_path.to_path_buf(),
+            None => match find_config_file() {
+                Some(config_file_path) => config_file_path,
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let config = fs::read_to_string(&config_file_path).map_err(|err| {
+            anyhow!(
+                "failed to read configuration file '{}': {}",
+                config_file_path.display(),
+                err
+            )
+        })?;
+        toml::from_str(&config)
+            .map_err(anyhow::Error::from)
+            .and_then(|config: Self| config.validate().map(|_| config))
+            .map_err(|err| {
+                anyhow!(
+                    "invalid configuration file '{}': {}",
+                    config_file_path.display(),
+                    err
+                )
+            })
+    }
+
+    /// Check that the configuration's values don't conflict with each other,
+    /// as the options they're the values of do.
+    fn validate(&self) -> Result<()> {
+        let match_modes = [
+            ("use-regex", self.list.use_regex),
+            ("glob", self.list.glob),
+            ("fuzzy", self.list.fuzzy),
+        ]
+        .into_iter()
+        .filter_map(|(key, enabled)| enabled.then_some(key))
+        .collect::<Vec<_>>();
+        if match_modes.len() > 1 {
+            return Err(anyhow!(
+                "'{}' cannot be enabled at the same time",
+                match_modes.join("', '")
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Fill in the options of `command` that haven't been given on the
+    /// command line with the configuration's values, except for flags
+    /// disabled on the command line and for values which conflict with the
+    /// options given on the command line.
+    pub fn apply(&self, command: &mut ResymOptions) {
+        let forward_only = matches!(
+            command,
+            ResymOptions::Dump {
+                forward_only: true,
+                ..
+            }
+        );
+        // Forward declarations can't be output in another format
+        if let (ResymOptions::Dump { output_format, .. }, false) = (&mut *command, forward_only) {
+            merge_option(output_format, &self.dump.format);
+        }
+        let is_html = matches!(
+            command,
+            ResymOptions::Dump {
+                output_format: Some(DumpFormat::Html),
+                ..
+            }
+        );
+
+        match command {
+            ResymOptions::Dump {
+                primitive_types_flavor,
+                print_header,
+                print_dependencies,
+                print_access_specifiers,
+                print_methods,
+                print_size_asserts,
+                print_padding_fields,
+                print_offset_comments,
+                offset_radix,
+                infer_packing,
+                pretty_templates,
+                namespaces,
+                sanitize_names,
+                resolve_typedefs,
+                enum_hex,
+                highlight_syntax,
+                theme,
+                theme_file_path,
+                disabled_flags,
+                ..
+            }
+            | ResymOptions::DumpAll {
+                primitive_types_flavor,
+                print_header,
+                print_dependencies,
+                print_access_specifiers,
+                print_methods,
+                print_size_asserts,
+                print_padding_fields,
+                print_offset_comments,
+                offset_radix,
+                infer_packing,
+                pretty_templates,
+                namespaces,
+                sanitize_names,
+                resolve_typedefs,
+                enum_hex,
+                highlight_syntax,
+                theme,
+                theme_file_path,
+                disabled_flags,
+                ..
+            } => {
+                let config = &self.dump;
+                let disabled = &*disabled_flags;
+                merge_option(primitive_types_flavor, &config.primitive_types_flavor);
+                merge_flag(print_header, config.print_header, disabled.no_print_header);
+                // Dependencies can't be printed along with forward declarations
+                merge_flag(
+                    print_dependencies,
+                    config.print_dependencies && !forward_only,
+                    disabled.no_print_dependencies,
+                );
+                merge_flag(
+                    print_access_specifiers,
+                    config.print_access_specifiers,
+                    disabled.no_print_access_specifiers,
+                );
+                merge_flag(print_methods, config.methods, disabled.no_print_methods);
+                merge_flag(
+                    print_size_asserts,
+                    config.emit_size_asserts,
+                    disabled.no_print_size_asserts,
+                );
+                merge_flag(
+                    print_padding_fields,
+                    config.emit_padding,
+                    disabled.no_print_padding_fields,
+                );
+                merge_flag(
+                    print_offset_comments,
+                    config.offsets,
+                    disabled.no_print_offset_comments,
+                );
+                // Radixes only apply to offset comments
+                if *print_offset_comments {
+                    merge_option(offset_radix, &config.offsets_radix);
+                }
+                merge_flag(
+                    infer_packing,
+                    config.infer_packing,
+                    disabled.no_infer_packing,
+                );
+                merge_flag(
+                    pretty_templates,
+                    config.pretty_templates,
+                    disabled.no_pretty_templates,
+                );
+                merge_flag(namespaces, config.namespaces, disabled.no_namespaces);
+                merge_flag(
+                    sanitize_names,
+                    config.sanitize_names,
+                    disabled.no_sanitize_names,
+                );
+                merge_flag(
+                    resolve_typedefs,
+                    config.resolve_typedefs,
+                    disabled.no_resolve_typedefs,
+                );
+                merge_flag(enum_hex, config.enum_hex, disabled.no_enum_hex);
+                merge_flag(
+                    highlight_syntax,
+                    config.highlight_syntax,
+                    disabled.no_highlight_syntax,
+                );
+                // Themes can only be given when highlighting
+                if *highlight_syntax || is_html {
+                    merge_option(theme, &config.theme);
+                    merge_option(theme_file_path, &config.theme_file);
+                }
+            }
+            ResymOptions::List {
+                case_insensitive,
+                use_regex,
+                use_glob,
+                use_fuzzy_matching,
+                output_format,
+                forward_only,
+                primitive_types_flavor,
+                null_separated,
+                disabled_flags,
+                ..
+            } => {
+                let config = &self.list;
+                let disabled = &*disabled_flags;
+                merge_flag(
+                    case_insensitive,
+                    config.case_insensitive,
+                    disabled.no_case_insensitive,
+                );
+                if !(*use_regex || *use_glob || *use_fuzzy_matching) {
+                    *use_regex = config.use_regex && !disabled.no_use_regex;
+                    *use_glob = config.glob && !disabled.no_use_glob;
+                    *use_fuzzy_matching = config.fuzzy && !disabled.no_use_fuzzy_matching;
+                }
+                // Forward declarations and NUL-separated names can't be
+                // output in another format
+                if !(*forward_only || *null_separated) {
+                    merge_option(output_format, &config.format);
+                }
+                // Flavors only apply to forward declarations
+                if *forward_only {
+                    merge_option(primitive_types_flavor, &config.primitive_types_flavor);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Return the path of the first configuration file found in the default
+/// locations, if any.
+fn find_config_file() -> Option<PathBuf> {
+    // Note: Relative paths are ignored, as mandated by the XDG specification
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|config_dir| config_dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home_dir| Path::new(&home_dir).join(".config")));
+
+    std::iter::once(PathBuf::from(CONFIG_FILE_NAME))
+        .chain(config_dir.map(|config_dir| config_dir.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME)))
+        .find(|config_file_path| config_file_path.is_file())
+}
+
+/// Enable `flag` if the configuration enables it, unless it's been disabled
+/// on the command line.
+fn merge_flag(flag: &mut bool, default: bool, disabled: bool) {
+    *flag |= default && !disabled;
+}
+
+fn merge_option<T: Clone>(option: &mut Option<T>, default: &Option<T>) {
+    if option.is_none() {
+        *option = default.clone();
+    }
+}
+
+/// Deserialize an optional value from its string representation, as parsed
+/// on the command line.
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| value.parse().map_err(de::Error::custom))
+        .transpose()
+}
@@ -0,0 +1,45 @@
+use std::{fmt, str::FromStr};
+
+use resym_core::pdb_types::OutputFormat;
+
+/// Representation used to output dumped types: either one of the formats
+/// types are reconstructed in, or highlighted HTML.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DumpFormat {
+    Reconstructed(OutputFormat),
+    /// C++ reconstruction, highlighted as a self-contained HTML `<pre>` block
+    Html,
+}
+
+impl DumpFormat {
+    /// Return the format types must be reconstructed in.
+    pub fn output_format(&self) -> OutputFormat {
+        match self {
+            DumpFormat::Reconstructed(output_format) => *output_format,
+            DumpFormat::Html => OutputFormat::Cpp,
+        }
+    }
+}
+
+impl FromStr for DumpFormat {
+    type Err = ParseDumpFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(DumpFormat::Html),
+            _ => OutputFormat::from_str(s)
+                .map(DumpFormat::Reconstructed)
+                .map_err(|_| ParseDumpFormatError {}),
+        }
+    }
+}
+
+/// An error returned when parsing a `DumpFormat` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseDumpFormatError {}
+
+impl fmt::Display for ParseDumpFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid output format".fmt(f)
+    }
+}
@@ -1,23 +1,73 @@
+use std::io::{self, Write};
+
 use anyhow::Result;
 use crossbeam_channel::{Receiver, Sender};
-use resym_core::frontend::{FrontendCommand, FrontendController};
+use resym_core::frontend::{FrontendCommand, FrontendController, ProgressStage};
+
+/// Width of the progress bar, in characters
+const PROGRESS_BAR_WIDTH: usize = 30;
 
 /// Frontend implementation for the CLI application
 /// This struct enables the backend to communicate with us (the frontend)
 pub struct CLIFrontendController {
     pub rx_ui: Receiver<FrontendCommand>,
     tx_ui: Sender<FrontendCommand>,
+    show_progress: bool,
 }
 
 impl FrontendController for CLIFrontendController {
     /// Used by the backend to send us commands and trigger a UI update
     fn send_command(&self, command: FrontendCommand) -> Result<()> {
-        Ok(self.tx_ui.send(command)?)
+        match command {
+            // Progress is rendered right away instead of being queued, so
+            // that commands' results can be waited for without having to
+            // skip progress reports
+            FrontendCommand::UpdateProgress {
+                current,
+                total,
+                stage,
+            } => {
+                if self.show_progress {
+                    render_progress(current, total, stage)?;
+                }
+                Ok(())
+            }
+            _ => Ok(self.tx_ui.send(command)?),
+        }
     }
 }
 
 impl CLIFrontendController {
-    pub fn new(tx_ui: Sender<FrontendCommand>, rx_ui: Receiver<FrontendCommand>) -> Self {
-        Self { rx_ui, tx_ui }
+    pub fn new(
+        tx_ui: Sender<FrontendCommand>,
+        rx_ui: Receiver<FrontendCommand>,
+        show_progress: bool,
+    ) -> Self {
+        Self {
+            rx_ui,
+            tx_ui,
+            show_progress,
+        }
+    }
+}
+
+/// Draw a progress bar on stderr, the bar is erased once the operation
+/// completes.
+fn render_progress(current: usize, total: usize, stage: ProgressStage) -> Result<()> {
+    let mut stderr = io::stderr().lock();
+    if current >= total {
+        // Erase the whole line
+        write!(stderr, "\r\x1b[2K")?;
+    } else {
+        let filled_width = current * PROGRESS_BAR_WIDTH / total;
+        write!(
+            stderr,
+            "\r{}: [{}{}] {}%",
+            stage,
+            "#".repeat(filled_width),
+            "-".repeat(PROGRESS_BAR_WIDTH - filled_width),
+            current * 100 / total
+        )?;
     }
+    Ok(stderr.flush()?)
 }
@@ -0,0 +1,62 @@
+use resym_core::pdb_file::{TypeGraph, TypeReferenceKind};
+
+use std::{fmt, str::FromStr};
+
+/// Representation used to output type graphs.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GraphFormat {
+    /// Graphviz's DOT language
+    Dot,
+}
+
+impl FromStr for GraphFormat {
+    type Err = ParseGraphFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "gv" => Ok(GraphFormat::Dot),
+            _ => Err(ParseGraphFormatError {}),
+        }
+    }
+}
+
+/// An error returned when parsing a `GraphFormat` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseGraphFormatError {}
+
+impl fmt::Display for ParseGraphFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid graph format".fmt(f)
+    }
+}
+
+/// Write the given graph in the DOT language. Embedded types are linked with
+/// solid edges, pointed-to types with dashed edges.
+pub fn fmt_dot_graph(graph: &TypeGraph, f: &mut impl fmt::Write) -> fmt::Result {
+    let root_type_name = graph
+        .type_names
+        .first()
+        .map(String::as_str)
+        .unwrap_or_default();
+    writeln!(f, "digraph {} {{", dot_string(root_type_name))?;
+    writeln!(f, "  node [shape=box];")?;
+    // Note: Nodes are identified by their index, as distinct types may share
+    // a name
+    for (type_index, type_name) in graph.type_names.iter().enumerate() {
+        writeln!(f, "  t{} [label={}];", type_index, dot_string(type_name))?;
+    }
+    for edge in &graph.edges {
+        match edge.kind {
+            TypeReferenceKind::Embedded => writeln!(f, "  t{} -> t{};", edge.from, edge.to)?,
+            TypeReferenceKind::Pointer => {
+                writeln!(f, "  t{} -> t{} [style=dashed];", edge.from, edge.to)?
+            }
+        }
+    }
+    writeln!(f, "}}")
+}
+
+/// Quote the given string as a DOT identifier.
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
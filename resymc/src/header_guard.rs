@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+/// Preamble making dumped C or C++ types usable as a header that can be
+/// included several times.
+#[derive(Clone, PartialEq, Debug)]
+pub enum HeaderGuard {
+    /// `#ifndef`/`#define`/`#endif` include guard, with the given macro name
+    IncludeGuard(String),
+    /// `#pragma once` directive
+    PragmaOnce,
+}
+
+impl HeaderGuard {
+    /// Return an include guard, whose macro is named `guard_name` or derived
+    /// from the name of the header written to `output_file_path`.
+    pub fn include_guard(
+        guard_name: Option<String>,
+        output_file_path: Option<&Path>,
+    ) -> Result<Self> {
+        let guard_name = match (guard_name, output_file_path) {
+            (Some(guard_name), _) => guard_name,
+            (None, Some(output_file_path)) => guard_macro_name(output_file_path)
+                .ok_or_else(|| anyhow!("Cannot derive a guard name from the output file path"))?,
+            (None, None) => {
+                return Err(anyhow!(
+                    "--header-guard requires --guard-name when writing to stdout"
+                ))
+            }
+        };
+        if !is_macro_name(&guard_name) {
+            return Err(anyhow!("Invalid guard name: '{}'", guard_name));
+        }
+
+        Ok(HeaderGuard::IncludeGuard(guard_name))
+    }
+
+    /// Wrap the given header `content` with the guard's preamble (and
+    /// epilogue).
+    pub fn wrap(&self, content: &str) -> String {
+        let content = content.trim_end_matches('\n');
+        match self {
+            HeaderGuard::IncludeGuard(guard_name) => format!(
+                "#ifndef {0}\n#define {0}\n{1}\n\n#endif // {0}\n",
+                guard_name, content
+            ),
+            HeaderGuard::PragmaOnce => format!("#pragma once\n{}\n", content),
+        }
+    }
+}
+
+/// Return the name of the include guard macro for the header at `file_path`
+/// (e.g., `MY_TYPES_H` for `out/my-types.h`).
+fn guard_macro_name(file_path: &Path) -> Option<String> {
+    let file_name = file_path.file_name()?.to_str()?;
+    let mut guard_name = file_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if guard_name.starts_with(|c: char| c.is_ascii_digit()) {
+        guard_name.insert(0, '_');
+    }
+
+    Some(guard_name)
+}
+
+fn is_macro_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
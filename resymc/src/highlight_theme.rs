@@ -0,0 +1,64 @@
+use std::{env, fmt, str::FromStr};
+
+use resym_core::syntax_highlighting::CodeTheme;
+
+/// Color theme used to highlight the output, depending on the background of
+/// the terminal.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HighlightTheme {
+    Dark,
+    Light,
+}
+
+impl HighlightTheme {
+    /// Guess the theme matching the terminal's background from the
+    /// `COLORFGBG` environment variable (e.g., "0;15"), which some terminals
+    /// set. Defaults to `Dark`.
+    pub fn detect() -> Self {
+        env::var("COLORFGBG")
+            .ok()
+            .and_then(|colors| Self::from_colorfgbg(&colors))
+            .unwrap_or(HighlightTheme::Dark)
+    }
+
+    /// Return the theme matching the background color advertised in a
+    /// `COLORFGBG` value, if any. The background is the last color, and is
+    /// light for white (7) and the bright colors except dark gray (8).
+    pub fn from_colorfgbg(colors: &str) -> Option<Self> {
+        let background = colors.rsplit(';').next()?.parse::<u8>().ok()?;
+        match background {
+            7 | 9..=15 => Some(HighlightTheme::Light),
+            0..=6 | 8 => Some(HighlightTheme::Dark),
+            _ => None,
+        }
+    }
+
+    pub fn code_theme(&self) -> CodeTheme {
+        match self {
+            HighlightTheme::Dark => CodeTheme::dark(),
+            HighlightTheme::Light => CodeTheme::light(),
+        }
+    }
+}
+
+impl FromStr for HighlightTheme {
+    type Err = ParseHighlightThemeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dark" => Ok(HighlightTheme::Dark),
+            "light" => Ok(HighlightTheme::Light),
+            _ => Err(ParseHighlightThemeError {}),
+        }
+    }
+}
+
+/// An error returned when parsing a `HighlightTheme` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseHighlightThemeError {}
+
+impl fmt::Display for ParseHighlightThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid theme".fmt(f)
+    }
+}
@@ -1,18 +1,56 @@
+mod config;
+mod dump_format;
 mod frontend;
+mod graph_format;
+mod header_guard;
+mod highlight_theme;
+mod repl;
+mod report_format;
+mod symbol_server;
 mod syntax_highlighting;
+mod watch;
 
-use std::{fs::File, io::Write, path::PathBuf, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::{HashSet, VecDeque},
+    env, fmt,
+    fs::{self, File},
+    io::{self, IsTerminal, Read, Write},
+    num::ParseIntError,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{anyhow, Result};
+use log::LevelFilter;
 use resym_core::{
-    backend::{Backend, BackendCommand, PDBSlot},
+    backend::{Backend, BackendCommand, FilterMatchMode, PDBSlot, TypeFilter},
+    diffing::TypeListChange,
+    error::ResymError,
     frontend::FrontendCommand,
-    pdb_types::PrimitiveReconstructionFlavor,
-    syntax_highlighting::CodeTheme,
+    pdb_file::{TypeKind, TypeReferenceKind},
+    pdb_types::{
+        merge_kaitai_documents, DataFormatConfiguration, OffsetRadix, OutputFormat,
+        PrimitiveReconstructionFlavor,
+    },
+    pe_file::PdbReference,
 };
+use serde::Serialize;
 use structopt::StructOpt;
 
-use crate::{frontend::CLIFrontendController, syntax_highlighting::highlight_code};
+use crate::{
+    config::{Config, DisabledDumpFlags, DisabledListFlags},
+    dump_format::DumpFormat,
+    frontend::CLIFrontendController,
+    graph_format::{fmt_dot_graph, GraphFormat},
+    header_guard::HeaderGuard,
+    highlight_theme::HighlightTheme,
+    report_format::ReportFormat,
+    symbol_server::{FetchedPdb, SymbolPath},
+    syntax_highlighting::{
+        highlight_code, highlight_code_html, output_format_language, SyntaxTheme,
+    },
+};
 
 const PKG_NAME: &str = env!("CARGO_PKG_NAME");
 
@@ -20,43 +58,435 @@ const PKG_NAME: &str = env!("CARGO_PKG_NAME");
 const PDB_MAIN_SLOT: PDBSlot = 0;
 /// Slot used for the PDB we're diffing to
 const PDB_DIFF_TO_SLOT: PDBSlot = 1;
+/// Number of levels of references followed by default in type graphs
+const DEFAULT_GRAPH_DEPTH: usize = 3;
+/// Maximum number of levels of references followed in type graphs, as
+/// graphs of deeply connected types quickly become unreadable (and huge)
+const MAX_GRAPH_DEPTH: usize = 16;
 
 fn main() -> Result<()> {
-    let app = ResymcApp::new()?;
+    let args = ResymcArgs::from_args();
+    init_logger(args.verbosity);
+    // Progress bars would clutter redirected output
+    let show_progress = !args.no_progress && io::stderr().is_terminal();
+    let use_colors = is_color_enabled(args.force_color);
+    let config = Config::load(args.config_file_path.as_deref())?;
+    let app = ResymcApp::new(show_progress)?;
+
+    run_command(&app, args.command, &config, use_colors)
+}
+
+/// Log messages to stderr. Nothing is logged by default (failing commands
+/// report their error anyway), `-v` logs the main steps of commands along
+/// with their duration, errors included, and `-vv` every detail (e.g., the
+/// PDB's streams as they're parsed).
+fn init_logger(verbosity: u8) {
+    let level = match verbosity {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    // Note: Dependencies' messages are only relevant when they fail
+    env_logger::Builder::new()
+        .filter_level(level.min(LevelFilter::Error))
+        .filter_module("resym_core", level)
+        .filter_module(PKG_NAME, level)
+        .init();
+}
 
-    // Process command and options
-    let opt = ResymOptions::from_args();
-    match opt {
+/// Process a command and its options, the options that haven't been given
+/// are taken from the configuration.
+fn run_command(
+    app: &ResymcApp,
+    mut command: ResymOptions,
+    config: &Config,
+    use_colors: bool,
+) -> Result<()> {
+    config.apply(&mut command);
+    match command {
         ResymOptions::List {
             pdb_path,
             type_name_filter,
             output_file_path,
             case_insensitive,
             use_regex,
-        } => app.list_types_command(
+            use_glob,
+            use_fuzzy_matching,
+            output_format,
+            forward_only,
+            primitive_types_flavor,
+            null_separated,
+            min_size,
+            max_size,
+            kinds,
+            exclusion_filters,
+            watch,
+            // Applied along with the configuration
+            disabled_flags: _,
+        } => {
+            let match_mode = if use_regex {
+                FilterMatchMode::Regex
+            } else if use_glob {
+                FilterMatchMode::Glob
+            } else if use_fuzzy_matching {
+                FilterMatchMode::Fuzzy
+            } else {
+                FilterMatchMode::Substring
+            };
+            let output_format = output_format.unwrap_or(ReportFormat::Text);
+            let primitive_types_flavor =
+                primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable);
+
+            app.run_watched_command(&pdb_path, watch, || {
+                app.list_types_command(
+                    pdb_path.clone(),
+                    type_name_filter.clone(),
+                    case_insensitive,
+                    match_mode,
+                    output_format,
+                    forward_only,
+                    primitive_types_flavor,
+                    null_separated,
+                    TypeFilter {
+                        min_size,
+                        max_size,
+                        kinds: kinds.clone(),
+                        exclusion_filters: exclusion_filters.clone(),
+                    },
+                    output_file_path.clone(),
+                )
+            })
+        }
+        ResymOptions::Symbols {
             pdb_path,
-            type_name_filter,
+            symbol_name_filter,
+            output_file_path,
+            case_insensitive,
+            use_regex,
+            use_glob,
+            output_format,
+            print_mangled_names,
+        } => app.list_symbols_command(
+            pdb_path,
+            symbol_name_filter,
+            case_insensitive,
+            if use_regex {
+                FilterMatchMode::Regex
+            } else if use_glob {
+                FilterMatchMode::Glob
+            } else {
+                FilterMatchMode::Substring
+            },
+            output_format.unwrap_or(ReportFormat::Text),
+            print_mangled_names,
+            output_file_path,
+        ),
+        ResymOptions::Globals {
+            pdb_path,
+            global_name_filter,
+            output_file_path,
+            case_insensitive,
+            use_regex,
+            use_glob,
+            output_format,
+            primitive_types_flavor,
+        } => app.list_globals_command(
+            pdb_path,
+            global_name_filter,
+            case_insensitive,
+            if use_regex {
+                FilterMatchMode::Regex
+            } else if use_glob {
+                FilterMatchMode::Glob
+            } else {
+                FilterMatchMode::Substring
+            },
+            output_format.unwrap_or(ReportFormat::Text),
+            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
+            output_file_path,
+        ),
+        ResymOptions::Resolve { pdb_path, rva } => app.resolve_address_command(pdb_path, rva),
+        ResymOptions::Modules {
+            pdb_path,
+            output_file_path,
+            output_format,
+            print_symbol_counts,
+        } => app.list_modules_command(
+            pdb_path,
+            output_format.unwrap_or(ReportFormat::Text),
+            print_symbol_counts,
+            output_file_path,
+        ),
+        ResymOptions::Stats {
+            pdb_path,
+            output_format,
+        } => app.statistics_command(pdb_path, output_format.unwrap_or(ReportFormat::Text)),
+        ResymOptions::Check { pdb_path } => app.check_command(pdb_path),
+        ResymOptions::Verify { pe_path, pdb_path } => app.verify_pe_command(pe_path, pdb_path),
+        ResymOptions::Com {
+            pdb_path,
+            output_file_path,
+            pe_path,
+            primitive_types_flavor,
+        } => app.com_command(
+            pdb_path,
+            pe_path,
+            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Microsoft),
+            output_file_path,
+        ),
+        ResymOptions::Srcinfo {
+            pdb_path,
+            symbol_name,
+            output_format,
+        } => app.source_info_command(
+            pdb_path,
+            symbol_name,
+            output_format.unwrap_or(ReportFormat::Text),
+        ),
+        ResymOptions::FindField {
+            pdb_path,
+            field_name_filter,
+            output_file_path,
+            case_insensitive,
+            use_regex,
+            use_glob,
+            output_format,
+            print_field_types,
+            primitive_types_flavor,
+        } => app.find_field_command(
+            pdb_path,
+            field_name_filter,
+            case_insensitive,
+            if use_regex {
+                FilterMatchMode::Regex
+            } else if use_glob {
+                FilterMatchMode::Glob
+            } else {
+                FilterMatchMode::Substring
+            },
+            output_format.unwrap_or(ReportFormat::Text),
+            print_field_types,
+            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
+            output_file_path,
+        ),
+        ResymOptions::GrepMembers {
+            pdb_path,
+            field_name_filter,
+            output_file_path,
             case_insensitive,
             use_regex,
+            use_glob,
+            output_format,
+            primitive_types_flavor,
+        } => app.find_field_command(
+            pdb_path,
+            field_name_filter,
+            case_insensitive,
+            if use_regex {
+                FilterMatchMode::Regex
+            } else if use_glob {
+                FilterMatchMode::Glob
+            } else {
+                FilterMatchMode::Substring
+            },
+            output_format.unwrap_or(ReportFormat::Text),
+            true,
+            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
+            output_file_path,
+        ),
+        ResymOptions::Xref {
+            pdb_path,
+            type_name,
+            output_file_path,
+            embedded_only,
+            pointers_only,
+        } => app.find_referencing_types_command(
+            pdb_path,
+            type_name,
+            if embedded_only {
+                Some(TypeReferenceKind::Embedded)
+            } else if pointers_only {
+                Some(TypeReferenceKind::Pointer)
+            } else {
+                None
+            },
             output_file_path,
         ),
+        ResymOptions::Graph {
+            pdb_path,
+            type_name,
+            output_file_path,
+            output_format,
+            depth,
+        } => {
+            let depth = depth.unwrap_or(DEFAULT_GRAPH_DEPTH);
+            if depth > MAX_GRAPH_DEPTH {
+                return Err(anyhow!("--depth can't exceed {}", MAX_GRAPH_DEPTH));
+            }
+            app.type_graph_command(
+                pdb_path,
+                type_name,
+                output_format.unwrap_or(GraphFormat::Dot),
+                depth,
+                output_file_path,
+            )
+        }
         ResymOptions::Dump {
             pdb_path,
             type_name,
+            additional_type_names,
+            names_file_path,
+            type_indices,
+            all_matches,
             output_file_path,
             primitive_types_flavor,
+            output_format,
             print_header,
             print_dependencies,
+            forward_only,
             print_access_specifiers,
+            print_methods,
+            print_size_asserts,
+            print_padding_fields,
+            print_offset_comments,
+            offset_radix,
+            infer_packing,
+            pretty_templates,
+            namespaces,
+            sanitize_names,
+            resolve_typedefs,
+            enum_hex,
             highlight_syntax,
-        } => app.dump_types_command(
+            theme,
+            theme_file_path,
+            header_guard,
+            pragma_once,
+            guard_name,
+            watch,
+            // Applied along with the configuration
+            disabled_flags: _,
+        } => {
+            let mut type_names: Vec<String> =
+                type_name.into_iter().chain(additional_type_names).collect();
+            if let Some(names_file_path) = names_file_path {
+                type_names.extend(read_type_names_file(&names_file_path)?);
+            }
+            let requested_types = type_names
+                .into_iter()
+                .map(RequestedType::Name)
+                .chain(type_indices.into_iter().map(RequestedType::Index))
+                .collect::<Vec<_>>();
+            if requested_types.is_empty() {
+                return Err(anyhow!("No type name or type index provided"));
+            }
+            let output_format =
+                output_format.unwrap_or(DumpFormat::Reconstructed(OutputFormat::Cpp));
+            let is_html = output_format == DumpFormat::Html;
+            if (theme.is_some() || theme_file_path.is_some()) && !highlight_syntax && !is_html {
+                return Err(anyhow!(
+                    "--theme and --theme-file can only be used with --highlight-syntax or --format html"
+                ));
+            }
+            let header_guard = if header_guard || pragma_once {
+                if !matches!(
+                    output_format,
+                    DumpFormat::Reconstructed(OutputFormat::Cpp | OutputFormat::C)
+                ) {
+                    return Err(anyhow!(
+                        "--header-guard and --pragma-once can only be used with C or C++ output"
+                    ));
+                }
+                Some(if pragma_once {
+                    HeaderGuard::PragmaOnce
+                } else {
+                    HeaderGuard::include_guard(guard_name, output_file_path.as_deref())?
+                })
+            } else {
+                None
+            };
+
+            let primitive_types_flavor =
+                primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable);
+            let fmt_configuration = DataFormatConfiguration {
+                print_access_specifiers,
+                print_methods,
+                print_size_asserts,
+                print_padding_fields,
+                print_offset_comments: print_offset_comments
+                    .then(|| offset_radix.unwrap_or(OffsetRadix::Hexadecimal)),
+                pretty_templates,
+                infer_packing,
+                print_enum_values_in_hex: enum_hex,
+                print_namespaces: namespaces,
+                sanitize_names,
+                resolve_typedefs,
+            };
+            let highlight_theme = highlight_theme(
+                (highlight_syntax && use_colors) || is_html,
+                theme,
+                theme_file_path,
+            );
+
+            app.run_watched_command(&pdb_path, watch, || {
+                app.dump_types_command(
+                    pdb_path.clone(),
+                    requested_types.clone(),
+                    primitive_types_flavor,
+                    output_format,
+                    print_header,
+                    print_dependencies,
+                    forward_only,
+                    all_matches,
+                    fmt_configuration.clone(),
+                    highlight_theme.clone(),
+                    header_guard.clone(),
+                    output_file_path.clone(),
+                )
+            })
+        }
+        ResymOptions::DumpAll {
             pdb_path,
-            type_name,
-            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
+            output_file_path,
+            primitive_types_flavor,
             print_header,
             print_dependencies,
             print_access_specifiers,
+            print_methods,
+            print_size_asserts,
+            print_padding_fields,
+            print_offset_comments,
+            offset_radix,
+            infer_packing,
+            pretty_templates,
+            namespaces,
+            sanitize_names,
+            resolve_typedefs,
+            enum_hex,
             highlight_syntax,
+            theme,
+            theme_file_path,
+            // Applied along with the configuration
+            disabled_flags: _,
+        } => app.dump_all_types_command(
+            pdb_path,
+            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
+            print_header,
+            print_dependencies,
+            DataFormatConfiguration {
+                print_access_specifiers,
+                print_methods,
+                print_size_asserts,
+                print_padding_fields,
+                print_offset_comments: print_offset_comments
+                    .then(|| offset_radix.unwrap_or(OffsetRadix::Hexadecimal)),
+                pretty_templates,
+                infer_packing,
+                print_enum_values_in_hex: enum_hex,
+                print_namespaces: namespaces,
+                sanitize_names,
+                resolve_typedefs,
+            },
+            highlight_theme(highlight_syntax && use_colors, theme, theme_file_path),
             output_file_path,
         ),
         ResymOptions::Diff {
@@ -68,18 +498,48 @@ fn main() -> Result<()> {
             print_header,
             print_dependencies,
             print_access_specifiers,
+            print_methods,
+            pretty_templates,
+            enum_hex,
             highlight_syntax,
-        } => app.diff_type_command(
-            from_pdb_path,
-            to_pdb_path,
-            type_name,
-            primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
-            print_header,
-            print_dependencies,
-            print_access_specifiers,
-            highlight_syntax,
+            theme,
+            theme_file_path,
+            semantic,
+            types,
+            output_format,
+        } => match type_name {
+            // A type name is required unless `--types` is given
+            Some(type_name) if !types => app.diff_type_command(
+                from_pdb_path,
+                to_pdb_path,
+                type_name,
+                primitive_types_flavor.unwrap_or(PrimitiveReconstructionFlavor::Portable),
+                print_header,
+                print_dependencies,
+                DataFormatConfiguration {
+                    print_access_specifiers,
+                    print_methods,
+                    pretty_templates,
+                    print_enum_values_in_hex: enum_hex,
+                    ..Default::default()
+                },
+                highlight_theme(highlight_syntax && use_colors, theme, theme_file_path),
+                semantic,
+                output_file_path,
+            ),
+            _ => app.diff_type_lists_command(
+                from_pdb_path,
+                to_pdb_path,
+                output_format.unwrap_or(ReportFormat::Text),
+            ),
+        },
+        ResymOptions::Fetch {
+            pe_path_or_pdb_id,
+            pdb_name,
+            symbol_path,
             output_file_path,
-        ),
+        } => app.fetch_pdb_command(pe_path_or_pdb_id, pdb_name, symbol_path, output_file_path),
+        ResymOptions::Repl { pdb_path } => repl::run_repl(app, pdb_path, config, use_colors),
     }
 }
 
@@ -88,6 +548,29 @@ fn main() -> Result<()> {
     name = PKG_NAME,
     about = "resymc is a utility that allows browsing and extracting types from PDB files."
 )]
+struct ResymcArgs {
+    /// Do not display progress bars
+    #[structopt(long = "no-progress", global = true)]
+    no_progress: bool,
+    /// Highlight output even if it isn't written to a terminal, or if the
+    /// NO_COLOR environment variable is set
+    #[structopt(long = "force-color", global = true)]
+    force_color: bool,
+    /// Log what is being done and how long it takes (can be repeated, -vv
+    /// also logs the details of PDB parsing)
+    #[structopt(short = "v", long = "verbose", parse(from_occurrences), global = true)]
+    verbosity: u8,
+    /// Path of a configuration file setting default values for the options
+    /// of the dump, dump-all and list commands. By default, resym.toml is
+    /// looked up in the working directory, then in $XDG_CONFIG_HOME/resym.
+    /// Options given on the command line take precedence
+    #[structopt(long = "config", global = true)]
+    config_file_path: Option<PathBuf>,
+    #[structopt(subcommand)]
+    command: ResymOptions,
+}
+
+#[derive(Debug, StructOpt)]
 enum ResymOptions {
     /// List types from a given PDB file
     List {
@@ -101,32 +584,440 @@ enum ResymOptions {
         #[structopt(short = "i", long)]
         case_insensitive: bool,
         /// Use regular expressions
-        #[structopt(short = "r", long)]
+        #[structopt(short = "r", long, conflicts_with = "use-glob")]
+        use_regex: bool,
+        /// Use shell-style glob patterns, matched against the full type name
+        #[structopt(short = "g", long = "glob")]
+        use_glob: bool,
+        /// Match type names containing the filter's characters in order (not
+        /// necessarily consecutively), from the best to the worst match
+        #[structopt(long = "fuzzy", conflicts_with_all = &["use-regex", "use-glob"])]
+        use_fuzzy_matching: bool,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+        /// Output C++ forward declarations of the matching types instead of
+        /// their names
+        #[structopt(long = "forward-only", conflicts_with = "output-format")]
+        forward_only: bool,
+        /// Representation of primitive types (used with --forward-only)
+        #[structopt(short = "f", long, requires = "forward-only")]
+        primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+        /// Separate type names with NUL characters instead of newlines
+        #[structopt(
+            long = "null-separated",
+            conflicts_with_all = &["output-format", "forward-only"]
+        )]
+        null_separated: bool,
+        /// Only list types whose size (in bytes, decimal or hexadecimal) is
+        /// at least the given one, sizes are then output along with names
+        #[structopt(long = "min-size", parse(try_from_str = parse_u64))]
+        min_size: Option<u64>,
+        /// Only list types whose size (in bytes, decimal or hexadecimal) is
+        /// at most the given one, sizes are then output along with names
+        #[structopt(long = "max-size", parse(try_from_str = parse_u64))]
+        max_size: Option<u64>,
+        /// Only list types of the given kind (struct, class, union, enum or
+        /// typedef, can be repeated). Typedefs are listed after the other
+        /// types
+        #[structopt(long = "kind", number_of_values = 1)]
+        kinds: Vec<TypeKind>,
+        /// Leave out types whose name matches the given filter, matched like
+        /// the search filter (can be repeated)
+        #[structopt(long = "exclude", number_of_values = 1)]
+        exclusion_filters: Vec<String>,
+        /// Run again every time the PDB file changes, until interrupted
+        #[structopt(long)]
+        watch: bool,
+        #[structopt(flatten)]
+        disabled_flags: DisabledListFlags,
+    },
+    /// List public symbols from a given PDB file, with their demangled name
+    Symbols {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Search filter, matched against demangled names
+        symbol_name_filter: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Do not match case
+        #[structopt(short = "i", long)]
+        case_insensitive: bool,
+        /// Use regular expressions
+        #[structopt(short = "r", long, conflicts_with = "use-glob")]
+        use_regex: bool,
+        /// Use shell-style glob patterns, matched against the full symbol name
+        #[structopt(short = "g", long = "glob")]
+        use_glob: bool,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+        /// Also output the mangled name of the symbols
+        #[structopt(long = "mangled")]
+        print_mangled_names: bool,
+    },
+    /// List global and static variables whose name matches a given filter
+    Globals {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Search filter, matched against demangled names
+        global_name_filter: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Do not match case
+        #[structopt(short = "i", long)]
+        case_insensitive: bool,
+        /// Use regular expressions
+        #[structopt(short = "r", long, conflicts_with = "use-glob")]
+        use_regex: bool,
+        /// Use shell-style glob patterns, matched against the full variable
+        /// name
+        #[structopt(short = "g", long = "glob")]
+        use_glob: bool,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+        /// Representation of primitive types
+        #[structopt(short = "f", long)]
+        primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+    },
+    /// Find the public symbol an address belongs to in a given PDB file
+    Resolve {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Relative virtual address to resolve, in decimal or hexadecimal
+        #[structopt(long, parse(try_from_str = parse_u32))]
+        rva: u32,
+    },
+    /// List modules (i.e., compilands) from a given PDB file
+    Modules {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+        /// Also output the number of symbols of each module
+        #[structopt(long = "counts")]
+        print_symbol_counts: bool,
+    },
+    /// Print a summary of the content of a given PDB file
+    Stats {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+    },
+    /// Check that a given PDB file is well-formed, by parsing its type and
+    /// debug information entirely (exits with an error if it isn't, and
+    /// doesn't output anything otherwise)
+    Check {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+    },
+    /// Check whether a given PDB file is the one referenced by a given PE
+    /// file (exits with an error if it isn't)
+    Verify {
+        /// Path to the PE file
+        pe_path: PathBuf,
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+    },
+    /// Extract GUID constants and COM interfaces (i.e., interfaces deriving
+    /// from IUnknown) from a given PDB file
+    Com {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Path to the PE file, to read the values of GUID constants from
+        #[structopt(long = "pe")]
+        pe_path: Option<PathBuf>,
+        /// Representation of primitive types (Microsoft's by default)
+        #[structopt(short = "f", long)]
+        primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+    },
+    /// Print the source files and lines a given type or function is defined
+    /// at
+    Srcinfo {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Name of the type or function
+        symbol_name: String,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+    },
+    /// Find types declaring a member whose name matches a given filter
+    FindField {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Search filter, matched against member names
+        field_name_filter: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Do not match case
+        #[structopt(short = "i", long)]
+        case_insensitive: bool,
+        /// Use regular expressions
+        #[structopt(short = "r", long, conflicts_with = "use-glob")]
+        use_regex: bool,
+        /// Use shell-style glob patterns, matched against the full member name
+        #[structopt(short = "g", long = "glob")]
+        use_glob: bool,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+        /// Also output the type of the members
+        #[structopt(short = "t", long = "field-types")]
+        print_field_types: bool,
+        /// Representation of primitive types
+        #[structopt(short = "f", long, requires = "print-field-types")]
+        primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+    },
+    /// List the members whose name matches a given filter, along with their
+    /// type and the type declaring them (like find-field --field-types)
+    GrepMembers {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Search filter, matched against member names
+        field_name_filter: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Do not match case
+        #[structopt(short = "i", long)]
+        case_insensitive: bool,
+        /// Use regular expressions
+        #[structopt(short = "r", long, conflicts_with = "use-glob")]
         use_regex: bool,
+        /// Use shell-style glob patterns, matched against the full member name
+        #[structopt(short = "g", long = "glob")]
+        use_glob: bool,
+        /// Output format (text or json)
+        #[structopt(long = "format")]
+        output_format: Option<ReportFormat>,
+        /// Representation of primitive types
+        #[structopt(short = "f", long)]
+        primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+    },
+    /// List types that refer to a given type through their members
+    Xref {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Name of the referenced type
+        type_name: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Only list types that embed the given type (i.e., whose layout
+        /// depends on it)
+        #[structopt(long = "embedded-only")]
+        embedded_only: bool,
+        /// Only list types that point to the given type
+        #[structopt(long = "pointers-only", conflicts_with = "embedded-only")]
+        pointers_only: bool,
+    },
+    /// Output the graph of the types a given type refers to through its
+    /// members and base classes, directly or not (embedded types are linked
+    /// with solid edges, pointed-to types with dashed edges)
+    Graph {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Name of the root type
+        type_name: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Output format (dot)
+        #[structopt(long = "format")]
+        output_format: Option<GraphFormat>,
+        /// Number of levels of references to follow from the root type (3 by
+        /// default, 16 at most)
+        #[structopt(long)]
+        depth: Option<usize>,
     },
     /// Dump type from a given PDB file
     Dump {
         /// Path to the PDB file
         pdb_path: PathBuf,
         /// Name of the type to extract
-        type_name: String,
+        type_name: Option<String>,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
+        /// Name of an additional type to extract (can be repeated)
+        #[structopt(short = "t", long = "type")]
+        additional_type_names: Vec<String>,
+        /// Path of a file containing names of types to extract, one per line
+        /// ("-" to read from stdin)
+        #[structopt(long = "names-file")]
+        names_file_path: Option<PathBuf>,
+        /// Index of a type to extract, in decimal or hexadecimal (can be repeated)
+        #[structopt(long = "index", parse(try_from_str = parse_type_index))]
+        type_indices: Vec<pdb::TypeIndex>,
+        /// Extract every type sharing the name of a requested type, instead
+        /// of failing when several distinct types have that name
+        #[structopt(long = "all-matches")]
+        all_matches: bool,
         /// Representation of primitive types
         #[structopt(short = "f", long)]
         primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
-        /// Print header
+        /// Output format (cpp, c, csharp, rust, go, ctypes, windbg, imhex, 010
+        /// (010 Editor binary template), kaitai, json or html, which is
+        /// highlighted C++)
+        #[structopt(long = "format")]
+        output_format: Option<DumpFormat>,
+        /// Print header (not available for JSON output)
         #[structopt(short = "h", long)]
         print_header: bool,
         /// Print declarations of referenced types
         #[structopt(short = "d", long)]
         print_dependencies: bool,
+        /// Only output C++ forward declarations of the types
+        #[structopt(
+            long = "forward-only",
+            conflicts_with_all = &["output-format", "print-dependencies"]
+        )]
+        forward_only: bool,
+        /// Print C++ access specifiers
+        #[structopt(short = "a", long)]
+        print_access_specifiers: bool,
+        /// Print member function declarations
+        #[structopt(short = "m", long = "methods")]
+        print_methods: bool,
+        /// Emit a static_assert checking the size of each struct/class/union
+        #[structopt(long = "emit-size-asserts")]
+        print_size_asserts: bool,
+        /// Insert explicit padding fields where struct members leave gaps
+        #[structopt(long = "emit-padding")]
+        print_padding_fields: bool,
+        /// Append a comment containing its offset to each member (only
+        /// bitfields' bit positions in hexadecimal, as lines start with it)
+        #[structopt(long = "offsets")]
+        print_offset_comments: bool,
+        /// Radix used in offset comments (hex or dec)
+        #[structopt(long = "offsets-radix", requires = "print-offset-comments")]
+        offset_radix: Option<OffsetRadix>,
+        /// Wrap types compiled with non-default packing in #pragma pack
+        /// directives (or emit explicit padding if it cannot be inferred)
+        #[structopt(long = "infer-packing")]
+        infer_packing: bool,
+        /// Collapse common STL template instantiations to their friendly
+        /// aliases (e.g., std::string)
+        #[structopt(long = "pretty-templates")]
+        pretty_templates: bool,
+        /// Declare types inside of blocks for their namespaces, under their
+        /// unqualified name, in C++ output
+        #[structopt(long)]
+        namespaces: bool,
+        /// Rewrite type names that aren't valid C identifiers (e.g., templates
+        /// or namespaced names) into identifiers in C++ output
+        #[structopt(long = "sanitize-names")]
+        sanitize_names: bool,
+        /// Type members with what their typedefs ultimately resolve to,
+        /// instead of with the typedefs' names
+        #[structopt(long = "resolve-typedefs")]
+        resolve_typedefs: bool,
+        /// Write enumerants' values in hexadecimal
+        #[structopt(long = "enum-hex")]
+        enum_hex: bool,
+        /// Highlight C++ output
+        #[structopt(short = "H", long)]
+        highlight_syntax: bool,
+        /// Highlighting theme (dark or light, used with -H or --format html),
+        /// guessed from the COLORFGBG environment variable by default
+        #[structopt(long)]
+        theme: Option<HighlightTheme>,
+        /// Path of a .tmTheme file to highlight output with, instead of the
+        /// dark or light theme
+        #[structopt(long = "theme-file")]
+        theme_file_path: Option<PathBuf>,
+        /// Wrap output in an include guard, whose macro is named after the
+        /// output file (or given by --guard-name)
+        #[structopt(long = "header-guard", conflicts_with = "pragma-once")]
+        header_guard: bool,
+        /// Start output with a #pragma once directive
+        #[structopt(long = "pragma-once")]
+        pragma_once: bool,
+        /// Name of the include guard macro (required with --header-guard when
+        /// writing to stdout)
+        #[structopt(long = "guard-name", requires = "header-guard")]
+        guard_name: Option<String>,
+        /// Run again every time the PDB file changes, until interrupted
+        #[structopt(long)]
+        watch: bool,
+        #[structopt(flatten)]
+        disabled_flags: DisabledDumpFlags,
+    },
+    /// Dump all types from a given PDB file
+    DumpAll {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Representation of primitive types
+        #[structopt(short = "f", long)]
+        primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
+        /// Print header
+        #[structopt(short = "h", long)]
+        print_header: bool,
+        /// Order types so that dependencies are declared before dependents
+        #[structopt(short = "d", long)]
+        print_dependencies: bool,
         /// Print C++ access specifiers
         #[structopt(short = "a", long)]
         print_access_specifiers: bool,
+        /// Print member function declarations
+        #[structopt(short = "m", long = "methods")]
+        print_methods: bool,
+        /// Emit a static_assert checking the size of each struct/class/union
+        #[structopt(long = "emit-size-asserts")]
+        print_size_asserts: bool,
+        /// Insert explicit padding fields where struct members leave gaps
+        #[structopt(long = "emit-padding")]
+        print_padding_fields: bool,
+        /// Append a comment containing its offset to each member (only
+        /// bitfields' bit positions in hexadecimal, as lines start with it)
+        #[structopt(long = "offsets")]
+        print_offset_comments: bool,
+        /// Radix used in offset comments (hex or dec)
+        #[structopt(long = "offsets-radix", requires = "print-offset-comments")]
+        offset_radix: Option<OffsetRadix>,
+        /// Wrap types compiled with non-default packing in #pragma pack
+        /// directives (or emit explicit padding if it cannot be inferred)
+        #[structopt(long = "infer-packing")]
+        infer_packing: bool,
+        /// Collapse common STL template instantiations to their friendly
+        /// aliases (e.g., std::string)
+        #[structopt(long = "pretty-templates")]
+        pretty_templates: bool,
+        /// Declare types inside of blocks for their namespaces, under their
+        /// unqualified name, in C++ output
+        #[structopt(long)]
+        namespaces: bool,
+        /// Rewrite type names that aren't valid C identifiers (e.g., templates
+        /// or namespaced names) into identifiers in C++ output
+        #[structopt(long = "sanitize-names")]
+        sanitize_names: bool,
+        /// Type members with what their typedefs ultimately resolve to,
+        /// instead of with the typedefs' names
+        #[structopt(long = "resolve-typedefs")]
+        resolve_typedefs: bool,
+        /// Write enumerants' values in hexadecimal
+        #[structopt(long = "enum-hex")]
+        enum_hex: bool,
         /// Highlight C++ output
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
+        /// Highlighting theme (dark or light), guessed from the COLORFGBG
+        /// environment variable by default
+        #[structopt(long, requires = "highlight-syntax")]
+        theme: Option<HighlightTheme>,
+        /// Path of a .tmTheme file to highlight output with, instead of the
+        /// dark or light theme
+        #[structopt(long = "theme-file", requires = "highlight-syntax")]
+        theme_file_path: Option<PathBuf>,
+        #[structopt(flatten)]
+        disabled_flags: DisabledDumpFlags,
     },
     /// Compute diff for a type between two given PDB files
     Diff {
@@ -135,7 +1026,8 @@ enum ResymOptions {
         /// Path of the PDB file to compute the diff to
         to_pdb_path: PathBuf,
         /// Name of the type to diff
-        type_name: String,
+        #[structopt(required_unless = "types")]
+        type_name: Option<String>,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
         /// Representation of primitive types
@@ -150,10 +1042,162 @@ enum ResymOptions {
         /// Print C++ access specifiers
         #[structopt(short = "a", long)]
         print_access_specifiers: bool,
+        /// Print member function declarations
+        #[structopt(short = "m", long = "methods")]
+        print_methods: bool,
+        /// Collapse common STL template instantiations to their friendly
+        /// aliases (e.g., std::string)
+        #[structopt(long = "pretty-templates")]
+        pretty_templates: bool,
+        /// Write enumerants' values in hexadecimal
+        #[structopt(long = "enum-hex")]
+        enum_hex: bool,
         /// Highlight C++ output and add/deleted lines
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
+        /// Highlighting theme (dark or light), guessed from the COLORFGBG
+        /// environment variable by default
+        #[structopt(long, requires = "highlight-syntax")]
+        theme: Option<HighlightTheme>,
+        /// Path of a .tmTheme file to highlight output with, instead of the
+        /// dark or light theme
+        #[structopt(long = "theme-file", requires = "highlight-syntax")]
+        theme_file_path: Option<PathBuf>,
+        /// Compare member offsets and types instead of C++ representations
+        #[structopt(long)]
+        semantic: bool,
+        /// List the types added, removed or resized between both PDB files
+        /// instead of diffing a single type (output is written to stdout)
+        #[structopt(long, conflicts_with_all = &["type-name", "semantic"])]
+        types: bool,
+        /// Output format of the type list diff (text or json)
+        #[structopt(long = "format", requires = "types")]
+        output_format: Option<ReportFormat>,
     },
+    /// Download the PDB file of a PE file from a symbol server, and print
+    /// its path
+    Fetch {
+        /// Path to the PE file, or identifier of the PDB file (i.e., its GUID
+        /// followed by its age)
+        pe_path_or_pdb_id: String,
+        /// Name of the PDB file, required when its identifier is given
+        #[structopt(long = "pdb-name")]
+        pdb_name: Option<String>,
+        /// Symbol path (e.g., srv*C:\symbols*https://msdl.microsoft.com/download/symbols),
+        /// defaults to the _NT_SYMBOL_PATH environment variable or to
+        /// Microsoft's public symbol server
+        #[structopt(long = "symbol-path")]
+        symbol_path: Option<String>,
+        /// Path of the output file, the PDB file is written to the current
+        /// directory if it isn't stored in a local symbol store
+        output_file_path: Option<PathBuf>,
+    },
+    /// Load a PDB file once and run list, dump and xref commands against it
+    /// interactively
+    Repl {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+    },
+}
+
+/// Check whether ANSI escape codes may be written to stdout, that is if it's a
+/// terminal and colors haven't been disabled with the `NO_COLOR` environment
+/// variable (see https://no-color.org), or if colors are forced.
+fn is_color_enabled(force_color: bool) -> bool {
+    force_color
+        || (io::stdout().is_terminal()
+            && env::var_os("NO_COLOR").is_none_or(|no_color| no_color.is_empty()))
+}
+
+/// Return the theme to highlight the output with, or `None` if it shouldn't
+/// be highlighted. Themes loaded from a file take precedence, the dark or light
+/// theme is used if loading fails.
+fn highlight_theme(
+    highlight_syntax: bool,
+    theme: Option<HighlightTheme>,
+    theme_file_path: Option<PathBuf>,
+) -> Option<SyntaxTheme> {
+    if !highlight_syntax {
+        return None;
+    }
+
+    if let Some(theme_file_path) = theme_file_path {
+        match SyntaxTheme::load_from_file(&theme_file_path) {
+            Ok(theme) => return Some(theme),
+            Err(err) => eprintln!(
+                "Warning: failed to load theme '{}', using the default theme instead: {}",
+                theme_file_path.display(),
+                err
+            ),
+        }
+    }
+
+    Some(SyntaxTheme::Builtin(
+        theme.unwrap_or_else(HighlightTheme::detect).code_theme(),
+    ))
+}
+
+/// Type requested by the user, identified either by its name or by its index.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum RequestedType {
+    Name(String),
+    Index(pdb::TypeIndex),
+}
+
+impl fmt::Display for RequestedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestedType::Name(type_name) => write!(f, "{}", type_name),
+            RequestedType::Index(type_index) => write!(f, "{}", type_index),
+        }
+    }
+}
+
+/// Entry of a type list, as output in JSON.
+#[derive(Serialize)]
+struct TypeListEntry {
+    name: String,
+    type_index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+}
+
+/// Entry of a symbol list, as output in JSON.
+#[derive(Serialize)]
+struct SymbolListEntry {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mangled_name: Option<String>,
+    rva: Option<u32>,
+}
+
+/// Entry of a global variable list, as output in JSON.
+#[derive(Serialize)]
+struct GlobalListEntry {
+    name: String,
+    rva: Option<u32>,
+    #[serde(rename = "type")]
+    type_name: String,
+    #[serde(rename = "static")]
+    is_static: bool,
+}
+
+/// Entry of a module list, as output in JSON.
+#[derive(Serialize)]
+struct ModuleListEntry {
+    name: String,
+    object_file_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol_count: Option<usize>,
+}
+
+/// Entry of a member list, as output in JSON.
+#[derive(Serialize)]
+struct FieldListEntry {
+    type_name: String,
+    name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    field_type_name: Option<String>,
 }
 
 /// Struct that represents our CLI application.
@@ -161,63 +1205,747 @@ enum ResymOptions {
 struct ResymcApp {
     frontend_controller: Arc<CLIFrontendController>,
     backend: Backend,
+    /// PDB kept loaded in the main slot (e.g., by the REPL), commands
+    /// targeting it don't load it again
+    persistent_pdb_path: RefCell<Option<PathBuf>>,
 }
 
 impl ResymcApp {
-    fn new() -> Result<Self> {
+    fn new(show_progress: bool) -> Result<Self> {
         // Initialize backend
         let (tx_ui, rx_ui) = crossbeam_channel::unbounded::<FrontendCommand>();
-        let frontend_controller = Arc::new(CLIFrontendController::new(tx_ui, rx_ui));
+        let frontend_controller = Arc::new(CLIFrontendController::new(tx_ui, rx_ui, show_progress));
         let backend = Backend::new(frontend_controller.clone())?;
 
         Ok(Self {
             frontend_controller,
             backend,
+            persistent_pdb_path: RefCell::new(None),
         })
     }
 
-    fn list_types_command(
-        &self,
+    /// Load the given PDB into the main slot, unless it's the persistent PDB.
+    fn load_main_pdb(&self, pdb_path: PathBuf) -> Result<()> {
+        if self.persistent_pdb_path.borrow().as_ref() == Some(&pdb_path) {
+            return Ok(());
+        }
+
+        self.load_pdb(BackendCommand::LoadPDB(PDB_MAIN_SLOT, pdb_path))
+    }
+
+    /// Load the given PDB into the main slot and keep it loaded, commands
+    /// targeting it won't load it again.
+    fn load_persistent_pdb(&self, pdb_path: PathBuf) -> Result<()> {
+        self.persistent_pdb_path.replace(None);
+        // Note: Persistent PDBs are read rather than mapped, as they may be
+        // rebuilt while they're loaded
+        self.load_pdb(BackendCommand::LoadPDBBuffered(
+            PDB_MAIN_SLOT,
+            pdb_path.clone(),
+        ))?;
+        self.persistent_pdb_path.replace(Some(pdb_path));
+
+        Ok(())
+    }
+
+    /// Run the given command, and if `watch` is set, run it again every time
+    /// the PDB at `pdb_path` changes (see `watch::run_command`).
+    ///
+    /// Watched PDBs are loaded as persistent PDBs, since they're read rather
+    /// than mapped and are rewritten while they're loaded. They're reloaded
+    /// with `BackendCommand::ReloadPDB` before the command runs again, the
+    /// previously loaded PDB is kept if reloading fails.
+    fn run_watched_command(
+        &self,
+        pdb_path: &Path,
+        watch: bool,
+        mut command: impl FnMut() -> Result<()>,
+    ) -> Result<()> {
+        if !watch {
+            return command();
+        }
+
+        let mut pdb_loaded = false;
+        watch::run_command(pdb_path, || {
+            if pdb_loaded {
+                self.load_pdb(BackendCommand::ReloadPDB(PDB_MAIN_SLOT))?;
+            } else {
+                self.load_persistent_pdb(pdb_path.to_path_buf())?;
+                pdb_loaded = true;
+            }
+            command()
+        })
+    }
+
+    /// Send a command loading a PDB to the backend, and wait for the PDB to
+    /// be loaded.
+    fn load_pdb(&self, load_command: BackendCommand) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend.send_command(load_command)?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(_, result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn list_types_command(
+        &self,
         pdb_path: PathBuf,
         type_name_filter: String,
         case_insensitive: bool,
-        use_regex: bool,
+        match_mode: FilterMatchMode,
+        output_format: ReportFormat,
+        forward_only: bool,
+        primitive_types_flavor: PrimitiveReconstructionFlavor,
+        null_separated: bool,
+        type_filter: TypeFilter,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        let type_list = if type_filter == TypeFilter::default() {
+            // Queue a request for the backend to return the list of types that
+            // match the given filter
+            self.backend.send_command(BackendCommand::UpdateTypeFilter(
+                PDB_MAIN_SLOT,
+                type_name_filter,
+                case_insensitive,
+                match_mode,
+            ))?;
+            // Wait for the backend to finish filtering types
+            if let FrontendCommand::UpdateFilteredTypes(_, type_list_result) =
+                self.frontend_controller.rx_ui.recv()?
+            {
+                type_list_result?
+                    .into_iter()
+                    .map(|(type_name, type_index)| (type_name, type_index, None))
+                    .collect::<Vec<_>>()
+            } else {
+                return Err(anyhow!("Invalid response received from the backend?"));
+            }
+        } else {
+            // Sizes are output along with type names when filtering types by
+            // size, to show why types matched
+            let print_sizes = type_filter.min_size.is_some() || type_filter.max_size.is_some();
+            self.backend.send_command(BackendCommand::FilterTypes(
+                PDB_MAIN_SLOT,
+                type_name_filter,
+                case_insensitive,
+                match_mode,
+                type_filter,
+            ))?;
+            if let FrontendCommand::FilterTypesResult(_, type_list_result) =
+                self.frontend_controller.rx_ui.recv()?
+            {
+                type_list_result?
+                    .into_iter()
+                    .map(|(type_name, type_index, type_size)| {
+                        (type_name, type_index, print_sizes.then_some(type_size))
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                return Err(anyhow!("Invalid response received from the backend?"));
+            }
+        };
+
+        if output_format == ReportFormat::Json {
+            let type_list = type_list
+                .into_iter()
+                .map(|(name, type_index, size)| TypeListEntry {
+                    name,
+                    type_index: type_index.0,
+                    size,
+                })
+                .collect::<Vec<_>>();
+            let serialized_type_list = serde_json::to_string_pretty(&type_list)?;
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(serialized_type_list.as_bytes())?;
+            } else {
+                println!("{}", serialized_type_list);
+            }
+            return Ok(());
+        }
+
+        if forward_only {
+            return self.list_forward_declarations(
+                type_list
+                    .into_iter()
+                    .map(|(_, type_index, _)| type_index)
+                    .collect(),
+                primitive_types_flavor,
+                output_file_path,
+            );
+        }
+
+        // Dump output
+        let mut output_writer: Box<dyn Write> = if let Some(output_file_path) = output_file_path {
+            Box::new(File::create(output_file_path)?)
+        } else {
+            Box::new(io::stdout())
+        };
+        let separator = if null_separated { "\0" } else { "\n" };
+        for (type_name, _, type_size) in type_list {
+            match type_size {
+                Some(type_size) => write!(output_writer, "{} ({:#x} bytes)", type_name, type_size)?,
+                None => output_writer.write_all(type_name.as_bytes())?,
+            }
+            output_writer.write_all(separator.as_bytes())?;
+        }
+        output_writer.flush()?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn list_symbols_command(
+        &self,
+        pdb_path: PathBuf,
+        symbol_name_filter: String,
+        case_insensitive: bool,
+        match_mode: FilterMatchMode,
+        output_format: ReportFormat,
+        print_mangled_names: bool,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to return the list of symbols that
+        // match the given filter
+        self.backend.send_command(BackendCommand::ListSymbols(
+            PDB_MAIN_SLOT,
+            symbol_name_filter,
+            case_insensitive,
+            match_mode,
+        ))?;
+        // Wait for the backend to finish filtering symbols
+        if let FrontendCommand::ListSymbolsResult(_, symbol_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let symbol_list = symbol_list_result?;
+            let output = if output_format == ReportFormat::Json {
+                let symbol_list = symbol_list
+                    .into_iter()
+                    .map(|(name, symbol)| SymbolListEntry {
+                        name,
+                        mangled_name: print_mangled_names.then_some(symbol.mangled_name),
+                        rva: symbol.rva,
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}\n", serde_json::to_string_pretty(&symbol_list)?)
+            } else {
+                let mut output = String::default();
+                for (name, symbol) in symbol_list {
+                    let rva = symbol
+                        .rva
+                        .map_or_else(|| "?".to_string(), |rva| format!("{:#010x}", rva));
+                    if print_mangled_names {
+                        output.push_str(&format!("{} {} {}\n", rva, name, symbol.mangled_name));
+                    } else {
+                        output.push_str(&format!("{} {}\n", rva, name));
+                    }
+                }
+                output
+            };
+
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(output.as_bytes())?;
+            } else {
+                print!("{}", output);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn list_globals_command(
+        &self,
+        pdb_path: PathBuf,
+        global_name_filter: String,
+        case_insensitive: bool,
+        match_mode: FilterMatchMode,
+        output_format: ReportFormat,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to return the list of global
+        // variables that match the given filter
+        self.backend.send_command(BackendCommand::ListGlobals(
+            PDB_MAIN_SLOT,
+            global_name_filter,
+            case_insensitive,
+            match_mode,
+            primitives_flavor,
+        ))?;
+        // Wait for the backend to finish filtering variables
+        if let FrontendCommand::ListGlobalsResult(_, global_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let global_list = global_list_result?;
+            let output = if output_format == ReportFormat::Json {
+                let global_list = global_list
+                    .into_iter()
+                    .map(|(name, global)| GlobalListEntry {
+                        name,
+                        rva: global.rva,
+                        type_name: global.type_name,
+                        is_static: global.is_static,
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}\n", serde_json::to_string_pretty(&global_list)?)
+            } else {
+                let mut output = String::default();
+                for (name, global) in global_list {
+                    let rva = global
+                        .rva
+                        .map_or_else(|| "?".to_string(), |rva| format!("{:#010x}", rva));
+                    output.push_str(&format!("{} {} {}\n", rva, global.type_name, name));
+                }
+                output
+            };
+
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(output.as_bytes())?;
+            } else {
+                print!("{}", output);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn resolve_address_command(&self, pdb_path: PathBuf, rva: u32) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to resolve the address
+        self.backend
+            .send_command(BackendCommand::ResolveAddress(PDB_MAIN_SLOT, rva))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ResolveAddressResult(_, resolved_address_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            match resolved_address_result? {
+                None => Err(anyhow!("No symbol found at RVA {:#x}", rva)),
+                Some(resolved_address) if resolved_address.offset == 0 => {
+                    println!("{}", resolved_address.symbol_name);
+                    Ok(())
+                }
+                Some(resolved_address) => {
+                    println!(
+                        "{}+{:#x}",
+                        resolved_address.symbol_name, resolved_address.offset
+                    );
+                    Ok(())
+                }
+            }
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn list_modules_command(
+        &self,
+        pdb_path: PathBuf,
+        output_format: ReportFormat,
+        print_symbol_counts: bool,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to return the list of modules
+        self.backend.send_command(BackendCommand::ListModules(
+            PDB_MAIN_SLOT,
+            print_symbol_counts,
+        ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ListModulesResult(_, module_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let module_list = module_list_result?;
+            let output = if output_format == ReportFormat::Json {
+                let module_list = module_list
+                    .into_iter()
+                    .map(|module| ModuleListEntry {
+                        name: module.name,
+                        object_file_name: module.object_file_name,
+                        symbol_count: module.symbol_count,
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}\n", serde_json::to_string_pretty(&module_list)?)
+            } else {
+                let mut output = String::default();
+                for module in module_list {
+                    // Fields are separated with tabs, as paths may contain
+                    // spaces
+                    if let Some(symbol_count) = module.symbol_count {
+                        output.push_str(&format!(
+                            "{}\t{}\t{}\n",
+                            symbol_count, module.name, module.object_file_name
+                        ));
+                    } else {
+                        output.push_str(&format!("{}\t{}\n", module.name, module.object_file_name));
+                    }
+                }
+                output
+            };
+
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(output.as_bytes())?;
+            } else {
+                print!("{}", output);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn statistics_command(&self, pdb_path: PathBuf, output_format: ReportFormat) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to compute statistics
+        self.backend
+            .send_command(BackendCommand::ComputeStatistics(PDB_MAIN_SLOT))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ComputeStatisticsResult(_, statistics_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let statistics = statistics_result?;
+            if output_format == ReportFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&statistics)?);
+                return Ok(());
+            }
+
+            println!("Structs:        {}", statistics.struct_count);
+            println!("Classes:        {}", statistics.class_count);
+            println!("Unions:         {}", statistics.union_count);
+            println!("Enums:          {}", statistics.enum_count);
+            println!("Typedefs:       {}", statistics.typedef_count);
+            println!("Public symbols: {}", statistics.public_symbol_count);
+            println!("Modules:        {}", statistics.module_count);
+            println!("Type records:   {}", statistics.type_record_count);
+            if let Some(pointer_size) = statistics.pointer_size {
+                println!("Pointer size:   {} bytes", pointer_size);
+            }
+            if let Some(largest_type) = statistics.largest_type {
+                println!(
+                    "Largest type:   {} ({:#x} bytes)",
+                    largest_type.name, largest_type.size
+                );
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn check_command(&self, pdb_path: PathBuf) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to parse the PDB's streams
+        self.backend
+            .send_command(BackendCommand::CheckPDB(PDB_MAIN_SLOT))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::CheckPDBResult(_, check_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            Ok(check_result?)
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn verify_pe_command(&self, pe_path: PathBuf, pdb_path: PathBuf) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to compare the PDB with the PE
+        self.backend
+            .send_command(BackendCommand::VerifyPE(PDB_MAIN_SLOT, pe_path))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::VerifyPEResult(_, verification_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let verification = verification_result?;
+            let pe_reference = &verification.pe_reference;
+            println!(
+                "PE:  {} {} age {}",
+                pe_reference.pdb_path, pe_reference.guid, pe_reference.age
+            );
+            println!(
+                "PDB: {} {} age {}",
+                verification.pdb_file_name, verification.pdb_guid, verification.pdb_age
+            );
+            if !verification.is_match() {
+                return Err(anyhow!("The PDB doesn't match the PE"));
+            }
+            if !verification.is_name_match() {
+                eprintln!(
+                    "Warning: the PE references the PDB as '{}'",
+                    pe_reference.pdb_file_name()
+                );
+            }
+            println!("The PDB matches the PE");
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn com_command(
+        &self,
+        pdb_path: PathBuf,
+        pe_path: Option<PathBuf>,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to reconstruct the COM definitions
+        self.backend
+            .send_command(BackendCommand::ReconstructComDefinitions(
+                PDB_MAIN_SLOT,
+                pe_path,
+                primitives_flavor,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ReconstructComDefinitionsResult(_, reconstructed_com_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let reconstructed_com = reconstructed_com_result?;
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(reconstructed_com.as_bytes())?;
+            } else {
+                print!("{}", reconstructed_com);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn source_info_command(
+        &self,
+        pdb_path: PathBuf,
+        symbol_name: String,
+        output_format: ReportFormat,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to locate the symbol's definitions
+        self.backend.send_command(BackendCommand::GetSourceInfo(
+            PDB_MAIN_SLOT,
+            symbol_name.clone(),
+        ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::GetSourceInfoResult(_, source_info_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let source_locations = source_info_result?;
+            if output_format == ReportFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&source_locations)?);
+                return Ok(());
+            }
+
+            // Symbols compiled without line information have no source
+            // information, this isn't an error
+            if source_locations.is_empty() {
+                println!("Source information unavailable for '{}'", symbol_name);
+            }
+            for source_location in source_locations {
+                println!("{}:{}", source_location.file_name, source_location.line);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn find_field_command(
+        &self,
+        pdb_path: PathBuf,
+        field_name_filter: String,
+        case_insensitive: bool,
+        match_mode: FilterMatchMode,
+        output_format: ReportFormat,
+        print_field_types: bool,
+        primitive_types_flavor: PrimitiveReconstructionFlavor,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to return the list of members that
+        // match the given filter
+        self.backend
+            .send_command(BackendCommand::FindTypesWithField(
+                PDB_MAIN_SLOT,
+                field_name_filter,
+                case_insensitive,
+                match_mode,
+                primitive_types_flavor,
+            ))?;
+        // Wait for the backend to finish filtering members
+        if let FrontendCommand::FindTypesWithFieldResult(_, field_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let field_list = field_list_result?;
+            let output = if output_format == ReportFormat::Json {
+                let field_list = field_list
+                    .into_iter()
+                    .map(|(name, field)| FieldListEntry {
+                        type_name: field.type_name,
+                        name,
+                        field_type_name: print_field_types.then_some(field.field_type_name),
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}\n", serde_json::to_string_pretty(&field_list)?)
+            } else {
+                let mut output = String::default();
+                for (name, field) in field_list {
+                    if print_field_types {
+                        output.push_str(&format!(
+                            "{}::{} : {}\n",
+                            field.type_name, name, field.field_type_name
+                        ));
+                    } else {
+                        output.push_str(&format!("{}::{}\n", field.type_name, name));
+                    }
+                }
+                output
+            };
+
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(output.as_bytes())?;
+            } else {
+                print!("{}", output);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn find_referencing_types_command(
+        &self,
+        pdb_path: PathBuf,
+        type_name: String,
+        reference_kind: Option<TypeReferenceKind>,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
-        // Request the backend to load the PDB
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to find the referencing types
         self.backend
-            .send_command(BackendCommand::LoadPDB(PDB_MAIN_SLOT, pdb_path))?;
-        // Wait for the backend to finish loading the PDB
-        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
-            if let Err(err) = result {
-                return Err(anyhow!("Failed to load PDB: {}", err));
+            .send_command(BackendCommand::FindReferencingTypes(
+                PDB_MAIN_SLOT,
+                type_name,
+                reference_kind,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::FindReferencingTypesResult(_, referencing_types_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let referencing_types = referencing_types_result?;
+            let mut output_writer: Box<dyn Write> = match output_file_path {
+                Some(output_file_path) => Box::new(File::create(output_file_path)?),
+                None => Box::new(io::stdout()),
+            };
+            for type_name in referencing_types {
+                writeln!(output_writer, "{}", type_name)?;
             }
+            Ok(())
         } else {
-            return Err(anyhow!("Invalid response received from the backend?"));
+            Err(anyhow!("Invalid response received from the backend?"))
         }
+    }
 
-        // Queue a request for the backend to return the list of types that
-        // match the given filter
-        self.backend.send_command(BackendCommand::UpdateTypeFilter(
+    fn type_graph_command(
+        &self,
+        pdb_path: PathBuf,
+        type_name: String,
+        output_format: GraphFormat,
+        depth: usize,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
+
+        // Queue a request for the backend to build the graph
+        self.backend.send_command(BackendCommand::BuildTypeGraph(
             PDB_MAIN_SLOT,
-            type_name_filter,
-            case_insensitive,
-            use_regex,
+            type_name,
+            depth,
         ))?;
-        // Wait for the backend to finish filtering types
-        if let FrontendCommand::UpdateFilteredTypes(type_list) =
+        // Wait for the backend to finish
+        if let FrontendCommand::BuildTypeGraphResult(_, type_graph_result) =
             self.frontend_controller.rx_ui.recv()?
         {
+            let type_graph = type_graph_result?;
+            let mut output = String::new();
+            match output_format {
+                GraphFormat::Dot => fmt_dot_graph(&type_graph, &mut output)?,
+            }
+
             // Dump output
             if let Some(output_file_path) = output_file_path {
                 let mut output_file = File::create(output_file_path)?;
-                for (type_name, _) in type_list {
-                    output_file.write_all(type_name.as_bytes())?;
-                }
+                output_file.write_all(output.as_bytes())?;
             } else {
-                for (type_name, _) in type_list {
-                    println!("{}", type_name);
-                }
+                print!("{}", output);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    /// Output forward declarations of the given types.
+    fn list_forward_declarations(
+        &self,
+        type_indices: Vec<pdb::TypeIndex>,
+        primitive_types_flavor: PrimitiveReconstructionFlavor,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Queue a request for the backend to reconstruct forward declarations
+        self.backend
+            .send_command(BackendCommand::ReconstructForwardDeclarations(
+                PDB_MAIN_SLOT,
+                type_indices,
+                primitive_types_flavor,
+                false,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ReconstructTypeResult(_, forward_declarations_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let forward_declarations = forward_declarations_result?;
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(forward_declarations.as_bytes())?;
+            } else {
+                print!("{}", forward_declarations);
             }
             Ok(())
         } else {
@@ -229,63 +1957,207 @@ impl ResymcApp {
     fn dump_types_command(
         &self,
         pdb_path: PathBuf,
-        type_name: String,
+        requested_types: Vec<RequestedType>,
         primitive_types_flavor: PrimitiveReconstructionFlavor,
+        dump_format: DumpFormat,
         print_header: bool,
         print_dependencies: bool,
-        print_access_specifiers: bool,
-        highlight_syntax: bool,
+        forward_only: bool,
+        all_matches: bool,
+        fmt_configuration: DataFormatConfiguration,
+        highlight_theme: Option<SyntaxTheme>,
+        header_guard: Option<HeaderGuard>,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
-        // Request the backend to load the PDB
-        self.backend
-            .send_command(BackendCommand::LoadPDB(PDB_MAIN_SLOT, pdb_path))?;
-        // Wait for the backend to finish loading the PDB
-        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
-            if let Err(err) = result {
-                return Err(anyhow!("Failed to load PDB: {}", err));
+        self.load_main_pdb(pdb_path)?;
+
+        let output_format = dump_format.output_format();
+        // Remove duplicates, while preserving the order in which types were requested
+        let mut unique_requested_types = HashSet::new();
+        let mut requested_types = requested_types
+            .into_iter()
+            .filter(|requested_type| unique_requested_types.insert(requested_type.clone()))
+            .collect::<VecDeque<_>>();
+
+        let mut reconstructed_types = Vec::with_capacity(requested_types.len());
+        let mut failed_type_count = 0;
+        while let Some(requested_type) = requested_types.pop_front() {
+            // Only print the header once
+            let print_header = print_header && reconstructed_types.is_empty();
+            // Queue a request for the backend to reconstruct the given type
+            self.backend.send_command(match &requested_type {
+                RequestedType::Name(type_name) if forward_only => {
+                    BackendCommand::ReconstructForwardDeclarationByName(
+                        PDB_MAIN_SLOT,
+                        type_name.clone(),
+                        primitive_types_flavor,
+                        print_header,
+                    )
+                }
+                RequestedType::Index(type_index) if forward_only => {
+                    BackendCommand::ReconstructForwardDeclarations(
+                        PDB_MAIN_SLOT,
+                        vec![*type_index],
+                        primitive_types_flavor,
+                        print_header,
+                    )
+                }
+                RequestedType::Name(type_name) => BackendCommand::ReconstructTypeByName(
+                    PDB_MAIN_SLOT,
+                    type_name.clone(),
+                    primitive_types_flavor,
+                    output_format,
+                    print_header,
+                    print_dependencies,
+                    fmt_configuration.clone(),
+                ),
+                RequestedType::Index(type_index) => BackendCommand::ReconstructTypeByIndex(
+                    PDB_MAIN_SLOT,
+                    *type_index,
+                    primitive_types_flavor,
+                    output_format,
+                    print_header,
+                    print_dependencies,
+                    fmt_configuration.clone(),
+                ),
+            })?;
+            // Wait for the backend to finish reconstructing the type
+            if let FrontendCommand::ReconstructTypeResult(_, reconstructed_type_result) =
+                self.frontend_controller.rx_ui.recv()?
+            {
+                match reconstructed_type_result {
+                    Err(err) => {
+                        if let ResymError::AmbiguousTypeName { type_indices, .. } = &err {
+                            if all_matches {
+                                // Dump the matching types in place of the requested name
+                                for type_index in type_indices.iter().rev() {
+                                    requested_types.push_front(RequestedType::Index(*type_index));
+                                }
+                                continue;
+                            }
+                            eprintln!(
+                                "Failed to dump type '{}': {}; specify --index (or --all-matches)",
+                                requested_type, err
+                            );
+                        } else if let ResymError::TypeNotFound { .. } = err {
+                            // Note: The error already mentions the type's name
+                            eprintln!("Failed to dump type: {}", err);
+                        } else {
+                            // Report the error and keep going with the other types
+                            eprintln!("Failed to dump type '{}': {}", requested_type, err);
+                        }
+                        failed_type_count += 1;
+                    }
+                    Ok(reconstructed_type) => {
+                        reconstructed_types.push((requested_type, reconstructed_type));
+                    }
+                }
+            } else {
+                return Err(anyhow!("Invalid response received from the backend?"));
             }
+        }
+        if reconstructed_types.is_empty() {
+            // Don't output anything if every type failed to be reconstructed
+            return Err(anyhow!("Failed to dump {} type(s)", failed_type_count));
+        }
+        let reconstructed_type = if forward_only {
+            // Forward declarations are meant to be grouped together
+            reconstructed_types
+                .into_iter()
+                .map(|(_, reconstructed_type)| reconstructed_type)
+                .collect()
         } else {
-            return Err(anyhow!("Invalid response received from the backend?"));
+            merge_reconstructed_types(output_format, reconstructed_types)?
+        };
+        // The header's preamble comes before any reconstructed content
+        let reconstructed_type = match header_guard {
+            Some(header_guard) => header_guard.wrap(&reconstructed_type),
+            None => reconstructed_type,
+        };
+
+        // Dump output
+        if dump_format == DumpFormat::Html {
+            const LANGUAGE_SYNTAX: &str = "cpp";
+            let theme = highlight_theme.unwrap_or_default();
+            let html_reconstructed_type =
+                highlight_code_html(&theme, &reconstructed_type, LANGUAGE_SYNTAX)
+                    .ok_or_else(|| anyhow!("Failed to convert the output to HTML"))?;
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                output_file.write_all(html_reconstructed_type.as_bytes())?;
+            } else {
+                print!("{}", html_reconstructed_type);
+            }
+        } else if let Some(output_file_path) = output_file_path {
+            let mut output_file = File::create(output_file_path)?;
+            output_file.write_all(reconstructed_type.as_bytes())?;
+        } else if let Some(theme) = &highlight_theme {
+            let language = output_format_language(output_format);
+            if let Some(colorized_reconstructed_type) =
+                highlight_code(theme, &reconstructed_type, language, None)
+            {
+                println!("{}", colorized_reconstructed_type);
+            }
+        } else {
+            println!("{}", reconstructed_type);
+        }
+
+        if failed_type_count > 0 {
+            Err(anyhow!("Failed to dump {} type(s)", failed_type_count))
+        } else {
+            Ok(())
         }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn dump_all_types_command(
+        &self,
+        pdb_path: PathBuf,
+        primitive_types_flavor: PrimitiveReconstructionFlavor,
+        print_header: bool,
+        print_dependencies: bool,
+        fmt_configuration: DataFormatConfiguration,
+        highlight_theme: Option<SyntaxTheme>,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.load_main_pdb(pdb_path)?;
 
-        // Queue a request for the backend to reconstruct the given type
+        // Queue a request for the backend to reconstruct all types
         self.backend
-            .send_command(BackendCommand::ReconstructTypeByName(
+            .send_command(BackendCommand::ReconstructAllTypes(
                 PDB_MAIN_SLOT,
-                type_name,
                 primitive_types_flavor,
                 print_header,
                 print_dependencies,
-                print_access_specifiers,
+                fmt_configuration,
             ))?;
-        // Wait for the backend to finish filtering types
-        if let FrontendCommand::ReconstructTypeResult(reconstructed_type_result) =
-            self.frontend_controller.rx_ui.recv()?
-        {
-            match reconstructed_type_result {
-                Err(err) => Err(err),
-                Ok(reconstructed_type) => {
-                    // Dump output
-                    if let Some(output_file_path) = output_file_path {
-                        let mut output_file = File::create(output_file_path)?;
-                        output_file.write_all(reconstructed_type.as_bytes())?;
-                    } else if highlight_syntax {
+
+        // Dump output as it comes
+        let mut output_writer: Box<dyn Write> = if let Some(output_file_path) = output_file_path {
+            Box::new(File::create(output_file_path)?)
+        } else {
+            Box::new(io::stdout())
+        };
+        loop {
+            match self.frontend_controller.rx_ui.recv()? {
+                FrontendCommand::ReconstructAllTypesChunk(reconstructed_types) => {
+                    if let Some(theme) = &highlight_theme {
                         const LANGUAGE_SYNTAX: &str = "cpp";
-                        let theme = CodeTheme::dark();
-                        if let Some(colorized_reconstructed_type) =
-                            highlight_code(&theme, &reconstructed_type, LANGUAGE_SYNTAX, None)
+                        if let Some(colorized_reconstructed_types) =
+                            highlight_code(theme, &reconstructed_types, LANGUAGE_SYNTAX, None)
                         {
-                            println!("{}", colorized_reconstructed_type);
+                            output_writer.write_all(colorized_reconstructed_types.as_bytes())?;
                         }
                     } else {
-                        println!("{}", reconstructed_type);
+                        output_writer.write_all(reconstructed_types.as_bytes())?;
                     }
-                    Ok(())
                 }
+                FrontendCommand::ReconstructAllTypesResult(result) => {
+                    output_writer.flush()?;
+                    return Ok(result?);
+                }
+                _ => return Err(anyhow!("Invalid response received from the backend?")),
             }
-        } else {
-            Err(anyhow!("Invalid response received from the backend?"))
         }
     }
 
@@ -298,44 +2170,19 @@ impl ResymcApp {
         primitive_types_flavor: PrimitiveReconstructionFlavor,
         print_header: bool,
         print_dependencies: bool,
-        print_access_specifiers: bool,
-        highlight_syntax: bool,
+        fmt_configuration: DataFormatConfiguration,
+        highlight_theme: Option<SyntaxTheme>,
+        semantic: bool,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
-        // Request the backend to load the first PDB
-        self.backend.send_command(BackendCommand::LoadPDB(
-            PDB_MAIN_SLOT,
-            from_pdb_path.clone(),
-        ))?;
-        // Wait for the backend to finish loading the PDB
-        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
-            if let Err(err) = result {
-                return Err(anyhow!(
-                    "Failed to load PDB '{}': {}",
-                    from_pdb_path.display(),
-                    err
-                ));
-            }
-        } else {
-            return Err(anyhow!("Invalid response received from the backend?"));
-        }
+        self.load_diffed_pdbs(from_pdb_path, to_pdb_path)?;
 
-        // Request the backend to load the second PDB
-        self.backend.send_command(BackendCommand::LoadPDB(
-            PDB_DIFF_TO_SLOT,
-            to_pdb_path.clone(),
-        ))?;
-        // Wait for the backend to finish loading the PDB
-        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
-            if let Err(err) = result {
-                return Err(anyhow!(
-                    "Failed to load PDB '{}': {}",
-                    to_pdb_path.display(),
-                    err
-                ));
-            }
-        } else {
-            return Err(anyhow!("Invalid response received from the backend?"));
+        if semantic {
+            return self.semantic_diff_type_command(
+                type_name,
+                primitive_types_flavor,
+                output_file_path,
+            );
         }
 
         // Queue a request for the backend to diff the given type
@@ -346,22 +2193,21 @@ impl ResymcApp {
             primitive_types_flavor,
             print_header,
             print_dependencies,
-            print_access_specifiers,
+            fmt_configuration,
         ))?;
         // Wait for the backend to finish
         if let FrontendCommand::DiffTypeResult(reconstructed_type_diff_result) =
             self.frontend_controller.rx_ui.recv()?
         {
             match reconstructed_type_diff_result {
-                Err(err) => Err(err),
+                Err(err) => Err(err.into()),
                 Ok(reconstructed_type_diff) => {
                     // Dump output
                     if let Some(output_file_path) = output_file_path {
                         let mut output_file = File::create(output_file_path)?;
                         output_file.write_all(reconstructed_type_diff.data.as_bytes())?;
-                    } else if highlight_syntax {
+                    } else if let Some(theme) = &highlight_theme {
                         const LANGUAGE_SYNTAX: &str = "cpp";
-                        let theme = CodeTheme::dark();
                         let line_descriptions =
                             reconstructed_type_diff
                                 .metadata
@@ -371,7 +2217,7 @@ impl ResymcApp {
                                     acc
                                 });
                         if let Some(colorized_reconstructed_type) = highlight_code(
-                            &theme,
+                            theme,
                             &reconstructed_type_diff.data,
                             LANGUAGE_SYNTAX,
                             Some(line_descriptions),
@@ -388,4 +2234,282 @@ impl ResymcApp {
             Err(anyhow!("Invalid response received from the backend?"))
         }
     }
+
+    /// Load the PDBs to compute a diff from and to.
+    fn load_diffed_pdbs(&self, from_pdb_path: PathBuf, to_pdb_path: PathBuf) -> Result<()> {
+        // Request the backend to load the first PDB
+        self.backend.send_command(BackendCommand::LoadPDB(
+            PDB_MAIN_SLOT,
+            from_pdb_path.clone(),
+        ))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(_, result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!(
+                    "Failed to load PDB '{}': {}",
+                    from_pdb_path.display(),
+                    err
+                ));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        // Request the backend to load the second PDB
+        self.backend.send_command(BackendCommand::LoadPDB(
+            PDB_DIFF_TO_SLOT,
+            to_pdb_path.clone(),
+        ))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(_, result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!(
+                    "Failed to load PDB '{}': {}",
+                    to_pdb_path.display(),
+                    err
+                ));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        Ok(())
+    }
+
+    fn diff_type_lists_command(
+        &self,
+        from_pdb_path: PathBuf,
+        to_pdb_path: PathBuf,
+        output_format: ReportFormat,
+    ) -> Result<()> {
+        self.load_diffed_pdbs(from_pdb_path, to_pdb_path)?;
+
+        // Queue a request for the backend to diff the type lists
+        self.backend.send_command(BackendCommand::DiffTypeLists(
+            PDB_MAIN_SLOT,
+            PDB_DIFF_TO_SLOT,
+        ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::DiffTypeListsResult(type_list_changes_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let type_list_changes = type_list_changes_result?;
+            if output_format == ReportFormat::Json {
+                println!("{}", serde_json::to_string_pretty(&type_list_changes)?);
+                return Ok(());
+            }
+
+            println!(
+                "{:<12} {:>10} {:>10}  Type",
+                "Change", "Old size", "New size"
+            );
+            for type_list_change in type_list_changes {
+                let (change, name, old_size, new_size) = match type_list_change {
+                    TypeListChange::Added { name, size } => ("added", name, None, Some(size)),
+                    TypeListChange::Removed { name, size } => ("removed", name, Some(size), None),
+                    TypeListChange::SizeChanged {
+                        name,
+                        old_size,
+                        new_size,
+                    } => ("size changed", name, Some(old_size), Some(new_size)),
+                };
+                let fmt_size =
+                    |size: Option<u64>| size.map_or("-".to_string(), |size| format!("{:#x}", size));
+                println!(
+                    "{:<12} {:>10} {:>10}  {}",
+                    change,
+                    fmt_size(old_size),
+                    fmt_size(new_size),
+                    name
+                );
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    /// Print the changes made to the layout of a type between the two loaded
+    /// PDBs, one per line.
+    fn semantic_diff_type_command(
+        &self,
+        type_name: String,
+        primitive_types_flavor: PrimitiveReconstructionFlavor,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        self.backend
+            .send_command(BackendCommand::SemanticDiffTypeByName(
+                PDB_MAIN_SLOT,
+                PDB_DIFF_TO_SLOT,
+                type_name,
+                primitive_types_flavor,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::SemanticDiffTypeResult(type_changes_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let type_changes = type_changes_result?;
+            let mut output_writer: Box<dyn Write> = match output_file_path {
+                Some(output_file_path) => Box::new(File::create(output_file_path)?),
+                None => Box::new(io::stdout()),
+            };
+            for type_change in type_changes {
+                writeln!(output_writer, "{}", type_change)?;
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    fn fetch_pdb_command(
+        &self,
+        pe_path_or_pdb_id: String,
+        pdb_name: Option<String>,
+        symbol_path: Option<String>,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        let (pdb_name, pdb_id) = match pdb_name {
+            Some(pdb_name) => {
+                let (guid, age) = PdbReference::parse_symbol_server_id(&pe_path_or_pdb_id)?;
+                (pdb_name, format!("{}{:X}", guid.to_simple_string(), age))
+            }
+            None => {
+                let pe_path = Path::new(&pe_path_or_pdb_id);
+                if !pe_path.is_file() {
+                    return Err(anyhow!(
+                        "'{}' isn't a file, --pdb-name is required to fetch a PDB given its identifier",
+                        pe_path_or_pdb_id
+                    ));
+                }
+                let pdb_reference = PdbReference::from_pe_file(pe_path)?;
+                (
+                    pdb_reference.pdb_file_name().to_string(),
+                    pdb_reference.symbol_server_id(),
+                )
+            }
+        };
+        let symbol_path = match symbol_path {
+            Some(symbol_path) => SymbolPath::parse(&symbol_path)?,
+            None => SymbolPath::from_env()?,
+        };
+
+        let fetched_pdb = symbol_path.fetch_pdb(&pdb_name, &pdb_id)?;
+        let pdb_path = match (output_file_path, fetched_pdb) {
+            (None, FetchedPdb::Stored(pdb_path)) => pdb_path,
+            (output_file_path, fetched_pdb) => {
+                let output_file_path = output_file_path.unwrap_or_else(|| PathBuf::from(&pdb_name));
+                fetched_pdb.write_to(&output_file_path)?;
+                output_file_path
+            }
+        };
+        println!("{}", pdb_path.display());
+
+        Ok(())
+    }
+}
+
+/// Parse a type index given in decimal or in hexadecimal (`0x` prefix).
+fn parse_type_index(type_index_str: &str) -> Result<pdb::TypeIndex, ParseIntError> {
+    Ok(pdb::TypeIndex(parse_u32(type_index_str)?))
+}
+
+/// Parse an integer given in decimal or in hexadecimal (`0x` prefix).
+fn parse_u64(integer_str: &str) -> Result<u64, ParseIntError> {
+    if let Some(hex_str) = integer_str
+        .strip_prefix("0x")
+        .or_else(|| integer_str.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex_str, 16)
+    } else {
+        integer_str.parse()
+    }
+}
+
+/// Parse an integer given in decimal or in hexadecimal (`0x` prefix).
+fn parse_u32(integer_str: &str) -> Result<u32, ParseIntError> {
+    if let Some(hex_str) = integer_str
+        .strip_prefix("0x")
+        .or_else(|| integer_str.strip_prefix("0X"))
+    {
+        u32::from_str_radix(hex_str, 16)
+    } else {
+        integer_str.parse()
+    }
+}
+
+/// Read type names from the given file (or from stdin if `-` is given).
+/// Blank lines and lines starting with `#` are ignored.
+fn read_type_names_file(names_file_path: &Path) -> Result<Vec<String>> {
+    let names_file_content = if names_file_path == Path::new("-") {
+        let mut content = String::default();
+        io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        fs::read_to_string(names_file_path)
+            .map_err(|err| anyhow!("Failed to read '{}': {}", names_file_path.display(), err))?
+    };
+
+    Ok(names_file_content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
+
+/// Merge the output of several type reconstructions into a single output.
+/// Source code outputs are concatenated with a separator comment between
+/// types, while JSON outputs are merged into a single array.
+fn merge_reconstructed_types(
+    output_format: OutputFormat,
+    reconstructed_types: Vec<(RequestedType, String)>,
+) -> Result<String> {
+    match output_format {
+        OutputFormat::Cpp
+        | OutputFormat::C
+        | OutputFormat::CSharp
+        | OutputFormat::Rust
+        | OutputFormat::WinDbg
+        | OutputFormat::ImHex
+        | OutputFormat::BinaryTemplate
+        | OutputFormat::Go
+        | OutputFormat::Ctypes => {
+            let mut merged_output = String::default();
+            for (i, (requested_type, reconstructed_type)) in
+                reconstructed_types.into_iter().enumerate()
+            {
+                if i > 0 {
+                    match output_format {
+                        // C89 doesn't support single-line comments
+                        OutputFormat::C => merged_output
+                            .push_str(&format!("\n/* ---- {} ---- */\n", requested_type)),
+                        OutputFormat::Ctypes => {
+                            merged_output.push_str(&format!("\n# ---- {} ----\n", requested_type))
+                        }
+                        _ => {
+                            merged_output.push_str(&format!("\n// ---- {} ----\n", requested_type))
+                        }
+                    }
+                }
+                merged_output.push_str(&reconstructed_type);
+            }
+            Ok(merged_output)
+        }
+        OutputFormat::Json => {
+            let mut merged_output: Vec<serde_json::Value> = vec![];
+            for (_, reconstructed_type) in reconstructed_types {
+                merged_output.extend(serde_json::from_str::<Vec<serde_json::Value>>(
+                    &reconstructed_type,
+                )?);
+            }
+            Ok(serde_json::to_string_pretty(&merged_output)?)
+        }
+        // Types must be described in a single document
+        OutputFormat::Kaitai => {
+            Ok(merge_kaitai_documents(reconstructed_types.iter().map(
+                |(_, reconstructed_type)| reconstructed_type.as_str(),
+            )))
+        }
+    }
 }
@@ -0,0 +1,106 @@
+use anyhow::{anyhow, Result};
+use rustyline::{error::ReadlineError, DefaultEditor};
+use structopt::{clap, StructOpt};
+
+use std::{ffi::OsString, path::Path, path::PathBuf};
+
+use crate::{config::Config, run_command, ResymOptions, ResymcApp, ResymcArgs, PKG_NAME};
+
+const PROMPT: &str = "resym> ";
+const HELP_MESSAGE: &str = "\
+Commands (use `<command> --help` to list their options):
+  list <filter> [output_file] [options]    List types whose name matches filter
+  dump <name> [output_file] [options]      Dump the type of the given name
+  xref <name> [output_file] [options]      List types referencing the given type
+  help                                     Print this message
+  quit                                     Exit (or press Ctrl-D)";
+
+/// Command entered in the REPL.
+enum ReplCommand {
+    /// Command run against the REPL's PDB
+    Run(Box<ResymOptions>),
+    Help,
+    Quit,
+}
+
+/// Load the PDB at `pdb_path` and run the commands entered by the user
+/// against it, until they quit.
+pub fn run_repl(
+    app: &ResymcApp,
+    pdb_path: PathBuf,
+    config: &Config,
+    use_colors: bool,
+) -> Result<()> {
+    app.load_persistent_pdb(pdb_path.clone())?;
+
+    // Note: Lines are kept in the editor's history, which can be browsed
+    // with the arrow keys
+    let mut editor = DefaultEditor::new()?;
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            // Ctrl-C discards the current line, Ctrl-D ends the session
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+
+        match parse_command(&pdb_path, line) {
+            Ok(ReplCommand::Run(command)) => {
+                if let Err(err) = run_command(app, *command, config, use_colors) {
+                    eprintln!("Error: {}", err);
+                }
+            }
+            Ok(ReplCommand::Help) => println!("{}", HELP_MESSAGE),
+            Ok(ReplCommand::Quit) => return Ok(()),
+            Err(err) => match err.downcast_ref::<clap::Error>() {
+                // Help and version messages are errors for clap
+                Some(err) if !err.use_stderr() => println!("{}", err.message),
+                Some(err) => eprintln!("{}", err.message),
+                None => eprintln!("Error: {}", err),
+            },
+        }
+    }
+}
+
+/// Parse a line entered in the REPL. Commands are parsed like `resymc`'s
+/// subcommands, as if the REPL's PDB had been given as their first argument.
+fn parse_command(pdb_path: &Path, line: &str) -> Result<ReplCommand> {
+    let words = shlex::split(line).ok_or_else(|| anyhow!("unterminated quote"))?;
+    let (command_name, args) = words
+        .split_first()
+        .ok_or_else(|| anyhow!("empty command"))?;
+    match command_name.as_str() {
+        "list" | "dump" | "xref" => {}
+        "help" => return Ok(ReplCommand::Help),
+        "quit" | "exit" => return Ok(ReplCommand::Quit),
+        _ => {
+            return Err(anyhow!(
+                "unknown command '{}', type 'help' to list commands",
+                command_name
+            ))
+        }
+    }
+
+    let command = ResymcArgs::from_iter_safe(
+        [PKG_NAME, command_name]
+            .into_iter()
+            .map(OsString::from)
+            .chain(std::iter::once(pdb_path.as_os_str().to_owned()))
+            .chain(args.iter().map(OsString::from)),
+    )?
+    .command;
+    if matches!(
+        command,
+        ResymOptions::List { watch: true, .. } | ResymOptions::Dump { watch: true, .. }
+    ) {
+        return Err(anyhow!("--watch cannot be used in the REPL"));
+    }
+
+    Ok(ReplCommand::Run(Box::new(command)))
+}
@@ -0,0 +1,30 @@
+use std::{fmt, str::FromStr};
+
+/// Representation used to output reports (e.g., type lists).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReportFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = ParseReportFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(ParseReportFormatError {}),
+        }
+    }
+}
+
+/// An error returned when parsing a `ReportFormat` from a string fails.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseReportFormatError {}
+
+impl fmt::Display for ParseReportFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "provided string was not recognized as a valid report format".fmt(f)
+    }
+}
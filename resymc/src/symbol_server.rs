@@ -0,0 +1,200 @@
+use anyhow::{anyhow, Result};
+
+use std::{
+    fs,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+/// Symbol path used when `_NT_SYMBOL_PATH` isn't set.
+pub const DEFAULT_SYMBOL_PATH: &str = "srv*https://msdl.microsoft.com/download/symbols";
+const SYMBOL_PATH_ENV_VAR: &str = "_NT_SYMBOL_PATH";
+/// Some symbol servers only answer to SymSrv's user agent
+const USER_AGENT: &str = "Microsoft-Symbol-Server/10.0.0.0";
+
+/// Location symbol files are looked up in: either a local directory or an
+/// HTTP(S) server, using SymSrv's layout (`<store>/<name>/<id>/<name>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolStore {
+    Directory(PathBuf),
+    Server(String),
+}
+
+/// Symbol path, in the format used by Microsoft's debuggers (e.g.,
+/// `srv*C:\symbols*https://msdl.microsoft.com/download/symbols`). Each
+/// `;`-separated element is a chain of stores, looked up from left to right.
+/// Files found in a store are copied into the local stores preceding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolPath {
+    store_chains: Vec<Vec<SymbolStore>>,
+}
+
+impl SymbolPath {
+    pub fn parse(symbol_path: &str) -> Result<Self> {
+        let store_chains = symbol_path
+            .split(';')
+            .filter(|element| !element.trim().is_empty())
+            .map(|element| {
+                let stores = match element.split_once('*') {
+                    Some((prefix, stores))
+                        if prefix.eq_ignore_ascii_case("srv")
+                            || prefix.eq_ignore_ascii_case("cache") =>
+                    {
+                        stores
+                    }
+                    _ => element,
+                };
+                stores
+                    .split('*')
+                    .filter(|store| !store.is_empty())
+                    .map(|store| {
+                        if store.starts_with("http://") || store.starts_with("https://") {
+                            SymbolStore::Server(store.trim_end_matches('/').to_string())
+                        } else {
+                            SymbolStore::Directory(PathBuf::from(store))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .filter(|stores| !stores.is_empty())
+            .collect::<Vec<_>>();
+        if store_chains.is_empty() {
+            return Err(anyhow!("empty symbol path"));
+        }
+
+        Ok(Self { store_chains })
+    }
+
+    /// Symbol path set in `_NT_SYMBOL_PATH`, or Microsoft's public symbol
+    /// server if the variable isn't set.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(SYMBOL_PATH_ENV_VAR) {
+            Ok(symbol_path) if !symbol_path.trim().is_empty() => Self::parse(&symbol_path),
+            _ => Self::parse(DEFAULT_SYMBOL_PATH),
+        }
+    }
+
+    /// Look a PDB up given its name and identifier (as returned by
+    /// `PdbReference::symbol_server_id`).
+    pub fn fetch_pdb(&self, pdb_name: &str, pdb_id: &str) -> Result<FetchedPdb> {
+        let relative_path = format!("{}/{}/{}", pdb_name, pdb_id, pdb_name);
+        for stores in &self.store_chains {
+            for (store_index, store) in stores.iter().enumerate() {
+                let pdb_data = match store {
+                    SymbolStore::Directory(directory) => {
+                        let pdb_path = directory.join(&relative_path);
+                        if pdb_path.is_file() {
+                            if store_index == 0 {
+                                return Ok(FetchedPdb::Stored(pdb_path));
+                            }
+                            fs::read(&pdb_path)?
+                        } else {
+                            continue;
+                        }
+                    }
+                    SymbolStore::Server(url) => match download_pdb(url, pdb_name, pdb_id)? {
+                        Some(pdb_data) => pdb_data,
+                        None => continue,
+                    },
+                };
+
+                // Cache the PDB into the local stores it wasn't found in
+                let mut stored_path = None;
+                for store in &stores[..store_index] {
+                    if let SymbolStore::Directory(directory) = store {
+                        let pdb_path = directory.join(&relative_path);
+                        if let Some(parent_directory) = pdb_path.parent() {
+                            fs::create_dir_all(parent_directory)?;
+                        }
+                        fs::write(&pdb_path, &pdb_data)?;
+                        stored_path.get_or_insert(pdb_path);
+                    }
+                }
+
+                return Ok(match stored_path {
+                    Some(pdb_path) => FetchedPdb::Stored(pdb_path),
+                    None => FetchedPdb::Downloaded(pdb_data),
+                });
+            }
+        }
+
+        Err(anyhow!(
+            "'{}' ({}) wasn't found in the symbol path",
+            pdb_name,
+            pdb_id
+        ))
+    }
+}
+
+/// PDB found in a symbol path.
+pub enum FetchedPdb {
+    /// The PDB is stored in a local store, at the given path
+    Stored(PathBuf),
+    /// The PDB has been downloaded, but there's no local store to cache it
+    /// into
+    Downloaded(Vec<u8>),
+}
+
+impl FetchedPdb {
+    /// Write the PDB to the given path.
+    pub fn write_to(&self, output_file_path: &Path) -> Result<()> {
+        match self {
+            FetchedPdb::Stored(pdb_path) => {
+                fs::copy(pdb_path, output_file_path)?;
+            }
+            FetchedPdb::Downloaded(pdb_data) => fs::write(output_file_path, pdb_data)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Download a PDB from a symbol server, `None` is returned if the server
+/// doesn't have it. Servers may store PDBs compressed in a cabinet whose
+/// name ends with an underscore (e.g., `ntdll.pd_`).
+fn download_pdb(server_url: &str, pdb_name: &str, pdb_id: &str) -> Result<Option<Vec<u8>>> {
+    let pdb_url = format!("{}/{}/{}/{}", server_url, pdb_name, pdb_id, pdb_name);
+    if let Some(pdb_data) = download_file(&pdb_url)? {
+        return Ok(Some(pdb_data));
+    }
+
+    let mut compressed_pdb_name = pdb_name.to_string();
+    compressed_pdb_name.pop();
+    compressed_pdb_name.push('_');
+    let compressed_pdb_url = format!(
+        "{}/{}/{}/{}",
+        server_url, pdb_name, pdb_id, compressed_pdb_name
+    );
+    match download_file(&compressed_pdb_url)? {
+        Some(cabinet_data) => Ok(Some(extract_cabinet(cabinet_data)?)),
+        None => Ok(None),
+    }
+}
+
+fn download_file(url: &str) -> Result<Option<Vec<u8>>> {
+    log::info!("Downloading '{}' ...", url);
+    match ureq::get(url).set("User-Agent", USER_AGENT).call() {
+        Ok(response) => {
+            let mut data = vec![];
+            response.into_reader().read_to_end(&mut data)?;
+            Ok(Some(data))
+        }
+        Err(ureq::Error::Status(404, _)) => Ok(None),
+        Err(err) => Err(anyhow!("download failed: {}", err)),
+    }
+}
+
+/// Extract the (single) file of a cabinet.
+fn extract_cabinet(cabinet_data: Vec<u8>) -> Result<Vec<u8>> {
+    let mut cabinet = cab::Cabinet::new(Cursor::new(cabinet_data))?;
+    let file_name = cabinet
+        .folder_entries()
+        .flat_map(|folder| folder.file_entries())
+        .map(|file| file.name().to_string())
+        .next()
+        .ok_or_else(|| anyhow!("empty cabinet"))?;
+    let mut file_data = vec![];
+    cabinet.read_file(&file_name)?.read_to_end(&mut file_data)?;
+
+    Ok(file_data)
+}
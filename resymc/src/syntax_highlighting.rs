@@ -1,12 +1,39 @@
-use resym_core::{diffing::DiffChange, syntax_highlighting::CodeTheme};
+use std::path::Path;
+
+use anyhow::Result;
+use resym_core::{diffing::DiffChange, pdb_types::OutputFormat, syntax_highlighting::CodeTheme};
 use syntect::{
     easy::HighlightLines,
-    highlighting::{Color, Style},
+    highlighting::{Color, Style, Theme, ThemeSet},
+    html::highlighted_html_for_string,
     util::{as_24_bit_terminal_escaped, LinesWithEndings},
 };
 
 pub type LineDescriptions = Vec<DiffChange>;
 
+/// Theme used to highlight code, either a built-in one or one loaded from a
+/// `.tmTheme` file.
+#[derive(Clone)]
+pub enum SyntaxTheme {
+    Builtin(CodeTheme),
+    Custom(Box<Theme>),
+}
+
+impl Default for SyntaxTheme {
+    fn default() -> Self {
+        SyntaxTheme::Builtin(CodeTheme::default())
+    }
+}
+
+impl SyntaxTheme {
+    /// Load a theme from a `.tmTheme` file.
+    pub fn load_from_file(theme_file_path: &Path) -> Result<Self> {
+        Ok(SyntaxTheme::Custom(Box::new(ThemeSet::get_theme(
+            theme_file_path,
+        )?)))
+    }
+}
+
 const COLOR_TRANSPARENT: Color = Color {
     r: 0x00,
     g: 0x00,
@@ -26,11 +53,32 @@ const COLOR_GREEN: Color = Color {
     a: 0xFF,
 };
 
+/// Return the name of the language types reconstructed in the given format
+/// are written in, as known to `syntect`.
+pub fn output_format_language(output_format: OutputFormat) -> &'static str {
+    match output_format {
+        OutputFormat::Cpp => "cpp",
+        OutputFormat::C => "c",
+        OutputFormat::CSharp => "cs",
+        OutputFormat::Rust => "rs",
+        OutputFormat::Json => "json",
+        // Layout listings aren't source code
+        OutputFormat::WinDbg => "txt",
+        // The pattern language's syntax is close to C++'s
+        OutputFormat::ImHex => "cpp",
+        // Binary templates are written in a C-like language
+        OutputFormat::BinaryTemplate => "c",
+        OutputFormat::Kaitai => "yaml",
+        OutputFormat::Go => "go",
+        OutputFormat::Ctypes => "py",
+    }
+}
+
 /// Function relying on `syntect` to highlight the given `code` str.
 /// In case of success, the result is a `String` that is ready to be printed in a
 /// terminal.
 pub fn highlight_code(
-    theme: &CodeTheme,
+    theme: &SyntaxTheme,
     code: &str,
     language: &str,
     line_descriptions: Option<LineDescriptions>,
@@ -39,6 +87,14 @@ pub fn highlight_code(
     highlighter.highlight(theme, code, language, line_descriptions)
 }
 
+/// Function relying on `syntect` to highlight the given `code` str as HTML.
+/// In case of success, the result is a self-contained `<pre>` block, styled
+/// inline.
+pub fn highlight_code_html(theme: &SyntaxTheme, code: &str, language: &str) -> Option<String> {
+    let highlighter = CodeHighlighter::default();
+    highlighter.highlight_html(theme, code, language)
+}
+
 struct CodeHighlighter {
     ps: syntect::parsing::SyntaxSet,
     ts: syntect::highlighting::ThemeSet,
@@ -54,23 +110,35 @@ impl Default for CodeHighlighter {
 }
 
 impl CodeHighlighter {
+    fn syntect_theme<'a>(&'a self, theme: &'a SyntaxTheme) -> &'a Theme {
+        match theme {
+            SyntaxTheme::Builtin(theme) => &self.ts.themes[theme.syntect_theme.syntect_key_name()],
+            SyntaxTheme::Custom(theme) => theme,
+        }
+    }
+
+    /// Return the syntax of the given language, or plain text if it isn't
+    /// supported.
+    fn find_syntax(&self, language: &str) -> &syntect::parsing::SyntaxReference {
+        self.ps
+            .find_syntax_by_name(language)
+            .or_else(|| self.ps.find_syntax_by_extension(language))
+            .unwrap_or_else(|| self.ps.find_syntax_plain_text())
+    }
+
     fn highlight(
         &self,
-        theme: &CodeTheme,
+        theme: &SyntaxTheme,
         code: &str,
         language: &str,
         line_descriptions: Option<LineDescriptions>,
     ) -> Option<String> {
         use std::fmt::Write;
 
-        let syntax = self
-            .ps
-            .find_syntax_by_name(language)
-            .or_else(|| self.ps.find_syntax_by_extension(language))?;
+        let syntax = self.find_syntax(language);
 
-        let theme = theme.syntect_theme.syntect_key_name();
         let mut output = String::default();
-        let mut h = HighlightLines::new(syntax, &self.ts.themes[theme]);
+        let mut h = HighlightLines::new(syntax, self.syntect_theme(theme));
         for (line_id, line) in LinesWithEndings::from(code).enumerate() {
             let mut regions = h.highlight_line(line, &self.ps).ok()?;
             // Apply highlight related to diff changes if needed
@@ -88,6 +156,13 @@ impl CodeHighlighter {
 
         Some(output)
     }
+
+    fn highlight_html(&self, theme: &SyntaxTheme, code: &str, language: &str) -> Option<String> {
+        let syntax = self.find_syntax(language);
+
+        // Special characters (e.g., in template names) are escaped
+        highlighted_html_for_string(code, &self.ps, syntax, self.syntect_theme(theme)).ok()
+    }
 }
 
 /// Changes the background of regions that have been affected in the diff.
@@ -0,0 +1,96 @@
+use anyhow::{anyhow, Result};
+use crossbeam_channel::{select, Receiver};
+use notify::{RecursiveMode, Watcher};
+
+use std::{path::Path, time::Duration};
+
+/// Time to wait for a file to stop changing before handling the change, so
+/// that a file being written isn't read before it's complete
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+
+/// Run the given command, and run it again every time the file at
+/// `watched_file_path` changes, until Ctrl-C is pressed. Errors are reported,
+/// and don't stop the watch.
+///
+/// The file's parent directory is watched rather than the file itself, so
+/// that files replaced by another one (e.g., by linkers writing a new file
+/// and renaming it) keep being watched.
+pub fn run_command(
+    watched_file_path: &Path,
+    mut command: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    let file_path = watched_file_path.canonicalize()?;
+    let directory_path = file_path
+        .parent()
+        .ok_or_else(|| anyhow!("'{}' has no parent directory", file_path.display()))?;
+
+    // Start watching before running the command, so that changes made while
+    // it runs aren't missed
+    let (tx_event, rx_event) = crossbeam_channel::unbounded();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        // The receiver is only dropped once the watch is over
+        let _ = tx_event.send(event);
+    })?;
+    watcher.watch(directory_path, RecursiveMode::NonRecursive)?;
+    let rx_interrupt = interrupt_receiver()?;
+
+    let mut run_command = || {
+        if let Err(err) = command() {
+            eprintln!("Error: {}", err);
+        }
+    };
+    run_command();
+    eprintln!(
+        "Watching '{}' for changes, press Ctrl-C to exit",
+        file_path.display()
+    );
+    loop {
+        select! {
+            recv(rx_interrupt) -> _ => return Ok(()),
+            recv(rx_event) -> event => {
+                if !concerns_file(event?, &file_path) {
+                    continue;
+                }
+            },
+        }
+        // Wait for the file to stop changing
+        loop {
+            select! {
+                recv(rx_interrupt) -> _ => return Ok(()),
+                recv(rx_event) -> event => {
+                    // Following events are coalesced with the first one
+                    let _ = event?;
+                },
+                default(DEBOUNCE_DELAY) => break,
+            }
+        }
+
+        // The file may be absent for a moment when it's replaced
+        if file_path.is_file() {
+            run_command();
+        }
+    }
+}
+
+/// Indicate whether the given watcher event concerns the file at
+/// `file_path`. Failed events are considered as concerning it, in case it
+/// has changed.
+fn concerns_file(event: notify::Result<notify::Event>, file_path: &Path) -> bool {
+    match event {
+        Ok(event) => !event.kind.is_access() && event.paths.iter().any(|path| path == file_path),
+        Err(err) => {
+            log::warn!("Failed to watch for changes: {}", err);
+            true
+        }
+    }
+}
+
+/// Return a channel receiving a message when Ctrl-C is pressed.
+fn interrupt_receiver() -> Result<Receiver<()>> {
+    let (tx_interrupt, rx_interrupt) = crossbeam_channel::bounded(1);
+    ctrlc::set_handler(move || {
+        let _ = tx_interrupt.try_send(());
+    })?;
+
+    Ok(rx_interrupt)
+}
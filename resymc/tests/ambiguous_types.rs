@@ -0,0 +1,39 @@
+use std::process::Command;
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+/// Unique name shared by distinct unnamed types
+const AMBIGUOUS_TYPE_NAME: &str = ".?AU<unnamed-tag>@@";
+
+#[test]
+fn test_dump_ambiguous_type_fails() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(AMBIGUOUS_TYPE_NAME)
+        .output()
+        .expect("run resymc");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("2 types are named"));
+    assert!(stderr.contains("0x1191, 0x140d"));
+    assert!(stderr.contains("specify --index"));
+}
+
+#[test]
+fn test_dump_ambiguous_type_all_matches() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(AMBIGUOUS_TYPE_NAME)
+        .arg("--all-matches")
+        .output()
+        .expect("run resymc");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("struct _unnamed_0x1191 {"));
+    assert!(stdout.contains("// ---- 0x140d ----"));
+    assert!(stdout.contains("struct _unnamed_0x140d {"));
+}
@@ -0,0 +1,36 @@
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Output},
+};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+fn check_pdb(pdb_file_path: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("check")
+        .arg(pdb_file_path)
+        .output()
+        .expect("run resymc")
+}
+
+#[test]
+fn test_check_valid_pdb() {
+    let output = check_pdb(Path::new(TEST_PDB_FILE_PATH));
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_check_truncated_pdb() {
+    let pdb_data = fs::read(TEST_PDB_FILE_PATH).expect("read test.pdb");
+    let truncated_pdb_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("truncated.pdb");
+    fs::write(&truncated_pdb_file_path, &pdb_data[..pdb_data.len() / 8])
+        .expect("write truncated PDB");
+
+    let output = check_pdb(&truncated_pdb_file_path);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("failed to parse PDB"), "{}", stderr);
+}
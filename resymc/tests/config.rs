@@ -0,0 +1,163 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+const TEST_TYPE_NAME: &str = "resym_test::StructTest";
+
+/// Create a directory containing a `resym.toml` file with the given content,
+/// and return its path.
+fn config_dir(dir_name: &str, config: &str) -> PathBuf {
+    let dir_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(dir_name);
+    fs::create_dir_all(&dir_path).expect("create config directory");
+    fs::write(dir_path.join("resym.toml"), config).expect("write config file");
+
+    dir_path
+}
+
+/// Run `resymc dump` from the given directory, with the given arguments.
+/// Configuration files from the user's configuration directory are ignored.
+fn dump_type(working_dir: &Path, args: &[&str]) -> Output {
+    let pdb_file_path = fs::canonicalize(TEST_PDB_FILE_PATH).expect("canonicalize PDB path");
+    Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .current_dir(working_dir)
+        .env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"))
+        .arg("dump")
+        .arg(pdb_file_path)
+        .arg(TEST_TYPE_NAME)
+        .args(args)
+        .output()
+        .expect("run resymc")
+}
+
+/// Run `resymc list` from the given directory, with the given arguments.
+/// Configuration files from the user's configuration directory are ignored.
+fn list_types(working_dir: &Path, type_name_filter: &str, args: &[&str]) -> Output {
+    let pdb_file_path = fs::canonicalize(TEST_PDB_FILE_PATH).expect("canonicalize PDB path");
+    Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .current_dir(working_dir)
+        .env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"))
+        .arg("list")
+        .arg(pdb_file_path)
+        .arg(type_name_filter)
+        .args(args)
+        .output()
+        .expect("run resymc")
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn test_config_file_in_working_dir() {
+    let working_dir = config_dir("config_working_dir", "[dump]\nprint-header = true\n");
+    let empty_dir = config_dir("config_empty", "");
+
+    let output = stdout(dump_type(&working_dir, &[]));
+    assert_eq!(output, stdout(dump_type(&empty_dir, &["-h"])));
+    assert_ne!(output, stdout(dump_type(&empty_dir, &[])));
+}
+
+#[test]
+fn test_config_file_option_takes_precedence() {
+    let config_file_path = config_dir(
+        "config_explicit",
+        "[dump]\nprimitive-types-flavor = \"microsoft\"\ntheme = \"light\"\n",
+    )
+    .join("resym.toml");
+    let config_file_path = config_file_path.to_str().expect("utf-8 path");
+    let empty_dir = config_dir("config_explicit_empty", "");
+
+    // Themes only apply when highlighting, and options given on the command
+    // line override the file's
+    assert_eq!(
+        stdout(dump_type(&empty_dir, &["--config", config_file_path])),
+        stdout(dump_type(&empty_dir, &["-f", "microsoft"]))
+    );
+    assert_eq!(
+        stdout(dump_type(
+            &empty_dir,
+            &["--config", config_file_path, "-f", "raw"]
+        )),
+        stdout(dump_type(&empty_dir, &["-f", "raw"]))
+    );
+}
+
+#[test]
+fn test_invalid_config_file() {
+    let working_dir = config_dir("config_invalid", "[dump]\nprint-headers = true\n");
+
+    let output = dump_type(&working_dir, &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid configuration file"), "{}", stderr);
+    assert!(stderr.contains("print-headers"), "{}", stderr);
+}
+
+#[test]
+fn test_config_flags_disabled_on_command_line() {
+    let working_dir = config_dir(
+        "config_disabled_flags",
+        "[dump]\nprint-header = true\n\n[list]\nuse-regex = true\n",
+    );
+    let empty_dir = config_dir("config_disabled_flags_empty", "");
+
+    assert_eq!(
+        stdout(dump_type(&working_dir, &["--no-print-header"])),
+        stdout(dump_type(&empty_dir, &[]))
+    );
+    // Flags can't be enabled and disabled at once
+    assert!(!dump_type(&working_dir, &["-h", "--no-print-header"])
+        .status
+        .success());
+
+    assert_eq!(
+        stdout(list_types(&working_dir, "EnumTest.", &[])),
+        "resym_test::EnumTest1\nresym_test::EnumTest2\n"
+    );
+    assert_eq!(
+        stdout(list_types(&working_dir, "EnumTest.", &["--no-use-regex"])),
+        ""
+    );
+}
+
+#[test]
+fn test_config_values_conflicting_with_command_line() {
+    let working_dir = config_dir(
+        "config_conflicting_values",
+        "[list]\nformat = \"json\"\nprimitive-types-flavor = \"microsoft\"\n",
+    );
+    let empty_dir = config_dir("config_conflicting_values_empty", "");
+
+    // Values which would conflict with the command line's options are ignored
+    assert_eq!(
+        stdout(list_types(&working_dir, "EnumTest", &["--null-separated"])),
+        stdout(list_types(&empty_dir, "EnumTest", &["--null-separated"]))
+    );
+    assert_eq!(
+        stdout(list_types(&working_dir, "EnumTest", &["--forward-only"])),
+        stdout(list_types(
+            &empty_dir,
+            "EnumTest",
+            &["--forward-only", "-f", "microsoft"]
+        ))
+    );
+}
+
+#[test]
+fn test_config_file_with_conflicting_values() {
+    let working_dir = config_dir(
+        "config_conflicting_keys",
+        "[list]\nuse-regex = true\nglob = true\n",
+    );
+
+    let output = list_types(&working_dir, "EnumTest", &[]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid configuration file"), "{}", stderr);
+    assert!(stderr.contains("'use-regex', 'glob'"), "{}", stderr);
+}
@@ -0,0 +1,81 @@
+use std::{fs, path::Path, process::Command};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+/// GUID and age of the test PDB
+const TEST_PDB_ID: &str = "04C3AA1BA45B4125827B3770512917DB6";
+
+/// Create a symbol store containing the test PDB.
+fn create_symbol_store(store_name: &str) -> String {
+    let store_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(store_name);
+    let pdb_directory = store_path.join("test.pdb").join(TEST_PDB_ID);
+    fs::create_dir_all(&pdb_directory).expect("create symbol store");
+    fs::copy(TEST_PDB_FILE_PATH, pdb_directory.join("test.pdb")).expect("copy PDB");
+
+    store_path.display().to_string()
+}
+
+fn fetch(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("fetch")
+        .args(args)
+        .output()
+        .expect("run resymc")
+}
+
+#[test]
+fn test_fetch_from_local_store() {
+    let store_path = create_symbol_store("fetch_store");
+    let cache_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("fetch_cache");
+    let _ = fs::remove_dir_all(&cache_path);
+
+    // The PDB is copied into the downstream store
+    let symbol_path = format!("srv*{}*{}", cache_path.display(), store_path);
+    let output = fetch(&[
+        TEST_PDB_ID,
+        "--pdb-name",
+        "test.pdb",
+        "--symbol-path",
+        &symbol_path,
+    ]);
+    assert!(output.status.success());
+    let cached_pdb_path = cache_path
+        .join("test.pdb")
+        .join(TEST_PDB_ID)
+        .join("test.pdb");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+        cached_pdb_path.display().to_string()
+    );
+    assert_eq!(
+        fs::read(cached_pdb_path).expect("read cached PDB"),
+        fs::read(TEST_PDB_FILE_PATH).expect("read PDB")
+    );
+
+    // The PDB is written to the output file if one is given
+    let output_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("fetched.pdb");
+    let output = fetch(&[
+        TEST_PDB_ID,
+        "--pdb-name",
+        "test.pdb",
+        "--symbol-path",
+        &format!("srv*{}", store_path),
+        &output_file_path.display().to_string(),
+    ]);
+    assert!(output.status.success());
+    assert!(output_file_path.is_file());
+}
+
+#[test]
+fn test_fetch_missing_pdb() {
+    let store_path = create_symbol_store("fetch_missing_store");
+
+    let output = fetch(&[
+        "04C3AA1BA45B4125827B3770512917DB7",
+        "--pdb-name",
+        "test.pdb",
+        "--symbol-path",
+        &format!("srv*{}", store_path),
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("wasn't found in the symbol path"));
+}
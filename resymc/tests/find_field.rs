@@ -0,0 +1,46 @@
+use std::process::{Command, Output};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+fn run_resymc(subcommand: &str, field_name_filter: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg(subcommand)
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(field_name_filter)
+        .args(args)
+        .output()
+        .expect("run resymc")
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn test_find_field() {
+    assert_eq!(
+        stdout(run_resymc("find-field", "^flink$", &["-r", "-i"])),
+        "_LIST_ENTRY::Flink\n"
+    );
+    assert_eq!(
+        stdout(run_resymc("find-field", "^flink$", &["-r", "-i", "-t"])),
+        "_LIST_ENTRY::Flink : _LIST_ENTRY*\n"
+    );
+}
+
+#[test]
+fn test_grep_members() {
+    // Members are always output along with their type
+    let output = stdout(run_resymc("grep-members", "^flink$", &["-r", "-i"]));
+    assert_eq!(output, "_LIST_ENTRY::Flink : _LIST_ENTRY*\n");
+    assert_eq!(
+        output,
+        stdout(run_resymc("find-field", "^flink$", &["-r", "-i", "-t"]))
+    );
+
+    assert_eq!(
+        stdout(run_resymc("grep-members", "^Flink$", &["-r", "--format", "json"])),
+        "[\n  {\n    \"type_name\": \"_LIST_ENTRY\",\n    \"name\": \"Flink\",\n    \"type\": \"_LIST_ENTRY*\"\n  }\n]\n"
+    );
+}
@@ -0,0 +1,55 @@
+use std::process::{Command, Output};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+fn type_graph(type_name: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("graph")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(type_name)
+        .args(args)
+        .output()
+        .expect("run resymc")
+}
+
+fn stdout(output: Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn test_graph_embedded_and_pointed_to_types() {
+    let graph = stdout(type_graph("_TP_CALLBACK_ENVIRON_V3", &["--depth", "1"]));
+    assert!(
+        graph.starts_with("digraph \"_TP_CALLBACK_ENVIRON_V3\" {\n"),
+        "{}",
+        graph
+    );
+    assert!(graph.contains("  t1 [label=\"_TP_POOL\"];\n"), "{}", graph);
+    assert!(graph.contains("  t0 -> t1 [style=dashed];\n"), "{}", graph);
+    assert!(
+        graph.contains("  t5 [label=\"_TP_CALLBACK_PRIORITY\"];\n"),
+        "{}",
+        graph
+    );
+    assert!(graph.contains("  t0 -> t5;\n"), "{}", graph);
+    assert!(graph.ends_with("}\n"), "{}", graph);
+}
+
+#[test]
+fn test_graph_self_reference() {
+    let graph = stdout(type_graph("_RTC_ALLOCA_NODE", &[]));
+    assert_eq!(
+        graph,
+        "digraph \"_RTC_ALLOCA_NODE\" {\n  node [shape=box];\n  t0 [label=\"_RTC_ALLOCA_NODE\"];\n  t0 -> t0 [style=dashed];\n}\n"
+    );
+}
+
+#[test]
+fn test_graph_depth() {
+    let graph = stdout(type_graph("_TP_CALLBACK_ENVIRON_V3", &["--depth", "0"]));
+    assert!(!graph.contains("->"), "{}", graph);
+
+    let output = type_graph("_TP_CALLBACK_ENVIRON_V3", &["--depth", "17"]);
+    assert!(!output.status.success());
+}
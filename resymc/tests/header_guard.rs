@@ -0,0 +1,78 @@
+use std::{fs, path::Path, process::Command};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+const TEST_TYPE_NAME: &str = "resym_test::EnumTest1";
+
+/// Run `resymc dump` with the given arguments and return the content of the
+/// output file.
+fn dump_type_to_file(output_file_name: &str, args: &[&str]) -> String {
+    let output_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(output_file_name);
+    let status = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(TEST_TYPE_NAME)
+        .arg(&output_file_path)
+        .args(args)
+        .status()
+        .expect("run resymc");
+    assert!(status.success());
+
+    fs::read_to_string(&output_file_path).expect("read output file")
+}
+
+#[test]
+fn test_dump_header_guard_from_output_file_name() {
+    let output = dump_type_to_file("dumped-types.h", &["--header-guard"]);
+    assert!(
+        output.starts_with("#ifndef DUMPED_TYPES_H\n#define DUMPED_TYPES_H\n"),
+        "{}",
+        output
+    );
+    assert!(
+        output.ends_with("\n#endif // DUMPED_TYPES_H\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("enum resym_test::EnumTest1"));
+}
+
+#[test]
+fn test_dump_header_guard_with_guard_name() {
+    let output = dump_type_to_file(
+        "header_guard_name.h",
+        &["--header-guard", "--guard-name", "RESYM_TEST_H"],
+    );
+    assert!(output.starts_with("#ifndef RESYM_TEST_H\n#define RESYM_TEST_H\n"));
+    assert!(output.ends_with("\n#endif // RESYM_TEST_H\n"));
+}
+
+#[test]
+fn test_dump_pragma_once() {
+    let output = dump_type_to_file("pragma_once.h", &["--pragma-once", "-h"]);
+    // The preamble comes before the header comment
+    assert!(output.starts_with("#pragma once\n"), "{}", output);
+    assert!(!output.contains("#endif"));
+}
+
+#[test]
+fn test_dump_header_guard_to_stdout_requires_guard_name() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(TEST_TYPE_NAME)
+        .arg("--header-guard")
+        .output()
+        .expect("run resymc");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(TEST_TYPE_NAME)
+        .args(["--header-guard", "--guard-name", "RESYM_TEST_H"])
+        .output()
+        .expect("run resymc");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("#ifndef RESYM_TEST_H\n"));
+}
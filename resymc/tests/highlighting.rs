@@ -0,0 +1,169 @@
+use std::{fs, path::Path, process::Command};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+/// Run `resymc dump -H --force-color` with the given arguments and `COLORFGBG` value, and
+/// return its output.
+fn dump_highlighted_type(args: &[&str], colorfgbg: Option<&str>) -> String {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_resymc"));
+    command
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("resym_test::StructTest")
+        .arg("-H")
+        .arg("--force-color")
+        .args(args)
+        .env_remove("COLORFGBG");
+    if let Some(colorfgbg) = colorfgbg {
+        command.env("COLORFGBG", colorfgbg);
+    }
+    let output = command.output().expect("run resymc");
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).expect("utf-8 output")
+}
+
+#[test]
+fn test_highlighting_theme() {
+    let dark_output = dump_highlighted_type(&["--theme", "dark"], None);
+    let light_output = dump_highlighted_type(&["--theme", "light"], None);
+    assert!(dark_output.contains("\x1b["));
+    assert_ne!(dark_output, light_output);
+
+    // Dark by default
+    assert_eq!(dump_highlighted_type(&[], None), dark_output);
+    // The background color advertised by the terminal is used, unless a theme
+    // is explicitly given
+    assert_eq!(dump_highlighted_type(&[], Some("0;15")), light_output);
+    assert_eq!(dump_highlighted_type(&[], Some("15;0")), dark_output);
+    assert_eq!(
+        dump_highlighted_type(&["--theme", "dark"], Some("0;15")),
+        dark_output
+    );
+}
+
+#[test]
+fn test_highlighting_theme_requires_highlighting() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("resym_test::StructTest")
+        .args(["--theme", "light"])
+        .output()
+        .expect("run resymc");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_html_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("__vcrt_va_list_is_reference<char const * const>")
+        .args(["--format", "html"])
+        .output()
+        .expect("run resymc");
+    assert!(output.status.success());
+    let output = String::from_utf8(output.stdout).expect("utf-8 output");
+
+    assert!(output.starts_with("<pre style=\""));
+    assert!(output.trim_end().ends_with("</pre>"));
+    assert!(!output.contains("\x1b["));
+    // Template names are escaped
+    assert!(output.contains("&lt;"));
+    assert!(output.contains("&gt;"));
+    assert!(!output.contains("reference<char"));
+}
+
+#[test]
+fn test_highlighting_disabled() {
+    let dump_type = |args: &[&str], no_color: Option<&str>| {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_resymc"));
+        command
+            .arg("dump")
+            .arg(TEST_PDB_FILE_PATH)
+            .arg("resym_test::StructTest")
+            .args(args)
+            .env_remove("NO_COLOR");
+        if let Some(no_color) = no_color {
+            command.env("NO_COLOR", no_color);
+        }
+        let output = command.output().expect("run resymc");
+        assert!(output.status.success());
+
+        String::from_utf8(output.stdout).expect("utf-8 output")
+    };
+
+    let plain_output = dump_type(&[], None);
+    assert!(!plain_output.contains("\x1b["));
+    // Output isn't highlighted when colors are disabled, or when it isn't
+    // written to a terminal
+    assert_eq!(dump_type(&["-H"], Some("1")), plain_output);
+    assert_eq!(dump_type(&["-H"], None), plain_output);
+    assert_eq!(dump_type(&["-H", "--theme", "light"], None), plain_output);
+    assert_ne!(dump_type(&["-H", "--force-color"], Some("1")), plain_output);
+}
+
+/// Minimal theme, writing everything in #123456.
+const TEST_THEME: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>name</key>
+    <string>Test</string>
+    <key>settings</key>
+    <array>
+        <dict>
+            <key>settings</key>
+            <dict>
+                <key>foreground</key>
+                <string>#123456</string>
+            </dict>
+        </dict>
+    </array>
+</dict>
+</plist>
+"#;
+
+#[test]
+fn test_highlighting_theme_file() {
+    let theme_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("test.tmTheme");
+    fs::write(&theme_file_path, TEST_THEME).expect("write theme file");
+    let output = dump_highlighted_type(
+        &[
+            "--theme-file",
+            theme_file_path.to_str().expect("theme path"),
+        ],
+        None,
+    );
+    assert!(output.contains("\x1b[38;2;18;52;86m"));
+
+    // Invalid themes are reported, and the default theme is used instead
+    let invalid_theme_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("invalid.tmTheme");
+    fs::write(&invalid_theme_file_path, "not a theme").expect("write theme file");
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("dump")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("resym_test::StructTest")
+        .args(["-H", "--force-color", "--theme", "dark", "--theme-file"])
+        .arg(&invalid_theme_file_path)
+        .output()
+        .expect("run resymc");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("failed to load theme"));
+    assert_eq!(
+        String::from_utf8(output.stdout).expect("utf-8 output"),
+        dump_highlighted_type(&["--theme", "dark"], None)
+    );
+}
+
+#[test]
+fn test_highlighting_language() {
+    // Keywords of the output format's language are highlighted (`pub` isn't
+    // a C++ keyword)
+    const KEYWORD_COLOR: &str = "\x1b[38;2;168;155;185m";
+    let rust_output = dump_highlighted_type(&["--theme", "dark", "--format", "rust"], None);
+    assert!(rust_output.contains(&format!("{}pub", KEYWORD_COLOR)));
+    let cpp_output = dump_highlighted_type(&["--theme", "dark", "--format", "cpp"], None);
+    assert!(cpp_output.contains(&format!("{}struct", KEYWORD_COLOR)));
+}
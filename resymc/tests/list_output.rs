@@ -0,0 +1,94 @@
+use std::{fs, path::Path, process::Command};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+/// Run `resymc list` with the given arguments and return the content of the
+/// output file.
+fn list_types_to_file(output_file_name: &str, args: &[&str]) -> String {
+    let output_file_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(output_file_name);
+    let status = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("list")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("resym_test::EnumTest")
+        .arg(&output_file_path)
+        .args(args)
+        .status()
+        .expect("run resymc");
+    assert!(status.success());
+
+    fs::read_to_string(&output_file_path).expect("read output file")
+}
+
+#[test]
+fn test_list_output_file_one_name_per_line() {
+    let output = list_types_to_file("list_output_lines.txt", &[]);
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec!["resym_test::EnumTest1", "resym_test::EnumTest2"]
+    );
+    assert!(output.ends_with('\n'));
+}
+
+#[test]
+fn test_list_output_file_null_separated() {
+    let output = list_types_to_file("list_output_null_separated.txt", &["--null-separated"]);
+    assert_eq!(output, "resym_test::EnumTest1\0resym_test::EnumTest2\0");
+}
+
+#[test]
+fn test_list_invalid_regex() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("list")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("[")
+        .arg("-r")
+        .output()
+        .expect("run resymc");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("Error: invalid regular expression: "),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_list_output_file_size_range() {
+    let output = list_types_to_file(
+        "list_output_size_range.txt",
+        &["--min-size", "4", "--max-size", "0x4"],
+    );
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec!["resym_test::EnumTest1 (0x4 bytes)"]
+    );
+}
+
+#[test]
+fn test_list_output_file_kinds() {
+    let output = list_types_to_file("list_output_enum_kind.txt", &["--kind", "enum"]);
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec!["resym_test::EnumTest1", "resym_test::EnumTest2"]
+    );
+
+    let output = list_types_to_file(
+        "list_output_struct_kinds.txt",
+        &["--kind", "struct", "--kind", "union"],
+    );
+    assert!(output.is_empty());
+}
+
+#[test]
+fn test_list_output_file_exclusions() {
+    let output = list_types_to_file(
+        "list_output_exclusions.txt",
+        &["--exclude", "Test1", "--exclude", "Test3"],
+    );
+    assert_eq!(
+        output.lines().collect::<Vec<_>>(),
+        vec!["resym_test::EnumTest2"]
+    );
+}
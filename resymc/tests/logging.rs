@@ -0,0 +1,39 @@
+use std::process::Command;
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+/// Run `resymc list` with the given arguments and return what it logged.
+fn list_types_log(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("list")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("resym_test::StructTest")
+        .args(args)
+        .output()
+        .expect("run resymc");
+    assert!(output.status.success());
+
+    String::from_utf8(output.stderr).expect("utf-8 output")
+}
+
+#[test]
+fn test_logging_verbosity() {
+    // Nothing is logged by default
+    assert_eq!(list_types_log(&[]), "");
+
+    let log = list_types_log(&["-v"]);
+    assert!(
+        log.contains("has been loaded successfully! (took "),
+        "{}",
+        log
+    );
+    assert!(log.contains("Type filtering took "), "{}", log);
+    assert!(!log.contains("Read the type information stream"), "{}", log);
+
+    let log = list_types_log(&["-vv"]);
+    assert!(
+        log.contains("Read the type information stream (2062 records)"),
+        "{}",
+        log
+    );
+}
@@ -0,0 +1,60 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+/// Run `resymc repl` with the given lines as input and return its output.
+fn run_repl(input: &str) -> Output {
+    let mut resymc = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("repl")
+        .arg(TEST_PDB_FILE_PATH)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run resymc");
+    resymc
+        .stdin
+        .take()
+        .expect("resymc's stdin")
+        .write_all(input.as_bytes())
+        .expect("write input");
+
+    resymc.wait_with_output().expect("wait for resymc")
+}
+
+#[test]
+fn test_repl_commands() {
+    let output = run_repl(
+        "list resym_test::EnumTest\n\
+         dump resym_test::EnumTest1 --enum-hex\n\
+         quit\n\
+         list resym_test::UnionTest\n",
+    );
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("resym_test::EnumTest1\nresym_test::EnumTest2\n"));
+    assert!(stdout.contains("enum resym_test::EnumTest1 : int32_t {"));
+    assert!(stdout.contains("  kEnumTest1Val2 = 0x1,"));
+    // Lines following `quit` aren't run
+    assert!(!stdout.contains("resym_test::UnionTest"));
+}
+
+#[test]
+fn test_repl_errors_dont_end_session() {
+    let output = run_repl(
+        "frobnicate\n\
+         dump resym_test::DoesNotExist\n\
+         dump \"resym_test::EnumTest2\"\n",
+    );
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown command 'frobnicate'"));
+    assert!(stderr.contains("type 'resym_test::DoesNotExist' not found"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("enum resym_test::EnumTest2 : unsigned char {"));
+}
@@ -0,0 +1,15 @@
+use std::process::Command;
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+#[test]
+fn test_srcinfo_unavailable() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("srcinfo")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg("resym_test::ClassWithNestedDeclarationsTest::NestedStruct")
+        .output()
+        .expect("run resymc");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Source information unavailable"));
+}
@@ -0,0 +1,15 @@
+use std::process::Command;
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+
+#[test]
+fn test_verify_invalid_pe() {
+    let output = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("verify")
+        .arg(TEST_PDB_FILE_PATH)
+        .arg(TEST_PDB_FILE_PATH)
+        .output()
+        .expect("run resymc");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not a PE file"));
+}
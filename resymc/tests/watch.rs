@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+const OUTPUT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Copy the test PDB into a directory of its own, so that tests don't see
+/// each other's changes.
+fn watched_pdb_file_path(directory_name: &str) -> PathBuf {
+    let watched_directory_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(directory_name);
+    fs::create_dir_all(&watched_directory_path).expect("create watched directory");
+    let pdb_file_path = watched_directory_path.join("test.pdb");
+    fs::copy(TEST_PDB_FILE_PATH, &pdb_file_path).expect("copy PDB");
+
+    pdb_file_path
+}
+
+/// Forward the lines read from `output` to the returned channel.
+fn output_lines(output: impl Read + Send + 'static) -> Receiver<String> {
+    let (tx_line, rx_line) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            if tx_line.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    rx_line
+}
+
+/// Run `resymc list --watch` on the given PDB, and return the process along
+/// with the lines of its stdout and stderr.
+fn watch_listing(pdb_file_path: &Path) -> (Child, Receiver<String>, Receiver<String>) {
+    let mut resymc = Command::new(env!("CARGO_BIN_EXE_resymc"))
+        .arg("list")
+        .arg(pdb_file_path)
+        .arg("resym_test::EnumTest")
+        .arg("--watch")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("run resymc");
+    let stdout = output_lines(resymc.stdout.take().expect("resymc's stdout"));
+    let stderr = output_lines(resymc.stderr.take().expect("resymc's stderr"));
+
+    (resymc, stdout, stderr)
+}
+
+fn receive_listing(rx_line: &Receiver<String>) -> Result<Vec<String>, RecvTimeoutError> {
+    (0..2)
+        .map(|_| rx_line.recv_timeout(OUTPUT_TIMEOUT))
+        .collect()
+}
+
+fn expected_listing() -> Vec<String> {
+    vec![
+        "resym_test::EnumTest1".to_string(),
+        "resym_test::EnumTest2".to_string(),
+    ]
+}
+
+#[test]
+fn test_list_watch_replaced_pdb() {
+    let pdb_file_path = watched_pdb_file_path("watch_replaced");
+    let (mut resymc, stdout, _stderr) = watch_listing(&pdb_file_path);
+
+    let first_listing = receive_listing(&stdout);
+    // Replace the PDB the way linkers do, by renaming a new file over it
+    let new_pdb_file_path = pdb_file_path.with_extension("pdb.tmp");
+    fs::copy(TEST_PDB_FILE_PATH, &new_pdb_file_path).expect("copy PDB");
+    fs::rename(&new_pdb_file_path, &pdb_file_path).expect("replace PDB");
+    let second_listing = receive_listing(&stdout);
+    resymc.kill().expect("kill resymc");
+    let _ = resymc.wait();
+
+    assert_eq!(first_listing, Ok(expected_listing()));
+    assert_eq!(second_listing, Ok(expected_listing()));
+}
+
+#[test]
+fn test_list_watch_rewritten_pdb() {
+    let pdb_file_path = watched_pdb_file_path("watch_rewritten");
+    let pdb_data = fs::read(TEST_PDB_FILE_PATH).expect("read PDB");
+    let (mut resymc, stdout, stderr) = watch_listing(&pdb_file_path);
+
+    let first_listing = receive_listing(&stdout);
+    // Truncate the PDB in place, the watch must report it and keep going
+    fs::write(&pdb_file_path, &pdb_data[..pdb_data.len() / 2]).expect("truncate PDB");
+    let reload_error = loop {
+        match stderr.recv_timeout(OUTPUT_TIMEOUT) {
+            Ok(line) if line.starts_with("Error:") => break Ok(line),
+            Ok(_) => continue,
+            Err(err) => break Err(err),
+        }
+    };
+    // Then write it again entirely
+    fs::write(&pdb_file_path, &pdb_data).expect("rewrite PDB");
+    let second_listing = receive_listing(&stdout);
+    let still_running = matches!(resymc.try_wait(), Ok(None));
+    resymc.kill().expect("kill resymc");
+    let _ = resymc.wait();
+
+    assert_eq!(first_listing, Ok(expected_listing()));
+    let reload_error = reload_error.expect("reload error");
+    assert!(
+        reload_error.contains("Failed to load PDB"),
+        "{}",
+        reload_error
+    );
+    assert_eq!(second_listing, Ok(expected_listing()));
+    assert!(still_running);
+}